@@ -0,0 +1,91 @@
+//! Acesso ao cluster para os comandos que escrevem on-chain: leitura de contas,
+//! envio de transações e montagem das instruções do programa.
+
+use std::error::Error;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+pub struct Chain {
+    client: RpcClient,
+    payer: Keypair,
+}
+
+impl Chain {
+    pub fn new(rpc_url: &str, payer: Keypair) -> Self {
+        Self { client: RpcClient::new(rpc_url.to_string()), payer }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.client
+    }
+
+    pub async fn genesis_hash(&self) -> Result<[u8; 32], Box<dyn Error>> {
+        Ok(self.client.get_genesis_hash().await?.to_bytes())
+    }
+
+    /// Todas as contas do programa, com o slot mínimo em que foram lidas
+    pub async fn program_accounts(&self) -> Result<(u64, Vec<(Pubkey, Vec<u8>)>), Box<dyn Error>> {
+        let slot = self.client.get_slot().await?;
+        let config = RpcProgramAccountsConfig {
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                min_context_slot: Some(slot),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let accounts = self
+            .client
+            .get_program_accounts_with_config(&workspace::ID, config)
+            .await?
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect();
+        Ok((slot, accounts))
+    }
+
+    pub async fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        let account = self
+            .client
+            .get_account_with_commitment(address, self.client.commitment())
+            .await?
+            .value;
+        Ok(account.map(|a| a.data))
+    }
+
+    pub async fn program_deployed(&self) -> Result<bool, Box<dyn Error>> {
+        Ok(self.account_data(&workspace::ID).await?.is_some())
+    }
+
+    /// Envia e confirma uma transação paga e assinada pela authority
+    pub async fn send(&self, instructions: &[Instruction]) -> Result<Signature, Box<dyn Error>> {
+        let blockhash = self.client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        Ok(self.client.send_and_confirm_transaction(&tx).await?)
+    }
+}
+
+/// Instrução do programa CATE a partir dos tipos gerados pelo Anchor
+pub fn program_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: workspace::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
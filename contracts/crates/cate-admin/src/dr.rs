@@ -0,0 +1,1128 @@
+//! `cate-admin dr` — procedimento de disaster recovery executável.
+//!
+//! `snapshot` salva todas as contas do programa. `restore` parte de um
+//! snapshot e de um novo conjunto de chaves: implanta o programa, inicializa
+//! o Config, re-assina o estado de cada ativo com a nova chave, re-enfileira
+//! as políticas (que passam pelo timelock normal), propõe o novo conjunto de
+//! signers, reescreve o ambiente do keeper e gera o relatório de verificação.
+//! `verify` gera só o relatório, comparando o estado on-chain com o snapshot.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::Command as Process;
+
+use anchor_lang::{AccountDeserialize, AnchorSerialize, Discriminator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use cate_client::{
+    asset_group_pda, asset_history_pda, asset_id_string, asset_meta_pda, asset_mint_pda,
+    asset_policy_pda, asset_risk_pda, config_pda, deadman_pda, pending_change_pda, portfolio_pda,
+    used_decisions_pda, used_decisions_pda_for, AssetGroup, AssetLifecycle, AssetMeta, AssetMint,
+    AssetPolicy, AssetRiskStatus, Config, DeadmanSwitch, DecisionPayload, PendingPolicyChange,
+    PolicyChange, PortfolioRisk, UpdateFee, REPLAY_SHARD_COUNT,
+};
+use cate_sdk::ed25519_ix;
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::{system_program, sysvar};
+use workspace::{accounts, instruction, AssetRiskStatusV1, DEFAULT_SIGNER_ROTATION_DELAY_SECS};
+
+use crate::chain::{program_ix, Chain};
+
+/// Folga sobre o atraso mínimo de rotação, para diferenças de relógio
+const ROTATION_MARGIN_SECS: i64 = 300;
+
+#[derive(Args)]
+pub struct DrArgs {
+    #[command(subcommand)]
+    command: DrCommand,
+}
+
+#[derive(Subcommand)]
+enum DrCommand {
+    /// Salva todas as contas do programa em um arquivo JSON
+    Snapshot {
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Reimplanta, restaura o estado e troca as chaves a partir de um snapshot
+    Restore(RestoreArgs),
+    /// Compara o estado on-chain com um snapshot e grava o relatório
+    Verify {
+        #[arg(long)]
+        snapshot: PathBuf,
+        #[arg(long, default_value = "dr-report.json")]
+        report: PathBuf,
+    },
+}
+
+#[derive(Args)]
+struct RestoreArgs {
+    #[arg(long)]
+    snapshot: PathBuf,
+
+    /// Keypair da nova authority; paga o rent e as transações
+    #[arg(long, env = "CATE_AUTHORITY_KEYPAIR")]
+    authority: PathBuf,
+
+    /// Keypairs do novo conjunto de signers (pode repetir); o primeiro re-assina o estado
+    #[arg(long = "signer", required = true)]
+    signers: Vec<PathBuf>,
+
+    /// Quorum do novo conjunto (padrão: o do snapshot, limitado ao número de signers)
+    #[arg(long)]
+    quorum: Option<u8>,
+
+    /// Binário do programa; sem ele o programa já deve estar implantado
+    #[arg(long, requires = "program_keypair")]
+    program_so: Option<PathBuf>,
+
+    /// Keypair do program id (deve ser o declare_id! deste build)
+    #[arg(long)]
+    program_keypair: Option<PathBuf>,
+
+    /// Arquivo de ambiente do keeper a apontar para o novo deploy
+    #[arg(long)]
+    keeper_env: Option<PathBuf>,
+
+    #[arg(long, default_value = "dr-report.json")]
+    report: PathBuf,
+}
+
+pub async fn run(rpc_url: &str, args: DrArgs) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        DrCommand::Snapshot { out } => snapshot(rpc_url, &out).await,
+        DrCommand::Restore(args) => restore(rpc_url, args).await,
+        DrCommand::Verify { snapshot, report: path } => {
+            let snapshot = Snapshot::load(&snapshot)?;
+            // Só leitura: este payer nunca assina transações
+            let chain = Chain::new(rpc_url, Keypair::new());
+            let mut report = Report::new(rpc_url, &snapshot);
+            verify(&chain, &snapshot.decode()?, &mut report).await?;
+            report.write(&path)
+        }
+    }
+}
+
+// ============================================================================
+// Snapshot
+// ============================================================================
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    program_id: String,
+    slot: u64,
+    taken_at: i64,
+    accounts: Vec<SnapshotAccount>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotAccount {
+    address: String,
+    kind: String,
+    /// Base64 dos dados brutos da conta
+    data: String,
+}
+
+/// Contas do snapshot que o restore sabe reconstruir
+#[derive(Default)]
+struct State {
+    config: Option<Config>,
+    assets: Vec<AssetRiskStatus>,
+    policies: Vec<AssetPolicy>,
+    mints: Vec<AssetMint>,
+    metas: Vec<AssetMeta>,
+    groups: Vec<AssetGroup>,
+    portfolio: Option<PortfolioRisk>,
+    deadman: Option<DeadmanSwitch>,
+    /// Contas operacionais (replay store, histórico, contadores) por tipo
+    not_restored: BTreeMap<String, usize>,
+}
+
+impl Snapshot {
+    fn from_accounts(slot: u64, accounts: Vec<(Pubkey, Vec<u8>)>) -> Self {
+        Self {
+            program_id: workspace::ID.to_string(),
+            slot,
+            taken_at: chrono::Utc::now().timestamp(),
+            accounts: accounts
+                .into_iter()
+                .map(|(address, data)| SnapshotAccount {
+                    address: address.to_string(),
+                    kind: account_kind(&data).to_string(),
+                    data: BASE64.encode(&data),
+                })
+                .collect(),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let snapshot: Snapshot = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if snapshot.program_id != workspace::ID.to_string() {
+            return Err(format!(
+                "snapshot is from program {}, this build targets {}",
+                snapshot.program_id,
+                workspace::ID
+            )
+            .into());
+        }
+        Ok(snapshot)
+    }
+
+    fn decode(&self) -> Result<State, Box<dyn Error>> {
+        let mut state = State::default();
+        for account in &self.accounts {
+            let data = BASE64.decode(&account.data)?;
+            let mut slice = data.as_slice();
+            match account.kind.as_str() {
+                "Config" => state.config = Some(Config::try_deserialize_tolerant(&data)?),
+                "AssetRiskStatus" => {
+                    state.assets.push(AssetRiskStatus::try_deserialize_tolerant(&data)?)
+                }
+                "AssetPolicy" => state.policies.push(AssetPolicy::try_deserialize(&mut slice)?),
+                "AssetMint" => state.mints.push(AssetMint::try_deserialize(&mut slice)?),
+                "AssetMeta" => state.metas.push(AssetMeta::try_deserialize(&mut slice)?),
+                "AssetGroup" => state.groups.push(AssetGroup::try_deserialize(&mut slice)?),
+                "PortfolioRisk" => state.portfolio = Some(PortfolioRisk::try_deserialize(&mut slice)?),
+                "DeadmanSwitch" => state.deadman = Some(DeadmanSwitch::try_deserialize(&mut slice)?),
+                other => *state.not_restored.entry(other.to_string()).or_default() += 1,
+            }
+        }
+        state.assets.sort_by_key(|a| a.asset_id);
+        state.policies.sort_by_key(|p| p.asset_id);
+        Ok(state)
+    }
+}
+
+/// Tipo da conta pelo discriminator do Anchor
+fn account_kind(data: &[u8]) -> &'static str {
+    // Mesmo discriminator do layout atual; não é restaurada (ver migrate-v1)
+    if AssetRiskStatusV1::is_v1(data) {
+        return "AssetRiskStatusV1";
+    }
+    let kinds: [(&[u8], &'static str); 9] = [
+        (Config::DISCRIMINATOR, "Config"),
+        (AssetRiskStatus::DISCRIMINATOR, "AssetRiskStatus"),
+        (AssetPolicy::DISCRIMINATOR, "AssetPolicy"),
+        (AssetMint::DISCRIMINATOR, "AssetMint"),
+        (AssetMeta::DISCRIMINATOR, "AssetMeta"),
+        (AssetGroup::DISCRIMINATOR, "AssetGroup"),
+        (PortfolioRisk::DISCRIMINATOR, "PortfolioRisk"),
+        (DeadmanSwitch::DISCRIMINATOR, "DeadmanSwitch"),
+        (PendingPolicyChange::DISCRIMINATOR, "PendingPolicyChange"),
+    ];
+    kinds
+        .iter()
+        .find(|(discriminator, _)| data.starts_with(discriminator))
+        .map_or("Other", |(_, kind)| kind)
+}
+
+async fn snapshot(rpc_url: &str, out: &Path) -> Result<(), Box<dyn Error>> {
+    let chain = Chain::new(rpc_url, Keypair::new());
+    let (slot, accounts) = chain.program_accounts().await?;
+    let snapshot = Snapshot::from_accounts(slot, accounts);
+    std::fs::write(out, serde_json::to_string_pretty(&snapshot)?)?;
+    println!("{} accounts at slot {} written to {}", snapshot.accounts.len(), slot, out.display());
+    Ok(())
+}
+
+// ============================================================================
+// Relatório
+// ============================================================================
+
+#[derive(Serialize)]
+struct Report {
+    program_id: String,
+    rpc_url: String,
+    snapshot_slot: u64,
+    generated_at: i64,
+    steps: Vec<Step>,
+    assets: Vec<AssetCheck>,
+    policies: Vec<PolicyCheck>,
+    /// Ações que dependem de timelock ou de intervenção manual
+    pending: Vec<String>,
+    ok: bool,
+}
+
+#[derive(Serialize)]
+struct Step {
+    name: String,
+    ok: bool,
+    detail: String,
+}
+
+#[derive(Serialize)]
+struct AssetCheck {
+    asset_id: String,
+    /// ok | mismatch | missing | skipped
+    status: &'static str,
+    mismatches: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct PolicyCheck {
+    change: String,
+    /// applied | queued | missing
+    status: &'static str,
+}
+
+impl Report {
+    fn new(rpc_url: &str, snapshot: &Snapshot) -> Self {
+        Self {
+            program_id: workspace::ID.to_string(),
+            rpc_url: rpc_url.to_string(),
+            snapshot_slot: snapshot.slot,
+            generated_at: chrono::Utc::now().timestamp(),
+            steps: Vec::new(),
+            assets: Vec::new(),
+            policies: Vec::new(),
+            pending: Vec::new(),
+            ok: true,
+        }
+    }
+
+    fn step(&mut self, name: &str, detail: impl Into<String>) {
+        let detail = detail.into();
+        println!("[ok]   {:<20} {}", name, detail);
+        self.steps.push(Step { name: name.to_string(), ok: true, detail });
+    }
+
+    fn failed(&mut self, name: &str, err: &dyn Error) {
+        println!("[fail] {:<20} {}", name, err);
+        self.steps.push(Step { name: name.to_string(), ok: false, detail: err.to_string() });
+        self.ok = false;
+    }
+
+    fn pending(&mut self, note: String) {
+        println!("[todo] {}", note);
+        self.pending.push(note);
+    }
+
+    fn write(mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        self.ok &= self.steps.iter().all(|s| s.ok)
+            && self.assets.iter().all(|a| matches!(a.status, "ok" | "skipped"))
+            && self.policies.iter().all(|p| p.status != "missing");
+        std::fs::write(path, serde_json::to_string_pretty(&self)?)?;
+        println!(
+            "report written to {}: {}",
+            path.display(),
+            if self.ok { "OK" } else { "FAILED" }
+        );
+        if self.ok {
+            Ok(())
+        } else {
+            Err("verification failed, see report".into())
+        }
+    }
+}
+
+// ============================================================================
+// Restore
+// ============================================================================
+
+async fn restore(rpc_url: &str, args: RestoreArgs) -> Result<(), Box<dyn Error>> {
+    let snapshot = Snapshot::load(&args.snapshot)?;
+    let state = snapshot.decode()?;
+    let authority = read_keypair_file(&args.authority)?;
+    let signers = args
+        .signers
+        .iter()
+        .map(read_keypair_file)
+        .collect::<Result<Vec<_>, _>>()?;
+    let quorum = args
+        .quorum
+        .or(state.config.as_ref().map(|c| c.quorum))
+        .unwrap_or(1)
+        .clamp(1, signers.len() as u8);
+
+    let chain = Chain::new(rpc_url, authority);
+    let mut report = Report::new(rpc_url, &snapshot);
+
+    // Cada etapa depende da anterior: na primeira falha o relatório é gravado e o
+    // restore para, para que o operador retome manualmente a partir dali
+    let result = restore_steps(&chain, &args, &state, &signers, quorum, &mut report).await;
+    if let Err(err) = result {
+        report.failed("restore", err.as_ref());
+        return report.write(&args.report);
+    }
+
+    match verify(&chain, &state, &mut report).await {
+        Ok(()) => report.step("verify", "on-chain state compared with snapshot"),
+        Err(err) => report.failed("verify", err.as_ref()),
+    }
+    report.write(&args.report)
+}
+
+async fn restore_steps(
+    chain: &Chain,
+    args: &RestoreArgs,
+    state: &State,
+    signers: &[Keypair],
+    quorum: u8,
+    report: &mut Report,
+) -> Result<(), Box<dyn Error>> {
+    deploy(chain, args, report).await?;
+
+    let (config, _) = config_pda();
+    if chain.account_data(&config).await?.is_some() {
+        return Err("config already initialized: restore targets a fresh deployment".into());
+    }
+    let genesis_hash = chain.genesis_hash().await?;
+    let ix = program_ix(
+        accounts::InitializeConfig {
+            config,
+            used_decisions: used_decisions_pda(0).0,
+            authority: chain.payer(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeConfig { trusted_signer: signers[0].pubkey(), genesis_hash },
+    );
+    let sig = chain.send(&[ix]).await?;
+    report.step("initialize_config", format!("signer {} ({})", signers[0].pubkey(), sig));
+
+    let shard_ixs: Vec<_> = (1..REPLAY_SHARD_COUNT)
+        .map(|shard| {
+            program_ix(
+                accounts::InitializeReplayShard {
+                    config,
+                    used_decisions: used_decisions_pda(shard).0,
+                    authority: chain.payer(),
+                    payer: chain.payer(),
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeReplayShard { shard },
+            )
+        })
+        .collect();
+    let sig = chain.send(&shard_ixs).await?;
+    report.step("replay_shards", format!("{} shards ({})", shard_ixs.len(), sig));
+
+    // Decisões só são aceitas para ativos cadastrados
+    for meta in &state.metas {
+        let asset_id = asset_id_string(&meta.asset_id);
+        let ix = program_ix(
+            accounts::RegisterAsset {
+                config,
+                asset_meta: asset_meta_pda(&asset_id).0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::RegisterAsset {
+                asset_id: asset_id.clone(),
+                mint: meta.mint,
+                pyth_feed: meta.pyth_feed,
+                category: meta.category,
+                decimals: meta.decimals,
+            },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("register_asset", format!("{} ({})", asset_id, sig));
+    }
+
+    for asset in &state.assets {
+        restore_asset(chain, asset, &signers[0], genesis_hash, report).await?;
+    }
+
+    // Depois dos ativos: cada entrada começa com o score já restaurado
+    for entry in state.portfolio.iter().flat_map(|p| p.entries.iter()) {
+        let asset_id = asset_id_string(&entry.asset_id);
+        let asset_risk = asset_risk_pda(&asset_id).0;
+        let ix = program_ix(
+            accounts::SetPortfolioWeight {
+                config,
+                portfolio: portfolio_pda().0,
+                asset_meta: asset_meta_pda(&asset_id).0,
+                asset_risk_status: chain.account_data(&asset_risk).await?.map(|_| asset_risk),
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::SetPortfolioWeight { asset_id: asset_id.clone(), weight: entry.weight },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step(
+            "set_portfolio_weight",
+            format!("{} weight={} ({})", asset_id, entry.weight, sig),
+        );
+    }
+
+    for mint in &state.mints {
+        let asset_id = asset_id_string(&mint.asset_id);
+        let ix = program_ix(
+            accounts::RegisterAssetMint {
+                config,
+                asset_mint: asset_mint_pda(&mint.mint).0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::RegisterAssetMint { asset_id: asset_id.clone(), mint: mint.mint },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("register_asset_mint", format!("{} -> {} ({})", mint.mint, asset_id, sig));
+    }
+
+    for group in &state.groups {
+        let group_id = asset_id_string(&group.group_id);
+        let ix = program_ix(
+            accounts::SetAssetGroup {
+                config,
+                asset_group: asset_group_pda(&group_id).0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::SetAssetGroup {
+                group_id: group_id.clone(),
+                assets: group.members.iter().map(asset_id_string).collect(),
+            },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step(
+            "set_asset_group",
+            format!("{} ({} members, {})", group_id, group.members.len(), sig),
+        );
+    }
+
+    if let Some(deadman) = &state.deadman {
+        let ix = program_ix(
+            accounts::ConfigureDeadman {
+                config,
+                deadman: deadman_pda().0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::ConfigureDeadman {
+                sentinel_asset_id: asset_id_string(&deadman.sentinel_asset_id),
+                window_seconds: deadman.window_seconds,
+                block_all: deadman.block_all,
+                assets: deadman.assets.iter().map(asset_id_string).collect(),
+            },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("configure_deadman", format!("window {}s ({})", deadman.window_seconds, sig));
+        if deadman.triggered {
+            report.pending("dead-man switch was triggered in the snapshot; it restarts armed".into());
+        }
+    }
+
+    // Políticas passam pelo timelock como qualquer outra mudança
+    let restored = decode_onchain(chain).await?;
+    let current: Vec<Vec<u8>> = policy_changes(&restored).iter().map(encode_change).collect();
+    let mut next_id = restored.config.as_ref().map_or(0, |c| c.nonce);
+    for change in policy_changes(state) {
+        if current.contains(&encode_change(&change)) {
+            continue;
+        }
+        let ix = program_ix(
+            accounts::QueuePolicyChange {
+                config,
+                pending_change: pending_change_pda(next_id).0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::QueuePolicyChange { change: change.clone() },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("queue_policy_change", format!("#{} {} ({})", next_id, describe(&change), sig));
+        report.pending(format!("apply policy change #{} after the policy delay", next_id));
+        next_id += 1;
+    }
+
+    if signers.len() > 1 || quorum > 1 {
+        let effective_at = chrono::Utc::now().timestamp()
+            + DEFAULT_SIGNER_ROTATION_DELAY_SECS
+            + ROTATION_MARGIN_SECS;
+        let ix = program_ix(
+            accounts::UpdateTrustedSigner { config, authority: chain.payer() },
+            instruction::ProposeSignerSet {
+                signers: signers.iter().map(|s| s.pubkey()).collect(),
+                weights: Vec::new(),
+                quorum,
+                effective_at,
+            },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step(
+            "propose_signer_set",
+            format!("{}-of-{} effective at {} ({})", quorum, signers.len(), effective_at, sig),
+        );
+        report.pending(format!("activate_signer after {}", effective_at));
+    }
+    // Os pesos do snapshot são das chaves antigas; o novo conjunto entra com peso 1
+    if state.config.as_ref().is_some_and(|c| !c.signer_weights.is_empty()) {
+        report.pending("re-propose signer weights for the new keys".into());
+    }
+
+    for (kind, count) in &state.not_restored {
+        report.step("skip", format!("{} {} accounts are operational state", count, kind));
+    }
+
+    if let Some(path) = &args.keeper_env {
+        write_keeper_env(path, chain, args)?;
+        report.step("keeper_env", format!("{} points at {}", path.display(), workspace::ID));
+    } else {
+        report.pending("re-point the keeper: no --keeper-env given".into());
+    }
+    Ok(())
+}
+
+async fn deploy(chain: &Chain, args: &RestoreArgs, report: &mut Report) -> Result<(), Box<dyn Error>> {
+    let (Some(so), Some(program_keypair)) = (&args.program_so, &args.program_keypair) else {
+        if !chain.program_deployed().await? {
+            return Err("program not deployed and no --program-so given".into());
+        }
+        report.step("deploy", "skipped, program already deployed");
+        return Ok(());
+    };
+
+    let program_id = read_keypair_file(program_keypair)?.pubkey();
+    if program_id != workspace::ID {
+        return Err(format!(
+            "program keypair is {}, this build declares {}",
+            program_id,
+            workspace::ID
+        )
+        .into());
+    }
+    let output = Process::new("solana")
+        .arg("program")
+        .arg("deploy")
+        .arg("--url")
+        .arg(chain.rpc().url())
+        .arg("--keypair")
+        .arg(&args.authority)
+        .arg("--program-id")
+        .arg(program_keypair)
+        .arg(so)
+        .output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "solana program deploy failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    report.step("deploy", String::from_utf8_lossy(&output.stdout).trim().to_string());
+    Ok(())
+}
+
+/// Re-assina o estado do ativo com a chave nova. Ativos `Listed` não são
+/// recriados: uma decisão os tornaria `Live`, liberando trading.
+async fn restore_asset(
+    chain: &Chain,
+    asset: &AssetRiskStatus,
+    signer: &Keypair,
+    genesis_hash: [u8; 32],
+    report: &mut Report,
+) -> Result<(), Box<dyn Error>> {
+    let asset_id = asset_id_string(&asset.asset_id);
+    if asset.lifecycle() == AssetLifecycle::Listed {
+        report.pending(format!("{} was listed but not live; list it again manually", asset_id));
+        return Ok(());
+    }
+    let now = chrono::Utc::now().timestamp();
+    // Regravar sem validade desbloquearia um ativo que o gate já trata como bloqueado
+    if asset.is_expired(now) {
+        report.pending(format!("{} decision had expired; wait for a fresh one", asset_id));
+        return Ok(());
+    }
+
+    let payload = DecisionPayload {
+        asset_id: asset.asset_id,
+        risk_score: asset.risk_score,
+        is_blocked: asset.is_blocked != 0,
+        reduce_only: asset.reduce_only != 0,
+        risk_flags: asset.risk_flags,
+        sub_scores: asset.sub_scores,
+        confidence_ratio: asset.confidence_ratio,
+        publisher_count: asset.publisher_count,
+        publisher_ids: asset.publisher_ids,
+        timestamp: now,
+        nonce: asset.nonce,
+        // O engine retoma acima da última sequence do snapshot
+        sequence: asset.sequences().iter().map(|s| s.last_sequence).max().unwrap_or(0).max(1),
+        valid_until: asset.valid_until,
+    };
+    let decision_hash =
+        workspace::message::decision_hash(&workspace::ID, &genesis_hash, &payload);
+    let (ed25519, signatures) = ed25519_ix(&decision_hash, &[signer]);
+    let update = program_ix(
+        accounts::UpdateRiskStatus {
+            config: config_pda().0,
+            used_decisions: Some(used_decisions_pda_for(&decision_hash).0),
+            asset_risk_status: asset_risk_pda(&asset_id).0,
+            authority: chain.payer(),
+            keeper: chain.payer(),
+            instructions_sysvar: sysvar::instructions::ID,
+            deadman: None,
+            asset_policy: asset_policy_pda(&asset_id).0,
+            pending_decision: None,
+            asset_history: asset_history_pda(&asset_id).0,
+            pyth_price: None,
+            switchboard_feed: None,
+            roles: None,
+            asset_meta: asset_meta_pda(&asset_id).0,
+            portfolio: None,
+            stats: None,
+            replay_bloom: None,
+            treasury: None,
+            payer_fee_token: None,
+            treasury_fee_token: None,
+            fee_mint: None,
+            token_program: None,
+            session_key: None,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateRiskStatus {
+            asset_id: asset_id.clone(),
+            risk_score: payload.risk_score,
+            is_blocked: payload.is_blocked,
+            reduce_only: payload.reduce_only,
+            risk_flags: payload.risk_flags,
+            sub_scores: payload.sub_scores,
+            confidence_ratio: payload.confidence_ratio,
+            publisher_count: payload.publisher_count,
+            publisher_ids: payload.publisher_ids,
+            timestamp: payload.timestamp,
+            nonce: payload.nonce,
+            sequence: payload.sequence,
+            valid_until: payload.valid_until,
+            decision_hash,
+            signatures,
+            signer_pubkeys: vec![signer.pubkey().to_bytes()],
+            secondary: None,
+        },
+    );
+    let sig = chain.send(&[ed25519, update]).await?;
+    report.step("restore_asset", format!("{} score={} ({})", asset_id, asset.risk_score, sig));
+
+    // Live -> Delisting -> Delisted, nas transições permitidas
+    let path: &[AssetLifecycle] = match asset.lifecycle() {
+        AssetLifecycle::Delisting => &[AssetLifecycle::Delisting],
+        AssetLifecycle::Delisted => &[AssetLifecycle::Delisting, AssetLifecycle::Delisted],
+        AssetLifecycle::Listed | AssetLifecycle::Live => &[],
+    };
+    for lifecycle in path {
+        let ix = program_ix(
+            accounts::SetAssetLifecycle {
+                config: config_pda().0,
+                asset_risk_status: asset_risk_pda(&asset_id).0,
+                authority: chain.payer(),
+                payer: chain.payer(),
+                system_program: system_program::ID,
+            },
+            instruction::SetAssetLifecycle { asset_id: asset_id.clone(), lifecycle: *lifecycle },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("set_asset_lifecycle", format!("{} {:?} ({})", asset_id, lifecycle, sig));
+    }
+
+    if asset.admin_reduce_only != 0 {
+        let ix = program_ix(
+            accounts::SetReduceOnly {
+                config: config_pda().0,
+                asset_risk_status: asset_risk_pda(&asset_id).0,
+                authority: chain.payer(),
+            },
+            instruction::SetReduceOnly { asset_id: asset_id.clone(), reduce_only: true },
+        );
+        let sig = chain.send(&[ix]).await?;
+        report.step("set_reduce_only", format!("{} ({})", asset_id, sig));
+    }
+
+    if asset.breaker_until > chrono::Utc::now().timestamp() {
+        report.pending(format!(
+            "{} had its circuit breaker armed until {}; block it manually if still needed",
+            asset_id, asset.breaker_until
+        ));
+    }
+    Ok(())
+}
+
+/// Atualiza as variáveis do keeper preservando o resto do arquivo
+fn write_keeper_env(path: &Path, chain: &Chain, args: &RestoreArgs) -> Result<(), Box<dyn Error>> {
+    let signers: Vec<String> = args.signers.iter().map(|p| p.display().to_string()).collect();
+    let values = [
+        ("CATE_PROGRAM_ID", workspace::ID.to_string()),
+        ("CATE_RPC_URL", chain.rpc().url()),
+        ("CATE_AUTHORITY_KEYPAIR", args.authority.display().to_string()),
+        ("CATE_SIGNER_KEYPAIRS", signers.join(",")),
+    ];
+
+    let existing = std::fs::read_to_string(path).unwrap_or_default();
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| !values.iter().any(|(key, _)| line.starts_with(&format!("{}=", key))))
+        .map(str::to_string)
+        .collect();
+    lines.extend(values.iter().map(|(key, value)| format!("{}={}", key, value)));
+    std::fs::write(path, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+// ============================================================================
+// Verificação
+// ============================================================================
+
+async fn decode_onchain(chain: &Chain) -> Result<State, Box<dyn Error>> {
+    let (slot, accounts) = chain.program_accounts().await?;
+    Snapshot::from_accounts(slot, accounts).decode()
+}
+
+async fn verify(chain: &Chain, expected: &State, report: &mut Report) -> Result<(), Box<dyn Error>> {
+    for asset in &expected.assets {
+        let asset_id = asset_id_string(&asset.asset_id);
+        let check = match chain.account_data(&asset_risk_pda(&asset_id).0).await? {
+            None if asset.lifecycle() == AssetLifecycle::Listed => {
+                AssetCheck { asset_id, status: "skipped", mismatches: Vec::new() }
+            }
+            None => AssetCheck { asset_id, status: "missing", mismatches: Vec::new() },
+            Some(data) => {
+                let actual = AssetRiskStatus::try_deserialize_tolerant(&data)?;
+                let mismatches = asset_mismatches(asset, &actual);
+                let status = if mismatches.is_empty() { "ok" } else { "mismatch" };
+                AssetCheck { asset_id, status, mismatches }
+            }
+        };
+        report.assets.push(check);
+    }
+
+    for meta in &expected.metas {
+        let asset_id = asset_id_string(&meta.asset_id);
+        let registered = match chain.account_data(&asset_meta_pda(&asset_id).0).await? {
+            Some(data) => {
+                let actual = AssetMeta::try_deserialize(&mut data.as_slice())?;
+                actual.mint == meta.mint && actual.pyth_feed == meta.pyth_feed
+            }
+            None => false,
+        };
+        report.assets.push(AssetCheck {
+            asset_id: format!("meta:{}", asset_id),
+            status: if registered { "ok" } else { "missing" },
+            mismatches: Vec::new(),
+        });
+    }
+
+    for mint in &expected.mints {
+        let registered = match chain.account_data(&asset_mint_pda(&mint.mint).0).await? {
+            Some(data) => AssetMint::try_deserialize(&mut data.as_slice())?.asset_id == mint.asset_id,
+            None => false,
+        };
+        report.assets.push(AssetCheck {
+            asset_id: format!("mint:{}", mint.mint),
+            status: if registered { "ok" } else { "missing" },
+            mismatches: Vec::new(),
+        });
+    }
+
+    let actual = decode_onchain(chain).await?;
+    let applied: Vec<Vec<u8>> = policy_changes(&actual).iter().map(encode_change).collect();
+    let mut queued = Vec::new();
+    for pending_address in pending_change_addresses(&actual) {
+        if let Some(data) = chain.account_data(&pending_address).await? {
+            let pending = PendingPolicyChange::try_deserialize(&mut data.as_slice())?;
+            queued.push(encode_change(&pending.change));
+        }
+    }
+    for change in policy_changes(expected) {
+        let encoded = encode_change(&change);
+        let status = if applied.contains(&encoded) {
+            "applied"
+        } else if queued.contains(&encoded) {
+            "queued"
+        } else {
+            "missing"
+        };
+        report.policies.push(PolicyCheck { change: describe(&change), status });
+    }
+    Ok(())
+}
+
+fn asset_mismatches(expected: &AssetRiskStatus, actual: &AssetRiskStatus) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let mut compare = |field: &str, expected: String, actual: String| {
+        if expected != actual {
+            mismatches.push(format!("{}: expected {}, found {}", field, expected, actual));
+        }
+    };
+    compare("risk_score", expected.risk_score.to_string(), actual.risk_score.to_string());
+    compare("is_blocked", expected.is_blocked.to_string(), actual.is_blocked.to_string());
+    compare("reduce_only", expected.reduce_only.to_string(), actual.reduce_only.to_string());
+    compare(
+        "admin_reduce_only",
+        expected.admin_reduce_only.to_string(),
+        actual.admin_reduce_only.to_string(),
+    );
+    compare("risk_flags", expected.risk_flags.to_string(), actual.risk_flags.to_string());
+    compare("valid_until", expected.valid_until.to_string(), actual.valid_until.to_string());
+    compare(
+        "sub_scores",
+        format!("{:?}", expected.sub_scores),
+        format!("{:?}", actual.sub_scores),
+    );
+    compare(
+        "confidence_ratio",
+        expected.confidence_ratio.to_string(),
+        actual.confidence_ratio.to_string(),
+    );
+    compare(
+        "publisher_count",
+        expected.publisher_count.to_string(),
+        actual.publisher_count.to_string(),
+    );
+    compare(
+        "publisher_ids",
+        format!("{:?}", expected.publisher_ids),
+        format!("{:?}", actual.publisher_ids),
+    );
+    compare(
+        "lifecycle",
+        format!("{:?}", expected.lifecycle()),
+        format!("{:?}", actual.lifecycle()),
+    );
+    mismatches
+}
+
+/// PDAs das mudanças que ainda podem estar pendentes (ids abaixo do nonce)
+fn pending_change_addresses(state: &State) -> Vec<Pubkey> {
+    let nonce = state.config.as_ref().map_or(0, |c| c.nonce);
+    (0..nonce).map(|id| pending_change_pda(id).0).collect()
+}
+
+/// Estado de política expresso como as mudanças que o reproduzem.
+/// PolicyDelay vai por último para não atrasar as demais.
+fn policy_changes(state: &State) -> Vec<PolicyChange> {
+    let mut changes = Vec::new();
+    if let Some(config) = &state.config {
+        changes.push(PolicyChange::MaxStaleness { max_staleness_secs: config.max_staleness_secs });
+        changes.push(PolicyChange::KeyUsageLimits {
+            max_unblocks_per_day: config.max_unblocks_per_day,
+            max_score_decrease_per_day: config.max_score_decrease_per_day,
+        });
+        changes.push(PolicyChange::CircuitBreaker {
+            circuit_breaker: config.circuit_breaker.clone(),
+        });
+        changes.push(PolicyChange::SignerRotationDelay {
+            delay_secs: config.signer_rotation_delay_secs,
+        });
+        changes.push(PolicyChange::TimestampWindow {
+            max_past_secs: config.max_past_secs,
+            max_future_secs: config.max_future_secs,
+        });
+        changes.push(PolicyChange::ReplayMode { replay_mode: config.replay_mode });
+        changes.push(PolicyChange::DualEngine { dual_engine: config.dual_engine.clone() });
+        for entry in &config.signer_key_types {
+            changes.push(PolicyChange::SignerKeyType {
+                signer: entry.signer,
+                key_type: entry.key_type,
+            });
+        }
+        for entry in &config.signer_expiries {
+            changes.push(PolicyChange::SignerExpiry {
+                signer: entry.signer,
+                expires_at: entry.expires_at,
+            });
+        }
+        for signer in &config.emergency_signers {
+            changes.push(PolicyChange::EmergencySigner { signer: *signer, enabled: true });
+        }
+        changes.push(PolicyChange::UnblockCooldown {
+            unblock_cooldown: config.unblock_cooldown.clone(),
+        });
+        changes.push(PolicyChange::ReopenRamp { steps: config.reopen_ramp.clone() });
+        changes.push(PolicyChange::PortfolioBreaker { threshold: config.portfolio_breaker });
+        changes.push(PolicyChange::ScoreEma { alpha_bps: config.score_ema_alpha_bps });
+        changes.push(PolicyChange::HeartbeatSla { sla_secs: config.heartbeat_sla_secs });
+        changes.push(PolicyChange::UpdateFee { update_fee: config.update_fee.clone() });
+        changes.push(PolicyChange::KeeperTip { keeper_tip: config.keeper_tip.clone() });
+        changes.push(PolicyChange::ConsumerAccess {
+            consumer_access: config.consumer_access.clone(),
+        });
+        changes.push(PolicyChange::TradeLimits {
+            min_confidence_ratio: config.min_confidence_ratio,
+            notional_tiers: config.notional_tiers.clone(),
+        });
+        changes.push(PolicyChange::DecisionFloor {
+            min_publisher_count: config.min_publisher_count,
+            min_confidence_bps: config.min_confidence_bps,
+            block_below_floor: config.block_below_floor,
+        });
+        changes.push(PolicyChange::RiskTiers { risk_tiers: config.risk_tier_thresholds });
+    }
+    for policy in &state.policies {
+        changes.push(PolicyChange::AssetPolicy {
+            asset_id: policy.asset_id,
+            advisory_only: policy.advisory_only,
+            block_threshold: policy.block_threshold,
+            max_staleness_secs: policy.max_staleness_secs,
+            pyth_check: policy.pyth_check.clone(),
+            switchboard_check: policy.switchboard_check.clone(),
+        });
+    }
+    if let Some(config) = &state.config {
+        changes.push(PolicyChange::PolicyDelay { delay_secs: config.policy_delay_secs });
+    }
+    changes
+}
+
+/// PolicyChange não implementa PartialEq; compara pela serialização Borsh
+fn encode_change(change: &PolicyChange) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    change.serialize(&mut bytes).expect("serializing to a Vec cannot fail");
+    bytes
+}
+
+fn describe(change: &PolicyChange) -> String {
+    match change {
+        PolicyChange::MaxStaleness { max_staleness_secs } => {
+            format!("max_staleness={}s", max_staleness_secs)
+        }
+        PolicyChange::KeyUsageLimits { max_unblocks_per_day, max_score_decrease_per_day } => {
+            format!(
+                "key_usage unblocks={:?} score_decrease={:?}",
+                max_unblocks_per_day, max_score_decrease_per_day
+            )
+        }
+        PolicyChange::CircuitBreaker { circuit_breaker } => match circuit_breaker {
+            Some(cb) => format!(
+                "circuit_breaker rise={} window={}s cooldown={}s",
+                cb.max_rise, cb.window_secs, cb.cooldown_secs
+            ),
+            None => "circuit_breaker off".to_string(),
+        },
+        PolicyChange::PolicyDelay { delay_secs } => format!("policy_delay={}s", delay_secs),
+        PolicyChange::SignerRotationDelay { delay_secs } => {
+            format!("signer_rotation_delay={}s", delay_secs)
+        }
+        PolicyChange::ReplayMode { replay_mode } => format!("replay_mode={:?}", replay_mode),
+        PolicyChange::TimestampWindow { max_past_secs, max_future_secs } => {
+            format!("timestamp_window past={}s future={}s", max_past_secs, max_future_secs)
+        }
+        PolicyChange::DualEngine { dual_engine } => match dual_engine {
+            Some(dual) => format!(
+                "dual_engine secondary={} guardian={} tolerance={} large_decrease={}",
+                dual.secondary_signer, dual.guardian, dual.tolerance, dual.large_decrease
+            ),
+            None => "dual_engine off".to_string(),
+        },
+        PolicyChange::SignerKeyType { signer, key_type } => {
+            format!("signer_key_type {}={:?}", signer, key_type)
+        }
+        PolicyChange::UnblockCooldown { unblock_cooldown } => match unblock_cooldown {
+            Some(cooldown) => format!(
+                "unblock_cooldown {}s decisions={}",
+                cooldown.cooldown_secs, cooldown.required_decisions
+            ),
+            None => "unblock_cooldown off".to_string(),
+        },
+        PolicyChange::ReopenRamp { steps } => format!(
+            "reopen_ramp [{}]",
+            steps
+                .iter()
+                .map(|step| format!("<{}s:{}bps", step.until_secs, step.factor_bps))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        PolicyChange::PortfolioBreaker { threshold } => match threshold {
+            Some(threshold) => format!("portfolio_breaker threshold={}", threshold),
+            None => "portfolio_breaker off".to_string(),
+        },
+        PolicyChange::ScoreEma { alpha_bps } => format!("score_ema alpha={}bps", alpha_bps),
+        PolicyChange::HeartbeatSla { sla_secs } => match sla_secs {
+            Some(sla) => format!("heartbeat_sla={}s", sla),
+            None => "heartbeat_sla off".to_string(),
+        },
+        PolicyChange::UpdateFee { update_fee } => match update_fee {
+            Some(UpdateFee { amount, mint: Some(mint) }) => {
+                format!("update_fee={} of mint {}", amount, mint)
+            }
+            Some(UpdateFee { amount, mint: None }) => format!("update_fee={} lamports", amount),
+            None => "update_fee off".to_string(),
+        },
+        PolicyChange::KeeperTip { keeper_tip } => match keeper_tip {
+            Some(tip) => format!(
+                "keeper_tip={} lamports after {}s",
+                tip.lamports, tip.min_age_secs
+            ),
+            None => "keeper_tip off".to_string(),
+        },
+        PolicyChange::ConsumerAccess { consumer_access } => match consumer_access {
+            Some(access) => {
+                format!("consumer_access fee={} lamports/epoch", access.fee_per_epoch)
+            }
+            None => "consumer_access off".to_string(),
+        },
+        PolicyChange::TradeLimits { min_confidence_ratio, notional_tiers } => format!(
+            "trade_limits min_confidence={:?} tiers=[{}]",
+            min_confidence_ratio,
+            notional_tiers
+                .iter()
+                .map(|tier| format!("<={}:{}", tier.max_score, tier.max_notional))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        PolicyChange::DecisionFloor {
+            min_publisher_count,
+            min_confidence_bps,
+            block_below_floor,
+        } => format!(
+            "decision_floor publishers>={} confidence>={}bps ({})",
+            min_publisher_count,
+            min_confidence_bps,
+            if *block_below_floor { "block" } else { "reject" }
+        ),
+        PolicyChange::SignerExpiry { signer, expires_at } => {
+            format!("signer_expiry {}={}", signer, expires_at)
+        }
+        PolicyChange::EmergencySigner { signer, enabled } => {
+            format!("emergency_signer {} {}", signer, if *enabled { "on" } else { "off" })
+        }
+        PolicyChange::RiskTiers { risk_tiers } => match risk_tiers {
+            Some(tiers) => format!(
+                "risk_tiers medium>={} high>={} critical>={}",
+                tiers.medium, tiers.high, tiers.critical
+            ),
+            None => "risk_tiers default".to_string(),
+        },
+        PolicyChange::AssetPolicy {
+            asset_id,
+            advisory_only,
+            block_threshold,
+            max_staleness_secs,
+            pyth_check,
+            switchboard_check,
+        } => {
+            format!(
+                "asset_policy {} advisory={} threshold={:?} staleness={:?} pyth={} switchboard={}",
+                asset_id_string(asset_id),
+                advisory_only,
+                block_threshold,
+                max_staleness_secs,
+                match pyth_check {
+                    Some(check) => format!(
+                        "{} tolerance={}bps max_age={}s",
+                        hex(&check.feed_id),
+                        check.tolerance_bps,
+                        check.max_age_secs
+                    ),
+                    None => "off".to_string(),
+                },
+                match switchboard_check {
+                    Some(check) => format!("{} max_age={}s", check.feed, check.max_age_secs),
+                    None => "off".to_string(),
+                }
+            )
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
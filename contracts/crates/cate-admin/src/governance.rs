@@ -0,0 +1,134 @@
+//! `cate-admin governance` — instruções para propostas do SPL Governance.
+//!
+//! Com `Config.authority` apontando para a governance (PDA que o programa de
+//! governança assina via CPI ao executar uma proposta), rotação de signers,
+//! troca de quorum e pausa passam por votação. Cada instrução sai em base64
+//! no formato `InstructionData` do SPL Governance, o que a opção "custom
+//! instruction" da Realms aceita. Nenhuma delas cria contas, então a proposta
+//! não depende de um pagador de aluguel.
+
+use std::error::Error;
+
+use anchor_lang::AnchorSerialize;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use cate_client::config_pda;
+use clap::{Args, Subcommand};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use workspace::{accounts, instruction, DEFAULT_SIGNER_ROTATION_DELAY_SECS};
+
+use crate::chain::program_ix;
+
+/// Folga sobre o atraso mínimo de rotação: a execução da proposta não é
+/// instantânea e o relógio do cluster pode diferir
+const DEFAULT_ROTATION_DELAY_SECS: i64 = DEFAULT_SIGNER_ROTATION_DELAY_SECS + 300;
+
+#[derive(Args)]
+pub struct GovernanceArgs {
+    /// Conta de governance que é (ou será) a authority do Config
+    #[arg(long)]
+    governance: Pubkey,
+
+    #[command(subcommand)]
+    action: GovernanceAction,
+}
+
+#[derive(Subcommand)]
+enum GovernanceAction {
+    /// Aceita a authority proposta para a governance (após propose_authority)
+    AcceptAuthority,
+    /// Propõe um novo conjunto de signers, ativável `delay_secs` após a execução
+    RotateSigners {
+        #[arg(long = "signer", required = true)]
+        signers: Vec<Pubkey>,
+        /// Peso de cada --signer, na mesma ordem (omitido = 1 para todos)
+        #[arg(long = "weight")]
+        weights: Vec<u8>,
+        /// Peso mínimo somado das assinaturas
+        #[arg(long)]
+        quorum: u8,
+        #[arg(long, default_value_t = DEFAULT_ROTATION_DELAY_SECS)]
+        delay_secs: i64,
+    },
+    /// Propõe um novo quorum para o conjunto atual
+    SetQuorum {
+        #[arg(long)]
+        quorum: u8,
+        #[arg(long, default_value_t = DEFAULT_ROTATION_DELAY_SECS)]
+        delay_secs: i64,
+    },
+    /// Ativa a rotação pendente (proposta separada, depois do timelock)
+    ActivateSigners,
+    Pause,
+    Unpause,
+}
+
+/// Layout borsh de `InstructionData` do SPL Governance
+#[derive(AnchorSerialize)]
+struct InstructionData {
+    program_id: Pubkey,
+    accounts: Vec<AccountMetaData>,
+    data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize)]
+struct AccountMetaData {
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl From<Instruction> for InstructionData {
+    fn from(ix: Instruction) -> Self {
+        Self {
+            program_id: ix.program_id,
+            accounts: ix
+                .accounts
+                .into_iter()
+                .map(|meta| AccountMetaData {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data,
+        }
+    }
+}
+
+pub fn run(args: GovernanceArgs) -> Result<(), Box<dyn Error>> {
+    let config = config_pda().0;
+    let authority = args.governance;
+    let ix = match args.action {
+        GovernanceAction::AcceptAuthority => program_ix(
+            accounts::AcceptAuthority { config, new_authority: authority },
+            instruction::AcceptAuthority {},
+        ),
+        GovernanceAction::RotateSigners { signers, weights, quorum, delay_secs } => program_ix(
+            accounts::UpdateTrustedSigner { config, authority },
+            instruction::ProposeSignerSetAfter { signers, weights, quorum, delay_secs },
+        ),
+        GovernanceAction::SetQuorum { quorum, delay_secs } => program_ix(
+            accounts::UpdateTrustedSigner { config, authority },
+            instruction::ProposeQuorumAfter { quorum, delay_secs },
+        ),
+        GovernanceAction::ActivateSigners => program_ix(
+            accounts::UpdateTrustedSigner { config, authority },
+            instruction::ActivateSigner {},
+        ),
+        GovernanceAction::Pause => program_ix(
+            accounts::SetPaused { config, authority, roles: None },
+            instruction::Pause {},
+        ),
+        GovernanceAction::Unpause => program_ix(
+            accounts::SetPaused { config, authority, roles: None },
+            instruction::Unpause {},
+        ),
+    };
+
+    let mut encoded = Vec::new();
+    InstructionData::from(ix).serialize(&mut encoded)?;
+    println!("{}", BASE64.encode(encoded));
+    Ok(())
+}
@@ -0,0 +1,58 @@
+//! cate-admin — CLI de operação do CATE
+//!
+//! `cate-admin watch` acompanha as contas de risco ao vivo e imprime um resumo
+//! legível de cada transição (tier antigo → novo, motivos, staleness).
+//! `cate-admin dr` executa o procedimento de disaster recovery.
+//! `cate-admin migrate-v1` migra e aposenta as contas da implantação v1.
+//! `cate-admin governance` gera instruções admin para propostas do SPL Governance.
+
+mod chain;
+mod dr;
+mod governance;
+mod migrate;
+mod tier;
+mod watch;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "cate-admin", version, about = "Operator CLI for the CATE trust layer")]
+struct Cli {
+    /// Solana RPC endpoint
+    #[arg(long, env = "CATE_RPC_URL", default_value = "https://api.devnet.solana.com", global = true)]
+    rpc_url: String,
+
+    /// Solana WebSocket endpoint
+    #[arg(long, env = "CATE_WS_URL", default_value = "wss://api.devnet.solana.com", global = true)]
+    ws_url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Acompanha eventos e imprime transições de risco
+    Watch(watch::WatchArgs),
+    /// Disaster recovery: snapshot, restore com chaves novas e verificação
+    Dr(dr::DrArgs),
+    /// Re-assina o estado v1 como decisões v2 e fecha as contas v1
+    MigrateV1(migrate::MigrateArgs),
+    /// Instruções admin em base64 para propostas do SPL Governance
+    Governance(governance::GovernanceArgs),
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Watch(args) => watch::run(&cli.rpc_url, &cli.ws_url, args).await,
+        Command::Dr(args) => dr::run(&cli.rpc_url, args).await,
+        Command::MigrateV1(args) => migrate::run(&cli.rpc_url, args).await,
+        Command::Governance(args) => governance::run(args),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
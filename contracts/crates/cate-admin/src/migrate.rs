@@ -0,0 +1,202 @@
+//! `cate-admin migrate-v1` — aposentadoria da implantação v1.
+//!
+//! Contas AssetRiskStatus no layout v1 (sem timestamp/nonce) ocupam o mesmo
+//! PDA do layout atual e não são lidas por nenhuma instrução v2. Para cada uma,
+//! o estado v1 é re-assinado pelo engine atual como decisão v2 e enviado numa
+//! única transação: `retire_v1_asset` fecha a conta v1 e `update_risk_status`
+//! a recria no layout novo. Ao final nenhuma conta v1 pode restar.
+
+use std::error::Error;
+use std::path::PathBuf;
+
+use anchor_lang::AnchorDeserialize;
+use cate_client::{
+    asset_history_pda, asset_id_string, asset_meta_pda, asset_policy_pda, asset_risk_pda,
+    config_pda, used_decisions_pda_for, DecisionPayload, MAX_PUBLISHER_IDS, RISK_CATEGORY_COUNT,
+};
+use cate_sdk::ed25519_ix;
+use clap::Args;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::{system_program, sysvar};
+use workspace::{accounts, instruction, AssetRiskStatusV1};
+
+use crate::chain::{program_ix, Chain};
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    /// Keypair da authority; paga as transações e recebe o rent das contas v1
+    #[arg(long, env = "CATE_AUTHORITY_KEYPAIR")]
+    authority: PathBuf,
+
+    /// Keypairs dos signers atuais do engine (pode repetir), o suficiente para o quorum
+    #[arg(long = "signer")]
+    signers: Vec<PathBuf>,
+
+    /// Migra só estes ativos (pode repetir); padrão: todas as contas v1
+    #[arg(long = "asset")]
+    assets: Vec<String>,
+
+    /// Lista as contas v1 sem enviar transações
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub async fn run(rpc_url: &str, args: MigrateArgs) -> Result<(), Box<dyn Error>> {
+    let authority = read_keypair_file(&args.authority)?;
+    let signers = args
+        .signers
+        .iter()
+        .map(read_keypair_file)
+        .collect::<Result<Vec<_>, _>>()?;
+    let chain = Chain::new(rpc_url, authority);
+
+    let legacy = v1_accounts(&chain, &args.assets).await?;
+    println!("{} v1 accounts found", legacy.len());
+    if args.dry_run {
+        for (address, v1) in &legacy {
+            println!(
+                "  {} {} score={} blocked={} updated={}",
+                asset_id_string(&v1.asset_id),
+                address,
+                v1.risk_score,
+                v1.is_blocked,
+                v1.last_updated
+            );
+        }
+        return Ok(());
+    }
+    if signers.is_empty() {
+        return Err("at least one --signer is required to re-sign v1 state".into());
+    }
+
+    let genesis_hash = chain.genesis_hash().await?;
+    let signer_refs: Vec<&Keypair> = signers.iter().collect();
+    for (_, v1) in &legacy {
+        let asset_id = asset_id_string(&v1.asset_id);
+        match migrate_asset(&chain, v1, &signer_refs, genesis_hash).await {
+            Ok(sig) => println!("[ok]   {:<16} score={} ({})", asset_id, v1.risk_score, sig),
+            Err(err) => println!("[fail] {:<16} {}", asset_id, err),
+        }
+    }
+
+    // Corte determinístico: só termina com sucesso sem nenhuma conta v1
+    let remaining = v1_accounts(&chain, &args.assets).await?;
+    if !remaining.is_empty() {
+        return Err(format!("{} v1 accounts remain, run again to retry", remaining.len()).into());
+    }
+    println!("v1 deployment retired: no v1 accounts left");
+    Ok(())
+}
+
+/// Contas do programa no layout v1, opcionalmente filtradas por ativo
+async fn v1_accounts(
+    chain: &Chain,
+    assets: &[String],
+) -> Result<Vec<(Pubkey, AssetRiskStatusV1)>, Box<dyn Error>> {
+    let (_, accounts) = chain.program_accounts().await?;
+    let mut legacy = Vec::new();
+    for (address, data) in accounts {
+        if !AssetRiskStatusV1::is_v1(&data) {
+            continue;
+        }
+        let v1 = AssetRiskStatusV1::deserialize(&mut &data[8..])?;
+        if assets.is_empty() || assets.contains(&asset_id_string(&v1.asset_id)) {
+            legacy.push((address, v1));
+        }
+    }
+    legacy.sort_by_key(|(_, v1)| v1.asset_id);
+    Ok(legacy)
+}
+
+/// retire_v1_asset + Ed25519 + update_risk_status na mesma transação
+async fn migrate_asset(
+    chain: &Chain,
+    v1: &AssetRiskStatusV1,
+    signers: &[&Keypair],
+    genesis_hash: [u8; 32],
+) -> Result<String, Box<dyn Error>> {
+    let asset_id = asset_id_string(&v1.asset_id);
+    // update_risk_status recusa ativos sem AssetMeta
+    let (asset_meta, _) = asset_meta_pda(&asset_id);
+    if chain.account_data(&asset_meta).await?.is_none() {
+        return Err(format!("{} is not registered; run register_asset first", asset_id).into());
+    }
+    let payload = DecisionPayload {
+        asset_id: v1.asset_id,
+        risk_score: v1.risk_score,
+        is_blocked: v1.is_blocked,
+        reduce_only: false,
+        risk_flags: 0,
+        sub_scores: [0; RISK_CATEGORY_COUNT],
+        confidence_ratio: v1.confidence_ratio,
+        publisher_count: v1.publisher_count,
+        publisher_ids: [[0; 8]; MAX_PUBLISHER_IDS],
+        timestamp: chrono::Utc::now().timestamp(),
+        nonce: 0,
+        // Menor sequence válida: não bloqueia a próxima decisão do engine
+        sequence: 1,
+        valid_until: 0, // v1 não tinha validade
+    };
+    let decision_hash =
+        workspace::message::decision_hash(&workspace::ID, &genesis_hash, &payload);
+    let (ed25519, signatures) = ed25519_ix(&decision_hash, signers);
+
+    let retire = program_ix(
+        accounts::RetireV1Asset {
+            config: config_pda().0,
+            v1_asset_risk: asset_risk_pda(&asset_id).0,
+            authority: chain.payer(),
+        },
+        instruction::RetireV1Asset { asset_id: asset_id.clone() },
+    );
+    let update = program_ix(
+        accounts::UpdateRiskStatus {
+            config: config_pda().0,
+            used_decisions: Some(used_decisions_pda_for(&decision_hash).0),
+            asset_risk_status: asset_risk_pda(&asset_id).0,
+            authority: chain.payer(),
+            keeper: chain.payer(),
+            instructions_sysvar: sysvar::instructions::ID,
+            deadman: None,
+            // Política do ativo, se houver, continua valendo para a decisão migrada
+            asset_policy: asset_policy_pda(&asset_id).0,
+            pending_decision: None,
+            asset_history: asset_history_pda(&asset_id).0,
+            pyth_price: None,
+            switchboard_feed: None,
+            roles: None,
+            asset_meta,
+            portfolio: None,
+            stats: None,
+            replay_bloom: None,
+            treasury: None,
+            payer_fee_token: None,
+            treasury_fee_token: None,
+            fee_mint: None,
+            token_program: None,
+            session_key: None,
+            system_program: system_program::ID,
+        },
+        instruction::UpdateRiskStatus {
+            asset_id,
+            risk_score: payload.risk_score,
+            is_blocked: payload.is_blocked,
+            reduce_only: payload.reduce_only,
+            risk_flags: payload.risk_flags,
+            sub_scores: payload.sub_scores,
+            confidence_ratio: payload.confidence_ratio,
+            publisher_count: payload.publisher_count,
+            publisher_ids: payload.publisher_ids,
+            timestamp: payload.timestamp,
+            nonce: payload.nonce,
+            sequence: payload.sequence,
+            valid_until: payload.valid_until,
+            decision_hash,
+            signatures,
+            signer_pubkeys: signers.iter().map(|s| s.pubkey().to_bytes()).collect(),
+            secondary: None,
+        },
+    );
+    Ok(chain.send(&[retire, ed25519, update]).await?.to_string())
+}
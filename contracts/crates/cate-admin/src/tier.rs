@@ -0,0 +1,36 @@
+use std::fmt;
+
+use cate_client::{AssetRiskStatus, RiskTier};
+
+/// Zonas de decisão do RISK_SCORE_MODEL.md
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Tier {
+    Allow,
+    Caution,
+    Block,
+}
+
+impl Tier {
+    /// Zona do RiskTier gravado on-chain; os cortes ficam só no Config
+    pub fn from_status(status: &AssetRiskStatus) -> Self {
+        if status.is_blocked != 0 {
+            return Tier::Block;
+        }
+        match status.risk_tier() {
+            RiskTier::Low => Tier::Allow,
+            RiskTier::Medium => Tier::Caution,
+            RiskTier::High | RiskTier::Critical => Tier::Block,
+        }
+    }
+}
+
+impl fmt::Display for Tier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Tier::Allow => "ALLOW",
+            Tier::Caution => "CAUTION",
+            Tier::Block => "BLOCK",
+        };
+        f.write_str(label)
+    }
+}
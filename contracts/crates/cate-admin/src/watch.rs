@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use cate_stream::{CateEvent, CateStream, RiskStatusUpdate};
+use clap::Args;
+use futures::StreamExt;
+
+use crate::tier::Tier;
+
+/// Confiança abaixo disso (bps) é sinalizada como motivo
+const LOW_CONFIDENCE_BPS: u64 = 9000;
+/// Menos publishers que isso é sinalizado como motivo
+const FEW_PUBLISHERS: u8 = 3;
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Decisões mais antigas que isso (segundos) são marcadas como STALE
+    #[arg(long, default_value_t = 300)]
+    stale_after: i64,
+
+    /// Toca o bell do terminal a cada mudança de tier
+    #[arg(long)]
+    bell: bool,
+
+    /// Notificação de desktop a cada mudança de tier (requer feature desktop-notify)
+    #[arg(long)]
+    notify: bool,
+
+    /// Imprime também atualizações sem mudança de tier
+    #[arg(long)]
+    all: bool,
+
+    /// Acompanha apenas estes ativos (pode repetir)
+    #[arg(long = "asset")]
+    assets: Vec<String>,
+}
+
+struct Previous {
+    tier: Tier,
+    risk_score: u8,
+}
+
+pub async fn run(rpc_url: &str, ws_url: &str, args: WatchArgs) -> Result<(), Box<dyn Error>> {
+    #[cfg(not(feature = "desktop-notify"))]
+    if args.notify {
+        return Err("--notify requires building with --features desktop-notify".into());
+    }
+
+    let mut events = CateStream::websocket(rpc_url, ws_url).start();
+    let mut previous: HashMap<String, Previous> = HashMap::new();
+
+    println!("watching CATE risk accounts via {} (ctrl-c to stop)", ws_url);
+    while let Some(event) = events.next().await {
+        match event {
+            CateEvent::Connected { slot } => println!("{} connected at slot {}", now(), slot),
+            CateEvent::Disconnected { reason } => println!("{} disconnected: {}", now(), reason),
+            CateEvent::Gap { from_slot, to_slot } => {
+                println!("{} gap {}..{} (backfilled)", now(), from_slot, to_slot)
+            }
+            CateEvent::RiskStatus(update) => {
+                let asset_id = update.asset_id();
+                if !args.assets.is_empty() && !args.assets.contains(&asset_id) {
+                    continue;
+                }
+                let tier = Tier::from_status(&update.status);
+                let old = previous.insert(
+                    asset_id.clone(),
+                    Previous { tier, risk_score: update.status.risk_score },
+                );
+                let changed = old.as_ref().map_or(true, |p| p.tier != tier);
+                if !changed && !args.all {
+                    continue;
+                }
+                println!("{}", summary(&asset_id, &update, old.as_ref(), tier, args.stale_after));
+                if changed && old.is_some() {
+                    alert(&args, &asset_id, old.as_ref().map(|p| p.tier), tier);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn summary(
+    asset_id: &str,
+    update: &RiskStatusUpdate,
+    old: Option<&Previous>,
+    tier: Tier,
+    stale_after: i64,
+) -> String {
+    let status = &update.status;
+    let transition = match old {
+        Some(p) if p.tier != tier => format!("{} -> {}", p.tier, tier),
+        Some(_) => format!("{} (unchanged)", tier),
+        None => format!("{} (initial)", tier),
+    };
+    let score = match old {
+        Some(p) => format!("{} -> {}", p.risk_score, status.risk_score),
+        None => status.risk_score.to_string(),
+    };
+    let age = chrono::Utc::now().timestamp() - status.timestamp;
+    format!(
+        "{} {:<16} {:<24} score={:<9} conf={}bps pubs={} age={}s [{}]{}",
+        now(),
+        asset_id,
+        transition,
+        score,
+        status.confidence_ratio,
+        status.publisher_count,
+        age,
+        reason_codes(update, stale_after).join(","),
+        if update.backfilled { " (backfill)" } else { "" },
+    )
+}
+
+fn reason_codes(update: &RiskStatusUpdate, stale_after: i64) -> Vec<&'static str> {
+    let status = &update.status;
+    let mut reasons = Vec::new();
+    if status.is_blocked != 0 {
+        reasons.push("BLOCKED_FLAG");
+    }
+    if status.risk_score >= 70 {
+        reasons.push("HIGH_SCORE");
+    }
+    if status.confidence_ratio < LOW_CONFIDENCE_BPS {
+        reasons.push("LOW_CONFIDENCE");
+    }
+    if status.publisher_count < FEW_PUBLISHERS {
+        reasons.push("FEW_PUBLISHERS");
+    }
+    if chrono::Utc::now().timestamp() - status.timestamp > stale_after {
+        reasons.push("STALE");
+    }
+    if reasons.is_empty() {
+        reasons.push("OK");
+    }
+    reasons
+}
+
+fn alert(args: &WatchArgs, asset_id: &str, old: Option<Tier>, new: Tier) {
+    if args.bell {
+        print!("\x07");
+    }
+    #[cfg(feature = "desktop-notify")]
+    if args.notify {
+        let body = match old {
+            Some(old) => format!("{}: {} -> {}", asset_id, old, new),
+            None => format!("{}: {}", asset_id, new),
+        };
+        let _ = notify_rust::Notification::new().summary("CATE risk change").body(&body).show();
+    }
+    #[cfg(not(feature = "desktop-notify"))]
+    let _ = (asset_id, old, new);
+}
+
+fn now() -> String {
+    chrono::Utc::now().format("%H:%M:%S").to_string()
+}
@@ -0,0 +1,47 @@
+-- Tabela esperada no banco do indexer (lida por /assets/{id}/history)
+CREATE TABLE IF NOT EXISTS risk_updates (
+    id               BIGSERIAL PRIMARY KEY,
+    asset_id         TEXT        NOT NULL,
+    slot             BIGINT      NOT NULL,
+    tx_signature     TEXT        NOT NULL UNIQUE,
+    risk_score       SMALLINT    NOT NULL,
+    is_blocked       BOOLEAN     NOT NULL,
+    confidence_ratio BIGINT      NOT NULL,
+    publisher_count  SMALLINT    NOT NULL,
+    timestamp        BIGINT      NOT NULL,
+    decision_hash    TEXT        NOT NULL,
+    correlation_id   BIGINT      NOT NULL,  -- nonce do payload (RiskStatusUpdated.nonce)
+    block_time       BIGINT,                -- unix seconds do bloco em que a tx entrou
+    indexed_at       TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS risk_updates_asset_slot_idx ON risk_updates (asset_id, slot DESC);
+
+-- Um registro por signer de cada decisão aceita (RiskStatusUpdated.signers)
+CREATE TABLE IF NOT EXISTS decision_signers (
+    tx_signature  TEXT   NOT NULL REFERENCES risk_updates (tx_signature),
+    signer        TEXT   NOT NULL,
+    PRIMARY KEY (tx_signature, signer)
+);
+
+CREATE INDEX IF NOT EXISTS decision_signers_signer_idx ON decision_signers (signer);
+
+-- Versão do modelo do engine associada a cada chave, mantida pela operação.
+-- Intervalos de signers diferentes que se sobrepõem são janelas de rotação.
+CREATE TABLE IF NOT EXISTS signer_versions (
+    signer            TEXT   NOT NULL,
+    model_version     TEXT   NOT NULL,
+    active_from_slot  BIGINT NOT NULL,
+    active_until_slot BIGINT,
+    PRIMARY KEY (signer, active_from_slot)
+);
+
+-- Timestamps de cada etapa do pipeline, reportados via POST /telemetry/hops.
+-- A etapa slot_landed vem de risk_updates.block_time (precisão de segundos).
+CREATE TABLE IF NOT EXISTS decision_hops (
+    asset_id       TEXT   NOT NULL,
+    correlation_id BIGINT NOT NULL,
+    hop            TEXT   NOT NULL CHECK (hop IN ('engine_emit', 'signer_sign', 'keeper_submit')),
+    at_ms          BIGINT NOT NULL,
+    PRIMARY KEY (asset_id, correlation_id, hop)
+);
@@ -0,0 +1,136 @@
+//! Snapshots assinados do estado de risco para consumidores sem acesso a RPC.
+//!
+//! A cada intervalo o serviço lê todas as contas `AssetRiskStatus`, monta um
+//! snapshot com o slot e o SHA-256 dos dados de cada conta, e assina
+//! `SHA256("CATE_ATTEST_V1" || payload)` com a chave de serviço. O consumidor
+//! só precisa da pubkey do serviço para verificar assinatura e frescor.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_lang::solana_program::hash::hashv;
+use cate_pull::{update_digest, RiskFeedMessage};
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+
+use crate::rpc::RiskReader;
+
+pub const ATTESTATION_DOMAIN: &[u8] = b"CATE_ATTEST_V1";
+
+#[derive(Serialize, ToSchema)]
+pub struct AttestedAsset {
+    pub asset_id: String,
+    pub address: String,
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub confidence_ratio: u64,
+    pub last_updated: i64,
+    /// Hex do SHA-256 dos dados brutos da conta
+    pub account_hash: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SnapshotBody {
+    pub program_id: String,
+    pub slot: u64,
+    pub produced_at: i64,
+    pub assets: Vec<AttestedAsset>,
+}
+
+#[derive(Clone, Serialize, ToSchema)]
+pub struct Attestation {
+    /// JSON exato que foi assinado (verificar sobre estes bytes, não re-serializar)
+    pub payload: String,
+    /// Hex da assinatura Ed25519 sobre SHA256(domain || payload)
+    pub signature: String,
+    /// Base58 da pubkey do serviço
+    pub signer: String,
+}
+
+/// Update assinado no formato de pull oracle
+#[derive(Serialize, ToSchema)]
+pub struct PullUpdate {
+    /// Hex do feed_id (SHA256("CATE_FEED_V1" || asset_id))
+    pub feed_id: String,
+    /// Hex da mensagem de 85 bytes no layout PriceFeedMessage
+    pub message: String,
+    /// Hex da assinatura Ed25519 sobre SHA256("CATE_PULL_V1" || message)
+    pub signature: String,
+    /// Base58 da pubkey do serviço
+    pub signer: String,
+    pub publish_time: i64,
+}
+
+pub struct Attestor {
+    key: SigningKey,
+    latest: RwLock<Option<Attestation>>,
+}
+
+impl Attestor {
+    /// Carrega um keypair no formato JSON do solana-keygen (64 bytes)
+    pub fn from_keypair_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let bytes: Vec<u8> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let secret: [u8; 32] = bytes
+            .get(..32)
+            .and_then(|s| s.try_into().ok())
+            .ok_or("keypair file must contain at least 32 bytes")?;
+        Ok(Self { key: SigningKey::from_bytes(&secret), latest: RwLock::new(None) })
+    }
+
+    pub fn signer(&self) -> String {
+        anchor_lang::prelude::Pubkey::new_from_array(self.key.verifying_key().to_bytes()).to_string()
+    }
+
+    pub async fn latest(&self) -> Option<Attestation> {
+        self.latest.read().await.clone()
+    }
+
+    pub fn sign(&self, body: &SnapshotBody) -> Result<Attestation, serde_json::Error> {
+        let payload = serde_json::to_string(body)?;
+        let digest = hashv(&[ATTESTATION_DOMAIN, payload.as_bytes()]).to_bytes();
+        let signature = self.key.sign(&digest);
+        Ok(Attestation {
+            payload,
+            signature: hex(&signature.to_bytes()),
+            signer: self.signer(),
+        })
+    }
+
+    /// Assina uma mensagem no formato de pull oracle (ver cate-pull)
+    pub fn sign_pull(&self, message: &RiskFeedMessage) -> PullUpdate {
+        let bytes = message.to_bytes();
+        let signature = self.key.sign(&update_digest(&bytes));
+        PullUpdate {
+            feed_id: hex(&message.feed_id),
+            message: hex(&bytes),
+            signature: hex(&signature.to_bytes()),
+            signer: self.signer(),
+            publish_time: message.publish_time,
+        }
+    }
+
+    /// Loop periódico; erros de RPC mantêm o último snapshot válido
+    pub async fn run(self: Arc<Self>, reader: Arc<RiskReader>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let body = match reader.snapshot().await {
+                Ok(body) => body,
+                Err(e) => {
+                    tracing::warn!("attestation snapshot failed: {}", e);
+                    continue;
+                }
+            };
+            match self.sign(&body) {
+                Ok(attestation) => *self.latest.write().await = Some(attestation),
+                Err(e) => tracing::error!("attestation serialization failed: {}", e),
+            }
+        }
+    }
+}
+
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,44 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    #[error("invalid asset id: {0}")]
+    InvalidAssetId(String),
+    #[error("asset not found: {0}")]
+    NotFound(String),
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+    pub code: &'static str,
+}
+
+impl ApiError {
+    fn status_and_code(&self) -> (StatusCode, &'static str) {
+        match self {
+            ApiError::InvalidAssetId(_) => (StatusCode::BAD_REQUEST, "INVALID_ASSET_ID"),
+            ApiError::NotFound(_) => (StatusCode::NOT_FOUND, "NOT_FOUND"),
+            ApiError::Rpc(_) => (StatusCode::BAD_GATEWAY, "RPC_ERROR"),
+            ApiError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR"),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, code) = self.status_and_code();
+        if status.is_server_error() {
+            tracing::error!("{}", self);
+        }
+        (status, Json(ErrorBody { error: self.to_string(), code })).into_response()
+    }
+}
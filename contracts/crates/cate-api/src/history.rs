@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use crate::error::ApiError;
+
+/// Limite de linhas por consulta de histórico
+pub const MAX_HISTORY_LIMIT: i64 = 500;
+
+/// Uma atualização de risco registrada pelo indexer (`risk_updates`)
+#[derive(Serialize, ToSchema, sqlx::FromRow)]
+pub struct HistoryEntry {
+    pub slot: i64,
+    pub tx_signature: String,
+    pub risk_score: i16,
+    pub is_blocked: bool,
+    pub confidence_ratio: i64,
+    pub publisher_count: i16,
+    pub timestamp: i64,
+    pub decision_hash: String,
+    pub indexed_at: DateTime<Utc>,
+}
+
+/// Decisão aceita atribuída a um signer (relatório de accountability)
+#[derive(Serialize, ToSchema, sqlx::FromRow)]
+pub struct SignerDecision {
+    pub asset_id: String,
+    pub slot: i64,
+    pub tx_signature: String,
+    pub decision_hash: String,
+    pub risk_score: i16,
+    pub is_blocked: bool,
+    /// Versão do modelo ativa para esta chave no slot (None se não registrada)
+    pub model_version: Option<String>,
+    /// Outro signer estava ativo no mesmo slot (janela de rotação)
+    pub during_rotation_overlap: bool,
+}
+
+/// Resumo por signer: quantas decisões e quais ativos influenciou
+#[derive(Serialize, ToSchema, sqlx::FromRow)]
+pub struct SignerSummary {
+    pub signer: String,
+    pub decisions: i64,
+    pub assets: Vec<String>,
+    pub first_slot: i64,
+    pub last_slot: i64,
+}
+
+pub struct HistoryStore {
+    pool: PgPool,
+}
+
+impl HistoryStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub async fn ping(&self) -> bool {
+        sqlx::query("SELECT 1").execute(&self.pool).await.is_ok()
+    }
+
+    pub async fn asset_history(
+        &self,
+        asset_id: &str,
+        limit: i64,
+        before_slot: Option<i64>,
+    ) -> Result<Vec<HistoryEntry>, ApiError> {
+        let rows = sqlx::query_as::<_, HistoryEntry>(
+            "SELECT slot, tx_signature, risk_score, is_blocked, confidence_ratio, \
+                    publisher_count, timestamp, decision_hash, indexed_at \
+             FROM risk_updates \
+             WHERE asset_id = $1 AND ($2::BIGINT IS NULL OR slot < $2) \
+             ORDER BY slot DESC \
+             LIMIT $3",
+        )
+        .bind(asset_id)
+        .bind(before_slot)
+        .bind(limit.clamp(1, MAX_HISTORY_LIMIT))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    /// Todas as decisões aceitas que contaram com a assinatura de `signer`
+    pub async fn signer_decisions(
+        &self,
+        signer: &str,
+        limit: i64,
+        before_slot: Option<i64>,
+    ) -> Result<Vec<SignerDecision>, ApiError> {
+        let rows = sqlx::query_as::<_, SignerDecision>(
+            "SELECT u.asset_id, u.slot, u.tx_signature, u.decision_hash, u.risk_score, u.is_blocked, \
+                    v.model_version, \
+                    EXISTS ( \
+                        SELECT 1 FROM signer_versions o \
+                        WHERE o.signer <> s.signer \
+                          AND o.active_from_slot <= u.slot \
+                          AND (o.active_until_slot IS NULL OR o.active_until_slot >= u.slot) \
+                    ) AS during_rotation_overlap \
+             FROM decision_signers s \
+             JOIN risk_updates u ON u.tx_signature = s.tx_signature \
+             LEFT JOIN signer_versions v \
+                    ON v.signer = s.signer \
+                   AND v.active_from_slot <= u.slot \
+                   AND (v.active_until_slot IS NULL OR v.active_until_slot >= u.slot) \
+             WHERE s.signer = $1 AND ($2::BIGINT IS NULL OR u.slot < $2) \
+             ORDER BY u.slot DESC \
+             LIMIT $3",
+        )
+        .bind(signer)
+        .bind(before_slot)
+        .bind(limit.clamp(1, MAX_HISTORY_LIMIT))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn signer_summaries(&self) -> Result<Vec<SignerSummary>, ApiError> {
+        let rows = sqlx::query_as::<_, SignerSummary>(
+            "SELECT s.signer, COUNT(*) AS decisions, \
+                    ARRAY_AGG(DISTINCT u.asset_id) AS assets, \
+                    MIN(u.slot) AS first_slot, MAX(u.slot) AS last_slot \
+             FROM decision_signers s \
+             JOIN risk_updates u ON u.tx_signature = s.tx_signature \
+             GROUP BY s.signer \
+             ORDER BY MAX(u.slot) DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+}
@@ -0,0 +1,98 @@
+//! CATE Risk API
+//!
+//! Exposes the on-chain `AssetRiskStatus` accounts (via RPC) and the update
+//! history recorded by the indexer (via its database) as a small REST API.
+//!
+//! Configuration (env):
+//! - `CATE_RPC_URL`      Solana RPC endpoint (default: devnet)
+//! - `CATE_DATABASE_URL` indexer Postgres URL
+//! - `CATE_API_BIND`     listen address (default: 0.0.0.0:3002)
+//! - `CATE_ATTESTATION_KEYPAIR` optional service keypair; enables signed snapshots
+//!   and pull-oracle updates (`/pull/{id}`)
+//! - `CATE_ATTESTATION_INTERVAL_SECS` snapshot interval (default: 30)
+//!
+//! `cate-api --openapi` prints the OpenAPI spec and exits.
+
+mod attestation;
+mod error;
+mod history;
+mod openapi;
+mod routes;
+mod rpc;
+mod telemetry;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::postgres::PgPoolOptions;
+use utoipa::OpenApi;
+
+use crate::attestation::Attestor;
+use crate::history::HistoryStore;
+use crate::rpc::RiskReader;
+
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+const DEFAULT_BIND: &str = "0.0.0.0:3002";
+const DEFAULT_ATTESTATION_INTERVAL_SECS: u64 = 30;
+
+pub struct AppState {
+    pub reader: Arc<RiskReader>,
+    pub history: HistoryStore,
+    pub attestor: Option<Arc<Attestor>>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if std::env::args().any(|arg| arg == "--openapi") {
+        println!("{}", openapi::ApiDoc::openapi().to_pretty_json()?);
+        return Ok(());
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    let rpc_url = std::env::var("CATE_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let database_url = std::env::var("CATE_DATABASE_URL")?;
+    let bind: SocketAddr = std::env::var("CATE_API_BIND")
+        .unwrap_or_else(|_| DEFAULT_BIND.to_string())
+        .parse()?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(8)
+        .connect(&database_url)
+        .await?;
+
+    let reader = Arc::new(RiskReader::new(rpc_url));
+
+    let attestor = match std::env::var("CATE_ATTESTATION_KEYPAIR") {
+        Ok(path) => {
+            let attestor = Arc::new(Attestor::from_keypair_file(&path)?);
+            let interval = std::env::var("CATE_ATTESTATION_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_ATTESTATION_INTERVAL_SECS);
+            tracing::info!("Signing attestations as {} every {}s", attestor.signer(), interval);
+            tokio::spawn(attestor.clone().run(reader.clone(), Duration::from_secs(interval)));
+            Some(attestor)
+        }
+        Err(_) => None,
+    };
+
+    let state = Arc::new(AppState {
+        reader,
+        history: HistoryStore::new(pool),
+        attestor,
+    });
+
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    tracing::info!("CATE API listening on {}", bind);
+    axum::serve(listener, routes::router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use utoipa::OpenApi;
+
+use crate::attestation::{Attestation, AttestedAsset, PullUpdate, SnapshotBody};
+use crate::error::ErrorBody;
+use crate::history::{HistoryEntry, SignerDecision, SignerSummary};
+use crate::routes::{self, HealthResponse};
+use crate::rpc::{AssetStatus, OverrideInfo};
+use crate::telemetry::{AssetLatency, Histogram, Hop, HopReport, LatencyReport};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "CATE Risk API", description = "Read-only access to CATE risk decisions"),
+    paths(
+        routes::health,
+        routes::list_assets,
+        routes::asset_status,
+        routes::asset_history,
+        routes::signer_summaries,
+        routes::signer_report,
+        routes::latest_attestation,
+        routes::pull_update,
+        routes::record_hop,
+        routes::latency_metrics,
+    ),
+    components(schemas(
+        AssetStatus,
+        OverrideInfo,
+        HistoryEntry,
+        HealthResponse,
+        ErrorBody,
+        Attestation,
+        SnapshotBody,
+        AttestedAsset,
+        PullUpdate,
+        SignerDecision,
+        SignerSummary,
+        Hop,
+        HopReport,
+        Histogram,
+        AssetLatency,
+        LatencyReport
+    ))
+)]
+pub struct ApiDoc;
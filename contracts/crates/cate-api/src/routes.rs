@@ -0,0 +1,253 @@
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use cate_pull::RiskFeedMessage;
+
+use crate::attestation::{Attestation, PullUpdate};
+use crate::error::{ApiError, ErrorBody};
+use crate::history::{HistoryEntry, SignerDecision, SignerSummary};
+use crate::openapi::ApiDoc;
+use crate::rpc::{validate_asset_id, AssetStatus};
+use crate::telemetry::{self, HopReport, LatencyReport};
+use crate::AppState;
+
+type AppResult<T> = Result<Json<T>, ApiError>;
+
+pub fn router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/assets", get(list_assets))
+        .route("/assets/:id/status", get(asset_status))
+        .route("/assets/:id/history", get(asset_history))
+        .route("/reports/signers", get(signer_summaries))
+        .route("/reports/signers/:signer", get(signer_report))
+        .route("/attestations/latest", get(latest_attestation))
+        .route("/pull/:id", get(pull_update))
+        .route("/telemetry/hops", post(record_hop))
+        .route("/metrics/latency", get(latency_metrics))
+        .route("/openapi.json", get(openapi_spec))
+        .with_state(state)
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub database: bool,
+    pub program_id: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct HistoryQuery {
+    /// Máximo de entradas (1-500, padrão 50)
+    pub limit: Option<i64>,
+    /// Paginação: apenas entradas anteriores a este slot
+    pub before_slot: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    responses((status = 200, body = HealthResponse))
+)]
+pub async fn health(State(state): State<Arc<AppState>>) -> Json<HealthResponse> {
+    let database = state.history.ping().await;
+    Json(HealthResponse {
+        status: if database { "ok" } else { "degraded" },
+        database,
+        program_id: workspace::ID.to_string(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/assets",
+    responses(
+        (status = 200, body = [AssetStatus]),
+        (status = 502, body = ErrorBody)
+    )
+)]
+pub async fn list_assets(State(state): State<Arc<AppState>>) -> AppResult<Vec<AssetStatus>> {
+    Ok(Json(state.reader.list_assets().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/assets/{id}/status",
+    params(("id" = String, Path, description = "Asset id, e.g. SOL/USD (URL-encoded)")),
+    responses(
+        (status = 200, body = AssetStatus),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody)
+    )
+)]
+pub async fn asset_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> AppResult<AssetStatus> {
+    Ok(Json(state.reader.asset_status(&id).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/assets/{id}/history",
+    params(
+        ("id" = String, Path, description = "Asset id, e.g. SOL/USD (URL-encoded)"),
+        HistoryQuery
+    ),
+    responses(
+        (status = 200, body = [HistoryEntry]),
+        (status = 400, body = ErrorBody)
+    )
+)]
+pub async fn asset_history(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> AppResult<Vec<HistoryEntry>> {
+    validate_asset_id(&id)?;
+    let entries = state
+        .history
+        .asset_history(&id, query.limit.unwrap_or(50), query.before_slot)
+        .await?;
+    Ok(Json(entries))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/signers",
+    responses((status = 200, body = [SignerSummary]))
+)]
+pub async fn signer_summaries(State(state): State<Arc<AppState>>) -> AppResult<Vec<SignerSummary>> {
+    Ok(Json(state.history.signer_summaries().await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reports/signers/{signer}",
+    params(
+        ("signer" = String, Path, description = "Base58 signer pubkey"),
+        HistoryQuery
+    ),
+    responses((status = 200, body = [SignerDecision]))
+)]
+pub async fn signer_report(
+    State(state): State<Arc<AppState>>,
+    Path(signer): Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> AppResult<Vec<SignerDecision>> {
+    let entries = state
+        .history
+        .signer_decisions(&signer, query.limit.unwrap_or(50), query.before_slot)
+        .await?;
+    Ok(Json(entries))
+}
+
+#[utoipa::path(
+    get,
+    path = "/attestations/latest",
+    responses(
+        (status = 200, body = Attestation),
+        (status = 404, body = ErrorBody)
+    )
+)]
+pub async fn latest_attestation(State(state): State<Arc<AppState>>) -> AppResult<Attestation> {
+    let attestor = state
+        .attestor
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("attestations are disabled".to_string()))?;
+    let attestation = attestor
+        .latest()
+        .await
+        .ok_or_else(|| ApiError::NotFound("no attestation produced yet".to_string()))?;
+    Ok(Json(attestation))
+}
+
+/// Estado atual do ativo como update assinado de pull oracle (layout Pyth)
+#[utoipa::path(
+    get,
+    path = "/pull/{id}",
+    params(("id" = String, Path, description = "Asset id, e.g. SOL/USD (URL-encoded)")),
+    responses(
+        (status = 200, body = PullUpdate),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody)
+    )
+)]
+pub async fn pull_update(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> AppResult<PullUpdate> {
+    let attestor = state
+        .attestor
+        .as_ref()
+        .ok_or_else(|| ApiError::NotFound("attestations are disabled".to_string()))?;
+    let (_, status) = state.reader.asset_account(&id).await?;
+
+    // prev_publish_time vem do histórico; sem banco, repete o publish_time
+    let prev_publish_time = state
+        .history
+        .asset_history(&id, 2, None)
+        .await
+        .ok()
+        .and_then(|entries| {
+            entries
+                .into_iter()
+                .map(|entry| entry.timestamp)
+                .find(|ts| *ts < status.timestamp)
+        });
+
+    let message = RiskFeedMessage::from_status(&status, prev_publish_time);
+    Ok(Json(attestor.sign_pull(&message)))
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct LatencyQuery {
+    /// Filtra por ativo
+    pub asset_id: Option<String>,
+    /// Janela em segundos (padrão 3600)
+    pub since_secs: Option<i64>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/telemetry/hops",
+    request_body = HopReport,
+    responses((status = 204), (status = 400, body = ErrorBody))
+)]
+pub async fn record_hop(
+    State(state): State<Arc<AppState>>,
+    Json(report): Json<HopReport>,
+) -> Result<StatusCode, ApiError> {
+    validate_asset_id(&report.asset_id)?;
+    telemetry::record_hop(state.history.pool(), &report).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/latency",
+    params(LatencyQuery),
+    responses((status = 200, body = LatencyReport))
+)]
+pub async fn latency_metrics(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LatencyQuery>,
+) -> AppResult<LatencyReport> {
+    let report = telemetry::latency_report(
+        state.history.pool(),
+        query.asset_id.as_deref(),
+        query.since_secs.unwrap_or(3600).max(1),
+    )
+    .await?;
+    Ok(Json(report))
+}
+
+pub async fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::Discriminator;
+use serde::Serialize;
+use solana_account::Account;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use utoipa::ToSchema;
+use workspace::{AssetRiskStatus, ASSET_RISK_SEED, SEED_NAMESPACE};
+
+use crate::attestation::{hex, AttestedAsset, SnapshotBody};
+use crate::error::ApiError;
+
+/// Estado atual de um ativo, como lido da conta on-chain
+#[derive(Serialize, ToSchema)]
+pub struct AssetStatus {
+    pub asset_id: String,
+    pub address: String,
+    pub risk_score: u8,
+    /// risk_score antes da última decisão (0 na primeira)
+    pub prev_risk_score: u8,
+    /// risk_score menos prev_risk_score
+    pub score_delta: i8,
+    /// Decisões gravadas no ativo desde a criação
+    pub updates_count: u64,
+    /// Média móvel exponencial do risk_score, menos sujeita a ruído do engine
+    pub ema_score: u8,
+    pub is_blocked: bool,
+    /// Engine, authority ou delisting: só operações que reduzem posição
+    pub reduce_only: bool,
+    /// Categorias em alerta: bit 0 liquidez, 1 volatilidade, 2 oráculo, 3 depeg
+    pub risk_flags: u8,
+    /// Score 0-100 por categoria, na mesma ordem dos bits
+    pub sub_scores: [u8; 4],
+    pub confidence_ratio: u64,
+    /// Confiança do feed Switchboard lido na mesma decisão, em bps
+    pub oracle_confidence_ratio: Option<u64>,
+    pub publisher_count: u8,
+    /// Hex dos ids de 8 bytes dos publishers da última decisão
+    pub publisher_ids: Vec<String>,
+    pub timestamp: i64,
+    pub last_updated: i64,
+    pub signer_count: u8,
+    /// Hex do decision_hash assinado
+    pub decision_hash: String,
+    /// Última intervenção manual da authority, se houver
+    pub last_override: Option<OverrideInfo>,
+    /// O estado atual veio do override, não de uma decisão do engine
+    pub overridden: bool,
+}
+
+/// Registro de override_risk_status
+#[derive(Serialize, ToSchema)]
+pub struct OverrideInfo {
+    pub authority: String,
+    pub timestamp: i64,
+    pub reason_code: u16,
+    pub is_blocked: bool,
+}
+
+pub struct RiskReader {
+    client: RpcClient,
+}
+
+impl RiskReader {
+    pub fn new(rpc_url: String) -> Self {
+        Self { client: RpcClient::new(rpc_url) }
+    }
+
+    async fn risk_accounts(
+        &self,
+        min_context_slot: Option<u64>,
+    ) -> Result<Vec<(Pubkey, Account)>, ApiError> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                AssetRiskStatus::DISCRIMINATOR,
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                min_context_slot,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        self.client
+            .get_program_accounts_with_config(&workspace::ID, config)
+            .await
+            .map_err(|e| ApiError::Rpc(e.to_string()))
+    }
+
+    pub async fn list_assets(&self) -> Result<Vec<AssetStatus>, ApiError> {
+        let accounts = self.risk_accounts(None).await?;
+
+        let mut assets = Vec::with_capacity(accounts.len());
+        for (address, account) in accounts {
+            // Contas com layout antigo são ignoradas em vez de derrubar a listagem
+            if let Ok(status) = AssetRiskStatus::try_deserialize_tolerant(&account.data) {
+                assets.push(to_asset_status(address, &status));
+            }
+        }
+        assets.sort_by(|a, b| a.asset_id.cmp(&b.asset_id));
+        Ok(assets)
+    }
+
+    /// Snapshot de todas as contas com slot mínimo e hash dos dados brutos
+    pub async fn snapshot(&self) -> Result<SnapshotBody, ApiError> {
+        let slot = self.client.get_slot().await.map_err(|e| ApiError::Rpc(e.to_string()))?;
+        let accounts = self.risk_accounts(Some(slot)).await?;
+
+        let mut assets = Vec::with_capacity(accounts.len());
+        for (address, account) in accounts {
+            let Ok(status) = AssetRiskStatus::try_deserialize_tolerant(&account.data) else {
+                continue;
+            };
+            let summary = to_asset_status(address, &status);
+            assets.push(AttestedAsset {
+                asset_id: summary.asset_id,
+                address: summary.address,
+                risk_score: status.risk_score,
+                is_blocked: status.is_blocked != 0,
+                confidence_ratio: status.confidence_ratio,
+                last_updated: status.last_updated,
+                account_hash: hex(&hashv(&[&account.data]).to_bytes()),
+            });
+        }
+        assets.sort_by(|a, b| a.asset_id.cmp(&b.asset_id));
+
+        Ok(SnapshotBody {
+            program_id: workspace::ID.to_string(),
+            slot,
+            produced_at: chrono::Utc::now().timestamp(),
+            assets,
+        })
+    }
+
+    pub async fn asset_status(&self, asset_id: &str) -> Result<AssetStatus, ApiError> {
+        let (address, status) = self.asset_account(asset_id).await?;
+        Ok(to_asset_status(address, &status))
+    }
+
+    pub async fn asset_account(
+        &self,
+        asset_id: &str,
+    ) -> Result<(Pubkey, AssetRiskStatus), ApiError> {
+        let address = asset_risk_address(asset_id)?;
+        let account = self
+            .client
+            .get_account_with_commitment(&address, self.client.commitment())
+            .await
+            .map_err(|e| ApiError::Rpc(e.to_string()))?
+            .value
+            .ok_or_else(|| ApiError::NotFound(asset_id.to_string()))?;
+
+        let status = AssetRiskStatus::try_deserialize_tolerant(&account.data)
+            .map_err(|e| ApiError::Rpc(e.to_string()))?;
+        Ok((address, status))
+    }
+}
+
+pub fn validate_asset_id(asset_id: &str) -> Result<(), ApiError> {
+    if asset_id.is_empty() || asset_id.len() > 16 {
+        return Err(ApiError::InvalidAssetId(asset_id.to_string()));
+    }
+    Ok(())
+}
+
+fn asset_risk_address(asset_id: &str) -> Result<Pubkey, ApiError> {
+    validate_asset_id(asset_id)?;
+    let (address, _) = Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        &workspace::ID,
+    );
+    Ok(address)
+}
+
+fn to_asset_status(address: Pubkey, status: &AssetRiskStatus) -> AssetStatus {
+    let len = status.asset_id.iter().position(|b| *b == 0).unwrap_or(16);
+    AssetStatus {
+        asset_id: String::from_utf8_lossy(&status.asset_id[..len]).into_owned(),
+        address: address.to_string(),
+        risk_score: status.risk_score,
+        prev_risk_score: status.prev_risk_score,
+        score_delta: status.score_delta,
+        updates_count: status.updates_count,
+        ema_score: status.ema_score(),
+        is_blocked: status.is_blocked != 0,
+        reduce_only: status.is_reduce_only(),
+        risk_flags: status.risk_flags,
+        sub_scores: status.sub_scores,
+        confidence_ratio: status.confidence_ratio,
+        oracle_confidence_ratio: status.oracle_confidence(),
+        publisher_count: status.publisher_count,
+        publisher_ids: status
+            .publisher_ids
+            .iter()
+            .take_while(|id| **id != [0u8; 8])
+            .map(|id| hex(id))
+            .collect(),
+        timestamp: status.timestamp,
+        last_updated: status.last_updated,
+        signer_count: status.signer_count,
+        decision_hash: hex(&status.decision_hash),
+        last_override: status.last_override().map(|record| OverrideInfo {
+            authority: record.authority.to_string(),
+            timestamp: record.timestamp,
+            reason_code: record.reason_code,
+            is_blocked: record.is_blocked,
+        }),
+        overridden: status.is_overridden(),
+    }
+}
@@ -0,0 +1,183 @@
+//! Latência ponta a ponta: cada componente reporta o timestamp da sua etapa
+//! usando o nonce do payload como correlation id, e o indexer fornece o
+//! momento em que a decisão entrou on-chain. Aqui agregamos em histogramas.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+use crate::error::ApiError;
+
+/// Limites superiores (ms) dos buckets; o último bucket é aberto
+pub const BUCKET_BOUNDS_MS: [i64; 8] = [100, 250, 500, 1_000, 2_000, 5_000, 10_000, 30_000];
+
+/// Máximo de decisões consideradas por consulta
+const MAX_SAMPLES: i64 = 10_000;
+
+#[derive(Clone, Copy, Deserialize, Serialize, ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Hop {
+    EngineEmit,
+    SignerSign,
+    KeeperSubmit,
+}
+
+impl Hop {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Hop::EngineEmit => "engine_emit",
+            Hop::SignerSign => "signer_sign",
+            Hop::KeeperSubmit => "keeper_submit",
+        }
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct HopReport {
+    pub asset_id: String,
+    /// Nonce do DecisionPayload
+    pub correlation_id: u64,
+    pub hop: Hop,
+    /// Unix ms
+    pub at_ms: i64,
+}
+
+#[derive(Serialize, ToSchema, Default)]
+pub struct Histogram {
+    /// Contagem por bucket, alinhada com `bucket_bounds_ms` (+1 bucket aberto)
+    pub counts: Vec<u64>,
+    pub samples: u64,
+    pub p50_ms: Option<i64>,
+    pub p99_ms: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AssetLatency {
+    pub asset_id: String,
+    pub emit_to_sign: Histogram,
+    pub sign_to_submit: Histogram,
+    pub submit_to_landed: Histogram,
+    pub emit_to_landed: Histogram,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LatencyReport {
+    pub bucket_bounds_ms: Vec<i64>,
+    pub since_secs: i64,
+    pub assets: Vec<AssetLatency>,
+}
+
+#[derive(sqlx::FromRow)]
+struct HopRow {
+    asset_id: String,
+    engine_emit: Option<i64>,
+    signer_sign: Option<i64>,
+    keeper_submit: Option<i64>,
+    landed_ms: Option<i64>,
+}
+
+pub async fn record_hop(pool: &PgPool, report: &HopReport) -> Result<(), ApiError> {
+    sqlx::query(
+        "INSERT INTO decision_hops (asset_id, correlation_id, hop, at_ms) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (asset_id, correlation_id, hop) DO NOTHING",
+    )
+    .bind(&report.asset_id)
+    .bind(report.correlation_id as i64)
+    .bind(report.hop.as_str())
+    .bind(report.at_ms)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn latency_report(
+    pool: &PgPool,
+    asset_id: Option<&str>,
+    since_secs: i64,
+) -> Result<LatencyReport, ApiError> {
+    let rows = sqlx::query_as::<_, HopRow>(
+        "SELECT h.asset_id, \
+                MAX(h.at_ms) FILTER (WHERE h.hop = 'engine_emit')   AS engine_emit, \
+                MAX(h.at_ms) FILTER (WHERE h.hop = 'signer_sign')   AS signer_sign, \
+                MAX(h.at_ms) FILTER (WHERE h.hop = 'keeper_submit') AS keeper_submit, \
+                MAX(u.block_time) * 1000                            AS landed_ms \
+         FROM decision_hops h \
+         LEFT JOIN risk_updates u \
+                ON u.asset_id = h.asset_id AND u.correlation_id = h.correlation_id \
+         WHERE ($1::TEXT IS NULL OR h.asset_id = $1) \
+           AND h.at_ms >= (EXTRACT(EPOCH FROM now())::BIGINT - $2) * 1000 \
+         GROUP BY h.asset_id, h.correlation_id \
+         LIMIT $3",
+    )
+    .bind(asset_id)
+    .bind(since_secs)
+    .bind(MAX_SAMPLES)
+    .fetch_all(pool)
+    .await?;
+
+    #[derive(Default)]
+    struct Segments {
+        emit_to_sign: Vec<i64>,
+        sign_to_submit: Vec<i64>,
+        submit_to_landed: Vec<i64>,
+        emit_to_landed: Vec<i64>,
+    }
+
+    let mut per_asset: BTreeMap<String, Segments> = BTreeMap::new();
+    for row in rows {
+        let segments = per_asset.entry(row.asset_id).or_default();
+        push_delta(&mut segments.emit_to_sign, row.engine_emit, row.signer_sign);
+        push_delta(&mut segments.sign_to_submit, row.signer_sign, row.keeper_submit);
+        push_delta(&mut segments.submit_to_landed, row.keeper_submit, row.landed_ms);
+        push_delta(&mut segments.emit_to_landed, row.engine_emit, row.landed_ms);
+    }
+
+    let assets = per_asset
+        .into_iter()
+        .map(|(asset_id, s)| AssetLatency {
+            asset_id,
+            emit_to_sign: histogram(s.emit_to_sign),
+            sign_to_submit: histogram(s.sign_to_submit),
+            submit_to_landed: histogram(s.submit_to_landed),
+            emit_to_landed: histogram(s.emit_to_landed),
+        })
+        .collect();
+
+    Ok(LatencyReport {
+        bucket_bounds_ms: BUCKET_BOUNDS_MS.to_vec(),
+        since_secs,
+        assets,
+    })
+}
+
+fn push_delta(out: &mut Vec<i64>, from: Option<i64>, to: Option<i64>) {
+    if let (Some(from), Some(to)) = (from, to) {
+        // block_time tem precisão de segundos; deltas negativos viram 0
+        out.push((to - from).max(0));
+    }
+}
+
+fn histogram(mut samples: Vec<i64>) -> Histogram {
+    if samples.is_empty() {
+        return Histogram { counts: vec![0; BUCKET_BOUNDS_MS.len() + 1], ..Default::default() };
+    }
+    samples.sort_unstable();
+    let mut counts = vec![0u64; BUCKET_BOUNDS_MS.len() + 1];
+    for sample in samples.iter() {
+        let bucket = BUCKET_BOUNDS_MS
+            .iter()
+            .position(|bound| sample <= bound)
+            .unwrap_or(BUCKET_BOUNDS_MS.len());
+        counts[bucket] += 1;
+    }
+    let percentile = |p: usize| samples[(samples.len() - 1) * p / 100];
+    Histogram {
+        counts,
+        samples: samples.len() as u64,
+        p50_ms: Some(percentile(50)),
+        p99_ms: Some(percentile(99)),
+    }
+}
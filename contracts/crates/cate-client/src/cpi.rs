@@ -0,0 +1,150 @@
+//! Builders de CPI para programas que fazem gating sobre o estado do CATE
+
+use anchor_lang::prelude::*;
+use workspace::cpi::accounts::{
+    AssertTradingAllowed, AssertTradingAllowedByMint, GetEngineHealth, ViewRiskStatus,
+};
+
+use crate::{EngineHealth, RiskView, TradingGate};
+
+/// Contas para o gate; `asset_policy` só é necessária para respeitar o modo advisory
+pub struct GateAccounts<'info> {
+    pub cate_program: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub asset_risk_status: AccountInfo<'info>,
+    pub asset_policy: Option<AccountInfo<'info>>,
+    /// `portfolio_pda()`; obrigatória quando o Config tem portfolio_breaker
+    pub portfolio: Option<AccountInfo<'info>>,
+    /// Opcional: `integration_stats_pda(programa, epoch)`, exige `instructions_sysvar`
+    pub integration_stats: Option<AccountInfo<'info>>,
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+    /// `consumer_pda(programa)`, gravável; obrigatória com consumer_access ligado.
+    /// Exige `instructions_sysvar`
+    pub consumer: Option<AccountInfo<'info>>,
+    /// `trading_schedule_pda(None)` e do ativo; obrigatórias enquanto existir
+    /// algum TradingSchedule (Config.trading_schedules), mesmo sem conta no PDA
+    pub global_schedule: Option<AccountInfo<'info>>,
+    pub asset_schedule: Option<AccountInfo<'info>>,
+}
+
+/// Chama `assert_trading_allowed` e retorna o TradingGate.
+/// Falha com o código do CATE (ProgramPaused, AssetBlocked, CircuitBreakerTripped, RiskDataStale,
+/// ConfidenceBelowFloor) exceto para ativos em modo advisory; AssetNotLive, AssetDelisted,
+/// PortfolioBreakerTripped, EngineSilent, ConsumerNotAuthorized, MarketClosed e
+/// MaintenanceWindowActive falham sempre.
+/// `TradingGate.reduce_only` sinaliza que só reduções de posição são permitidas.
+pub fn assert_trading_allowed<'info>(
+    accounts: GateAccounts<'info>,
+    asset_id: &str,
+    max_staleness: Option<i64>,
+    min_confidence_ratio: Option<u64>,
+) -> Result<TradingGate> {
+    let cpi_ctx = CpiContext::new(
+        accounts.cate_program,
+        AssertTradingAllowed {
+            config: accounts.config,
+            asset_risk_status: accounts.asset_risk_status,
+            asset_policy: accounts.asset_policy,
+            portfolio: accounts.portfolio,
+            integration_stats: accounts.integration_stats,
+            instructions_sysvar: accounts.instructions_sysvar,
+            consumer: accounts.consumer,
+            global_schedule: accounts.global_schedule,
+            asset_schedule: accounts.asset_schedule,
+        },
+    );
+    let gate = workspace::cpi::assert_trading_allowed(
+        cpi_ctx,
+        asset_id.to_string(),
+        max_staleness,
+        min_confidence_ratio,
+    )?;
+    Ok(gate.get())
+}
+
+/// Contas para o gate por mint; `asset_mint` é o PDA de `asset_mint_pda`
+pub struct GateByMintAccounts<'info> {
+    pub cate_program: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub asset_mint: AccountInfo<'info>,
+    pub asset_risk_status: AccountInfo<'info>,
+    pub asset_policy: Option<AccountInfo<'info>>,
+    /// `portfolio_pda()`; obrigatória quando o Config tem portfolio_breaker
+    pub portfolio: Option<AccountInfo<'info>>,
+    /// Opcional: `integration_stats_pda(programa, epoch)`, exige `instructions_sysvar`
+    pub integration_stats: Option<AccountInfo<'info>>,
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+    /// `consumer_pda(programa)`, gravável; obrigatória com consumer_access ligado.
+    /// Exige `instructions_sysvar`
+    pub consumer: Option<AccountInfo<'info>>,
+    /// `trading_schedule_pda(None)` e do ativo; obrigatórias enquanto existir
+    /// algum TradingSchedule (Config.trading_schedules), mesmo sem conta no PDA
+    pub global_schedule: Option<AccountInfo<'info>>,
+    pub asset_schedule: Option<AccountInfo<'info>>,
+}
+
+/// Igual a `assert_trading_allowed`, endereçado pelo mint SPL do ativo
+pub fn assert_trading_allowed_by_mint<'info>(
+    accounts: GateByMintAccounts<'info>,
+    mint: Pubkey,
+    max_staleness: Option<i64>,
+    min_confidence_ratio: Option<u64>,
+) -> Result<TradingGate> {
+    let cpi_ctx = CpiContext::new(
+        accounts.cate_program,
+        AssertTradingAllowedByMint {
+            config: accounts.config,
+            asset_mint: accounts.asset_mint,
+            asset_risk_status: accounts.asset_risk_status,
+            asset_policy: accounts.asset_policy,
+            portfolio: accounts.portfolio,
+            integration_stats: accounts.integration_stats,
+            instructions_sysvar: accounts.instructions_sysvar,
+            consumer: accounts.consumer,
+            global_schedule: accounts.global_schedule,
+            asset_schedule: accounts.asset_schedule,
+        },
+    );
+    let gate = workspace::cpi::assert_trading_allowed_by_mint(
+        cpi_ctx,
+        mint,
+        max_staleness,
+        min_confidence_ratio,
+    )?;
+    Ok(gate.get())
+}
+
+/// Contas para `view_risk_status`; `consumer` como em `GateAccounts`
+pub struct ViewAccounts<'info> {
+    pub cate_program: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub asset_risk_status: AccountInfo<'info>,
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+    pub consumer: Option<AccountInfo<'info>>,
+}
+
+/// Chama `view_risk_status` e retorna o resumo (score, bloqueio, confiança, idade).
+/// Não aplica frescor nem política: para decidir um trade use `assert_trading_allowed`.
+pub fn view_risk_status<'info>(accounts: ViewAccounts<'info>, asset_id: &str) -> Result<RiskView> {
+    let cpi_ctx = CpiContext::new(
+        accounts.cate_program,
+        ViewRiskStatus {
+            config: accounts.config,
+            asset_risk_status: accounts.asset_risk_status,
+            instructions_sysvar: accounts.instructions_sysvar,
+            consumer: accounts.consumer,
+        },
+    );
+    let view = workspace::cpi::view_risk_status(cpi_ctx, asset_id.to_string())?;
+    Ok(view.get())
+}
+
+/// Chama `get_engine_health`: último heartbeat do engine e se o SLA estourou
+pub fn get_engine_health<'info>(
+    cate_program: AccountInfo<'info>,
+    config: AccountInfo<'info>,
+) -> Result<EngineHealth> {
+    let cpi_ctx = CpiContext::new(cate_program, GetEngineHealth { config });
+    let health = workspace::cpi::get_engine_health(cpi_ctx)?;
+    Ok(health.get())
+}
@@ -0,0 +1,221 @@
+//! cate-client
+//!
+//! Tudo que um programa ou cliente precisa para integrar com o CATE sem copiar
+//! seeds e layouts: derivação de PDAs, structs das contas e (com a feature
+//! `cpi`) builders de CPI para o gate de trading.
+
+use anchor_lang::prelude::Pubkey;
+
+pub use workspace::gating::{effective_status, max_allowed_fill, staleness_window, FillLimits};
+pub use workspace::{
+    publisher_id, secp256k1_signer, AssetCategory, AssetGroup, AssetLifecycle, AssetMeta, AssetMint,
+    AssetPolicy, AssetRiskHistory, AssetRiskStatus, AssetRiskStatusV1, AssetUpdateCount,
+    CircuitBreakerConfig, Config, Consumer, ConsumerAccess, DeadmanSwitch, DecisionPayload,
+    DualEngineConfig, EffectiveStatus, EngineHealth, FeeVault, InsuranceFund, IntegrationStats,
+    KeeperTip, KeyType, MaintenanceWindow, NotionalTier, OverrideRecord, PendingDecision,
+    PendingPolicyChange, PolicyChange, PortfolioEntry, PortfolioRisk, PublisherId, PythCheck,
+    RampStep, RejectionClass, RejectionRecord, ReplayBloom, ReplayMode, RiskHistoryEntry, RiskView,
+    Role, RoleGrant, Roles, ScoreSource, SecondaryAssessment, SessionKey, SignerExpiry,
+    SignerKeyType, SignerSequence, SignerUsage, SignerWeight, Stats, SwitchboardCheck, TradeSide,
+    TradingGate, TradingSchedule, Treasury, UnblockCooldown, UpdateFee, UsedDecisions, WeeklyWindow,
+    ASSET_GROUP_SEED, ASSET_HISTORY_SEED, ASSET_META_SEED, ASSET_MINT_SEED, ASSET_POLICY_SEED,
+    ASSET_RISK_SEED, ASSET_RISK_VERSION, BLOOM_FILTER_BYTES, CONFIG_SEED, CONFIG_VERSION,
+    CONSUMER_SEED, DEADMAN_SEED, DEFAULT_SCORE_EMA_ALPHA_BPS, FEE_VAULT_SEED, INSURANCE_FUND_SEED,
+    INTEGRATION_STATS_SEED, MAX_NOTIONAL_TIERS, MAX_PORTFOLIO_ASSETS, MAX_PUBLISHER_IDS,
+    MAX_RAMP_STEPS, MAX_REPLAY_SHARD_SIZE, MAX_ROLE_GRANTS, MAX_SCHEDULE_WINDOWS,
+    MAX_SESSION_ASSETS, MAX_SESSION_DURATION_SECS, MAX_STATS_ASSETS, PENDING_CHANGE_SEED,
+    PENDING_DECISION_SEED, PORTFOLIO_SEED, REJECTION_CLASS_COUNT, REPLAY_BLOOM_SEED,
+    REPLAY_SHARD_COUNT, RISK_CATEGORY_COUNT, RISK_FLAGS_ALL, RISK_FLAG_DEPEG, RISK_FLAG_LIQUIDITY,
+    RISK_FLAG_ORACLE, RISK_FLAG_VOLATILITY, RISK_HISTORY_LEN, ROLES_SEED, SCHEDULE_SEED,
+    SEED_NAMESPACE, SESSION_KEY_SEED, SIGNER_EXPIRY_WARNING_SECS, SIGNER_USAGE_SEED, STATS_SEED,
+    SUB_SCORE_DEPEG, SUB_SCORE_LIQUIDITY, SUB_SCORE_ORACLE, SUB_SCORE_VOLATILITY, TREASURY_SEED,
+    USED_DECISIONS_SEED, WORMHOLE_EMITTER_SEED, WORMHOLE_MESSAGE_SEED,
+};
+pub use workspace::pyth::{PythPrice, PYTH_RECEIVER_ID};
+pub use workspace::switchboard::{SwitchboardReading, SWITCHBOARD_ON_DEMAND_ID};
+pub use workspace::wormhole::{WORMHOLE_CORE_DEVNET_ID, WORMHOLE_CORE_MAINNET_ID};
+pub use workspace::{replay_shard, ErrorCode, RiskTier, RiskTierThresholds, ID as PROGRAM_ID};
+
+#[cfg(feature = "cpi")]
+pub mod cpi;
+
+pub fn config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, CONFIG_SEED], &PROGRAM_ID)
+}
+
+pub fn asset_risk_pda(asset_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Grupo de ativos bloqueados juntos por `block_group`
+pub fn asset_group_pda(group_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_GROUP_SEED, group_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn asset_meta_pda(asset_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_META_SEED, asset_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Índice mint SPL -> asset_id
+pub fn asset_mint_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, ASSET_MINT_SEED, mint.as_ref()], &PROGRAM_ID)
+}
+
+pub fn asset_policy_pda(asset_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn asset_history_pda(asset_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_HISTORY_SEED, asset_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn used_decisions_pda(shard: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard]], &PROGRAM_ID)
+}
+
+/// Shard do replay store que o programa vai exigir para este decision_hash
+pub fn used_decisions_pda_for(decision_hash: &[u8; 32]) -> (Pubkey, u8) {
+    used_decisions_pda(replay_shard(decision_hash))
+}
+
+/// Contadores diários do signer; passados em remaining_accounts das atualizações
+pub fn signer_usage_pda(signer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, SIGNER_USAGE_SEED, signer.as_ref()], &PROGRAM_ID)
+}
+
+/// Mudança de política enfileirada; `id` é o Config.nonce no momento do queue
+pub fn pending_change_pda(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, PENDING_CHANGE_SEED, &id.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Decisão estacionada para revisão do guardian no modo de dois engines
+pub fn pending_decision_pda(asset_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, PENDING_DECISION_SEED, asset_id.as_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Contador de uso do gate por programa consumidor e epoch
+pub fn integration_stats_pda(consumer: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, INTEGRATION_STATS_SEED, consumer.as_ref(), &epoch.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Papéis operacionais (updater, pauser, guardian) concedidos pela authority
+pub fn roles_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, ROLES_SEED], &PROGRAM_ID)
+}
+
+/// Cofre da recompensa de prune_used_decisions; abastecido por transferência de SOL
+pub fn fee_vault_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, FEE_VAULT_SEED], &PROGRAM_ID)
+}
+
+/// Horário de negociação do ativo; `None` = horário global
+pub fn trading_schedule_pda(asset_id: Option<&str>) -> (Pubkey, u8) {
+    let scope = asset_id.unwrap_or_default();
+    Pubkey::find_program_address(&[SEED_NAMESPACE, SCHEDULE_SEED, scope.as_bytes()], &PROGRAM_ID)
+}
+
+/// Cadastro do programa consumidor do gate (allowlist ou assinatura paga)
+pub fn consumer_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, CONSUMER_SEED, program_id.as_ref()], &PROGRAM_ID)
+}
+
+/// Recebe a taxa por atualização; tokens SPL ficam em contas com owner neste PDA
+pub fn treasury_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, TREASURY_SEED], &PROGRAM_ID)
+}
+
+/// Fundo de seguro: depósitos, fração das taxas do Treasury e indenizações
+pub fn insurance_fund_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, INSURANCE_FUND_SEED], &PROGRAM_ID)
+}
+
+/// Delegação de uma chave de sessão, endereçada pela própria chave
+pub fn session_key_pda(session_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, SESSION_KEY_SEED, session_key.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Bloom filter do ReplayMode::Bloom; exigido nas atualizações nesse modo
+pub fn replay_bloom_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, REPLAY_BLOOM_SEED], &PROGRAM_ID)
+}
+
+/// Score agregado da carteira; exigido pelo gate quando há portfolio_breaker
+pub fn portfolio_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, PORTFOLIO_SEED], &PROGRAM_ID)
+}
+
+/// Contadores globais de decisões aceitas, estacionadas e rejeitadas
+pub fn stats_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, STATS_SEED], &PROGRAM_ID)
+}
+
+pub fn deadman_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, DEADMAN_SEED], &PROGRAM_ID)
+}
+
+/// Emitter do CATE no Wormhole; os contratos EVM registram este endereço
+pub fn wormhole_emitter_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEED_NAMESPACE, WORMHOLE_EMITTER_SEED], &PROGRAM_ID)
+}
+
+/// Conta da mensagem publicada com a `sequence` do emitter
+pub fn wormhole_message_pda(sequence: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SEED_NAMESPACE, WORMHOLE_MESSAGE_SEED, &sequence.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// PDAs do core bridge usados por publish_risk_attestation: (bridge, fee_collector, sequence)
+pub fn wormhole_bridge_accounts(core_bridge: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+    let emitter = wormhole_emitter_pda().0;
+    let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, core_bridge).0;
+    (
+        find(&[workspace::wormhole::BRIDGE_SEED]),
+        find(&[workspace::wormhole::FEE_COLLECTOR_SEED]),
+        find(&[workspace::wormhole::SEQUENCE_SEED, emitter.as_ref()]),
+    )
+}
+
+/// asset_id em bytes com padding, como armazenado em AssetRiskStatus.asset_id
+pub fn asset_id_bytes(asset_id: &str) -> Option<[u8; 16]> {
+    if asset_id.is_empty() || asset_id.len() > 16 {
+        return None;
+    }
+    let mut out = [0u8; 16];
+    out[..asset_id.len()].copy_from_slice(asset_id.as_bytes());
+    Some(out)
+}
+
+/// Inverso de `asset_id_bytes`
+pub fn asset_id_string(asset_id: &[u8; 16]) -> String {
+    let len = asset_id.iter().position(|b| *b == 0).unwrap_or(16);
+    String::from_utf8_lossy(&asset_id[..len]).into_owned()
+}
@@ -0,0 +1,253 @@
+//! cate-decision
+//!
+//! Formato canônico da decisão de risco (ver DECISION_HASH_SPEC.txt): o
+//! payload, sua serialização e o decision_hash. O programa recomputa o hash
+//! com estas funções e o engine off-chain assina o mesmo resultado, então os
+//! dois lados não podem divergir.
+//!
+//! `no_std` e sem alocação. Com a feature `sha2` (padrão) o hash é calculado
+//! aqui; on-chain o programa passa as mesmas partes para a syscall sha256.
+
+#![no_std]
+
+/// Tag de domínio incluída em toda mensagem assinada pelo engine
+pub const DOMAIN_TAG: &[u8] = b"CATE_RISK_V1";
+
+/// Tamanho do prefixo de domínio: DOMAIN_TAG || program_id || genesis_hash
+pub const DOMAIN_PREFIX_LEN: usize = 12 + 32 + 32;
+
+/// Marca da mensagem de heartbeat; o payload de decisão tem tamanho fixo
+/// diferente, então as duas mensagens nunca colidem
+pub const HEARTBEAT_TAG: &[u8] = b"HEARTBEAT";
+
+/// Marca da delegação de uma chave de sessão; o corpo tem no máximo
+/// 7 + 32 + 8 + 1 + 16 * MAX_SESSION_ASSETS bytes, menor que o payload
+pub const SESSION_TAG: &[u8] = b"SESSION";
+
+/// Ativos que uma delegação de sessão pode listar (nenhum = todos)
+pub const MAX_SESSION_ASSETS: usize = 8;
+
+/// Categorias de risco sinalizadas em `risk_flags`
+pub const RISK_FLAG_LIQUIDITY: u8 = 1 << 0;
+pub const RISK_FLAG_VOLATILITY: u8 = 1 << 1;
+pub const RISK_FLAG_ORACLE: u8 = 1 << 2;
+pub const RISK_FLAG_DEPEG: u8 = 1 << 3;
+pub const RISK_FLAGS_ALL: u8 =
+    RISK_FLAG_LIQUIDITY | RISK_FLAG_VOLATILITY | RISK_FLAG_ORACLE | RISK_FLAG_DEPEG;
+
+/// Sub-scores por categoria, na mesma ordem dos bits de `risk_flags`
+pub const RISK_CATEGORY_COUNT: usize = 4;
+pub const SUB_SCORE_LIQUIDITY: usize = 0;
+pub const SUB_SCORE_VOLATILITY: usize = 1;
+pub const SUB_SCORE_ORACLE: usize = 2;
+pub const SUB_SCORE_DEPEG: usize = 3;
+
+/// Publishers identificados na decisão: primeiros 8 bytes da pubkey de cada um
+pub const MAX_PUBLISHER_IDS: usize = 16;
+pub type PublisherId = [u8; 8];
+
+/// Tamanho do payload serializado (campos 1-12 da spec)
+pub const PAYLOAD_LEN: usize =
+    16 + 1 + 1 + 8 + 1 + 8 + 8 + 1 + RISK_CATEGORY_COUNT + 8 * MAX_PUBLISHER_IDS + 8 + 8;
+
+/// Decisão assinada pelo engine, campo a campo como na spec
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decision {
+    pub asset_id: [u8; 16], // UTF-8, completado com zeros à direita
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub reduce_only: bool, // Só operações que reduzem posição
+    pub risk_flags: u8, // Categorias em alerta (RISK_FLAG_*)
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT], // 0-100 por categoria (SUB_SCORE_*)
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub publisher_ids: [PublisherId; MAX_PUBLISHER_IDS], // Preenchidos primeiro, resto zerado
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub sequence: u64, // Monotônico por signer e ativo (modo ReplayMode::Sequence)
+    pub valid_until: i64, // Depois disto o ativo é tratado como bloqueado (0 = sem validade)
+}
+
+impl Decision {
+    /// Byte de flags: bit 0 = is_blocked, bit 1 = reduce_only.
+    /// Sem reduce_only o byte é idêntico ao antigo campo is_blocked (0/1).
+    pub fn flags(&self) -> u8 {
+        (self.is_blocked as u8) | ((self.reduce_only as u8) << 1)
+    }
+
+    /// Serialização canônica: campos na ordem, little-endian, sem separadores
+    pub fn to_bytes(&self) -> [u8; PAYLOAD_LEN] {
+        let mut out = [0u8; PAYLOAD_LEN];
+        out[0..16].copy_from_slice(&self.asset_id);
+        out[16] = self.risk_score;
+        out[17] = self.flags();
+        out[18..26].copy_from_slice(&self.confidence_ratio.to_le_bytes());
+        out[26] = self.publisher_count;
+        out[27..35].copy_from_slice(&self.timestamp.to_le_bytes());
+        out[35..43].copy_from_slice(&self.nonce.to_le_bytes());
+        out[43] = self.risk_flags;
+        out[44..48].copy_from_slice(&self.sub_scores);
+        for (i, id) in self.publisher_ids.iter().enumerate() {
+            out[48 + 8 * i..56 + 8 * i].copy_from_slice(id);
+        }
+        out[176..184].copy_from_slice(&self.sequence.to_le_bytes());
+        out[184..192].copy_from_slice(&self.valid_until.to_le_bytes());
+        out
+    }
+}
+
+/// Versão da spec da decisão (DECISION_HASH_SPEC.txt) a que a atestação se refere
+pub const DECISION_SPEC_VERSION: u8 = 8;
+
+/// Marca e versão do payload publicado via Wormhole (publish_risk_attestation).
+/// Mudou o layout de `Attestation` ou os campos de `Decision` que ela repete,
+/// sobe ATTESTATION_VERSION.
+pub const ATTESTATION_MAGIC: &[u8; 4] = b"CATE";
+pub const ATTESTATION_VERSION: u8 = 2;
+
+/// magic + version + spec_version + campos da `Attestation`
+pub const ATTESTATION_LEN: usize =
+    4 + 1 + 1 + 16 + 1 + 1 + 1 + RISK_CATEGORY_COUNT + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 32;
+
+/// Bits do byte de flags da atestação, nas posições de `Decision::flags`.
+/// Refletem o estado do ativo: reduce_only vem do engine, da authority ou
+/// de delisting.
+pub const ATTESTATION_FLAG_BLOCKED: u8 = 1 << 0;
+pub const ATTESTATION_FLAG_REDUCE_ONLY: u8 = 1 << 1;
+
+/// Último estado aceito de um ativo, como publicado para outras chains.
+/// Os contratos EVM leem big-endian, ao contrário do payload assinado.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attestation {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub flags: u8, // ATTESTATION_FLAG_*
+    pub risk_flags: u8,
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub signer_count: u8, // Signers que atingiram o quorum
+    pub timestamp: i64, // Da decisão assinada
+    pub last_updated: i64, // Quando o programa aceitou a decisão
+    pub breaker_until: i64, // 0 = circuit breaker livre
+    pub valid_until: i64, // Validade da decisão assinada (0 = sem validade)
+    pub decision_hash: [u8; 32],
+}
+
+impl Attestation {
+    pub fn to_bytes(&self) -> [u8; ATTESTATION_LEN] {
+        let mut out = [0u8; ATTESTATION_LEN];
+        out[0..4].copy_from_slice(ATTESTATION_MAGIC);
+        out[4] = ATTESTATION_VERSION;
+        out[5] = DECISION_SPEC_VERSION;
+        out[6..22].copy_from_slice(&self.asset_id);
+        out[22] = self.risk_score;
+        out[23] = self.flags;
+        out[24] = self.risk_flags;
+        out[25..29].copy_from_slice(&self.sub_scores);
+        out[29..37].copy_from_slice(&self.confidence_ratio.to_be_bytes());
+        out[37] = self.publisher_count;
+        out[38] = self.signer_count;
+        out[39..47].copy_from_slice(&self.timestamp.to_be_bytes());
+        out[47..55].copy_from_slice(&self.last_updated.to_be_bytes());
+        out[55..63].copy_from_slice(&self.breaker_until.to_be_bytes());
+        out[63..71].copy_from_slice(&self.valid_until.to_be_bytes());
+        out[71..103].copy_from_slice(&self.decision_hash);
+        out
+    }
+
+    /// Inverso de `to_bytes`; `None` para outro magic, versão ou tamanho
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() != ATTESTATION_LEN
+            || &data[0..4] != ATTESTATION_MAGIC
+            || data[4] != ATTESTATION_VERSION
+        {
+            return None;
+        }
+        let be8 = |at: usize| -> [u8; 8] { data[at..at + 8].try_into().unwrap() };
+        Some(Self {
+            asset_id: data[6..22].try_into().unwrap(),
+            risk_score: data[22],
+            flags: data[23],
+            risk_flags: data[24],
+            sub_scores: data[25..29].try_into().unwrap(),
+            confidence_ratio: u64::from_be_bytes(be8(29)),
+            publisher_count: data[37],
+            signer_count: data[38],
+            timestamp: i64::from_be_bytes(be8(39)),
+            last_updated: i64::from_be_bytes(be8(47)),
+            breaker_until: i64::from_be_bytes(be8(55)),
+            valid_until: i64::from_be_bytes(be8(63)),
+            decision_hash: data[71..103].try_into().unwrap(),
+        })
+    }
+}
+
+/// asset_id em texto para os 16 bytes do payload; `None` se não couber
+pub fn asset_id_bytes(asset_id: &str) -> Option<[u8; 16]> {
+    let bytes = asset_id.as_bytes();
+    let mut out = [0u8; 16];
+    out.get_mut(..bytes.len())?.copy_from_slice(bytes);
+    Some(out)
+}
+
+/// Prefixo de domínio: DOMAIN_TAG || program_id || genesis_hash do cluster.
+/// Uma assinatura feita para devnet ou para outro programa nunca valida.
+pub fn domain_prefix(program_id: &[u8; 32], genesis_hash: &[u8; 32]) -> [u8; DOMAIN_PREFIX_LEN] {
+    let mut out = [0u8; DOMAIN_PREFIX_LEN];
+    out[..12].copy_from_slice(DOMAIN_TAG);
+    out[12..44].copy_from_slice(program_id);
+    out[44..].copy_from_slice(genesis_hash);
+    out
+}
+
+/// decision_hash = SHA256(domain_prefix || payload) — é a mensagem assinada
+#[cfg(feature = "sha2")]
+pub fn decision_hash(
+    program_id: &[u8; 32],
+    genesis_hash: &[u8; 32],
+    decision: &Decision,
+) -> [u8; 32] {
+    sha256(&[&domain_prefix(program_id, genesis_hash), &decision.to_bytes()])
+}
+
+/// heartbeat_hash = SHA256(domain_prefix || HEARTBEAT_TAG || timestamp LE)
+#[cfg(feature = "sha2")]
+pub fn heartbeat_hash(program_id: &[u8; 32], genesis_hash: &[u8; 32], timestamp: i64) -> [u8; 32] {
+    sha256(&[&domain_prefix(program_id, genesis_hash), HEARTBEAT_TAG, &timestamp.to_le_bytes()])
+}
+
+/// session_hash = SHA256(domain_prefix || SESSION_TAG || session_key ||
+/// expires_at LE || u8 count || asset_ids) — assinada pelo signer confiável
+#[cfg(feature = "sha2")]
+pub fn session_hash(
+    program_id: &[u8; 32],
+    genesis_hash: &[u8; 32],
+    session_key: &[u8; 32],
+    expires_at: i64,
+    asset_ids: &[[u8; 16]],
+) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(domain_prefix(program_id, genesis_hash));
+    hasher.update(SESSION_TAG);
+    hasher.update(session_key);
+    hasher.update(expires_at.to_le_bytes());
+    hasher.update([asset_ids.len() as u8]);
+    for asset_id in asset_ids {
+        hasher.update(asset_id);
+    }
+    hasher.finalize().into()
+}
+
+#[cfg(feature = "sha2")]
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
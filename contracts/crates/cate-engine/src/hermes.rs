@@ -0,0 +1,84 @@
+//! Cliente mínimo do Hermes (`/v2/updates/price/latest`): só os campos
+//! parseados de preço e confiança, sem o VAA.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HermesError {
+    #[error("hermes request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("invalid hermes price for {0}")]
+    Invalid(String),
+}
+
+/// Preço com o expoente do feed: valor = price * 10^expo, conf idem
+#[derive(Clone, Copy, Debug)]
+pub struct HermesPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+#[derive(Deserialize)]
+struct LatestResponse {
+    parsed: Vec<ParsedUpdate>,
+}
+
+#[derive(Deserialize)]
+struct ParsedUpdate {
+    id: String,
+    price: RawPrice,
+}
+
+/// O Hermes serializa price e conf como string
+#[derive(Deserialize)]
+struct RawPrice {
+    price: String,
+    conf: String,
+    expo: i32,
+    publish_time: i64,
+}
+
+pub struct HermesClient {
+    http: reqwest::Client,
+    endpoint: String,
+}
+
+impl HermesClient {
+    pub fn new(endpoint: &str) -> Result<Self, HermesError> {
+        let http = reqwest::Client::builder().timeout(Duration::from_secs(10)).build()?;
+        Ok(Self { http, endpoint: endpoint.trim_end_matches('/').to_string() })
+    }
+
+    /// Último preço de cada feed pedido, por id em hex sem `0x`
+    pub async fn latest(&self, ids: &[String]) -> Result<Vec<(String, HermesPrice)>, HermesError> {
+        let mut query: Vec<(&str, &str)> = ids.iter().map(|id| ("ids[]", id.as_str())).collect();
+        query.push(("parsed", "true"));
+        let response: LatestResponse = self
+            .http
+            .get(format!("{}/v2/updates/price/latest", self.endpoint))
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        response
+            .parsed
+            .into_iter()
+            .map(|update| {
+                let raw = update.price;
+                let (Ok(price), Ok(conf)) = (raw.price.parse(), raw.conf.parse()) else {
+                    return Err(HermesError::Invalid(update.id));
+                };
+                let id = update.id.trim_start_matches("0x").to_lowercase();
+                let publish_time = raw.publish_time;
+                Ok((id, HermesPrice { price, conf, expo: raw.expo, publish_time }))
+            })
+            .collect()
+    }
+}
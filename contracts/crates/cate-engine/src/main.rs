@@ -0,0 +1,203 @@
+//! CATE Engine
+//!
+//! Off-chain half of CATE: polls Pyth Hermes for price and confidence,
+//! scores each feed with the model in `model.rs` (documented in
+//! RISK_SCORE_MODEL.md), signs the resulting decisions with the trusted
+//! engine keys and serves them at `GET /decisions` for `cate-keeper`
+//! (`CATE_DECISION_FEED_URL=http://<bind>/decisions`).
+//!
+//! A feed is re-signed when its assessment changes, and at least every
+//! `CATE_ENGINE_REFRESH_SECS` so the on-chain status never goes stale.
+//!
+//! Configuration (env):
+//! - `CATE_RPC_URL`        Solana RPC endpoint, read once for the genesis hash (default: devnet)
+//! - `CATE_PROGRAM_ID`     CATE program (default: the deployed program id)
+//! - `CATE_ENGINE_KEYPAIRS` comma-separated keypair files of the trusted signers
+//! - `CATE_HERMES_URL`     Hermes endpoint (default: https://hermes.pyth.network)
+//! - `CATE_ENGINE_FEEDS`   `ASSET=pyth_feed_id` pairs, comma-separated (default: BTC/USD, ETH/USD)
+//! - `CATE_ENGINE_INTERVAL_SECS` Hermes polling interval (default: 5)
+//! - `CATE_ENGINE_REFRESH_SECS`  re-sign unchanged decisions after this long (default: 60)
+//! - `CATE_ENGINE_MAX_PRICE_AGE_SECS` older Hermes prices score as a failed oracle (default: 60)
+//! - `CATE_ENGINE_PUBLISHER_COUNT` publisher_count signed into every decision; Hermes does not
+//!   report it (default: 0)
+//! - `CATE_ENGINE_VALIDITY_SECS` signed `valid_until` is the decision timestamp plus this; the
+//!   program treats the asset as blocked afterwards (default: 0, no expiry)
+//! - `CATE_ENGINE_BIND`    listen address (default: 0.0.0.0:3003)
+
+mod hermes;
+mod model;
+mod signer;
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::read_keypair_file;
+use tokio::sync::RwLock;
+
+use crate::hermes::HermesClient;
+use crate::model::{assess, Assessment, ModelParams, PriceWindow};
+use crate::signer::{DecisionSigner, SignedDecision};
+
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+const DEFAULT_PROGRAM_ID: &str = "2CVGjnZ2BRebSeDHdo3VZknm5jVjxZmWu9m95M14sTN3";
+const DEFAULT_HERMES_URL: &str = "https://hermes.pyth.network";
+const DEFAULT_FEEDS: [(&str, &str); 2] = [
+    ("BTC/USD", "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43"),
+    ("ETH/USD", "ff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace"),
+];
+const DEFAULT_BIND: &str = "0.0.0.0:3003";
+
+type Latest = Arc<RwLock<BTreeMap<String, SignedDecision>>>;
+
+struct Feed {
+    asset_id: String,
+    window: PriceWindow,
+    last: Option<(Assessment, i64)>,
+}
+
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    let rpc_url = std::env::var("CATE_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let program_id: Pubkey = std::env::var("CATE_PROGRAM_ID")
+        .unwrap_or_else(|_| DEFAULT_PROGRAM_ID.to_string())
+        .parse()?;
+    let keypairs = std::env::var("CATE_ENGINE_KEYPAIRS")?
+        .split(',')
+        .map(|path| read_keypair_file(path.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let hermes_url =
+        std::env::var("CATE_HERMES_URL").unwrap_or_else(|_| DEFAULT_HERMES_URL.to_string());
+    let feed_pairs: Vec<(String, String)> = match std::env::var("CATE_ENGINE_FEEDS") {
+        Ok(spec) => spec
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (asset_id, feed_id) =
+                    pair.split_once('=').ok_or("CATE_ENGINE_FEEDS: expected ASSET=feed_id")?;
+                Ok((asset_id.trim().to_string(), feed_id.trim().to_string()))
+            })
+            .collect::<Result<_, &str>>()?,
+        Err(_) => DEFAULT_FEEDS.iter().map(|(a, f)| (a.to_string(), f.to_string())).collect(),
+    };
+    let interval = Duration::from_secs(env_or("CATE_ENGINE_INTERVAL_SECS", 5));
+    let refresh_secs: i64 = env_or("CATE_ENGINE_REFRESH_SECS", 60);
+    let publisher_count: u8 = env_or("CATE_ENGINE_PUBLISHER_COUNT", 0);
+    let validity_secs: i64 = env_or("CATE_ENGINE_VALIDITY_SECS", 0);
+    let params = ModelParams {
+        max_price_age_secs: env_or("CATE_ENGINE_MAX_PRICE_AGE_SECS", 60),
+        ..ModelParams::default()
+    };
+    let bind: SocketAddr = std::env::var("CATE_ENGINE_BIND")
+        .unwrap_or_else(|_| DEFAULT_BIND.to_string())
+        .parse()?;
+
+    // Por feed id em hex minúsculo sem `0x`, como o Hermes devolve
+    let mut feeds: HashMap<String, Feed> = feed_pairs
+        .into_iter()
+        .map(|(asset_id, feed_id)| {
+            let feed = Feed { asset_id, window: PriceWindow::default(), last: None };
+            (feed_id.trim_start_matches("0x").to_lowercase(), feed)
+        })
+        .collect();
+    let feed_ids: Vec<String> = feeds.keys().cloned().collect();
+
+    let genesis_hash = RpcClient::new(rpc_url).get_genesis_hash().await?.to_bytes();
+    let mut signer = DecisionSigner::new(keypairs, &program_id, genesis_hash, validity_secs);
+    let hermes = HermesClient::new(&hermes_url)?;
+    tracing::info!("Scoring {} feed(s); signers {:?}", feeds.len(), signer.signers());
+
+    let latest: Latest = Arc::default();
+    let app = Router::new()
+        .route("/decisions", get(decisions))
+        .route("/health", get(|| async { "ok" }))
+        .with_state(latest.clone());
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    tracing::info!("Serving decisions on {}", bind);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::error!("HTTP server stopped: {}", e);
+        }
+    });
+
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        let prices = match hermes.latest(&feed_ids).await {
+            Ok(prices) => prices,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                continue;
+            }
+        };
+        let clock = now();
+        let now_secs = clock.as_secs() as i64;
+        for (feed_id, price) in prices {
+            let Some(feed) = feeds.get_mut(&feed_id) else {
+                continue;
+            };
+            feed.window.push(&price);
+            let assessment = assess(&price, &feed.window, now_secs, &params);
+            let due = match feed.last {
+                Some((last, signed_at)) => {
+                    last != assessment || now_secs.saturating_sub(signed_at) >= refresh_secs
+                }
+                None => true,
+            };
+            if !due {
+                continue;
+            }
+            let Some(decision) = signer.sign(
+                &feed.asset_id,
+                &assessment,
+                publisher_count,
+                now_secs,
+                clock.as_millis() as u64,
+            ) else {
+                tracing::error!("Asset id {:?} does not fit in 16 bytes", feed.asset_id);
+                continue;
+            };
+            tracing::info!(
+                "{}: score {} (oracle {}, volatility {}), confidence {} bps{}",
+                feed.asset_id,
+                assessment.risk_score,
+                assessment.sub_scores[cate_decision::SUB_SCORE_ORACLE],
+                assessment.sub_scores[cate_decision::SUB_SCORE_VOLATILITY],
+                assessment.confidence_ratio,
+                if assessment.is_blocked { ", blocked" } else { "" }
+            );
+            feed.last = Some((assessment, now_secs));
+            latest.write().await.insert(feed.asset_id.clone(), decision);
+        }
+    }
+
+    Ok(())
+}
+
+/// Última decisão assinada de cada ativo
+async fn decisions(State(latest): State<Latest>) -> Json<Vec<SignedDecision>> {
+    Json(latest.read().await.values().cloned().collect())
+}
@@ -0,0 +1,157 @@
+//! Modelo de risco do engine (RISK_SCORE_MODEL.md, seção "Modelo do
+//! cate-engine"). Duas entradas, ambas relativas ao preço:
+//!
+//! - oráculo: `conf / price` em bps, a mesma escala de confidence_ratio e de
+//!   `PythPrice::confidence_ratio_bps` on-chain;
+//! - volatilidade: desvio padrão relativo dos últimos `VOLATILITY_WINDOW`
+//!   preços publicados, em bps.
+//!
+//! Cada entrada vira um sub-score 0-100 por interpolação linear entre um piso
+//! (0) e um teto (100). O score final pondera 70% oráculo e 30% volatilidade,
+//! os mesmos pesos do modelo TypeScript (src/lib/risk/engine.ts).
+
+use std::collections::VecDeque;
+
+use cate_decision::{
+    RISK_CATEGORY_COUNT, RISK_FLAG_ORACLE, RISK_FLAG_VOLATILITY, SUB_SCORE_ORACLE,
+    SUB_SCORE_VOLATILITY,
+};
+
+use crate::hermes::HermesPrice;
+
+/// Preços guardados para a volatilidade
+pub const VOLATILITY_WINDOW: usize = 20;
+/// Abaixo disso a volatilidade ainda não é estimada (sub-score 0)
+pub const MIN_VOLATILITY_SAMPLES: usize = 5;
+/// Pesos do score final, em décimos
+const ORACLE_WEIGHT: u32 = 7;
+const VOLATILITY_WEIGHT: u32 = 3;
+/// Mesmos limiares de decisão do modelo: <= 30 libera, >= 70 bloqueia
+pub const ALLOW_THRESHOLD: u8 = 30;
+pub const BLOCK_THRESHOLD: u8 = 70;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ModelParams {
+    /// conf/price em bps com sub-score 0 e 100
+    pub confidence_floor_bps: u64,
+    pub confidence_ceiling_bps: u64,
+    /// Desvio padrão relativo em bps com sub-score 0 e 100
+    pub volatility_floor_bps: u64,
+    pub volatility_ceiling_bps: u64,
+    /// Preço publicado há mais tempo que isso conta como oráculo falho
+    pub max_price_age_secs: i64,
+}
+
+impl Default for ModelParams {
+    fn default() -> Self {
+        Self {
+            confidence_floor_bps: 10,
+            confidence_ceiling_bps: 100,
+            volatility_floor_bps: 20,
+            volatility_ceiling_bps: 200,
+            max_price_age_secs: 60,
+        }
+    }
+}
+
+/// Campos da decisão que saem do modelo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Assessment {
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub reduce_only: bool,
+    pub risk_flags: u8,
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+}
+
+/// Últimos preços de um feed, um por publish_time
+#[derive(Default)]
+pub struct PriceWindow {
+    prices: VecDeque<f64>,
+    last_publish_time: i64,
+}
+
+impl PriceWindow {
+    /// `false` se o preço não é mais novo que o último visto
+    pub fn push(&mut self, price: &HermesPrice) -> bool {
+        if price.publish_time <= self.last_publish_time {
+            return false;
+        }
+        self.last_publish_time = price.publish_time;
+        if price.price > 0 {
+            self.prices.push_back(price.price as f64 * 10f64.powi(price.expo));
+            if self.prices.len() > VOLATILITY_WINDOW {
+                self.prices.pop_front();
+            }
+        }
+        true
+    }
+
+    /// Desvio padrão populacional / média, em bps
+    pub fn volatility_bps(&self) -> Option<u64> {
+        if self.prices.len() < MIN_VOLATILITY_SAMPLES {
+            return None;
+        }
+        let n = self.prices.len() as f64;
+        let mean = self.prices.iter().sum::<f64>() / n;
+        let variance = self.prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / n;
+        Some((variance.sqrt() / mean * 10_000.0).round() as u64)
+    }
+}
+
+/// Interpolação linear de `value` entre `floor` (0) e `ceiling` (100)
+fn sub_score(value: u64, floor: u64, ceiling: u64) -> u8 {
+    if value <= floor {
+        return 0;
+    }
+    if value >= ceiling || ceiling <= floor {
+        return 100;
+    }
+    ((value - floor) * 100 / (ceiling - floor)) as u8
+}
+
+pub fn assess(
+    price: &HermesPrice,
+    window: &PriceWindow,
+    now: i64,
+    params: &ModelParams,
+) -> Assessment {
+    // Preço não positivo não tem intervalo relativo: razão máxima
+    let confidence_ratio = match u64::try_from(price.price) {
+        Ok(p) if p > 0 => (u128::from(price.conf) * 10_000 / u128::from(p)).min(10_000) as u64,
+        _ => 10_000,
+    };
+    let stale = now.saturating_sub(price.publish_time) > params.max_price_age_secs;
+    let oracle = if stale {
+        100
+    } else {
+        sub_score(confidence_ratio, params.confidence_floor_bps, params.confidence_ceiling_bps)
+    };
+    let volatility = window.volatility_bps().map_or(0, |v| {
+        sub_score(v, params.volatility_floor_bps, params.volatility_ceiling_bps)
+    });
+
+    let weighted = ORACLE_WEIGHT * u32::from(oracle) + VOLATILITY_WEIGHT * u32::from(volatility);
+    let risk_score = ((weighted + 5) / (ORACLE_WEIGHT + VOLATILITY_WEIGHT)) as u8;
+
+    let mut sub_scores = [0u8; RISK_CATEGORY_COUNT];
+    sub_scores[SUB_SCORE_ORACLE] = oracle;
+    sub_scores[SUB_SCORE_VOLATILITY] = volatility;
+    let mut risk_flags = 0;
+    if oracle >= BLOCK_THRESHOLD {
+        risk_flags |= RISK_FLAG_ORACLE;
+    }
+    if volatility >= BLOCK_THRESHOLD {
+        risk_flags |= RISK_FLAG_VOLATILITY;
+    }
+
+    Assessment {
+        risk_score,
+        is_blocked: risk_score >= BLOCK_THRESHOLD,
+        reduce_only: risk_score > ALLOW_THRESHOLD && risk_score < BLOCK_THRESHOLD,
+        risk_flags,
+        sub_scores,
+        confidence_ratio,
+    }
+}
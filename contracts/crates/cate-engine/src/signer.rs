@@ -0,0 +1,116 @@
+//! Assinatura das decisões com as chaves confiáveis do engine, no formato
+//! JSON que o cate-keeper lê do feed (assinaturas em hex, signers em base58).
+
+use cate_decision::{
+    asset_id_bytes, decision_hash, Decision, MAX_PUBLISHER_IDS, RISK_CATEGORY_COUNT,
+};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::model::Assessment;
+
+/// Decisão assinada como publicada em `GET /decisions`
+#[derive(Clone, Debug, Serialize)]
+pub struct SignedDecision {
+    pub asset_id: String,
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub reduce_only: bool,
+    pub risk_flags: u8,
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub publisher_ids: Vec<String>,
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub sequence: u64,
+    pub valid_until: i64,
+    /// Hex do decision_hash assinado, para conferência
+    pub decision_hash: String,
+    pub signatures: Vec<String>,
+    pub signer_pubkeys: Vec<String>,
+}
+
+pub struct DecisionSigner {
+    keypairs: Vec<Keypair>,
+    program_id: [u8; 32],
+    genesis_hash: [u8; 32],
+    /// Última sequence emitida; nunca volta, nem entre reinícios (parte do relógio)
+    last_sequence: u64,
+    /// Validade assinada em cada decisão, a partir do timestamp (0 = sem validade)
+    validity_secs: i64,
+}
+
+impl DecisionSigner {
+    pub fn new(
+        keypairs: Vec<Keypair>,
+        program_id: &Pubkey,
+        genesis_hash: [u8; 32],
+        validity_secs: i64,
+    ) -> Self {
+        Self {
+            keypairs,
+            program_id: program_id.to_bytes(),
+            genesis_hash,
+            last_sequence: 0,
+            validity_secs,
+        }
+    }
+
+    pub fn signers(&self) -> Vec<Pubkey> {
+        self.keypairs.iter().map(Keypair::pubkey).collect()
+    }
+
+    /// `None` se o asset_id não cabe nos 16 bytes do payload
+    pub fn sign(
+        &mut self,
+        asset_id: &str,
+        assessment: &Assessment,
+        publisher_count: u8,
+        timestamp: i64,
+        now_millis: u64,
+    ) -> Option<SignedDecision> {
+        // Mesma sequence para todos os ativos: monotônica por ativo também
+        self.last_sequence = now_millis.max(self.last_sequence + 1);
+        let decision = Decision {
+            asset_id: asset_id_bytes(asset_id)?,
+            risk_score: assessment.risk_score,
+            is_blocked: assessment.is_blocked,
+            reduce_only: assessment.reduce_only,
+            risk_flags: assessment.risk_flags,
+            sub_scores: assessment.sub_scores,
+            confidence_ratio: assessment.confidence_ratio,
+            publisher_count,
+            publisher_ids: [[0u8; 8]; MAX_PUBLISHER_IDS],
+            timestamp,
+            nonce: self.last_sequence,
+            sequence: self.last_sequence,
+            valid_until: if self.validity_secs > 0 { timestamp + self.validity_secs } else { 0 },
+        };
+        let hash = decision_hash(&self.program_id, &self.genesis_hash, &decision);
+
+        Some(SignedDecision {
+            asset_id: asset_id.to_string(),
+            risk_score: decision.risk_score,
+            is_blocked: decision.is_blocked,
+            reduce_only: decision.reduce_only,
+            risk_flags: decision.risk_flags,
+            sub_scores: decision.sub_scores,
+            confidence_ratio: decision.confidence_ratio,
+            publisher_count,
+            publisher_ids: Vec::new(),
+            timestamp,
+            nonce: decision.nonce,
+            sequence: decision.sequence,
+            valid_until: decision.valid_until,
+            decision_hash: hex(&hash),
+            signatures: self.keypairs.iter().map(|k| hex(k.sign_message(&hash).as_ref())).collect(),
+            signer_pubkeys: self.signers().iter().map(Pubkey::to_string).collect(),
+        })
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,259 @@
+//! Feed de decisões assinadas pelo engine. WebSocket: uma decisão JSON por
+//! mensagem de texto. HTTP: array JSON com as decisões recentes, consultado
+//! a cada intervalo. Os dois reconectam sozinhos e descartam repetições.
+
+use std::collections::{HashSet, VecDeque};
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cate_client::{asset_id_bytes, DecisionPayload, MAX_PUBLISHER_IDS, RISK_CATEGORY_COUNT};
+use cate_sdk::PresignedDecision;
+use futures::StreamExt;
+use serde::Deserialize;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::metrics::Metrics;
+
+/// Quantas assinaturas recentes são lembradas para descartar repetições
+const SEEN_CAPACITY: usize = 4096;
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, thiserror::Error)]
+pub enum FeedError {
+    #[error("feed connection error: {0}")]
+    Connect(String),
+    #[error("invalid decision: {0}")]
+    Decode(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum FeedSource {
+    WebSocket(String),
+    Http { url: String, interval: Duration },
+}
+
+impl FeedSource {
+    /// `ws://`/`wss://` assina o feed; qualquer outra URL é consultada por polling
+    pub fn from_url(url: &str, interval: Duration) -> Self {
+        if url.starts_with("ws://") || url.starts_with("wss://") {
+            FeedSource::WebSocket(url.to_string())
+        } else {
+            FeedSource::Http { url: url.to_string(), interval }
+        }
+    }
+}
+
+/// Decisão como o engine publica: payload campo a campo, assinaturas em hex
+/// e signers em base58, alinhados por posição
+#[derive(Debug, Deserialize)]
+pub struct DecisionMsg {
+    pub asset_id: String,
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    #[serde(default)]
+    pub reduce_only: bool,
+    #[serde(default)]
+    pub risk_flags: u8,
+    #[serde(default)]
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    /// Hex de 8 bytes por publisher, na ordem assinada
+    #[serde(default)]
+    pub publisher_ids: Vec<String>,
+    pub timestamp: i64,
+    pub nonce: u64,
+    #[serde(default)]
+    pub sequence: u64,
+    #[serde(default)]
+    pub valid_until: i64,
+    pub signatures: Vec<String>,
+    pub signer_pubkeys: Vec<String>,
+}
+
+impl DecisionMsg {
+    pub fn into_presigned(self) -> Result<PresignedDecision, FeedError> {
+        let asset_id = asset_id_bytes(&self.asset_id)
+            .ok_or_else(|| FeedError::Decode(format!("asset id {:?}", self.asset_id)))?;
+        if self.publisher_ids.len() > MAX_PUBLISHER_IDS {
+            return Err(FeedError::Decode("too many publisher ids".to_string()));
+        }
+        let mut publisher_ids = [[0u8; 8]; MAX_PUBLISHER_IDS];
+        for (slot, id) in publisher_ids.iter_mut().zip(self.publisher_ids.iter()) {
+            *slot = decode_hex(id)?;
+        }
+        let signatures = self
+            .signatures
+            .iter()
+            .map(|s| decode_hex(s))
+            .collect::<Result<Vec<[u8; 64]>, _>>()?;
+        let signer_pubkeys = self
+            .signer_pubkeys
+            .iter()
+            .map(|k| {
+                Pubkey::from_str(k)
+                    .map(|k| k.to_bytes())
+                    .map_err(|_| FeedError::Decode(format!("signer {:?}", k)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PresignedDecision {
+            payload: DecisionPayload {
+                asset_id,
+                risk_score: self.risk_score,
+                is_blocked: self.is_blocked,
+                reduce_only: self.reduce_only,
+                risk_flags: self.risk_flags,
+                sub_scores: self.sub_scores,
+                confidence_ratio: self.confidence_ratio,
+                publisher_count: self.publisher_count,
+                publisher_ids,
+                timestamp: self.timestamp,
+                nonce: self.nonce,
+                sequence: self.sequence,
+                valid_until: self.valid_until,
+            },
+            signatures,
+            signer_pubkeys,
+        })
+    }
+}
+
+fn decode_hex<const N: usize>(text: &str) -> Result<[u8; N], FeedError> {
+    let text = text.trim_start_matches("0x");
+    let invalid = || FeedError::Decode(format!("expected {} hex bytes", N));
+    if text.len() != 2 * N {
+        return Err(invalid());
+    }
+    let mut out = [0u8; N];
+    for (byte, pair) in out.iter_mut().zip(text.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+    Ok(out)
+}
+
+/// Assinaturas já vistas, com esquecimento das mais antigas
+#[derive(Default)]
+struct Seen {
+    set: HashSet<[u8; 64]>,
+    order: VecDeque<[u8; 64]>,
+}
+
+impl Seen {
+    fn insert(&mut self, signature: [u8; 64]) -> bool {
+        if !self.set.insert(signature) {
+            return false;
+        }
+        self.order.push_back(signature);
+        if self.order.len() > SEEN_CAPACITY {
+            if let Some(old) = self.order.pop_front() {
+                self.set.remove(&old);
+            }
+        }
+        true
+    }
+}
+
+/// Loop de reconexão do feed; termina quando o receptor é descartado
+pub async fn run(source: FeedSource, tx: mpsc::Sender<PresignedDecision>, metrics: Arc<Metrics>) {
+    let mut seen = Seen::default();
+    let mut delay = Duration::from_secs(1);
+    while !tx.is_closed() {
+        let result = match &source {
+            FeedSource::WebSocket(url) => websocket(url, &tx, &mut seen, &metrics).await,
+            FeedSource::Http { url, interval } => {
+                poll(url, *interval, &tx, &mut seen, &metrics).await
+            }
+        };
+        match result {
+            Ok(()) => delay = Duration::from_secs(1),
+            Err(e) => tracing::warn!("{}; reconnecting in {:?}", e, delay),
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+    }
+}
+
+async fn websocket(
+    url: &str,
+    tx: &mpsc::Sender<PresignedDecision>,
+    seen: &mut Seen,
+    metrics: &Metrics,
+) -> Result<(), FeedError> {
+    let (mut stream, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .map_err(|e| FeedError::Connect(e.to_string()))?;
+    tracing::info!("Decision feed connected: {}", url);
+    while let Some(message) = stream.next().await {
+        let text = match message.map_err(|e| FeedError::Connect(e.to_string()))? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let decision = serde_json::from_str::<DecisionMsg>(&text)
+            .map_err(|e| FeedError::Decode(e.to_string()))
+            .and_then(DecisionMsg::into_presigned);
+        if !forward(decision, tx, seen, metrics).await {
+            break;
+        }
+    }
+    Ok(())
+}
+
+async fn poll(
+    url: &str,
+    interval: Duration,
+    tx: &mpsc::Sender<PresignedDecision>,
+    seen: &mut Seen,
+    metrics: &Metrics,
+) -> Result<(), FeedError> {
+    let client = reqwest::Client::new();
+    loop {
+        let batch: Vec<DecisionMsg> = client
+            .get(url)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| FeedError::Connect(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| FeedError::Decode(e.to_string()))?;
+        for message in batch {
+            if !forward(message.into_presigned(), tx, seen, metrics).await {
+                return Ok(());
+            }
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Encaminha decisões novas; `false` quando o keeper parou de receber
+async fn forward(
+    decision: Result<PresignedDecision, FeedError>,
+    tx: &mpsc::Sender<PresignedDecision>,
+    seen: &mut Seen,
+    metrics: &Metrics,
+) -> bool {
+    let decision = match decision {
+        Ok(decision) => decision,
+        Err(e) => {
+            metrics.invalid.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!("{}", e);
+            return true;
+        }
+    };
+    // Decisão sem assinatura é recusada adiante pelo cate-sdk
+    if let Some(signature) = decision.signatures.first() {
+        if !seen.insert(*signature) {
+            metrics.duplicates.fetch_add(1, Ordering::Relaxed);
+            return true;
+        }
+    }
+    metrics.received.fetch_add(1, Ordering::Relaxed);
+    tx.send(decision).await.is_ok()
+}
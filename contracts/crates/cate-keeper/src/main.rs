@@ -0,0 +1,181 @@
+//! CATE Keeper
+//!
+//! Long-running relayer: reads decisions already signed by the risk engine
+//! from its feed, batches them and submits `update_risk_status` /
+//! `update_risk_status_batch` transactions with a priority fee taken from
+//! recent fees on the written accounts. Decisions signed by a proposed signer
+//! set are held until the authority activates it; stale or untrusted ones are
+//! dropped. Counters are exposed for Prometheus at `/metrics`.
+//!
+//! Configuration (env):
+//! - `CATE_RPC_URL`           Solana RPC endpoint (default: devnet)
+//! - `CATE_KEEPER_KEYPAIR`    fee payer; the Config authority or an Updater
+//! - `CATE_DECISION_FEED_URL` `ws://`/`wss://` stream, or an HTTP endpoint
+//!   returning a JSON array of recent decisions
+//! - `CATE_DECISION_POLL_SECS` HTTP polling interval (default: 2)
+//! - `CATE_KEEPER_BATCH_WINDOW_MS` flush interval (default: 400)
+//! - `CATE_KEEPER_MIN_PRIORITY_FEE` / `CATE_KEEPER_MAX_PRIORITY_FEE` priority
+//!   fee bounds in micro-lamports per CU (default: 1000 / 1000000)
+//! - `CATE_KEEPER_MAX_RETRIES` resends per transaction (default: 4)
+//! - `CATE_KEEPER_CONFIG_REFRESH_SECS` Config re-read interval (default: 10)
+//! - `CATE_KEEPER_METRICS_BIND` metrics listen address (default: 0.0.0.0:9102)
+//!
+//! Ctrl-C flushes the decisions already admitted before exiting.
+
+mod feed;
+mod metrics;
+mod signers;
+mod submit;
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cate_sdk::PresignedDecision;
+use solana_sdk::signature::read_keypair_file;
+use tokio::sync::mpsc;
+
+use crate::feed::FeedSource;
+use crate::metrics::Metrics;
+use crate::signers::{Admission, SignerView};
+use crate::submit::{unix_now, FeePolicy, Submitter};
+
+const DEFAULT_RPC_URL: &str = "https://api.devnet.solana.com";
+const DEFAULT_METRICS_BIND: &str = "0.0.0.0:9102";
+const FEED_CHANNEL_CAPACITY: usize = 1024;
+
+fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    let rpc_url = std::env::var("CATE_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    let payer = read_keypair_file(std::env::var("CATE_KEEPER_KEYPAIR")?)?;
+    let feed_url = std::env::var("CATE_DECISION_FEED_URL")?;
+    let poll = Duration::from_secs(env_or("CATE_DECISION_POLL_SECS", 2));
+    let batch_window = Duration::from_millis(env_or("CATE_KEEPER_BATCH_WINDOW_MS", 400));
+    let refresh = Duration::from_secs(env_or("CATE_KEEPER_CONFIG_REFRESH_SECS", 10));
+    let metrics_bind: SocketAddr = std::env::var("CATE_KEEPER_METRICS_BIND")
+        .unwrap_or_else(|_| DEFAULT_METRICS_BIND.to_string())
+        .parse()?;
+    let fees = FeePolicy {
+        min: env_or("CATE_KEEPER_MIN_PRIORITY_FEE", 1_000),
+        max: env_or("CATE_KEEPER_MAX_PRIORITY_FEE", 1_000_000),
+        percentile: 75,
+        retry_multiplier: 2,
+    };
+    let max_retries = env_or("CATE_KEEPER_MAX_RETRIES", 4);
+
+    let metrics = Arc::new(Metrics::default());
+    tokio::spawn({
+        let metrics = metrics.clone();
+        async move {
+            if let Err(e) = metrics::serve(metrics_bind, metrics).await {
+                tracing::error!("Metrics server stopped: {}", e);
+            }
+        }
+    });
+
+    let mut submitter = Submitter::new(rpc_url, payer, fees, max_retries, metrics.clone());
+    let config = submitter.config().await?;
+    submitter.refresh_accounts(&config).await?;
+    let mut view = SignerView::from_config(&config);
+    tracing::info!("Keeper {} relaying from {}", submitter.payer(), feed_url);
+
+    let (tx, mut rx) = mpsc::channel(FEED_CHANNEL_CAPACITY);
+    tokio::spawn(feed::run(FeedSource::from_url(&feed_url, poll), tx, metrics.clone()));
+
+    let mut ready: Vec<PresignedDecision> = Vec::new();
+    let mut held: Vec<PresignedDecision> = Vec::new();
+    let mut flush = tokio::time::interval(batch_window);
+    let mut refresh_tick = tokio::time::interval(refresh);
+    loop {
+        tokio::select! {
+            Some(decision) = rx.recv() => {
+                admit(decision, &view, &metrics, &mut ready, &mut held);
+            }
+            _ = flush.tick() => {
+                if ready.is_empty() || view.paused {
+                    continue;
+                }
+                // Re-admite: a janela de frescor pode ter passado durante a espera
+                for decision in std::mem::take(&mut ready) {
+                    admit(decision, &view, &metrics, &mut ready, &mut held);
+                }
+                if submitter.submit(std::mem::take(&mut ready), &view).await > 0 {
+                    // Falhas costumam vir de Config desatualizado (rotação, taxa, replay)
+                    refresh_tick.reset_immediately();
+                }
+            }
+            _ = refresh_tick.tick() => {
+                match submitter.config().await {
+                    Ok(config) => {
+                        if let Err(e) = submitter.refresh_accounts(&config).await {
+                            tracing::warn!("Account refresh failed: {}", e);
+                        }
+                        view = SignerView::from_config(&config);
+                        for decision in std::mem::take(&mut held) {
+                            admit(decision, &view, &metrics, &mut ready, &mut held);
+                        }
+                    }
+                    Err(e) => tracing::warn!("Config refresh failed: {}", e),
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                tracing::info!("Shutting down; flushing {} decision(s)", ready.len());
+                rx.close();
+                while let Ok(decision) = rx.try_recv() {
+                    admit(decision, &view, &metrics, &mut ready, &mut held);
+                }
+                if !ready.is_empty() && !view.paused {
+                    submitter.submit(std::mem::take(&mut ready), &view).await;
+                }
+                break;
+            }
+        }
+        metrics.held.store(held.len() as u64, Ordering::Relaxed);
+    }
+
+    Ok(())
+}
+
+/// Classifica a decisão contra o signer set atual. Na fila de envio fica só a
+/// decisão mais nova de cada ativo: a anterior seria recusada ou sobrescrita.
+fn admit(
+    decision: PresignedDecision,
+    view: &SignerView,
+    metrics: &Metrics,
+    ready: &mut Vec<PresignedDecision>,
+    held: &mut Vec<PresignedDecision>,
+) {
+    match view.admit(&decision, unix_now()) {
+        Admission::Submit => {
+            let asset_id = decision.payload.asset_id;
+            match ready.iter_mut().find(|d| d.payload.asset_id == asset_id) {
+                Some(queued) if queued.payload.timestamp < decision.payload.timestamp => {
+                    *queued = decision
+                }
+                Some(_) => {
+                    metrics.dropped_stale.fetch_add(1, Ordering::Relaxed);
+                }
+                None => ready.push(decision),
+            }
+        }
+        Admission::Hold => held.push(decision),
+        Admission::Stale => {
+            metrics.dropped_stale.fetch_add(1, Ordering::Relaxed);
+            tracing::debug!("Dropped stale decision for {}", decision.asset_id());
+        }
+        Admission::Untrusted => {
+            metrics.dropped_untrusted.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!("Dropped decision for {} from untrusted signers", decision.asset_id());
+        }
+    }
+}
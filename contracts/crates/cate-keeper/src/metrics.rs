@@ -0,0 +1,83 @@
+//! Contadores do keeper no formato texto do Prometheus, em `GET /metrics`
+
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::Router;
+
+#[derive(Default)]
+pub struct Metrics {
+    pub received: AtomicU64,
+    pub duplicates: AtomicU64,
+    pub invalid: AtomicU64,
+    pub dropped_stale: AtomicU64,
+    pub dropped_untrusted: AtomicU64,
+    pub held: AtomicU64,
+    pub submitted_txs: AtomicU64,
+    pub submitted_decisions: AtomicU64,
+    pub failed_txs: AtomicU64,
+    pub retries: AtomicU64,
+    pub priority_fee: AtomicU64,
+    pub last_submit: AtomicI64,
+}
+
+impl Metrics {
+    pub fn render(&self) -> String {
+        let counters: [(&str, &str, &AtomicU64); 10] = [
+            ("received", "Decisions received from the feed", &self.received),
+            ("duplicates", "Decisions already seen", &self.duplicates),
+            ("invalid", "Feed messages that failed to decode", &self.invalid),
+            ("dropped_stale", "Decisions outside the freshness window", &self.dropped_stale),
+            ("dropped_untrusted", "Decisions from untrusted signers", &self.dropped_untrusted),
+            ("submitted_txs", "Confirmed update transactions", &self.submitted_txs),
+            (
+                "submitted_decisions",
+                "Decisions in confirmed transactions",
+                &self.submitted_decisions,
+            ),
+            ("failed_txs", "Transactions that exhausted their retries", &self.failed_txs),
+            ("retries", "Transaction retries", &self.retries),
+            ("priority_fee", "Last priority fee in micro-lamports per CU", &self.priority_fee),
+        ];
+        let mut out = String::new();
+        for (name, help, value) in counters {
+            let kind = if name == "priority_fee" { "gauge" } else { "counter" };
+            let _ = writeln!(out, "# HELP cate_keeper_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE cate_keeper_{} {}", name, kind);
+            let _ = writeln!(out, "cate_keeper_{} {}", name, value.load(Ordering::Relaxed));
+        }
+        let gauges = [
+            (
+                "held",
+                "Decisions waiting for a signer rotation",
+                self.held.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "last_submit_timestamp",
+                "Unix time of the last confirmed transaction",
+                self.last_submit.load(Ordering::Relaxed),
+            ),
+        ];
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP cate_keeper_{} {}", name, help);
+            let _ = writeln!(out, "# TYPE cate_keeper_{} gauge", name);
+            let _ = writeln!(out, "cate_keeper_{} {}", name, value);
+        }
+        out
+    }
+}
+
+pub async fn serve(bind: SocketAddr, metrics: Arc<Metrics>) -> std::io::Result<()> {
+    let app = Router::new()
+        .route(
+            "/metrics",
+            get(|State(metrics): State<Arc<Metrics>>| async move { metrics.render() }),
+        )
+        .with_state(metrics);
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,112 @@
+//! Conjunto de signers do Config visto pelo keeper. Decide, antes de pagar a
+//! transação, se uma decisão seria aceita agora, se deve esperar a ativação
+//! de uma rotação pendente ou se nunca será aceita.
+
+use cate_client::{Config, SignerExpiry, SignerWeight};
+use cate_sdk::PresignedDecision;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    Submit,
+    /// Assinada pelo conjunto pendente: espera o activate_signer da authority
+    Hold,
+    /// Fora da janela de max_past_secs
+    Stale,
+    /// Signers fora do conjunto atual e do pendente, ou com a chave vencida
+    Untrusted,
+}
+
+#[derive(Clone)]
+pub struct SignerView {
+    pub genesis_hash: [u8; 32],
+    trusted: Vec<Pubkey>,
+    old: Vec<Pubkey>,
+    rotation_deadline: i64,
+    quorum: u8,
+    weights: Vec<SignerWeight>,
+    /// Só bloqueiam ou sobem o score; o programa confere contra o ativo
+    emergency: Vec<Pubkey>,
+    expiries: Vec<SignerExpiry>,
+    pending: Option<(Vec<Pubkey>, u8, Vec<SignerWeight>)>,
+    max_past_secs: i64,
+    max_future_secs: i64,
+    /// Limites diários por signer: atualizações precisam das contas SignerUsage
+    pub usage_limits: bool,
+    pub paused: bool,
+}
+
+impl SignerView {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            genesis_hash: config.genesis_hash,
+            trusted: config.trusted_signers.clone(),
+            old: config.old_signers.clone(),
+            rotation_deadline: config.rotation_deadline,
+            quorum: config.quorum,
+            weights: config.signer_weights.clone(),
+            emergency: config.emergency_signers.clone(),
+            expiries: config.signer_expiries.clone(),
+            pending: config
+                .pending_signer_set
+                .as_ref()
+                .map(|p| (p.signers.clone(), p.quorum, config.pending_signer_weights.clone())),
+            max_past_secs: config.max_past_secs,
+            max_future_secs: config.max_future_secs,
+            usage_limits: config.max_unblocks_per_day.is_some()
+                || config.max_score_decrease_per_day.is_some(),
+            paused: config.paused,
+        }
+    }
+
+    /// Mesma regra de Config::is_trusted_signer
+    fn is_trusted(&self, key: &Pubkey, now: i64) -> bool {
+        self.trusted.contains(key) || (now < self.rotation_deadline && self.old.contains(key))
+    }
+
+    pub fn admit(&self, decision: &PresignedDecision, now: i64) -> Admission {
+        let timestamp = decision.payload.timestamp;
+        if timestamp < now.saturating_sub(self.max_past_secs) {
+            return Admission::Stale;
+        }
+        // Relógio do engine adiantado: ainda pode entrar na janela
+        if timestamp > now.saturating_add(self.max_future_secs) {
+            return Admission::Hold;
+        }
+
+        let signers: Vec<Pubkey> =
+            decision.signer_pubkeys.iter().map(|k| Pubkey::new_from_array(*k)).collect();
+        // Mesma regra de check_signer_expiry: vencida não volta a valer
+        let expired = |k: &Pubkey| {
+            self.expiries.iter().any(|e| e.signer == *k && timestamp >= e.expires_at)
+        };
+        if signers.iter().any(expired) {
+            return Admission::Untrusted;
+        }
+        if signers.iter().all(|k| self.is_trusted(k, now))
+            && total_weight(&self.weights, &signers) >= self.quorum as u16
+        {
+            return Admission::Submit;
+        }
+        // Mesma regra de Config::is_emergency_decision
+        if let [key] = signers.as_slice() {
+            if self.emergency.contains(key) && !self.is_trusted(key, now) {
+                return Admission::Submit;
+            }
+        }
+        match &self.pending {
+            Some((pending, quorum, weights))
+                if signers.iter().all(|k| pending.contains(k))
+                    && total_weight(weights, &signers) >= *quorum as u16 =>
+            {
+                Admission::Hold
+            }
+            _ => Admission::Untrusted,
+        }
+    }
+}
+
+/// Peso somado dos signers, como em verify_quorum
+fn total_weight(weights: &[SignerWeight], signers: &[Pubkey]) -> u16 {
+    signers.iter().map(|k| SignerWeight::lookup(weights, k) as u16).sum()
+}
@@ -0,0 +1,321 @@
+//! Envio das decisões: agrupa em transações que cabem em um pacote, define a
+//! taxa de prioridade a partir das taxas recentes das contas escritas e
+//! reenvia com blockhash novo e taxa maior até `max_retries`.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cate_client::{
+    config_pda, deadman_pda, portfolio_pda, replay_bloom_pda, roles_pda, signer_usage_pda,
+    stats_pda, treasury_pda, Config, ReplayMode,
+};
+use cate_sdk::{
+    batch_update_instructions, presigned_update_instructions, OptionalAccounts,
+    PresignedDecision, SdkError, MAX_BATCH_SIZE,
+};
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::metrics::Metrics;
+use crate::signers::SignerView;
+
+/// Unidades de computação reservadas por decisão na transação
+const COMPUTE_UNITS_PER_DECISION: u32 = 150_000;
+const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SubmitError {
+    #[error("rpc error: {0}")]
+    Rpc(#[from] ClientError),
+    #[error("{0}")]
+    Sdk(#[from] SdkError),
+    #[error("config account: {0}")]
+    Config(String),
+}
+
+/// Taxa de prioridade em micro-lamports por unidade de computação
+#[derive(Clone, Copy, Debug)]
+pub struct FeePolicy {
+    pub min: u64,
+    pub max: u64,
+    /// Percentil das taxas recentes usado na primeira tentativa
+    pub percentile: u8,
+    /// Multiplicador aplicado a cada nova tentativa
+    pub retry_multiplier: u64,
+}
+
+impl FeePolicy {
+    fn pick(&self, mut recent: Vec<u64>) -> u64 {
+        recent.sort_unstable();
+        let fee = match recent.len() {
+            0 => self.min,
+            n => recent[(n - 1) * self.percentile.min(100) as usize / 100],
+        };
+        fee.clamp(self.min, self.max)
+    }
+
+    fn bump(&self, fee: u64) -> u64 {
+        fee.max(1).saturating_mul(self.retry_multiplier).clamp(self.min, self.max)
+    }
+}
+
+pub struct Submitter {
+    client: RpcClient,
+    payer: Keypair,
+    fees: FeePolicy,
+    max_retries: u32,
+    optional: OptionalAccounts,
+    metrics: Arc<Metrics>,
+}
+
+impl Submitter {
+    pub fn new(
+        rpc_url: String,
+        payer: Keypair,
+        fees: FeePolicy,
+        max_retries: u32,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            client: RpcClient::new(rpc_url),
+            payer,
+            fees,
+            max_retries,
+            optional: OptionalAccounts::default(),
+            metrics,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    pub async fn config(&self) -> Result<Config, SubmitError> {
+        let data = self.client.get_account_data(&config_pda().0).await?;
+        Config::try_deserialize_tolerant(&data).map_err(|e| SubmitError::Config(e.to_string()))
+    }
+
+    /// Contas opcionais que o Config exige ou que existem on-chain. Stats,
+    /// Roles e Deadman só entram quando já foram criados.
+    pub async fn refresh_accounts(&mut self, config: &Config) -> Result<(), SubmitError> {
+        let candidates = [deadman_pda().0, roles_pda().0, stats_pda().0, treasury_pda().0];
+        let exists: Vec<bool> = self
+            .client
+            .get_multiple_accounts(&candidates)
+            .await?
+            .iter()
+            .map(Option::is_some)
+            .collect();
+        let fee_in_lamports = config.update_fee.as_ref().is_some_and(|fee| fee.mint.is_none());
+        if config.update_fee.as_ref().is_some_and(|fee| fee.mint.is_some()) {
+            tracing::warn!("Config charges the update fee in SPL tokens; updates will be rejected");
+        }
+        self.optional = OptionalAccounts {
+            deadman: exists[0].then_some(candidates[0]),
+            roles: exists[1].then_some(candidates[1]),
+            stats: exists[2].then_some(candidates[2]),
+            treasury: (exists[3] && (fee_in_lamports || config.keeper_tip.is_some()))
+                .then_some(candidates[3]),
+            portfolio: config.portfolio_breaker.map(|_| portfolio_pda().0),
+            replay_bloom: matches!(config.replay_mode, ReplayMode::Bloom)
+                .then(|| replay_bloom_pda().0),
+            omit_replay_shard: !matches!(config.replay_mode, ReplayMode::HashList),
+            ..OptionalAccounts::default()
+        };
+        Ok(())
+    }
+
+    /// Envia todas as decisões; retorna quantas não entraram on-chain
+    pub async fn submit(&self, mut decisions: Vec<PresignedDecision>, view: &SignerView) -> usize {
+        let mut failed = 0;
+        while !decisions.is_empty() {
+            let count = match self.pack(&decisions, view) {
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::warn!("Dropping decision for {}: {}", decisions[0].asset_id(), e);
+                    decisions.remove(0);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let chunk: Vec<PresignedDecision> = decisions.drain(..count).collect();
+            if self.send_chunk(&chunk, view).await.is_ok() {
+                continue;
+            }
+            if chunk.len() == 1 {
+                failed += 1;
+                continue;
+            }
+            // O batch não cria ativos nem aceita decisões parciais: tenta uma a uma
+            tracing::warn!("Batch of {} failed; retrying decisions one by one", chunk.len());
+            for decision in chunk {
+                if self.send_chunk(std::slice::from_ref(&decision), view).await.is_err() {
+                    failed += 1;
+                }
+            }
+        }
+        failed
+    }
+
+    /// Quantas decisões do início da fila cabem em uma transação
+    fn pack(&self, decisions: &[PresignedDecision], view: &SignerView) -> Result<usize, SdkError> {
+        let mut count = 0;
+        for n in 1..=decisions.len().min(MAX_BATCH_SIZE) {
+            // Assinaturas além das conferidas pelo programa ficam para o próximo batch
+            let instructions = match self.instructions(&decisions[..n], view, 0) {
+                Err(SdkError::TooManySignatures) => break,
+                result => result?,
+            };
+            let tx = Transaction::new_with_payer(&instructions, Some(&self.payer.pubkey()));
+            let size = bincode::serialized_size(&tx).unwrap_or(u64::MAX);
+            if size > PACKET_DATA_SIZE as u64 {
+                break;
+            }
+            count = n;
+        }
+        if count == 0 {
+            // Nem sozinha a decisão cabe em uma transação
+            return Err(SdkError::TooManySigners);
+        }
+        Ok(count)
+    }
+
+    fn instructions(
+        &self,
+        decisions: &[PresignedDecision],
+        view: &SignerView,
+        priority_fee: u64,
+    ) -> Result<Vec<Instruction>, SdkError> {
+        let units = (COMPUTE_UNITS_PER_DECISION * decisions.len() as u32).min(MAX_COMPUTE_UNITS);
+        let mut instructions = vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(units),
+            ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+        ];
+        let extra = self.extra_accounts(decisions, view);
+        let payer = self.payer.pubkey();
+        let [verify, mut update] = match decisions {
+            [decision] => {
+                presigned_update_instructions(decision, &view.genesis_hash, &payer, &self.optional)?
+            }
+            _ => batch_update_instructions(
+                decisions,
+                &view.genesis_hash,
+                &payer,
+                &self.optional,
+                &extra,
+            )?,
+        };
+        if decisions.len() == 1 {
+            update.accounts.extend(extra);
+        }
+        instructions.push(verify);
+        instructions.push(update);
+        Ok(instructions)
+    }
+
+    /// SignerUsage dos signers, exigidos quando o Config tem tetos de uso;
+    /// no batch também o PortfolioRisk
+    fn extra_accounts(
+        &self,
+        decisions: &[PresignedDecision],
+        view: &SignerView,
+    ) -> Vec<AccountMeta> {
+        let mut extra = Vec::new();
+        if view.usage_limits {
+            let signers: BTreeSet<[u8; 32]> =
+                decisions.iter().flat_map(|d| d.signer_pubkeys.iter().copied()).collect();
+            extra.extend(signers.into_iter().map(|key| {
+                AccountMeta::new(signer_usage_pda(&Pubkey::new_from_array(key)).0, false)
+            }));
+        }
+        if decisions.len() > 1 {
+            if let Some(portfolio) = self.optional.portfolio {
+                extra.push(AccountMeta::new(portfolio, false));
+            }
+        }
+        extra
+    }
+
+    async fn priority_fee(&self, instructions: &[Instruction]) -> u64 {
+        let writable: Vec<Pubkey> = instructions
+            .iter()
+            .flat_map(|ix| ix.accounts.iter())
+            .filter(|meta| meta.is_writable)
+            .map(|meta| meta.pubkey)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .take(128)
+            .collect();
+        match self.client.get_recent_prioritization_fees(&writable).await {
+            Ok(recent) => self.fees.pick(recent.iter().map(|f| f.prioritization_fee).collect()),
+            Err(e) => {
+                tracing::debug!("Prioritization fees unavailable: {}", e);
+                self.fees.min
+            }
+        }
+    }
+
+    async fn send_chunk(
+        &self,
+        decisions: &[PresignedDecision],
+        view: &SignerView,
+    ) -> Result<Signature, SubmitError> {
+        let mut fee = self.priority_fee(&self.instructions(decisions, view, 0)?).await;
+        let mut attempt = 0;
+        loop {
+            let instructions = self.instructions(decisions, view, fee)?;
+            match self.send(&instructions).await {
+                Ok(signature) => {
+                    self.metrics.submitted_txs.fetch_add(1, Ordering::Relaxed);
+                    self.metrics
+                        .submitted_decisions
+                        .fetch_add(decisions.len() as u64, Ordering::Relaxed);
+                    self.metrics.priority_fee.store(fee, Ordering::Relaxed);
+                    self.metrics.last_submit.store(unix_now(), Ordering::Relaxed);
+                    tracing::info!(
+                        "Submitted {} decision(s) at {} micro-lamports/CU: {}",
+                        decisions.len(),
+                        fee,
+                        signature
+                    );
+                    return Ok(signature);
+                }
+                // Erro do programa: reenviar não muda o resultado
+                Err(e) if e.get_transaction_error().is_some() || attempt >= self.max_retries => {
+                    self.metrics.failed_txs.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!("Update transaction failed: {}", e);
+                    return Err(e.into());
+                }
+                Err(e) => {
+                    attempt += 1;
+                    fee = self.fees.bump(fee);
+                    self.metrics.retries.fetch_add(1, Ordering::Relaxed);
+                    tracing::debug!("Retry {} at {} micro-lamports/CU: {}", attempt, fee, e);
+                }
+            }
+        }
+    }
+
+    async fn send(&self, instructions: &[Instruction]) -> Result<Signature, ClientError> {
+        let blockhash = self.client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+        self.client.send_and_confirm_transaction(&tx).await
+    }
+}
+
+pub fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
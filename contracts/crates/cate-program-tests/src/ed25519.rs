@@ -0,0 +1,133 @@
+//! Instruções Ed25519 montadas byte a byte. `local_ix` reproduz o layout do
+//! cate-sdk; `forged_ix` aceita qualquer cabeçalho e offsets, para os casos
+//! que o programa precisa recusar: offsets trocados, mensagens de outro
+//! tamanho, índices de outras instruções e cabeçalhos truncados.
+
+use cate_sdk::Ed25519Entry;
+use solana_sdk::ed25519_program;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Tamanho do cabeçalho (num_signatures + padding)
+pub const HEADER_LEN: usize = 2;
+/// Tamanho de cada entrada de offsets
+pub const OFFSETS_LEN: usize = 14;
+/// Índice que aponta para a própria instrução Ed25519
+pub const THIS_INSTRUCTION: u16 = u16::MAX;
+/// pubkey || assinatura || mensagem de 32 bytes
+pub const ENTRY_DATA_LEN: usize = 32 + 64 + 32;
+
+/// Os sete campos de uma entrada de offsets, na ordem do programa nativo
+#[derive(Clone, Copy, Debug)]
+pub struct Offsets {
+    pub signature_offset: u16,
+    pub signature_ix: u16,
+    pub pubkey_offset: u16,
+    pub pubkey_ix: u16,
+    pub message_offset: u16,
+    pub message_size: u16,
+    pub message_ix: u16,
+}
+
+impl Offsets {
+    /// Offsets da entrada `index` de `count`, com dados locais no layout do cate-sdk
+    pub fn local(index: usize, count: usize) -> Self {
+        let base = (HEADER_LEN + OFFSETS_LEN * count + ENTRY_DATA_LEN * index) as u16;
+        Self {
+            signature_offset: base + 32,
+            signature_ix: THIS_INSTRUCTION,
+            pubkey_offset: base,
+            pubkey_ix: THIS_INSTRUCTION,
+            message_offset: base + 96,
+            message_size: 32,
+            message_ix: THIS_INSTRUCTION,
+        }
+    }
+
+    /// Os mesmos offsets lidos da instrução `index` da transação
+    pub fn in_instruction(self, index: u16) -> Self {
+        Self { signature_ix: index, pubkey_ix: index, message_ix: index, ..self }
+    }
+
+    pub fn to_bytes(&self) -> [u8; OFFSETS_LEN] {
+        let mut out = [0u8; OFFSETS_LEN];
+        let fields = [
+            self.signature_offset,
+            self.signature_ix,
+            self.pubkey_offset,
+            self.pubkey_ix,
+            self.message_offset,
+            self.message_size,
+            self.message_ix,
+        ];
+        for (chunk, field) in out.chunks_exact_mut(2).zip(fields) {
+            chunk.copy_from_slice(&field.to_le_bytes());
+        }
+        out
+    }
+}
+
+/// Assinatura de `signer` sobre `message`
+pub fn sign(signer: &Keypair, message: &[u8; 32]) -> Ed25519Entry {
+    Ed25519Entry {
+        pubkey: signer.pubkey().to_bytes(),
+        signature: signer.sign_message(message).into(),
+        message: *message,
+    }
+}
+
+/// Dados das entradas, concatenados no layout pubkey || assinatura || mensagem
+pub fn entry_data(entries: &[Ed25519Entry]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(ENTRY_DATA_LEN * entries.len());
+    for entry in entries {
+        data.extend_from_slice(&entry.pubkey);
+        data.extend_from_slice(&entry.signature);
+        data.extend_from_slice(&entry.message);
+    }
+    data
+}
+
+/// Instrução bem formada com todos os dados locais
+pub fn local_ix(entries: &[Ed25519Entry]) -> Instruction {
+    cate_sdk::ed25519_verify_ix(entries)
+}
+
+/// Instrução com cabeçalho e offsets arbitrários seguidos de `payload`.
+/// Offsets são relativos ao início dos dados da instrução.
+pub fn forged_ix(
+    num_signatures: u8,
+    padding: u8,
+    offsets: &[Offsets],
+    payload: &[u8],
+) -> Instruction {
+    let mut data = vec![num_signatures, padding];
+    for entry in offsets {
+        data.extend_from_slice(&entry.to_bytes());
+    }
+    data.extend_from_slice(payload);
+    Instruction { program_id: ed25519_program::ID, accounts: Vec::new(), data }
+}
+
+/// Como `local_ix`, com `mutate` aplicado aos offsets de cada entrada
+pub fn local_ix_with(
+    entries: &[Ed25519Entry],
+    mutate: impl Fn(usize, &mut Offsets),
+) -> Instruction {
+    let offsets: Vec<Offsets> = (0..entries.len())
+        .map(|i| {
+            let mut offsets = Offsets::local(i, entries.len());
+            mutate(i, &mut offsets);
+            offsets
+        })
+        .collect();
+    forged_ix(entries.len() as u8, 0, &offsets, &entry_data(entries))
+}
+
+/// Instrução cujas entradas leem pubkey, assinatura e mensagem da instrução
+/// `source_index`, que deve ser um `local_ix` com as mesmas `entries`
+pub fn cross_ix(entries: &[Ed25519Entry], source_index: u16) -> Instruction {
+    let offsets: Vec<Offsets> = (0..entries.len())
+        .map(|i| Offsets::local(i, entries.len()).in_instruction(source_index))
+        .collect();
+    forged_ix(entries.len() as u8, 0, &offsets, &[])
+}
@@ -0,0 +1,381 @@
+//! cate-program-tests
+//!
+//! Harness LiteSVM para o programa CATE compilado (`anchor build`): cria o
+//! Config com um engine 1-of-1, todos os shards do replay store e um ativo
+//! cadastrado, e expõe o relógio e o envio de transações aos testes em
+//! `tests/`. As instruções Ed25519 válidas e forjadas ficam em `ed25519`.
+//!
+//! O `.so` é lido de `CATE_PROGRAM_SO` ou de `target/deploy/workspace.so`.
+//! Os testes que usam o harness exigem a feature `sbf`; sem ela o `cargo test`
+//! do workspace roda só os que não dependem do programa compilado:
+//!
+//! ```text
+//! anchor build
+//! cargo test -p cate-program-tests --features sbf
+//! ```
+
+pub mod ed25519;
+
+use std::path::PathBuf;
+
+use anchor_lang::{AccountDeserialize, InstructionData, ToAccountMetas};
+use cate_client::{
+    asset_id_bytes, asset_meta_pda, asset_policy_pda, asset_risk_pda, config_pda,
+    pending_change_pda, treasury_pda, used_decisions_pda, AssetCategory, AssetRiskStatus, Config,
+    DecisionPayload, ErrorCode, PolicyChange, MAX_PUBLISHER_IDS, PROGRAM_ID, REPLAY_SHARD_COUNT,
+    RISK_CATEGORY_COUNT,
+};
+use cate_sdk::{
+    batch_update_instructions, presigned_update_instructions, OptionalAccounts, PresignedDecision,
+};
+use litesvm::types::{FailedTransactionMetadata, TransactionResult};
+use litesvm::LiteSVM;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use workspace::{accounts, instruction};
+
+/// Relógio inicial do cluster simulado
+pub const START_TIME: i64 = 1_700_000_000;
+/// Genesis do cluster simulado, gravado no Config e no domínio das assinaturas
+pub const GENESIS_HASH: [u8; 32] = [7; 32];
+/// Ativo cadastrado pelo harness
+pub const ASSET_ID: &str = "SOL/USD";
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub authority: Keypair,
+    /// Único signer confiável do Config
+    pub engine: Keypair,
+    nonce: u64,
+}
+
+impl Harness {
+    pub fn program_path() -> PathBuf {
+        std::env::var_os("CATE_PROGRAM_SO").map(PathBuf::from).unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy/workspace.so")
+        })
+    }
+
+    pub fn new() -> Self {
+        let path = Self::program_path();
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(PROGRAM_ID, &path)
+            .unwrap_or_else(|e| panic!("{} ({}): run `anchor build` first", path.display(), e));
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100 * LAMPORTS_PER_SOL).expect("airdrop");
+
+        let mut harness = Self { svm, authority, engine: Keypair::new(), nonce: 0 };
+        harness.set_time(START_TIME);
+        harness.initialize();
+        // Shards recusam decisões com timestamp até a criação (reset_at)
+        harness.set_time(START_TIME + 1);
+        harness
+    }
+
+    fn initialize(&mut self) {
+        let authority = self.authority.pubkey();
+        let config = config_pda().0;
+        let init = program_ix(
+            accounts::InitializeConfig {
+                config,
+                used_decisions: used_decisions_pda(0).0,
+                authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeConfig {
+                trusted_signer: self.engine.pubkey(),
+                genesis_hash: GENESIS_HASH,
+            },
+        );
+        self.send(&[init]).expect("initialize_config");
+
+        for shard in 1..REPLAY_SHARD_COUNT {
+            let ix = program_ix(
+                accounts::InitializeReplayShard {
+                    config,
+                    used_decisions: used_decisions_pda(shard).0,
+                    authority,
+                    payer: authority,
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeReplayShard { shard },
+            );
+            self.send(&[ix]).expect("initialize_replay_shard");
+        }
+        self.register_asset(ASSET_ID);
+    }
+
+    /// Cadastra `asset_id` com um mint novo
+    pub fn register_asset(&mut self, asset_id: &str) {
+        let authority = self.authority.pubkey();
+        let register = program_ix(
+            accounts::RegisterAsset {
+                config: config_pda().0,
+                asset_meta: asset_meta_pda(asset_id).0,
+                authority,
+                payer: authority,
+                system_program: system_program::ID,
+            },
+            instruction::RegisterAsset {
+                asset_id: asset_id.to_string(),
+                mint: Pubkey::new_unique(),
+                pyth_feed: [0; 32],
+                category: AssetCategory::Major,
+                decimals: 9,
+            },
+        );
+        self.send(&[register]).expect("register_asset");
+    }
+
+    /// Cria o Treasury e deposita `lamports` acima do rent
+    pub fn initialize_treasury(&mut self, lamports: u64) {
+        let authority = self.authority.pubkey();
+        let treasury = treasury_pda().0;
+        let init = program_ix(
+            accounts::InitializeTreasury {
+                config: config_pda().0,
+                treasury,
+                authority,
+                payer: authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeTreasury {},
+        );
+        self.send(&[init]).expect("initialize_treasury");
+        self.svm.airdrop(&treasury, lamports).expect("fund treasury");
+    }
+
+    pub fn now(&self) -> i64 {
+        self.svm.get_sysvar::<Clock>().unix_timestamp
+    }
+
+    pub fn set_time(&mut self, unix_timestamp: i64) {
+        let mut clock = self.svm.get_sysvar::<Clock>();
+        clock.unix_timestamp = unix_timestamp;
+        self.svm.set_sysvar(&clock);
+    }
+
+    /// Transação paga e assinada pela authority, com blockhash novo a cada
+    /// envio para que transações idênticas não colidam
+    pub fn send(&mut self, instructions: &[Instruction]) -> TransactionResult {
+        self.svm.expire_blockhash();
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(tx)
+    }
+
+    /// Payload de ASSET_ID com nonce único; o relógio avança um segundo para
+    /// que cada decisão seja mais nova que a gravada no ativo
+    pub fn payload(&mut self, risk_score: u8) -> DecisionPayload {
+        self.payload_for(ASSET_ID, risk_score)
+    }
+
+    /// Como `payload`, para outro ativo cadastrado
+    pub fn payload_for(&mut self, asset_id: &str, risk_score: u8) -> DecisionPayload {
+        self.nonce += 1;
+        self.set_time(self.now() + 1);
+        DecisionPayload {
+            asset_id: asset_id_bytes(asset_id).expect("asset id fits"),
+            risk_score,
+            is_blocked: false,
+            reduce_only: false,
+            risk_flags: 0,
+            sub_scores: [0; RISK_CATEGORY_COUNT],
+            confidence_ratio: 9_500,
+            publisher_count: 5,
+            publisher_ids: [[0; 8]; MAX_PUBLISHER_IDS],
+            timestamp: self.now(),
+            nonce: self.nonce,
+            sequence: 0,
+            valid_until: 0,
+        }
+    }
+
+    pub fn decision_hash(&self, payload: &DecisionPayload) -> [u8; 32] {
+        workspace::message::decision_hash(&PROGRAM_ID, &GENESIS_HASH, payload)
+    }
+
+    /// Decisão assinada por `signers` sobre o hash correto do payload
+    pub fn presign(&self, payload: &DecisionPayload, signers: &[&Keypair]) -> PresignedDecision {
+        let hash = self.decision_hash(payload);
+        PresignedDecision {
+            payload: payload.clone(),
+            signatures: signers.iter().map(|s| s.sign_message(&hash).into()).collect(),
+            signer_pubkeys: signers.iter().map(|s| s.pubkey().to_bytes()).collect(),
+        }
+    }
+
+    /// update_risk_status da decisão, sem a instrução Ed25519: cada teste
+    /// escolhe o que vai antes
+    pub fn update_ix(&self, decision: &PresignedDecision) -> Instruction {
+        let [_, update] = presigned_update_instructions(
+            decision,
+            &GENESIS_HASH,
+            &self.authority.pubkey(),
+            &OptionalAccounts::default(),
+        )
+        .expect("valid decision");
+        update
+    }
+
+    /// Instrução Ed25519 com a assinatura do engine seguida do
+    /// update_risk_status de `payload`
+    pub fn update_instructions(&self, payload: &DecisionPayload) -> [Instruction; 2] {
+        let decision = self.presign(payload, &[&self.engine]);
+        let entry = ed25519::sign(&self.engine, &self.decision_hash(payload));
+        [ed25519::local_ix(&[entry]), self.update_ix(&decision)]
+    }
+
+    /// Envia `payload` assinado pelo engine
+    pub fn submit(&mut self, payload: &DecisionPayload) -> TransactionResult {
+        let instructions = self.update_instructions(payload);
+        self.send(&instructions)
+    }
+
+    /// Instrução Ed25519 e update_risk_status_batch de `payloads`, todos
+    /// assinados pelo engine; `extra` vai ao fim de remaining_accounts
+    pub fn batch_instructions(
+        &self,
+        payloads: &[DecisionPayload],
+        extra: &[AccountMeta],
+    ) -> [Instruction; 2] {
+        let decisions: Vec<PresignedDecision> =
+            payloads.iter().map(|p| self.presign(p, &[&self.engine])).collect();
+        batch_update_instructions(
+            &decisions,
+            &GENESIS_HASH,
+            &self.authority.pubkey(),
+            &OptionalAccounts::default(),
+            extra,
+        )
+        .expect("valid batch")
+    }
+
+    pub fn config(&self) -> Config {
+        let account = self.svm.get_account(&config_pda().0).expect("config");
+        Config::try_deserialize(&mut account.data.as_slice()).expect("config layout")
+    }
+
+    /// Enfileira `change` e a aplica ao fim do timelock, adiantando o relógio
+    pub fn apply_policy(&mut self, change: PolicyChange) -> TransactionResult {
+        let id = self.queue_policy(change)?;
+        let apply = program_ix(
+            accounts::ApplyPolicyChange {
+                config: config_pda().0,
+                pending_change: pending_change_pda(id).0,
+                authority: self.authority.pubkey(),
+            },
+            instruction::ApplyPolicyChange { id },
+        );
+        self.send(&[apply])
+    }
+
+    /// Como `apply_policy`, para um PolicyChange::AssetPolicy de `asset_id`
+    pub fn apply_asset_policy(
+        &mut self,
+        asset_id: &str,
+        change: PolicyChange,
+    ) -> TransactionResult {
+        let id = self.queue_policy(change)?;
+        let authority = self.authority.pubkey();
+        let apply = program_ix(
+            accounts::ApplyAssetPolicyChange {
+                config: config_pda().0,
+                pending_change: pending_change_pda(id).0,
+                asset_policy: asset_policy_pda(asset_id).0,
+                authority,
+                payer: authority,
+                system_program: system_program::ID,
+            },
+            instruction::ApplyAssetPolicyChange { id, asset_id: asset_id.to_string() },
+        );
+        self.send(&[apply])
+    }
+
+    /// Enfileira `change` e adianta o relógio até o fim do timelock
+    fn queue_policy(&mut self, change: PolicyChange) -> Result<u64, FailedTransactionMetadata> {
+        let authority = self.authority.pubkey();
+        let id = self.config().nonce;
+        let queue = program_ix(
+            accounts::QueuePolicyChange {
+                config: config_pda().0,
+                pending_change: pending_change_pda(id).0,
+                authority,
+                payer: authority,
+                system_program: system_program::ID,
+            },
+            instruction::QueuePolicyChange { change },
+        );
+        self.send(&[queue])?;
+        self.set_time(self.now() + self.config().policy_delay_secs);
+        Ok(id)
+    }
+
+    pub fn asset(&self, asset_id: &str) -> Option<AssetRiskStatus> {
+        let account = self.svm.get_account(&asset_risk_pda(asset_id).0)?;
+        AssetRiskStatus::from_account_data(&account.data).ok()
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn program_ix(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Erro da transação: índice da instrução e o InstructionError
+pub fn instruction_error(result: &TransactionResult) -> Option<(u8, InstructionError)> {
+    match result {
+        Err(failed) => match &failed.err {
+            TransactionError::InstructionError(index, error) => Some((*index, error.clone())),
+            _ => None,
+        },
+        Ok(_) => None,
+    }
+}
+
+/// Falha com `expected` vinda de uma instrução do programa
+#[track_caller]
+pub fn assert_program_error(result: &TransactionResult, expected: ErrorCode) {
+    let code = u32::from(expected);
+    match instruction_error(result) {
+        Some((_, InstructionError::Custom(actual))) if actual == code => {}
+        _ => panic!(
+            "expected program error {} ({}), got {:?}",
+            expected,
+            code,
+            result_summary(result)
+        ),
+    }
+}
+
+/// Falha recusada pelo programa nativo Ed25519 na instrução `index`,
+/// antes de o CATE executar
+#[track_caller]
+pub fn assert_precompile_error(result: &TransactionResult, index: u8) {
+    match instruction_error(result) {
+        Some((actual, _)) if actual == index => {}
+        _ => panic!("expected precompile failure at {}, got {:?}", index, result_summary(result)),
+    }
+}
+
+fn result_summary(result: &TransactionResult) -> Result<(), TransactionError> {
+    result.as_ref().map(|_| ()).map_err(|failed| failed.err.clone())
+}
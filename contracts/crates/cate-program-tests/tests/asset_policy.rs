@@ -0,0 +1,58 @@
+//! AssetPolicy lida em toda atualização: o PDA vai sempre, e deixá-lo de fora
+//! não desliga o block_threshold do ativo.
+
+use anchor_lang::error::ErrorCode as AnchorError;
+use cate_client::{asset_id_bytes, asset_policy_pda, ErrorCode, PolicyChange};
+use cate_program_tests::{assert_program_error, instruction_error, Harness, ASSET_ID};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::pubkey::Pubkey;
+
+const BLOCK_THRESHOLD: u8 = 50;
+
+fn with_block_threshold() -> Harness {
+    let mut h = Harness::new();
+    let change = PolicyChange::AssetPolicy {
+        asset_id: asset_id_bytes(ASSET_ID).unwrap(),
+        advisory_only: false,
+        block_threshold: Some(BLOCK_THRESHOLD),
+        max_staleness_secs: None,
+        pyth_check: None,
+        switchboard_check: None,
+    };
+    h.apply_asset_policy(ASSET_ID, change).expect("asset policy applied");
+    h
+}
+
+#[test]
+fn single_update_reads_the_policy_pda() {
+    let mut h = with_block_threshold();
+    let payload = h.payload(BLOCK_THRESHOLD + 10);
+
+    let [verify, mut update] = h.update_instructions(&payload);
+    let policy = asset_policy_pda(ASSET_ID).0;
+    let meta = update.accounts.iter_mut().find(|m| m.pubkey == policy).expect("policy meta");
+    meta.pubkey = Pubkey::new_unique();
+    let result = h.send(&[verify, update]);
+    let seeds = InstructionError::Custom(AnchorError::ConstraintSeeds.into());
+    assert_eq!(instruction_error(&result), Some((1, seeds)));
+
+    h.submit(&payload).expect("update with policy");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").is_blocked, 1);
+}
+
+#[test]
+fn batch_requires_the_policy_pda() {
+    let mut h = with_block_threshold();
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial update");
+
+    let payload = h.payload(BLOCK_THRESHOLD + 10);
+    let [verify, update] = h.batch_instructions(&[payload], &[]);
+    let mut skipped = update.clone();
+    skipped.accounts.retain(|m| m.pubkey != asset_policy_pda(ASSET_ID).0);
+    let result = h.send(&[verify.clone(), skipped]);
+    assert_program_error(&result, ErrorCode::InvalidAssetAccount);
+
+    h.send(&[verify, update]).expect("batch with policy");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").is_blocked, 1);
+}
@@ -0,0 +1,186 @@
+//! update_risk_status_batch: várias decisões em uma instrução, com as mesmas
+//! verificações do fluxo simples.
+
+use anchor_lang::{AccountDeserialize, ToAccountMetas};
+use cate_client::{
+    asset_history_pda, asset_risk_pda, treasury_pda, CircuitBreakerConfig, ErrorCode,
+    PolicyChange, Treasury, UpdateFee,
+};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID, GENESIS_HASH};
+use cate_sdk::{
+    batch_update_instructions, OptionalAccounts, PresignedDecision, SdkError,
+    MAX_PRECOMPILE_ENTRIES_SCANNED,
+};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use workspace::accounts;
+
+const OTHER_ASSET: &str = "ETH/USD";
+
+fn without(mut ix: Instruction, address: Pubkey) -> Instruction {
+    ix.accounts.retain(|meta| meta.pubkey != address);
+    ix
+}
+
+/// Contas nomeadas do update_risk_status_batch, antes de remaining_accounts
+fn named_accounts() -> usize {
+    accounts::UpdateRiskStatusBatch {
+        config: Pubkey::default(),
+        authority: Pubkey::default(),
+        keeper: Pubkey::default(),
+        instructions_sysvar: Pubkey::default(),
+        deadman: None,
+        roles: None,
+        stats: None,
+        replay_bloom: None,
+        treasury: None,
+        payer_fee_token: None,
+        treasury_fee_token: None,
+        fee_mint: None,
+        token_program: None,
+        system_program: None,
+    }
+    .to_account_metas(None)
+    .len()
+}
+
+/// Dois ativos já gravados pelo fluxo simples: o batch não cria contas
+fn two_assets() -> Harness {
+    let mut h = Harness::new();
+    h.register_asset(OTHER_ASSET);
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial SOL/USD update");
+    let payload = h.payload_for(OTHER_ASSET, 10);
+    h.submit(&payload).expect("initial ETH/USD update");
+    h
+}
+
+#[test]
+fn updates_several_assets_in_one_instruction() {
+    let mut h = two_assets();
+    let payloads = [h.payload(30), h.payload_for(OTHER_ASSET, 60)];
+    let instructions = h.batch_instructions(&payloads, &[]);
+    h.send(&instructions).expect("batch");
+
+    let sol = h.asset(ASSET_ID).expect("SOL/USD");
+    let eth = h.asset(OTHER_ASSET).expect("ETH/USD");
+    assert_eq!((sol.risk_score, eth.risk_score), (30, 60));
+    assert_eq!(sol.timestamp, payloads[0].timestamp);
+    assert_eq!(eth.timestamp, payloads[1].timestamp);
+}
+
+#[test]
+fn rejects_two_decisions_for_the_same_asset() {
+    let mut h = two_assets();
+    let payloads = [h.payload(30), h.payload(40)];
+    let instructions = h.batch_instructions(&payloads, &[]);
+    let result = h.send(&instructions);
+    assert_program_error(&result, ErrorCode::DuplicateBatchAsset);
+    assert_eq!(h.asset(ASSET_ID).expect("asset").risk_score, 10);
+}
+
+#[test]
+fn asset_accounts_must_follow_the_decisions() {
+    let mut h = two_assets();
+    let payloads = [h.payload(30), h.payload_for(OTHER_ASSET, 60)];
+    let [verify, update] = h.batch_instructions(&payloads, &[]);
+
+    // Só as contas dos ativos: faltam shards, AssetMeta e históricos
+    let mut truncated = update.clone();
+    truncated.accounts.truncate(named_accounts() + payloads.len());
+    let result = h.send(&[verify.clone(), truncated]);
+    assert_program_error(&result, ErrorCode::InvalidAssetAccount);
+
+    // Contas dos ativos fora da ordem das decisões
+    let mut swapped = update;
+    swapped.accounts.swap(named_accounts(), named_accounts() + 1);
+    assert_eq!(swapped.accounts[named_accounts()].pubkey, asset_risk_pda(OTHER_ASSET).0);
+    let result = h.send(&[verify, swapped]);
+    assert_program_error(&result, ErrorCode::InvalidAssetAccount);
+}
+
+#[test]
+fn fee_is_charged_per_applied_decision() {
+    const FEE: u64 = 10_000;
+    let mut h = two_assets();
+    h.initialize_treasury(LAMPORTS_PER_SOL);
+    let update_fee = UpdateFee { amount: FEE, mint: None };
+    h.apply_policy(PolicyChange::UpdateFee { update_fee: Some(update_fee) })
+        .expect("update fee configured");
+
+    let decisions = [h.payload(30), h.payload_for(OTHER_ASSET, 60)]
+        .map(|payload| h.presign(&payload, &[&h.engine]));
+    let optional =
+        OptionalAccounts { treasury: Some(treasury_pda().0), ..OptionalAccounts::default() };
+    let instructions = batch_update_instructions(
+        &decisions,
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+        &optional,
+        &[],
+    )
+    .expect("valid batch");
+
+    let treasury = treasury_pda().0;
+    let before = h.svm.get_balance(&treasury).expect("treasury");
+    h.send(&instructions).expect("batch with fee");
+    assert_eq!(h.svm.get_balance(&treasury).expect("treasury"), before + 2 * FEE);
+    let account = h.svm.get_account(&treasury).expect("treasury");
+    let collected = Treasury::try_deserialize(&mut account.data.as_slice())
+        .expect("treasury layout")
+        .lamports_collected;
+    assert_eq!(collected, 2 * FEE);
+
+    // Sem o Treasury a taxa não tem para onde ir
+    let payload = h.payload(35);
+    let instructions = h.batch_instructions(&[payload], &[]);
+    let result = h.send(&instructions);
+    assert_program_error(&result, ErrorCode::FeeAccountsMissing);
+}
+
+#[test]
+fn breaker_cannot_be_skipped_by_omitting_the_history() {
+    let mut h = Harness::new();
+    let breaker = CircuitBreakerConfig { max_rise: 30, window_secs: 600, cooldown_secs: 300 };
+    h.apply_policy(PolicyChange::CircuitBreaker { circuit_breaker: Some(breaker) })
+        .expect("circuit breaker configured");
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial update");
+
+    let payload = h.payload(80);
+    let [verify, update] = h.batch_instructions(&[payload], &[]);
+    let skipped = without(update.clone(), asset_history_pda(ASSET_ID).0);
+    let result = h.send(&[verify.clone(), skipped]);
+    assert_program_error(&result, ErrorCode::InvalidAssetAccount);
+
+    h.send(&[verify, update]).expect("batch with history");
+    let asset = h.asset(ASSET_ID).expect("asset");
+    assert_eq!(asset.risk_score, 80);
+    assert!(asset.breaker_until > h.now(), "breaker should trip");
+}
+
+#[test]
+fn sdk_refuses_more_signatures_than_the_program_scans() {
+    let mut h = Harness::new();
+    let second = Keypair::new();
+    // 9 decisões com quorum 2: 18 entradas Ed25519
+    let decisions: Vec<PresignedDecision> = (0..9)
+        .map(|_| {
+            let payload = h.payload(20);
+            h.presign(&payload, &[&h.engine, &second])
+        })
+        .collect();
+    let entries: usize = decisions.iter().map(|d| d.signatures.len()).sum();
+    assert!(entries > MAX_PRECOMPILE_ENTRIES_SCANNED);
+
+    let result = batch_update_instructions(
+        &decisions,
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+        &OptionalAccounts::default(),
+        &[],
+    );
+    assert_eq!(result.err(), Some(SdkError::TooManySignatures));
+}
@@ -0,0 +1,74 @@
+//! Custo em compute units de update_risk_status no programa compilado. Os
+//! números saem com `cargo test -p cate-program-tests --test compute_units
+//! -- --nocapture`; os tetos pegam regressões bem antes do limite padrão de
+//! 200k por instrução. Fora do ReplayMode::HashList a atualização vai sem o
+//! shard UsedDecisions, como o keeper a envia.
+
+use cate_client::{PolicyChange, ReplayMode};
+use cate_program_tests::ed25519::{local_ix, sign};
+use cate_program_tests::{Harness, GENESIS_HASH};
+use cate_sdk::{presigned_update_instructions, Ed25519Entry, OptionalAccounts};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// Teto da atualização com um signer, contando a criação do ativo
+const MAX_UPDATE_CU: u64 = 150_000;
+
+/// Decisão do engine com a assinatura depois de `foreign` entradas de outro
+/// protocolo, opcionalmente com uma instrução de compute budget no meio
+fn update(h: &mut Harness, foreign: usize, budget_ix: bool) -> u64 {
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let other = Keypair::new();
+    let mut entries: Vec<Ed25519Entry> = (0..foreign).map(|_| sign(&other, &[1; 32])).collect();
+    entries.push(sign(&h.engine, &h.decision_hash(&payload)));
+
+    let mut instructions: Vec<Instruction> = vec![local_ix(&entries)];
+    if budget_ix {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(1));
+    }
+    instructions.push(h.update_ix(&decision));
+    h.send(&instructions).expect("valid update").compute_units_consumed
+}
+
+/// Decisão do engine enviada sem o shard de replay; a sequence acompanha o
+/// nonce para valer também no ReplayMode::Sequence
+fn update_without_shard(h: &mut Harness) -> u64 {
+    let mut payload = h.payload(30);
+    payload.sequence = payload.nonce;
+    let decision = h.presign(&payload, &[&h.engine]);
+    let optional = OptionalAccounts { omit_replay_shard: true, ..OptionalAccounts::default() };
+    let [_, update] =
+        presigned_update_instructions(&decision, &GENESIS_HASH, &h.authority.pubkey(), &optional)
+            .expect("valid decision");
+    let verify = local_ix(&[sign(&h.engine, &h.decision_hash(&payload))]);
+    h.send(&[verify, update]).expect("valid update").compute_units_consumed
+}
+
+#[test]
+fn update_risk_status_compute_units() {
+    let mut h = Harness::new();
+    let first = update(&mut h, 0, false);
+    let steady = update(&mut h, 0, false);
+    let foreign = update(&mut h, 4, false);
+    let padded = update(&mut h, 0, true);
+    println!("update_risk_status: first {first} CU, steady {steady} CU");
+    println!("  4 foreign entries: {foreign} CU, compute budget in between: {padded} CU");
+
+    for cost in [first, steady, foreign, padded] {
+        assert!(cost <= MAX_UPDATE_CU, "{cost} CU > {MAX_UPDATE_CU}");
+    }
+    assert!(steady < first, "asset creation should be the expensive update");
+
+    let mut modes = Vec::new();
+    for replay_mode in [ReplayMode::PerAsset, ReplayMode::Sequence] {
+        h.apply_policy(PolicyChange::ReplayMode { replay_mode }).expect("replay mode");
+        modes.push((replay_mode, update_without_shard(&mut h)));
+    }
+    println!("  without replay shard: {modes:?}");
+    for (replay_mode, cost) in modes {
+        assert!(cost <= MAX_UPDATE_CU, "{replay_mode:?}: {cost} CU > {MAX_UPDATE_CU}");
+        assert!(cost < steady, "{replay_mode:?} should skip the shard cost ({cost} CU)");
+    }
+}
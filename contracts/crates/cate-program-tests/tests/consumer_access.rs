@@ -0,0 +1,149 @@
+//! Consumer access: com `Config.consumer_access` ligado, toda leitura do
+//! estado via CPI exige o Consumer do programa de topo, não só o gate.
+//! O chamador é o mock-multisig, que repassa a instrução recebida.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{
+    asset_risk_pda, config_pda, consumer_pda, Consumer, ConsumerAccess, ErrorCode, PolicyChange,
+    ScoreSource, TradeSide, PROGRAM_ID,
+};
+use cate_program_tests::{assert_program_error, program_ix, Harness, ASSET_ID};
+use solana_sdk::hash::hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::{pubkey, system_program, sysvar};
+use workspace::{accounts, instruction};
+
+/// declare_id! de programs/mock-multisig
+const MOCK_MULTISIG_ID: Pubkey = pubkey!("FxbpYFL2KgfN9X9H3YMEEfZ43P4ViKFRhY4uikVDM1Bc");
+
+/// Ativo com decisão, consumer_access sem taxa e o mock-multisig carregado
+fn setup() -> Harness {
+    let mut h = Harness::new();
+    let payload = h.payload(20);
+    h.submit(&payload).expect("initial update");
+    let consumer_access = ConsumerAccess { fee_per_epoch: 0 };
+    h.apply_policy(PolicyChange::ConsumerAccess { consumer_access: Some(consumer_access) })
+        .expect("consumer access configured");
+
+    let path = Harness::program_path().with_file_name("mock_multisig.so");
+    h.svm
+        .add_program_from_file(MOCK_MULTISIG_ID, &path)
+        .unwrap_or_else(|e| panic!("{} ({}): run `anchor build` first", path.display(), e));
+    h
+}
+
+/// `ix` repassada pelo mock-multisig: o CATE a recebe via CPI
+fn via_cpi(ix: Instruction) -> Instruction {
+    let vault = Pubkey::find_program_address(&[b"vault"], &MOCK_MULTISIG_ID).0;
+    let mut data = hash(b"global:execute").to_bytes()[..8].to_vec();
+    data.extend_from_slice(&(ix.data.len() as u32).to_le_bytes());
+    data.extend_from_slice(&ix.data);
+    let mut metas =
+        vec![AccountMeta::new_readonly(vault, false), AccountMeta::new_readonly(PROGRAM_ID, false)];
+    metas.extend(ix.accounts);
+    // O CPI precisa da conta do programa chamado entre as repassadas
+    metas.push(AccountMeta::new_readonly(PROGRAM_ID, false));
+    Instruction { program_id: MOCK_MULTISIG_ID, accounts: metas, data }
+}
+
+/// Consumer do mock-multisig, se `with_consumer`, e o sysvar que o acompanha
+fn consumer_accounts(with_consumer: bool) -> (Option<Pubkey>, Option<Pubkey>) {
+    if with_consumer {
+        (Some(sysvar::instructions::ID), Some(consumer_pda(&MOCK_MULTISIG_ID).0))
+    } else {
+        (None, None)
+    }
+}
+
+fn get_risk_status_ix(with_consumer: bool) -> Instruction {
+    let (instructions_sysvar, consumer) = consumer_accounts(with_consumer);
+    program_ix(
+        accounts::GetRiskStatus {
+            config: config_pda().0,
+            asset_risk_status: asset_risk_pda(ASSET_ID).0,
+            instructions_sysvar,
+            consumer,
+        },
+        instruction::GetRiskStatus { _asset_id: ASSET_ID.to_string() },
+    )
+}
+
+fn effective_status_accounts() -> accounts::GetEffectiveStatus {
+    accounts::GetEffectiveStatus {
+        config: config_pda().0,
+        asset_risk_status: asset_risk_pda(ASSET_ID).0,
+        asset_policy: None,
+        portfolio: None,
+        instructions_sysvar: None,
+        consumer: None,
+        global_schedule: None,
+        asset_schedule: None,
+    }
+}
+
+fn get_effective_status_ix() -> Instruction {
+    program_ix(
+        effective_status_accounts(),
+        instruction::GetEffectiveStatus { _asset_id: ASSET_ID.to_string() },
+    )
+}
+
+fn pre_trade_check_ix() -> Instruction {
+    program_ix(
+        effective_status_accounts(),
+        instruction::PreTradeCheck {
+            _asset_id: ASSET_ID.to_string(),
+            side: TradeSide::Buy,
+            notional: 1_000,
+            score_source: ScoreSource::Raw,
+        },
+    )
+}
+
+#[test]
+fn get_risk_status_via_cpi_requires_a_consumer() {
+    let mut h = setup();
+    let result = h.send(&[via_cpi(get_risk_status_ix(false))]);
+    assert_program_error(&result, ErrorCode::ConsumerNotAuthorized);
+    // Fora de CPI a leitura segue livre
+    h.send(&[get_risk_status_ix(false)]).expect("top-level read");
+}
+
+#[test]
+fn get_effective_status_via_cpi_requires_a_consumer() {
+    let mut h = setup();
+    let result = h.send(&[via_cpi(get_effective_status_ix())]);
+    assert_program_error(&result, ErrorCode::ConsumerNotAuthorized);
+    h.send(&[get_effective_status_ix()]).expect("top-level read");
+}
+
+#[test]
+fn pre_trade_check_via_cpi_requires_a_consumer() {
+    let mut h = setup();
+    let result = h.send(&[via_cpi(pre_trade_check_ix())]);
+    assert_program_error(&result, ErrorCode::ConsumerNotAuthorized);
+}
+
+#[test]
+fn allowlisted_consumer_reads_via_cpi() {
+    let mut h = setup();
+    let authority = h.authority.pubkey();
+    let allow = program_ix(
+        accounts::SetConsumerAllowlisted {
+            config: config_pda().0,
+            consumer: consumer_pda(&MOCK_MULTISIG_ID).0,
+            authority,
+            payer: authority,
+            system_program: system_program::ID,
+        },
+        instruction::SetConsumerAllowlisted { program_id: MOCK_MULTISIG_ID, allowlisted: true },
+    );
+    h.send(&[allow]).expect("set_consumer_allowlisted");
+
+    h.send(&[via_cpi(get_risk_status_ix(true))]).expect("allowlisted read");
+    let account = h.svm.get_account(&consumer_pda(&MOCK_MULTISIG_ID).0).expect("consumer");
+    let consumer = Consumer::try_deserialize(&mut account.data.as_slice()).expect("layout");
+    assert_eq!(consumer.gate_calls, 1);
+}
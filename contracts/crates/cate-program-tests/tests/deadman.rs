@@ -0,0 +1,142 @@
+//! Dead-man switch acionado falha fechado: decisões não desbloqueiam os ativos
+//! até a authority rearmar o switch.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{
+    asset_risk_pda, config_pda, deadman_pda, effective_status, DeadmanSwitch, ErrorCode,
+};
+use cate_program_tests::{assert_program_error, program_ix, Harness, ASSET_ID};
+use cate_reader::layout;
+use litesvm::types::TransactionResult;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_program;
+use workspace::{accounts, instruction};
+
+const WINDOW_SECS: i64 = 60;
+
+fn configure(h: &mut Harness) -> TransactionResult {
+    let authority = h.authority.pubkey();
+    let ix = program_ix(
+        accounts::ConfigureDeadman {
+            config: config_pda().0,
+            deadman: deadman_pda().0,
+            authority,
+            payer: authority,
+            system_program: system_program::ID,
+        },
+        instruction::ConfigureDeadman {
+            sentinel_asset_id: "SENTINEL".to_string(),
+            window_seconds: WINDOW_SECS,
+            block_all: false,
+            assets: vec![ASSET_ID.to_string()],
+        },
+    );
+    h.send(&[ix])
+}
+
+fn asset_meta() -> AccountMeta {
+    AccountMeta::new(asset_risk_pda(ASSET_ID).0, false)
+}
+
+fn deadman(h: &Harness) -> DeadmanSwitch {
+    let account = h.svm.get_account(&deadman_pda().0).expect("deadman");
+    DeadmanSwitch::try_deserialize(&mut account.data.as_slice()).expect("deadman layout")
+}
+
+/// Ativo em score 20 e switch acionado depois que o sentinela se cala
+fn triggered() -> Harness {
+    let mut h = Harness::new();
+    let payload = h.payload(20);
+    h.submit(&payload).expect("initial update");
+    configure(&mut h).expect("configure_deadman");
+
+    h.set_time(h.now() + WINDOW_SECS + 1);
+    let trigger_accounts = accounts::TriggerDeadman { deadman: deadman_pda().0 };
+    let mut trigger = program_ix(trigger_accounts, instruction::TriggerDeadman {});
+    trigger.accounts.push(asset_meta());
+    h.send(&[trigger]).expect("trigger_deadman");
+    h
+}
+
+fn rearm(h: &mut Harness) -> TransactionResult {
+    rearm_with(h, asset_meta())
+}
+
+fn rearm_with(h: &mut Harness, asset: AccountMeta) -> TransactionResult {
+    let mut ix = program_ix(
+        accounts::RearmDeadman {
+            config: config_pda().0,
+            deadman: deadman_pda().0,
+            authority: h.authority.pubkey(),
+        },
+        instruction::RearmDeadman {},
+    );
+    ix.accounts.push(asset);
+    h.send(&[ix])
+}
+
+#[test]
+fn decisions_cannot_unblock_until_rearmed() {
+    let mut h = triggered();
+    let payload = h.payload(10);
+    h.submit(&payload).expect("decision accepted");
+
+    let asset = h.asset(ASSET_ID).expect("asset");
+    assert_eq!(asset.risk_score, 10);
+    assert_eq!(asset.is_blocked, 1);
+    assert!(asset.is_deadman_blocked());
+    assert!(effective_status(&asset, &h.config(), None, h.now()).is_blocked);
+    assert!(deadman(&h).triggered);
+    let account = h.svm.get_account(&asset_risk_pda(ASSET_ID).0).expect("asset account");
+    assert_eq!(account.data[layout::DEADMAN_BLOCKED], 1);
+
+    rearm(&mut h).expect("rearm_deadman");
+    assert!(!deadman(&h).triggered);
+    assert!(!h.asset(ASSET_ID).expect("asset").is_deadman_blocked());
+
+    let payload = h.payload(10);
+    h.submit(&payload).expect("unblocking decision");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").is_blocked, 0);
+}
+
+#[test]
+fn reconfiguring_does_not_release_a_trigger() {
+    let mut h = triggered();
+    let result = configure(&mut h);
+    assert_program_error(&result, ErrorCode::DeadmanStillTriggered);
+}
+
+#[test]
+fn rearm_requires_a_fresh_decision() {
+    let mut h = triggered();
+    // Só o acionamento gravou o ativo
+    let result = rearm(&mut h);
+    assert_program_error(&result, ErrorCode::DeadmanNoFreshDecision);
+
+    let payload = h.payload(10);
+    h.submit(&payload).expect("decision accepted");
+    h.set_time(h.now() + WINDOW_SECS + 1);
+    let result = rearm(&mut h);
+    assert_program_error(&result, ErrorCode::DeadmanNoFreshDecision);
+
+    let payload = h.payload(10);
+    h.submit(&payload).expect("fresh decision");
+    rearm(&mut h).expect("rearm_deadman");
+}
+
+#[test]
+fn rearm_rejects_a_copy_of_the_asset_account() {
+    let mut h = triggered();
+    let payload = h.payload(10);
+    h.submit(&payload).expect("decision accepted");
+
+    // Mesmos dados e dono, fora do PDA do ativo
+    let account = h.svm.get_account(&asset_risk_pda(ASSET_ID).0).expect("asset account");
+    let copy = Pubkey::new_unique();
+    h.svm.set_account(copy, account).expect("copy");
+    let result = rearm_with(&mut h, AccountMeta::new(copy, false));
+    assert_program_error(&result, ErrorCode::InvalidAssetAccount);
+    assert!(h.asset(ASSET_ID).expect("asset").is_deadman_blocked());
+}
@@ -0,0 +1,65 @@
+//! valid_until da decisão assinada: validado na entrada, gravado no
+//! AssetRiskStatus e tratado como bloqueio depois que passa.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{config_pda, effective_status, Config, ErrorCode};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID};
+
+fn config(h: &Harness) -> Config {
+    let account = h.svm.get_account(&config_pda().0).expect("config");
+    Config::try_deserialize(&mut account.data.as_slice()).expect("config layout")
+}
+
+#[test]
+fn expired_decision_reads_as_blocked() {
+    let mut h = Harness::new();
+    let mut payload = h.payload(20);
+    payload.valid_until = payload.timestamp + 60;
+    h.submit(&payload).expect("valid update");
+
+    let asset = h.asset(ASSET_ID).expect("asset created");
+    assert_eq!(asset.valid_until, payload.timestamp + 60);
+    let config = config(&h);
+    assert!(!effective_status(&asset, &config, None, payload.timestamp + 59).is_blocked);
+
+    let status = effective_status(&asset, &config, None, payload.timestamp + 60);
+    assert!(status.expired);
+    assert!(status.is_blocked);
+    assert!(!status.reported_blocked);
+}
+
+#[test]
+fn newer_decision_without_expiry_clears_it() {
+    let mut h = Harness::new();
+    let mut payload = h.payload(20);
+    payload.valid_until = payload.timestamp + 60;
+    h.submit(&payload).expect("first update");
+
+    h.set_time(payload.timestamp + 30);
+    let payload = h.payload(25);
+    h.submit(&payload).expect("second update");
+
+    let asset = h.asset(ASSET_ID).expect("asset created");
+    assert_eq!(asset.valid_until, 0);
+    assert!(!asset.is_expired(i64::MAX));
+}
+
+#[test]
+fn rejects_expiry_not_after_timestamp() {
+    let mut h = Harness::new();
+    let mut payload = h.payload(20);
+    payload.valid_until = payload.timestamp;
+    let result = h.submit(&payload);
+    assert_program_error(&result, ErrorCode::InvalidDecisionExpiry);
+}
+
+#[test]
+fn rejects_decision_already_expired() {
+    let mut h = Harness::new();
+    let mut payload = h.payload(20);
+    payload.valid_until = payload.timestamp + 10;
+    // Ainda dentro da janela de frescor, mas já vencida
+    h.set_time(payload.timestamp + 10);
+    let result = h.submit(&payload);
+    assert_program_error(&result, ErrorCode::DecisionExpired);
+}
@@ -0,0 +1,224 @@
+//! Caminho de verificação Ed25519 de update_risk_status contra o programa
+//! compilado: instruções válidas em várias formas e as forjadas que o
+//! programa (ou o programa nativo) precisa recusar.
+
+use cate_client::ErrorCode;
+use cate_program_tests::ed25519::{
+    cross_ix, entry_data, forged_ix, local_ix, local_ix_with, sign, Offsets,
+};
+use cate_program_tests::{assert_precompile_error, assert_program_error, Harness, ASSET_ID};
+use litesvm::types::TransactionResult;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signer};
+use workspace::MAX_PRECOMPILE_ENTRIES_SCANNED;
+
+#[test]
+fn accepts_local_signature() {
+    let mut h = Harness::new();
+    let payload = h.payload(42);
+    h.submit(&payload).expect("valid update");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 42);
+}
+
+#[test]
+fn accepts_signature_among_foreign_entries() {
+    let mut h = Harness::new();
+    let payload = h.payload(10);
+    let decision = h.presign(&payload, &[&h.engine]);
+    // Assinaturas de outros protocolos na mesma instrução são ignoradas
+    let other = Keypair::new();
+    let entries = [
+        sign(&other, &[1; 32]),
+        sign(&h.engine, &h.decision_hash(&payload)),
+        sign(&other, &[2; 32]),
+    ];
+    let update = h.update_ix(&decision);
+
+    h.send(&[local_ix(&entries), update]).expect("valid update");
+}
+
+#[test]
+fn accepts_verify_instruction_before_other_instructions() {
+    let mut h = Harness::new();
+    let payload = h.payload(15);
+    let [verify, update] = h.update_instructions(&payload);
+
+    let instructions = [
+        verify,
+        ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+        ComputeBudgetInstruction::set_compute_unit_price(1),
+        update,
+    ];
+    h.send(&instructions).expect("valid update");
+}
+
+#[test]
+fn accepts_cross_instruction_offsets() {
+    let mut h = Harness::new();
+    let payload = h.payload(20);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entries = [sign(&h.engine, &h.decision_hash(&payload))];
+    // A segunda instrução lê pubkey, assinatura e mensagem da primeira
+    let instructions = [local_ix(&entries), cross_ix(&entries, 0), h.update_ix(&decision)];
+
+    h.send(&instructions).expect("valid update");
+}
+
+#[test]
+fn rejects_missing_verify_instruction() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[update]);
+    assert_program_error(&result, ErrorCode::MissingEd25519Instruction);
+}
+
+#[test]
+fn rejects_signature_over_another_message() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    // Assinatura válida do engine, mas de outro payload
+    let other = h.payload(99);
+    let verify = local_ix(&[sign(&h.engine, &h.decision_hash(&other))]);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_program_error(&result, ErrorCode::SignatureVerificationFailed);
+}
+
+#[test]
+fn rejects_untrusted_signer() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let intruder = Keypair::new();
+    let decision = h.presign(&payload, &[&intruder]);
+    let verify = local_ix(&[sign(&intruder, &h.decision_hash(&payload))]);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_program_error(&result, ErrorCode::InvalidSigner);
+}
+
+#[test]
+fn rejects_duplicate_signer() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine, &h.engine]);
+    let entry = sign(&h.engine, &h.decision_hash(&payload));
+    let verify = local_ix(&[entry, entry]);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_program_error(&result, ErrorCode::DuplicateSigner);
+}
+
+#[test]
+fn rejects_message_of_other_size() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    // 31 bytes do hash: assinatura válida para o programa nativo, mas o CATE
+    // só aceita mensagens de 32 bytes
+    let hash = h.decision_hash(&payload);
+    let mut entry = sign(&h.engine, &hash);
+    let short = &hash[..31];
+    entry.signature = h.engine.sign_message(short).into();
+    let verify = local_ix_with(&[entry], |_, offsets| offsets.message_size = 31);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_program_error(&result, ErrorCode::SignatureVerificationFailed);
+}
+
+#[test]
+fn rejects_swapped_offsets_in_precompile() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entry = sign(&h.engine, &h.decision_hash(&payload));
+    // Pubkey e mensagem trocados: a assinatura não confere
+    let verify = local_ix_with(&[entry], |_, offsets| {
+        std::mem::swap(&mut offsets.pubkey_offset, &mut offsets.message_offset)
+    });
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_precompile_error(&result, 0);
+}
+
+#[test]
+fn rejects_offsets_past_the_data() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entry = sign(&h.engine, &h.decision_hash(&payload));
+    let verify = local_ix_with(&[entry], |_, offsets| offsets.message_offset = u16::MAX - 8);
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_precompile_error(&result, 0);
+}
+
+#[test]
+fn rejects_instruction_index_outside_the_transaction() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entries = [sign(&h.engine, &h.decision_hash(&payload))];
+    let verify = local_ix_with(&entries, |_, offsets| *offsets = offsets.in_instruction(7));
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_precompile_error(&result, 0);
+}
+
+#[test]
+fn rejects_nonzero_padding() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entries = [sign(&h.engine, &h.decision_hash(&payload))];
+    let verify = forged_ix(1, 1, &[Offsets::local(0, 1)], &entry_data(&entries));
+    let update = h.update_ix(&decision);
+
+    let result = h.send(&[verify, update]);
+    assert_program_error(&result, ErrorCode::Ed25519InvalidPadding);
+}
+
+#[test]
+fn rejects_replayed_decision() {
+    let mut h = Harness::new();
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entries = [sign(&h.engine, &h.decision_hash(&payload))];
+    let update = h.update_ix(&decision);
+    h.send(&[local_ix(&entries), update.clone()]).expect("first update");
+
+    let result = h.send(&[local_ix(&entries), update]);
+    assert_program_error(&result, ErrorCode::DecisionAlreadyUsed);
+}
+
+/// `count` entradas válidas, todas de outro protocolo menos a última, que é
+/// a do engine; os offsets repetidos apontam para os mesmos dois blocos
+fn engine_entry_last(h: &mut Harness, count: usize) -> TransactionResult {
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let entries = [sign(&Keypair::new(), &[1; 32]), sign(&h.engine, &h.decision_hash(&payload))];
+    let offsets: Vec<Offsets> =
+        (0..count).map(|i| Offsets::local(usize::from(i + 1 == count), count)).collect();
+    let verify = forged_ix(count as u8, 0, &offsets, &entry_data(&entries));
+    let update = h.update_ix(&decision);
+    h.send(&[verify, update])
+}
+
+#[test]
+fn scans_a_bounded_number_of_entries() {
+    let mut h = Harness::new();
+    engine_entry_last(&mut h, MAX_PRECOMPILE_ENTRIES_SCANNED).expect("last scanned entry");
+
+    let result = engine_entry_last(&mut h, MAX_PRECOMPILE_ENTRIES_SCANNED + 1);
+    assert_program_error(&result, ErrorCode::SignatureVerificationFailed);
+}
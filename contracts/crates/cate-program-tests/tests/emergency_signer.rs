@@ -0,0 +1,65 @@
+//! Signer de emergência: sozinho, só bloqueia ou sobe o score do ativo.
+
+use cate_client::{ErrorCode, PolicyChange};
+use cate_program_tests::ed25519::{local_ix, sign};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn submit(
+    h: &mut Harness,
+    signer: &Keypair,
+    risk_score: u8,
+    is_blocked: bool,
+) -> TransactionResult {
+    let mut payload = h.payload(risk_score);
+    payload.is_blocked = is_blocked;
+    let decision = h.presign(&payload, &[signer]);
+    let verify = local_ix(&[sign(signer, &h.decision_hash(&payload))]);
+    let update = h.update_ix(&decision);
+    h.send(&[verify, update])
+}
+
+/// Harness com o ativo em score 40 e `detector` habilitado como emergência
+fn with_detector() -> (Harness, Keypair) {
+    let mut h = Harness::new();
+    let detector = Keypair::new();
+    h.apply_policy(PolicyChange::EmergencySigner { signer: detector.pubkey(), enabled: true })
+        .expect("emergency signer enabled");
+    let engine = h.engine.insecure_clone();
+    submit(&mut h, &engine, 40, false).expect("engine update");
+    (h, detector)
+}
+
+#[test]
+fn raises_score_but_cannot_lower_it() {
+    let (mut h, detector) = with_detector();
+    submit(&mut h, &detector, 60, false).expect("raise");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").risk_score, 60);
+
+    let result = submit(&mut h, &detector, 60, false);
+    assert_program_error(&result, ErrorCode::EmergencySignerCannotRelax);
+    let result = submit(&mut h, &detector, 50, false);
+    assert_program_error(&result, ErrorCode::EmergencySignerCannotRelax);
+}
+
+#[test]
+fn blocks_but_cannot_unblock() {
+    let (mut h, detector) = with_detector();
+    submit(&mut h, &detector, 10, true).expect("block");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").is_blocked, 1);
+
+    let result = submit(&mut h, &detector, 90, false);
+    assert_program_error(&result, ErrorCode::EmergencySignerCannotRelax);
+
+    let engine = h.engine.insecure_clone();
+    submit(&mut h, &engine, 20, false).expect("engine unblocks");
+}
+
+#[test]
+fn trusted_signer_cannot_be_emergency() {
+    let mut h = Harness::new();
+    let engine = h.engine.pubkey();
+    let result = h.apply_policy(PolicyChange::EmergencySigner { signer: engine, enabled: true });
+    assert_program_error(&result, ErrorCode::InvalidEmergencySigner);
+}
@@ -0,0 +1,123 @@
+//! keeper_tip: a gorjeta do Treasury vai ao keeper que paga a atualização,
+//! não à authority que tem o papel Updater.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{treasury_pda, KeeperTip, PolicyChange, Treasury, UpdateFee};
+use cate_program_tests::{Harness, GENESIS_HASH};
+use cate_sdk::{batch_update_instructions, presigned_update_instructions, OptionalAccounts};
+use litesvm::types::TransactionResult;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+const TIP: u64 = 1_000_000;
+const FEE: u64 = 10_000;
+
+/// Treasury com saldo, taxa em lamports e gorjeta para dado com mais de 60s;
+/// o relógio avança até o dado gravado ficar velho
+fn configure(h: &mut Harness) -> Keypair {
+    h.initialize_treasury(LAMPORTS_PER_SOL);
+    let keeper_tip = KeeperTip { min_age_secs: 60, lamports: TIP };
+    h.apply_policy(PolicyChange::KeeperTip { keeper_tip: Some(keeper_tip) })
+        .expect("keeper tip configured");
+    let update_fee = UpdateFee { amount: FEE, mint: None };
+    h.apply_policy(PolicyChange::UpdateFee { update_fee: Some(update_fee) })
+        .expect("update fee configured");
+    h.set_time(h.now() + 120);
+
+    let keeper = Keypair::new();
+    h.svm.airdrop(&keeper.pubkey(), LAMPORTS_PER_SOL).expect("airdrop");
+    keeper
+}
+
+fn optional(keeper: &Keypair) -> OptionalAccounts {
+    OptionalAccounts {
+        treasury: Some(treasury_pda().0),
+        keeper: Some(keeper.pubkey()),
+        ..OptionalAccounts::default()
+    }
+}
+
+/// A authority paga a taxa da rede e assina como Updater; o keeper co-assina
+fn send_with_keeper(
+    h: &mut Harness,
+    instructions: &[Instruction],
+    keeper: &Keypair,
+) -> TransactionResult {
+    h.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&h.authority.pubkey()),
+        &[&h.authority, keeper],
+        h.svm.latest_blockhash(),
+    );
+    h.svm.send_transaction(tx)
+}
+
+fn balance(h: &Harness, address: &Pubkey) -> u64 {
+    h.svm.get_balance(address).unwrap_or(0)
+}
+
+fn tips_paid(h: &Harness) -> u64 {
+    let account = h.svm.get_account(&treasury_pda().0).expect("treasury");
+    Treasury::try_deserialize(&mut account.data.as_slice()).expect("treasury layout").tips_paid
+}
+
+#[test]
+fn single_update_tips_the_keeper() {
+    let mut h = Harness::new();
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial update");
+    let keeper = configure(&mut h);
+
+    let payload = h.payload(20);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let instructions = presigned_update_instructions(
+        &decision,
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+        &optional(&keeper),
+    )
+    .expect("valid decision");
+
+    let treasury = treasury_pda().0;
+    let (treasury_before, keeper_before) = (balance(&h, &treasury), balance(&h, &keeper.pubkey()));
+    send_with_keeper(&mut h, &instructions, &keeper).expect("update with keeper");
+
+    // O keeper paga a taxa e recebe a gorjeta; a authority fica de fora
+    assert_eq!(balance(&h, &treasury), treasury_before + FEE - TIP);
+    assert_eq!(balance(&h, &keeper.pubkey()), keeper_before + TIP - FEE);
+    assert_eq!(tips_paid(&h), TIP);
+}
+
+#[test]
+fn batch_tips_the_keeper_per_refreshed_asset() {
+    let mut h = Harness::new();
+    h.register_asset("ETH/USD");
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial SOL update");
+    let payload = h.payload_for("ETH/USD", 10);
+    h.submit(&payload).expect("initial ETH update");
+    let keeper = configure(&mut h);
+
+    let decisions = [h.payload(20), h.payload_for("ETH/USD", 20)]
+        .map(|payload| h.presign(&payload, &[&h.engine]));
+    let instructions = batch_update_instructions(
+        &decisions,
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+        &optional(&keeper),
+        &[],
+    )
+    .expect("valid batch");
+
+    let treasury = treasury_pda().0;
+    let (treasury_before, keeper_before) = (balance(&h, &treasury), balance(&h, &keeper.pubkey()));
+    send_with_keeper(&mut h, &instructions, &keeper).expect("batch with keeper");
+
+    assert_eq!(balance(&h, &treasury), treasury_before + 2 * FEE - 2 * TIP);
+    assert_eq!(balance(&h, &keeper.pubkey()), keeper_before + 2 * TIP - 2 * FEE);
+    assert_eq!(tips_paid(&h), 2 * TIP);
+}
@@ -0,0 +1,41 @@
+//! Atualização em transação v0 com as contas do CATE resolvidas por uma
+//! address lookup table criada pelos builders do SDK.
+
+use cate_program_tests::{Harness, ASSET_ID};
+use cate_sdk::lookup_table::{
+    build_v0_tx, create_lookup_table_instructions, lookup_table_account, lookup_table_addresses,
+};
+use solana_sdk::hash::Hash;
+use solana_sdk::signature::Signer;
+use solana_sdk::slot_hashes::SlotHashes;
+use solana_sdk::transaction::Transaction;
+
+#[test]
+fn update_resolves_accounts_through_lookup_table() {
+    let mut h = Harness::new();
+    // create_lookup_table exige o slot de derivação em SlotHashes
+    h.svm.set_sysvar(&SlotHashes::new(&[(0, Hash::default())]));
+    let authority = h.authority.pubkey();
+    let addresses = lookup_table_addresses(&[ASSET_ID]);
+    let (table, setup) = create_lookup_table_instructions(&authority, &authority, 0, &addresses);
+    for ix in setup {
+        h.send(&[ix]).expect("lookup table setup");
+    }
+    // Endereços acrescentados só valem a partir do slot seguinte
+    h.svm.warp_to_slot(1);
+    let data = h.svm.get_account(&table).expect("table created").data;
+    let table = lookup_table_account(table, &data).expect("lookup table layout");
+    assert_eq!(table.addresses, addresses);
+
+    let payload = h.payload(55);
+    let instructions = h.update_instructions(&payload);
+    let legacy = Transaction::new_with_payer(&instructions, Some(&authority));
+
+    h.svm.expire_blockhash();
+    let blockhash = h.svm.latest_blockhash();
+    let tx = build_v0_tx(&instructions, &h.authority, &[&h.authority], &[table], blockhash)
+        .expect("v0 transaction");
+    assert!(tx.message.static_account_keys().len() < legacy.message.account_keys.len());
+    h.svm.send_transaction(tx).expect("v0 update");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 55);
+}
@@ -0,0 +1,36 @@
+//! Shard UsedDecisions opcional: só o ReplayMode::HashList o exige.
+
+use cate_client::{ErrorCode, PolicyChange, ReplayMode};
+use cate_program_tests::ed25519::{local_ix, sign};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID, GENESIS_HASH};
+use cate_sdk::{presigned_update_instructions, OptionalAccounts};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::Signer;
+
+fn submit_without_shard(h: &mut Harness, risk_score: u8) -> TransactionResult {
+    let payload = h.payload(risk_score);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let optional = OptionalAccounts { omit_replay_shard: true, ..OptionalAccounts::default() };
+    let [_, update] =
+        presigned_update_instructions(&decision, &GENESIS_HASH, &h.authority.pubkey(), &optional)
+            .expect("valid decision");
+    let verify = local_ix(&[sign(&h.engine, &h.decision_hash(&payload))]);
+    h.send(&[verify, update])
+}
+
+#[test]
+fn per_asset_mode_does_not_need_the_shard() {
+    let mut h = Harness::new();
+    h.apply_policy(PolicyChange::ReplayMode { replay_mode: ReplayMode::PerAsset })
+        .expect("per-asset replay mode");
+
+    submit_without_shard(&mut h, 40).expect("update without shard");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 40);
+}
+
+#[test]
+fn hash_list_mode_requires_the_shard() {
+    let mut h = Harness::new();
+    let result = submit_without_shard(&mut h, 40);
+    assert_program_error(&result, ErrorCode::ReplayShardMissing);
+}
@@ -0,0 +1,228 @@
+//! Propriedades da replay protection do modo HashList: a janela de frescor
+//! de ReplayPolicy e o UsedDecisions do programa como ReplayStore, com a
+//! política efetiva do programa (REPLAY_POLICY) e políticas arbitrárias.
+//! No modo Bloom, a rotação dos dois filtros do ReplayBloom.
+
+use cate_replay::{ReplayError, ReplayPolicy, ReplayStore};
+use proptest::prelude::*;
+use workspace::{ReplayBloom, UsedDecisions, BLOOM_FILTER_BYTES, REPLAY_POLICY};
+
+fn shard(max_size: u16) -> UsedDecisions {
+    UsedDecisions {
+        bump: 0,
+        decisions: Vec::new(),
+        max_size,
+        shard: 0,
+        generation: 0,
+        reset_at: 0,
+    }
+}
+
+/// ReplayBloom vazio, ainda na janela 0; a primeira marcação rotaciona
+fn bloom(window_secs: i64) -> ReplayBloom {
+    ReplayBloom {
+        window_secs,
+        window: 0,
+        inserted: [0; 2],
+        capacity: u32::MAX,
+        bump: 0,
+        num_hashes: 7,
+        _padding: [0; 2],
+        filters: [[0; BLOOM_FILTER_BYTES]; 2],
+    }
+}
+
+fn hash(n: u32) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash[..4].copy_from_slice(&n.to_le_bytes());
+    hash
+}
+
+/// Políticas válidas: retenção cobre as duas bordas da janela, como exige
+/// PolicyChange::TimestampWindow
+fn policies() -> impl Strategy<Value = ReplayPolicy> {
+    (0i64..3_600, 0i64..600, 0i64..3_600).prop_map(|(max_past, max_future, extra)| {
+        ReplayPolicy {
+            max_past_secs: max_past,
+            max_future_secs: max_future,
+            retention_secs: max_past.max(max_future) + extra,
+            shard_count: REPLAY_POLICY.shard_count,
+        }
+    })
+}
+
+const NOW: std::ops::Range<i64> = 1_000_000_000..2_000_000_000;
+
+proptest! {
+    #[test]
+    fn window_is_the_closed_interval(
+        policy in policies(),
+        now in NOW,
+        offset in -10_000i64..10_000,
+    ) {
+        let timestamp = now + offset;
+        let inside = timestamp >= now - policy.max_past_secs
+            && timestamp <= now + policy.max_future_secs;
+        prop_assert_eq!(policy.check_window(timestamp, now).is_ok(), inside);
+    }
+
+    #[test]
+    fn window_boundaries_are_inclusive(policy in policies(), now in NOW) {
+        prop_assert!(policy.check_window(now - policy.max_past_secs, now).is_ok());
+        prop_assert!(policy.check_window(now + policy.max_future_secs, now).is_ok());
+        prop_assert_eq!(
+            policy.check_window(now - policy.max_past_secs - 1, now),
+            Err(ReplayError::TimestampOutOfWindow)
+        );
+        prop_assert_eq!(
+            policy.check_window(now + policy.max_future_secs + 1, now),
+            Err(ReplayError::TimestampOutOfWindow)
+        );
+    }
+
+    /// Signer adiantado em até max_future é aceito; atrasado, até max_past
+    #[test]
+    fn clock_skew_within_tolerance_is_accepted(
+        policy in policies(),
+        now in NOW,
+        skew in -3_600i64..600,
+    ) {
+        let result = policy.check_window(now + skew, now);
+        let tolerated = -policy.max_past_secs <= skew && skew <= policy.max_future_secs;
+        prop_assert_eq!(result.is_ok(), tolerated);
+    }
+
+    #[test]
+    fn window_never_overflows(
+        policy in policies(),
+        timestamp in any::<i64>(),
+        now in any::<i64>(),
+    ) {
+        let _ = policy.check_window(timestamp, now);
+    }
+
+    /// Uma decisão aceita nunca é aceita de novo enquanto o timestamp estiver
+    /// na janela, não importa quanto o relógio ande nem quantas outras entrem
+    #[test]
+    fn accepted_decision_is_never_replayed(
+        policy in policies(),
+        start in NOW,
+        skew in -600i64..600,
+        steps in prop::collection::vec((0i64..900, any::<bool>()), 1..40),
+    ) {
+        let mut store = shard(u16::MAX);
+        let timestamp = start + skew;
+        prop_assume!(policy.check_window(timestamp, start).is_ok());
+        policy.check_and_mark(&mut store, hash(0), timestamp, start).unwrap();
+
+        let mut now = start;
+        for (i, (advance, insert_other)) in steps.into_iter().enumerate() {
+            now += advance;
+            if insert_other {
+                let _ = policy.check_and_mark(&mut store, hash(i as u32 + 1), now, now);
+            }
+            let replay = policy.check_and_mark(&mut store, hash(0), timestamp, now);
+            prop_assert!(replay.is_err(), "replayed at now = {}", now);
+            if policy.check_window(timestamp, now).is_ok() {
+                prop_assert_eq!(replay, Err(ReplayError::AlreadyUsed));
+            }
+        }
+    }
+
+    /// Prune só remove registros que a janela já recusa por conta própria
+    #[test]
+    fn pruned_records_are_outside_the_window(
+        policy in policies(),
+        now in NOW,
+        ages in prop::collection::vec(-600i64..10_000, 1..50),
+    ) {
+        let mut store = shard(u16::MAX);
+        for (i, age) in ages.iter().enumerate() {
+            store.insert(hash(i as u32), now - age).unwrap();
+        }
+        let before: Vec<_> = store.decisions.iter().map(|d| (d.hash, d.timestamp)).collect();
+        store.prune(now - policy.retention_secs);
+
+        for (h, timestamp) in before {
+            if !store.contains(&h) {
+                prop_assert!(timestamp < now - policy.retention_secs);
+                prop_assert!(policy.check_window(timestamp, now).is_err());
+            } else {
+                prop_assert!(timestamp >= now - policy.retention_secs);
+            }
+        }
+    }
+
+    /// O shard nunca passa de max_size; cheio, recusa com StoreFull sem
+    /// registrar, e volta a aceitar quando registros saem da retenção,
+    /// sobrescrevendo um vencido no lugar
+    #[test]
+    fn full_shard_rejects_until_records_expire(max_size in 1u16..32, now in NOW) {
+        let policy = REPLAY_POLICY;
+        let mut store = shard(max_size);
+        for i in 0..max_size {
+            policy.check_and_mark(&mut store, hash(i.into()), now, now).unwrap();
+        }
+        let extra = hash(u32::from(max_size));
+        prop_assert_eq!(
+            policy.check_and_mark(&mut store, extra, now, now),
+            Err(ReplayError::StoreFull)
+        );
+        prop_assert_eq!(store.decisions.len(), usize::from(max_size));
+        prop_assert!(!store.contains(&extra));
+
+        let later = now + policy.retention_secs + 1;
+        prop_assert!(policy.check_and_mark(&mut store, extra, later, later).is_ok());
+        prop_assert_eq!(store.decisions.len(), usize::from(max_size));
+        prop_assert!(store.contains(&extra));
+    }
+
+    /// O registro com timestamp exatamente na borda da retenção sobrevive
+    #[test]
+    fn retention_boundary_is_kept(now in NOW) {
+        let policy = REPLAY_POLICY;
+        let mut store = shard(4);
+        store.insert(hash(1), now - policy.retention_secs).unwrap();
+        store.insert(hash(2), now - policy.retention_secs - 1).unwrap();
+        store.prune(now - policy.retention_secs);
+        prop_assert!(store.contains(&hash(1)));
+        prop_assert!(!store.contains(&hash(2)));
+    }
+
+    #[test]
+    fn shard_is_in_range(policy in policies(), first in any::<u8>()) {
+        let mut h = [0u8; 32];
+        h[0] = first;
+        prop_assert!(policy.shard_for(&h) < policy.shard_count.max(1));
+    }
+
+    /// Bloom: o hash marcado segue recusado depois de uma rotação, pois o
+    /// filtro da janela anterior continua sendo consultado
+    #[test]
+    fn bloom_rejects_a_replay_after_one_rotation(
+        window_secs in 1i64..3_600,
+        now in NOW,
+        n in any::<u32>(),
+    ) {
+        let mut store = bloom(window_secs);
+        store.check_and_mark(&hash(n), now).unwrap();
+        let next = (now.div_euclid(window_secs) + 1) * window_secs;
+        prop_assert_eq!(store.check_and_mark(&hash(n), next), Err(ReplayError::AlreadyUsed));
+        prop_assert!(store.contains(&hash(n)));
+    }
+
+    /// Bloom: após duas rotações o filtro da janela do hash foi zerado e ele
+    /// volta a ser aceito; a janela de frescor é quem o recusa antes disso
+    #[test]
+    fn bloom_forgets_a_hash_after_two_rotations(
+        window_secs in 1i64..3_600,
+        now in NOW,
+        n in any::<u32>(),
+    ) {
+        let mut store = bloom(window_secs);
+        store.check_and_mark(&hash(n), now).unwrap();
+        let later = (now.div_euclid(window_secs) + 2) * window_secs;
+        prop_assert_eq!(store.check_and_mark(&hash(n), later), Ok(()));
+        prop_assert_eq!(store.inserted.iter().sum::<u32>(), 1);
+    }
+}
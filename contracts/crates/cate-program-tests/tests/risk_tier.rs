@@ -0,0 +1,90 @@
+//! RiskTier gravado em cada decisão a partir dos limites do Config.
+
+use cate_client::{
+    asset_risk_pda, ErrorCode, PolicyChange, RiskTier, RiskTierThresholds, ASSET_RISK_VERSION,
+};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID};
+use cate_reader::layout;
+
+fn update(h: &mut Harness, risk_score: u8) {
+    let payload = h.payload(risk_score);
+    h.submit(&payload).expect("update");
+}
+
+fn tier(h: &Harness) -> RiskTier {
+    h.asset(ASSET_ID).expect("asset").risk_tier()
+}
+
+#[test]
+fn default_thresholds_follow_the_score() {
+    let mut h = Harness::new();
+    for (risk_score, expected) in [
+        (30, RiskTier::Low),
+        (31, RiskTier::Medium),
+        (70, RiskTier::High),
+        (90, RiskTier::Critical),
+        (10, RiskTier::Low),
+    ] {
+        update(&mut h, risk_score);
+        assert_eq!(tier(&h), expected, "score {}", risk_score);
+    }
+    let data = h.svm.get_account(&asset_risk_pda(ASSET_ID).0).expect("asset").data;
+    assert_eq!(data[layout::RISK_TIER], RiskTier::Low as u8);
+}
+
+#[test]
+fn configured_thresholds_apply_from_the_next_decision() {
+    let mut h = Harness::new();
+    update(&mut h, 50);
+    assert_eq!(tier(&h), RiskTier::Medium);
+
+    let thresholds = RiskTierThresholds { medium: 20, high: 40, critical: 60 };
+    h.apply_policy(PolicyChange::RiskTiers { risk_tiers: Some(thresholds) })
+        .expect("risk tiers configured");
+    assert_eq!(h.config().risk_tier_thresholds(), thresholds);
+    // Decisão já gravada mantém a faixa até ser substituída
+    assert_eq!(tier(&h), RiskTier::Medium);
+
+    update(&mut h, 50);
+    assert_eq!(tier(&h), RiskTier::High);
+
+    h.apply_policy(PolicyChange::RiskTiers { risk_tiers: None }).expect("back to default");
+    update(&mut h, 60);
+    assert_eq!(tier(&h), RiskTier::Medium);
+}
+
+#[test]
+fn rejects_thresholds_out_of_order() {
+    let mut h = Harness::new();
+    for thresholds in [
+        RiskTierThresholds { medium: 0, high: 40, critical: 60 },
+        RiskTierThresholds { medium: 40, high: 40, critical: 60 },
+        RiskTierThresholds { medium: 20, high: 70, critical: 60 },
+        RiskTierThresholds { medium: 20, high: 40, critical: 101 },
+    ] {
+        let result = h.apply_policy(PolicyChange::RiskTiers { risk_tiers: Some(thresholds) });
+        assert_program_error(&result, ErrorCode::InvalidRiskTierThresholds);
+    }
+}
+
+#[test]
+fn account_without_stored_tier_uses_default_thresholds() {
+    let mut h = Harness::new();
+    let thresholds = RiskTierThresholds { medium: 20, high: 40, critical: 60 };
+    h.apply_policy(PolicyChange::RiskTiers { risk_tiers: Some(thresholds) })
+        .expect("risk tiers configured");
+    update(&mut h, 75);
+
+    // Conta gravada antes do campo existir: versão 4, risk_tier zerado
+    let address = asset_risk_pda(ASSET_ID).0;
+    let mut account = h.svm.get_account(&address).expect("asset");
+    account.data[layout::VERSION] = 4;
+    account.data[layout::RISK_TIER] = 0;
+    h.svm.set_account(address, account).expect("set account");
+    assert_eq!(tier(&h), RiskTier::High);
+
+    update(&mut h, 50);
+    let asset = h.asset(ASSET_ID).expect("asset");
+    assert_eq!(asset.version, ASSET_RISK_VERSION);
+    assert_eq!(asset.risk_tier(), RiskTier::High);
+}
@@ -0,0 +1,27 @@
+//! prev_risk_score, score_delta e updates_count gravados a cada decisão.
+
+use cate_program_tests::{Harness, ASSET_ID};
+
+fn update(h: &mut Harness, risk_score: u8) {
+    let payload = h.payload(risk_score);
+    h.submit(&payload).expect("valid update");
+}
+
+/// (prev_risk_score, score_delta, updates_count)
+fn history(h: &Harness) -> (u8, i8, u64) {
+    let asset = h.asset(ASSET_ID).expect("asset created");
+    (asset.prev_risk_score, asset.score_delta, asset.updates_count)
+}
+
+#[test]
+fn tracks_direction_of_each_update() {
+    let mut h = Harness::new();
+    update(&mut h, 40);
+    assert_eq!(history(&h), (0, 40, 1));
+
+    update(&mut h, 75);
+    assert_eq!(history(&h), (40, 35, 2));
+
+    update(&mut h, 0);
+    assert_eq!(history(&h), (75, -75, 3));
+}
@@ -0,0 +1,182 @@
+//! Caminho de verificação secp256k1 (engines em HSM) contra o programa
+//! compilado: o signer é um endereço Ethereum registrado com
+//! KeyType::Secp256k1 ao lado do engine Ed25519, com quorum 1.
+
+use cate_client::{config_pda, secp256k1_signer, ErrorCode, KeyType, PolicyChange};
+use cate_program_tests::{
+    assert_precompile_error, assert_program_error, program_ix, Harness, ASSET_ID,
+};
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use litesvm::types::TransactionResult;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signer;
+use solana_sdk::{keccak, secp256k1_program};
+use workspace::message::DecisionPayload;
+use workspace::{
+    accounts, instruction, DEFAULT_SIGNER_ROTATION_DELAY_SECS, MAX_PRECOMPILE_ENTRIES_SCANNED,
+};
+
+/// num_signatures
+const HEADER_LEN: usize = 1;
+/// 4 u16 + 3 u8 por entrada
+const OFFSETS_LEN: usize = 11;
+/// endereço || assinatura || recovery id || mensagem de 32 bytes
+const ENTRY_DATA_LEN: usize = 20 + 64 + 1 + 32;
+
+/// Uma entrada da instrução Secp256k1
+#[derive(Clone, Copy)]
+struct Entry {
+    address: [u8; 20],
+    signature: [u8; 64],
+    recovery_id: u8,
+    message: [u8; 32],
+}
+
+fn secret(seed: u8) -> SecretKey {
+    SecretKey::parse(&[seed; 32]).expect("valid secret key")
+}
+
+/// Endereço Ethereum: últimos 20 bytes do keccak da chave pública sem prefixo
+fn address(key: &SecretKey) -> [u8; 20] {
+    let public = PublicKey::from_secret_key(key).serialize();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak::hash(&public[1..]).to_bytes()[12..]);
+    address
+}
+
+/// Assinatura de `key` sobre keccak(`message`), como o programa nativo confere
+fn sign(key: &SecretKey, message: &[u8; 32]) -> Entry {
+    let digest = Message::parse(&keccak::hash(message).to_bytes());
+    let (signature, recovery_id) = libsecp256k1::sign(&digest, key);
+    Entry {
+        address: address(key),
+        signature: signature.serialize(),
+        recovery_id: recovery_id.serialize(),
+        message: *message,
+    }
+}
+
+/// Instrução Secp256k1 na posição 0 da transação: a entrada `i` dos offsets
+/// aponta para o bloco `picks[i]` de `entries`
+fn verify_ix(entries: &[Entry], picks: &[usize]) -> Instruction {
+    let mut data = vec![picks.len() as u8];
+    let start = HEADER_LEN + OFFSETS_LEN * picks.len();
+    for &pick in picks {
+        let base = (start + ENTRY_DATA_LEN * pick) as u16;
+        data.extend_from_slice(&(base + 20).to_le_bytes());
+        data.push(0);
+        data.extend_from_slice(&base.to_le_bytes());
+        data.push(0);
+        data.extend_from_slice(&(base + 85).to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.push(0);
+    }
+    for entry in entries {
+        data.extend_from_slice(&entry.address);
+        data.extend_from_slice(&entry.signature);
+        data.push(entry.recovery_id);
+        data.extend_from_slice(&entry.message);
+    }
+    Instruction { program_id: secp256k1_program::ID, accounts: vec![], data }
+}
+
+fn update_signer(h: &Harness) -> accounts::UpdateTrustedSigner {
+    accounts::UpdateTrustedSigner { config: config_pda().0, authority: h.authority.pubkey() }
+}
+
+/// Engine Ed25519 e o endereço de `hsm` como signers, quorum 1
+fn setup(hsm: &SecretKey) -> Harness {
+    let mut h = Harness::new();
+    let signer = secp256k1_signer(&address(hsm));
+    let key_type = PolicyChange::SignerKeyType { signer, key_type: KeyType::Secp256k1 };
+    h.apply_policy(key_type).expect("secp256k1 key type");
+
+    let effective_at = h.now() + DEFAULT_SIGNER_ROTATION_DELAY_SECS;
+    let propose = program_ix(
+        update_signer(&h),
+        instruction::ProposeSignerSet {
+            signers: vec![h.engine.pubkey(), signer],
+            weights: vec![1, 1],
+            quorum: 1,
+            effective_at,
+        },
+    );
+    h.send(&[propose]).expect("propose signer set");
+    h.set_time(effective_at);
+    let activate = program_ix(update_signer(&h), instruction::ActivateSigner {});
+    h.send(&[activate]).expect("activate signer set");
+    h
+}
+
+/// update_risk_status de `payload` em nome do endereço de `claimed`, com a
+/// assinatura de `entry`
+fn update_ix(
+    h: &Harness,
+    payload: &DecisionPayload,
+    claimed: &SecretKey,
+    entry: &Entry,
+) -> Instruction {
+    let mut decision = h.presign(payload, &[]);
+    decision.signatures.push(entry.signature);
+    decision.signer_pubkeys.push(secp256k1_signer(&address(claimed)).to_bytes());
+    h.update_ix(&decision)
+}
+
+#[test]
+fn accepts_ethereum_address_signer() {
+    let hsm = secret(1);
+    let mut h = setup(&hsm);
+    let payload = h.payload(42);
+    let entry = sign(&hsm, &h.decision_hash(&payload));
+    let update = update_ix(&h, &payload, &hsm, &entry);
+
+    h.send(&[verify_ix(&[entry], &[0]), update]).expect("valid secp256k1 update");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 42);
+}
+
+#[test]
+fn rejects_wrong_recovery_id() {
+    let hsm = secret(1);
+    let mut h = setup(&hsm);
+    let payload = h.payload(30);
+    let mut entry = sign(&hsm, &h.decision_hash(&payload));
+    // Outro recovery id recupera outra chave: o endereço não confere
+    entry.recovery_id ^= 1;
+    let update = update_ix(&h, &payload, &hsm, &entry);
+
+    let result = h.send(&[verify_ix(&[entry], &[0]), update]);
+    assert_precompile_error(&result, 0);
+}
+
+#[test]
+fn rejects_signature_from_another_address() {
+    let hsm = secret(1);
+    let mut h = setup(&hsm);
+    let payload = h.payload(30);
+    // Entrada válida para o programa nativo, mas de outra chave
+    let entry = sign(&secret(2), &h.decision_hash(&payload));
+    let update = update_ix(&h, &payload, &hsm, &entry);
+
+    let result = h.send(&[verify_ix(&[entry], &[0]), update]);
+    assert_program_error(&result, ErrorCode::SignatureVerificationFailed);
+}
+
+/// `count` entradas válidas, todas de outra chave menos a última, que é a do
+/// HSM; os offsets repetidos apontam para os mesmos dois blocos
+fn hsm_entry_last(h: &mut Harness, hsm: &SecretKey, count: usize) -> TransactionResult {
+    let payload = h.payload(30);
+    let entries = [sign(&secret(2), &[1; 32]), sign(hsm, &h.decision_hash(&payload))];
+    let picks: Vec<usize> = (0..count).map(|i| usize::from(i + 1 == count)).collect();
+    let update = update_ix(h, &payload, hsm, &entries[1]);
+    h.send(&[verify_ix(&entries, &picks), update])
+}
+
+#[test]
+fn scans_a_bounded_number_of_entries() {
+    let hsm = secret(1);
+    let mut h = setup(&hsm);
+    hsm_entry_last(&mut h, &hsm, MAX_PRECOMPILE_ENTRIES_SCANNED).expect("last scanned entry");
+
+    let result = hsm_entry_last(&mut h, &hsm, MAX_PRECOMPILE_ENTRIES_SCANNED + 1);
+    assert_program_error(&result, ErrorCode::SignatureVerificationFailed);
+}
@@ -0,0 +1,130 @@
+//! Chaves de sessão: decisões assinadas pela sessão contam para o signer
+//! que a delegou, enquanto a delegação vale para o ativo.
+
+use cate_client::{asset_id_bytes, config_pda, session_key_pda, ErrorCode};
+use cate_program_tests::ed25519::{local_ix, sign};
+use cate_program_tests::{assert_program_error, program_ix, Harness, ASSET_ID, GENESIS_HASH};
+use cate_sdk::{
+    batch_update_instructions, presigned_update_instructions, register_session_instructions,
+    OptionalAccounts,
+};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+use workspace::{accounts, instruction};
+
+fn register(h: &mut Harness, session: &Keypair, asset_ids: Vec<[u8; 16]>) -> i64 {
+    let expires_at = h.now() + 3_600;
+    let ixs = register_session_instructions(
+        &h.engine,
+        &session.pubkey(),
+        expires_at,
+        asset_ids,
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+    );
+    h.send(&ixs).expect("register_session_key");
+    expires_at
+}
+
+fn submit(h: &mut Harness, session: &Keypair, with_account: bool) -> TransactionResult {
+    let payload = h.payload(35);
+    let decision = h.presign(&payload, &[session]);
+    let optional = OptionalAccounts {
+        session_key: with_account.then(|| session_key_pda(&session.pubkey()).0),
+        ..OptionalAccounts::default()
+    };
+    let [_, update] =
+        presigned_update_instructions(&decision, &GENESIS_HASH, &h.authority.pubkey(), &optional)
+            .expect("valid decision");
+    let verify = local_ix(&[sign(session, &h.decision_hash(&payload))]);
+    h.send(&[verify, update])
+}
+
+#[test]
+fn session_key_signs_for_the_root() {
+    let mut h = Harness::new();
+    let session = Keypair::new();
+    register(&mut h, &session, vec![asset_id_bytes(ASSET_ID).unwrap()]);
+
+    submit(&mut h, &session, true).expect("delegated decision");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 35);
+
+    let result = submit(&mut h, &session, false);
+    assert_program_error(&result, ErrorCode::InvalidSigner);
+}
+
+/// Batch com uma decisão da sessão em ASSET_ID e outra do engine em ETH/USD
+fn submit_batch(h: &mut Harness, session: &Keypair, with_account: bool) -> TransactionResult {
+    let delegated = h.payload(35);
+    let delegated = h.presign(&delegated, &[session]);
+    let direct = h.payload_for("ETH/USD", 20);
+    let direct = h.presign(&direct, &[&h.engine]);
+    let optional = OptionalAccounts {
+        session_key: with_account.then(|| session_key_pda(&session.pubkey()).0),
+        ..OptionalAccounts::default()
+    };
+    let instructions = batch_update_instructions(
+        &[delegated, direct],
+        &GENESIS_HASH,
+        &h.authority.pubkey(),
+        &optional,
+        &[],
+    )
+    .expect("valid batch");
+    h.send(&instructions)
+}
+
+#[test]
+fn batch_accepts_session_signed_decisions() {
+    let mut h = Harness::new();
+    h.register_asset("ETH/USD");
+    // O batch só atualiza ativos já gravados
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial SOL/USD update");
+    let payload = h.payload_for("ETH/USD", 10);
+    h.submit(&payload).expect("initial ETH/USD update");
+    let session = Keypair::new();
+    register(&mut h, &session, vec![asset_id_bytes(ASSET_ID).unwrap()]);
+
+    let result = submit_batch(&mut h, &session, false);
+    assert_program_error(&result, ErrorCode::InvalidSigner);
+
+    submit_batch(&mut h, &session, true).expect("batch with session");
+    assert_eq!(h.asset(ASSET_ID).expect("asset").risk_score, 35);
+    assert_eq!(h.asset("ETH/USD").expect("asset").risk_score, 20);
+}
+
+#[test]
+fn delegation_is_bounded_by_asset_and_time() {
+    let mut h = Harness::new();
+    let other_asset = Keypair::new();
+    register(&mut h, &other_asset, vec![asset_id_bytes("ETH/USD").unwrap()]);
+    let result = submit(&mut h, &other_asset, true);
+    assert_program_error(&result, ErrorCode::SessionKeyNotValid);
+
+    let session = Keypair::new();
+    let expires_at = register(&mut h, &session, Vec::new());
+    h.set_time(expires_at);
+    let result = submit(&mut h, &session, true);
+    assert_program_error(&result, ErrorCode::SessionKeyNotValid);
+}
+
+#[test]
+fn revoked_session_is_refused() {
+    let mut h = Harness::new();
+    let session = Keypair::new();
+    register(&mut h, &session, Vec::new());
+
+    let revoke = program_ix(
+        accounts::RevokeSessionKey {
+            config: config_pda().0,
+            session: session_key_pda(&session.pubkey()).0,
+            authority: h.authority.pubkey(),
+        },
+        instruction::RevokeSessionKey {},
+    );
+    h.send(&[revoke]).expect("revoke_session_key");
+
+    let result = submit(&mut h, &session, true);
+    assert_program_error(&result, ErrorCode::SessionKeyNotValid);
+}
@@ -0,0 +1,40 @@
+//! expires_at por signer: decisões assinadas a partir da validade são recusadas.
+
+use cate_client::{ErrorCode, PolicyChange};
+use cate_program_tests::{assert_program_error, Harness};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::Signer;
+
+fn submit(h: &mut Harness) -> TransactionResult {
+    let payload = h.payload(30);
+    h.submit(&payload)
+}
+
+#[test]
+fn rejects_decisions_signed_after_expiry() {
+    let mut h = Harness::new();
+    let signer = h.engine.pubkey();
+    let expires_at = h.now() + 2 * h.config().policy_delay_secs;
+    h.apply_policy(PolicyChange::SignerExpiry { signer, expires_at }).expect("expiry set");
+    assert_eq!(h.config().signer_expires_at(&signer), Some(expires_at));
+
+    h.set_time(expires_at - 2);
+    submit(&mut h).expect("signed before expiry");
+
+    h.set_time(expires_at);
+    let result = submit(&mut h);
+    assert_program_error(&result, ErrorCode::SignerExpired);
+}
+
+#[test]
+fn zero_clears_the_expiry() {
+    let mut h = Harness::new();
+    let signer = h.engine.pubkey();
+    let expires_at = h.now() + 2 * h.config().policy_delay_secs;
+    h.apply_policy(PolicyChange::SignerExpiry { signer, expires_at }).expect("expiry set");
+    h.apply_policy(PolicyChange::SignerExpiry { signer, expires_at: 0 }).expect("expiry cleared");
+    assert_eq!(h.config().signer_expires_at(&signer), None);
+
+    h.set_time(expires_at + 1);
+    submit(&mut h).expect("no expiry");
+}
@@ -0,0 +1,84 @@
+//! Quorum por peso: o engine principal vale 2 e as chaves reserva 1, com
+//! quorum 2 — o principal sozinho ou as duas reservas juntas.
+
+use cate_client::{config_pda, ErrorCode};
+use cate_program_tests::ed25519::{local_ix, sign};
+use cate_program_tests::{assert_program_error, program_ix, Harness, ASSET_ID};
+use litesvm::types::TransactionResult;
+use solana_sdk::signature::{Keypair, Signer};
+use workspace::{accounts, instruction, DEFAULT_SIGNER_ROTATION_DELAY_SECS};
+
+fn submit(h: &mut Harness, signers: &[&Keypair]) -> TransactionResult {
+    let payload = h.payload(30);
+    let decision = h.presign(&payload, signers);
+    let hash = h.decision_hash(&payload);
+    let entries: Vec<_> = signers.iter().map(|s| sign(s, &hash)).collect();
+    let update = h.update_ix(&decision);
+    h.send(&[local_ix(&entries), update])
+}
+
+fn update_signer(h: &Harness) -> accounts::UpdateTrustedSigner {
+    accounts::UpdateTrustedSigner { config: config_pda().0, authority: h.authority.pubkey() }
+}
+
+fn rotate(
+    h: &mut Harness,
+    signers: &[&Keypair],
+    weights: Vec<u8>,
+    quorum: u8,
+) -> TransactionResult {
+    let effective_at = h.now() + DEFAULT_SIGNER_ROTATION_DELAY_SECS;
+    let propose = program_ix(
+        update_signer(h),
+        instruction::ProposeSignerSet {
+            signers: signers.iter().map(|s| s.pubkey()).collect(),
+            weights,
+            quorum,
+            effective_at,
+        },
+    );
+    h.send(&[propose])?;
+    h.set_time(effective_at);
+    let activate = program_ix(update_signer(h), instruction::ActivateSigner {});
+    h.send(&[activate])
+}
+
+/// Engine com peso 2 e duas reservas com peso 1, quorum 2
+fn weighted() -> (Harness, Keypair, Keypair) {
+    let mut h = Harness::new();
+    let (backup_a, backup_b) = (Keypair::new(), Keypair::new());
+    let engine = h.engine.insecure_clone();
+    rotate(&mut h, &[&engine, &backup_a, &backup_b], vec![2, 1, 1], 2).expect("rotation");
+    (h, backup_a, backup_b)
+}
+
+#[test]
+fn primary_key_alone_meets_quorum() {
+    let (mut h, _, _) = weighted();
+    let engine = h.engine.insecure_clone();
+    submit(&mut h, &[&engine]).expect("weight 2 of 2");
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 30);
+}
+
+#[test]
+fn backup_keys_need_each_other() {
+    let (mut h, backup_a, backup_b) = weighted();
+    let result = submit(&mut h, &[&backup_a]);
+    assert_program_error(&result, ErrorCode::QuorumNotMet);
+
+    submit(&mut h, &[&backup_a, &backup_b]).expect("weight 1 + 1");
+}
+
+#[test]
+fn rejects_weights_that_cannot_reach_quorum() {
+    let mut h = Harness::new();
+    let engine = h.engine.insecure_clone();
+    let backup = Keypair::new();
+
+    let result = rotate(&mut h, &[&engine, &backup], vec![2], 2);
+    assert_program_error(&result, ErrorCode::InvalidSignerWeight);
+    let result = rotate(&mut h, &[&engine, &backup], vec![0, 1], 1);
+    assert_program_error(&result, ErrorCode::InvalidSignerWeight);
+    let result = rotate(&mut h, &[&engine, &backup], vec![2, 1], 4);
+    assert_program_error(&result, ErrorCode::InvalidQuorum);
+}
@@ -0,0 +1,29 @@
+//! Decisão assinada antes da gravada no ativo é recusada em qualquer modo de
+//! replay, mesmo dentro da janela de frescor.
+
+use cate_client::ErrorCode;
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID};
+
+#[test]
+fn older_decision_cannot_roll_state_back() {
+    let mut h = Harness::new();
+    let older = h.payload(20);
+    let newer = h.payload(70);
+
+    h.submit(&newer).expect("newer decision");
+    let result = h.submit(&older);
+    assert_program_error(&result, ErrorCode::DecisionNotNewerThanAsset);
+    assert_eq!(h.asset(ASSET_ID).expect("asset created").risk_score, 70);
+}
+
+#[test]
+fn same_second_is_not_newer() {
+    let mut h = Harness::new();
+    let first = h.payload(20);
+    let mut second = h.payload(30);
+    second.timestamp = first.timestamp;
+
+    h.submit(&first).expect("first decision");
+    let result = h.submit(&second);
+    assert_program_error(&result, ErrorCode::DecisionNotNewerThanAsset);
+}
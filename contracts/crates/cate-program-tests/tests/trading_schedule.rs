@@ -0,0 +1,96 @@
+//! Janelas do TradingSchedule: dia da semana em UTC, fim exclusivo e
+//! janelas que atravessam a meia-noite.
+
+use workspace::{MaintenanceWindow, TradingSchedule, WeeklyWindow};
+
+/// Segunda-feira, 01/01/2024 00:00 UTC
+const MONDAY: i64 = 1_704_067_200;
+const DAY: i64 = 86_400;
+
+const SUNDAY_BIT: u8 = 1 << 0;
+const WEEKDAYS: u8 = 0b0011_1110;
+const FRIDAY_BIT: u8 = 1 << 5;
+
+fn at(day: i64, hour: i64, minute: i64) -> i64 {
+    MONDAY + day * DAY + hour * 3_600 + minute * 60
+}
+
+fn schedule(
+    open_hours: Vec<WeeklyWindow>,
+    closed_hours: Vec<WeeklyWindow>,
+    maintenance_windows: Vec<MaintenanceWindow>,
+) -> TradingSchedule {
+    TradingSchedule {
+        bump: 0,
+        asset_id: [0u8; 16],
+        open_hours,
+        closed_hours,
+        maintenance_windows,
+        updated_at: 0,
+    }
+}
+
+/// Pregão de segunda a sexta, 14:30-21:00 UTC
+fn market_hours() -> WeeklyWindow {
+    WeeklyWindow { days: WEEKDAYS, start_minute: 14 * 60 + 30, end_minute: 21 * 60 }
+}
+
+#[test]
+fn weekly_window_follows_weekday_and_minutes() {
+    let window = market_hours();
+    assert!(window.contains(at(0, 14, 30)));
+    assert!(window.contains(at(4, 20, 59)));
+    assert!(!window.contains(at(0, 14, 29)));
+    assert!(!window.contains(at(0, 21, 0)), "end is exclusive");
+    assert!(!window.contains(at(5, 15, 0)), "saturday");
+    assert!(!window.contains(at(-1, 15, 0)), "sunday");
+}
+
+#[test]
+fn overnight_window_ends_on_the_next_day() {
+    let window = WeeklyWindow { days: FRIDAY_BIT, start_minute: 22 * 60, end_minute: 2 * 60 };
+    assert!(window.contains(at(4, 22, 0)));
+    assert!(window.contains(at(5, 1, 59)));
+    assert!(!window.contains(at(5, 2, 0)));
+    assert!(!window.contains(at(4, 21, 59)));
+    assert!(!window.contains(at(0, 1, 0)), "monday after a sunday that is not marked");
+}
+
+#[test]
+fn window_validation() {
+    assert!(market_hours().is_valid());
+    let invalid = [
+        WeeklyWindow { days: 0, start_minute: 0, end_minute: 60 },
+        WeeklyWindow { days: 1 << 7, start_minute: 0, end_minute: 60 },
+        WeeklyWindow { days: SUNDAY_BIT, start_minute: 0, end_minute: 1_440 },
+        WeeklyWindow { days: SUNDAY_BIT, start_minute: 600, end_minute: 600 },
+    ];
+    for window in invalid {
+        assert!(!window.is_valid(), "{:?}", window);
+    }
+}
+
+#[test]
+fn schedule_without_open_hours_is_always_open() {
+    let always = schedule(Vec::new(), Vec::new(), Vec::new());
+    assert!(always.is_open(at(5, 3, 0)));
+    assert!(!always.in_maintenance(at(5, 3, 0)));
+
+    let equities = schedule(vec![market_hours()], Vec::new(), Vec::new());
+    assert!(equities.is_open(at(2, 16, 0)));
+    assert!(!equities.is_open(at(2, 22, 0)));
+}
+
+#[test]
+fn maintenance_blocks_recurring_and_one_off_windows() {
+    let sunday_upgrade =
+        WeeklyWindow { days: SUNDAY_BIT, start_minute: 2 * 60, end_minute: 3 * 60 };
+    let incident = MaintenanceWindow { start: at(2, 10, 0), end: at(2, 11, 0) };
+    let schedule = schedule(Vec::new(), vec![sunday_upgrade], vec![incident]);
+
+    assert!(schedule.in_maintenance(at(6, 2, 30)));
+    assert!(!schedule.in_maintenance(at(6, 3, 0)));
+    assert!(schedule.in_maintenance(incident.start));
+    assert!(!schedule.in_maintenance(incident.end));
+    assert!(!schedule.in_maintenance(at(2, 12, 0)));
+}
@@ -0,0 +1,100 @@
+//! Treasury: saques só pela authority e nunca abaixo do rent-exempt, que
+//! também limita a gorjeta dos keepers.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{config_pda, treasury_pda, ErrorCode, KeeperTip, PolicyChange, Treasury};
+use cate_program_tests::{assert_program_error, program_ix, Harness, GENESIS_HASH};
+use cate_sdk::{presigned_update_instructions, OptionalAccounts};
+use litesvm::types::TransactionResult;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+use workspace::{accounts, instruction};
+
+const FUNDED: u64 = LAMPORTS_PER_SOL;
+
+fn rent_floor(h: &Harness) -> u64 {
+    h.svm.minimum_balance_for_rent_exemption(8 + Treasury::LEN)
+}
+
+fn balance(h: &Harness) -> u64 {
+    h.svm.get_balance(&treasury_pda().0).expect("treasury")
+}
+
+/// withdraw_treasury em lamports, assinado e pago por `authority`
+fn withdraw(h: &mut Harness, authority: &Keypair, amount: u64) -> TransactionResult {
+    let ix = program_ix(
+        accounts::WithdrawTreasury {
+            config: config_pda().0,
+            treasury: treasury_pda().0,
+            authority: authority.pubkey(),
+            treasury_token: None,
+            recipient_token: None,
+            mint: None,
+            token_program: None,
+        },
+        instruction::WithdrawTreasury { amount },
+    );
+    h.svm.expire_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        h.svm.latest_blockhash(),
+    );
+    h.svm.send_transaction(tx)
+}
+
+#[test]
+fn withdraw_keeps_the_rent_exempt_floor() {
+    let mut h = Harness::new();
+    h.initialize_treasury(FUNDED);
+    assert_eq!(balance(&h), rent_floor(&h) + FUNDED);
+
+    let authority = h.authority.insecure_clone();
+    let result = withdraw(&mut h, &authority, FUNDED + 1);
+    assert_program_error(&result, ErrorCode::TreasuryInsufficientFunds);
+
+    withdraw(&mut h, &authority, FUNDED).expect("withdraw above the floor");
+    assert_eq!(balance(&h), rent_floor(&h));
+}
+
+#[test]
+fn only_the_authority_withdraws() {
+    let mut h = Harness::new();
+    h.initialize_treasury(FUNDED);
+    let intruder = Keypair::new();
+    h.svm.airdrop(&intruder.pubkey(), LAMPORTS_PER_SOL).expect("airdrop");
+
+    let result = withdraw(&mut h, &intruder, 1);
+    assert_program_error(&result, ErrorCode::Unauthorized);
+    assert_eq!(balance(&h), rent_floor(&h) + FUNDED);
+}
+
+#[test]
+fn keeper_tip_stops_at_the_rent_floor() {
+    let mut h = Harness::new();
+    let payload = h.payload(10);
+    h.submit(&payload).expect("initial update");
+    // Saldo acima do rent menor que a gorjeta configurada
+    h.initialize_treasury(1_000);
+    let keeper_tip = KeeperTip { min_age_secs: 60, lamports: 5_000 };
+    h.apply_policy(PolicyChange::KeeperTip { keeper_tip: Some(keeper_tip) })
+        .expect("keeper tip configured");
+    h.set_time(h.now() + 120);
+
+    let payload = h.payload(20);
+    let decision = h.presign(&payload, &[&h.engine]);
+    let optional =
+        OptionalAccounts { treasury: Some(treasury_pda().0), ..OptionalAccounts::default() };
+    let authority = h.authority.pubkey();
+    let instructions =
+        presigned_update_instructions(&decision, &GENESIS_HASH, &authority, &optional)
+            .expect("valid decision");
+    h.send(&instructions).expect("update with tip");
+
+    assert_eq!(balance(&h), rent_floor(&h));
+    let account = h.svm.get_account(&treasury_pda().0).expect("treasury");
+    let treasury = Treasury::try_deserialize(&mut account.data.as_slice()).expect("layout");
+    assert_eq!(treasury.tips_paid, 1_000);
+}
@@ -0,0 +1,55 @@
+//! Taxa por atualização (Config.update_fee): cobrada do keeper em cada
+//! decisão aceita e creditada ao Treasury.
+
+use anchor_lang::AccountDeserialize;
+use cate_client::{treasury_pda, ErrorCode, PolicyChange, Treasury, UpdateFee};
+use cate_program_tests::{assert_program_error, Harness, ASSET_ID, GENESIS_HASH};
+use cate_sdk::{presigned_update_instructions, OptionalAccounts};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::Signer;
+
+const FEE: u64 = 25_000;
+
+fn with_fee() -> Harness {
+    let mut h = Harness::new();
+    h.initialize_treasury(LAMPORTS_PER_SOL);
+    let update_fee = UpdateFee { amount: FEE, mint: None };
+    h.apply_policy(PolicyChange::UpdateFee { update_fee: Some(update_fee) })
+        .expect("update fee configured");
+    h
+}
+
+fn treasury(h: &Harness) -> Treasury {
+    let account = h.svm.get_account(&treasury_pda().0).expect("treasury");
+    Treasury::try_deserialize(&mut account.data.as_slice()).expect("treasury layout")
+}
+
+#[test]
+fn each_update_credits_the_treasury() {
+    let mut h = with_fee();
+    let optional =
+        OptionalAccounts { treasury: Some(treasury_pda().0), ..OptionalAccounts::default() };
+    let before = h.svm.get_balance(&treasury_pda().0).expect("treasury");
+
+    for score in [20, 30] {
+        let payload = h.payload(score);
+        let decision = h.presign(&payload, &[&h.engine]);
+        let authority = h.authority.pubkey();
+        let instructions =
+            presigned_update_instructions(&decision, &GENESIS_HASH, &authority, &optional)
+                .expect("valid decision");
+        h.send(&instructions).expect("update with fee");
+    }
+
+    assert_eq!(h.svm.get_balance(&treasury_pda().0).expect("treasury"), before + 2 * FEE);
+    assert_eq!(treasury(&h).lamports_collected, 2 * FEE);
+}
+
+#[test]
+fn update_without_the_treasury_is_rejected() {
+    let mut h = with_fee();
+    let payload = h.payload(20);
+    let result = h.submit(&payload);
+    assert_program_error(&result, ErrorCode::FeeAccountsMissing);
+    assert!(h.asset(ASSET_ID).is_none());
+}
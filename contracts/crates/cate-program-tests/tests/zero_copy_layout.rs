@@ -0,0 +1,40 @@
+//! Layout zero-copy do AssetRiskStatus: os offsets de `cate_reader::layout`
+//! leem a conta gravada pelo programa sem desserializar.
+
+use cate_client::{asset_id_bytes, asset_risk_pda, AssetLifecycle, AssetRiskStatus};
+use cate_program_tests::{Harness, ASSET_ID};
+use cate_reader::layout;
+
+fn i64_at(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+#[test]
+fn size_is_pinned() {
+    // Mudar o tamanho quebra integradores que leem por offset
+    assert_eq!(AssetRiskStatus::LEN, 960);
+    assert_eq!(layout::ACCOUNT_LEN, 968);
+}
+
+#[test]
+fn offsets_read_the_account_written_by_the_program() {
+    let mut h = Harness::new();
+    let mut payload = h.payload(64);
+    payload.is_blocked = true;
+    payload.valid_until = payload.timestamp + 600;
+    h.submit(&payload).expect("valid update");
+
+    let data = h.svm.get_account(&asset_risk_pda(ASSET_ID).0).expect("asset created").data;
+    assert_eq!(data.len(), layout::ACCOUNT_LEN);
+    assert_eq!(data[layout::ASSET_ID..layout::ASSET_ID + 16], asset_id_bytes(ASSET_ID).unwrap());
+    assert_eq!(data[layout::RISK_SCORE], 64);
+    assert_eq!(data[layout::IS_BLOCKED], 1);
+    assert_eq!(data[layout::LIFECYCLE], AssetLifecycle::Live as u8);
+    assert_eq!(i64_at(&data, layout::TIMESTAMP), payload.timestamp);
+    assert_eq!(i64_at(&data, layout::VALID_UNTIL), payload.valid_until);
+    assert_eq!(i64_at(&data, layout::LAST_UPDATED), h.now());
+
+    let status = h.asset(ASSET_ID).expect("asset created");
+    assert_eq!(data[layout::VERSION], status.version);
+    assert_eq!(data[layout::BUMP], status.bump);
+}
@@ -0,0 +1,149 @@
+//! cate-pull
+//!
+//! Republica o estado de risco do CATE no formato de mensagem de um pull
+//! oracle (o `PriceFeedMessage` do Pyth), para que protocolos que já consomem
+//! feeds assinados possam ler risco com o mesmo código de parsing.
+//!
+//! Mapeamento dos campos:
+//! - `feed_id`: `SHA256("CATE_FEED_V1" || asset_id com padding)`
+//! - `price`: risk_score (0-100), `exponent` = 0
+//! - `conf`: confidence_ratio em bps
+//! - `publish_time`: timestamp da decisão assinada
+//! - `prev_publish_time`: timestamp da decisão anterior (ou o mesmo, se desconhecido)
+//! - `ema_price`: 1 se o ativo está bloqueado, 0 caso contrário (risco não tem EMA)
+//! - `ema_conf`: publisher_count
+//!
+//! A mensagem é assinada com Ed25519 sobre `SHA256("CATE_PULL_V1" || message)`.
+//! O consumidor só confia na pubkey do publicador, como faria com o guardian set.
+
+use anchor_lang::solana_program::hash::hashv;
+use workspace::AssetRiskStatus;
+
+pub const FEED_DOMAIN: &[u8] = b"CATE_FEED_V1";
+pub const UPDATE_DOMAIN: &[u8] = b"CATE_PULL_V1";
+
+/// Discriminante do PriceFeedMessage no enum de mensagens do Pyth
+pub const PRICE_FEED_MESSAGE_TAG: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullError {
+    InvalidLength,
+    InvalidTag,
+    FeedIdMismatch,
+    PriceTooOld,
+}
+
+/// Mesmo layout e ordem de campos do `PriceFeedMessage` do Pyth
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiskFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Preço no formato do `pyth_solana_receiver_sdk::price_update::Price`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Price {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+pub fn feed_id(asset_id: &[u8; 16]) -> [u8; 32] {
+    hashv(&[FEED_DOMAIN, asset_id]).to_bytes()
+}
+
+/// Digest que o publicador assina
+pub fn update_digest(message: &[u8]) -> [u8; 32] {
+    hashv(&[UPDATE_DOMAIN, message]).to_bytes()
+}
+
+impl RiskFeedMessage {
+    /// tag + feed_id + 7 campos numéricos, big-endian como no Pyth
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8;
+
+    pub fn from_status(status: &AssetRiskStatus, prev_publish_time: Option<i64>) -> Self {
+        Self {
+            feed_id: feed_id(&status.asset_id),
+            price: i64::from(status.risk_score),
+            conf: status.confidence_ratio,
+            exponent: 0,
+            publish_time: status.timestamp,
+            prev_publish_time: prev_publish_time.unwrap_or(status.timestamp),
+            ema_price: i64::from(status.is_blocked),
+            ema_conf: u64::from(status.publisher_count),
+        }
+    }
+
+    pub fn risk_score(&self) -> i64 {
+        self.price
+    }
+
+    pub fn is_blocked(&self) -> bool {
+        self.ema_price != 0
+    }
+
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        let mut out = [0u8; Self::LEN];
+        out[0] = PRICE_FEED_MESSAGE_TAG;
+        out[1..33].copy_from_slice(&self.feed_id);
+        out[33..41].copy_from_slice(&self.price.to_be_bytes());
+        out[41..49].copy_from_slice(&self.conf.to_be_bytes());
+        out[49..53].copy_from_slice(&self.exponent.to_be_bytes());
+        out[53..61].copy_from_slice(&self.publish_time.to_be_bytes());
+        out[61..69].copy_from_slice(&self.prev_publish_time.to_be_bytes());
+        out[69..77].copy_from_slice(&self.ema_price.to_be_bytes());
+        out[77..85].copy_from_slice(&self.ema_conf.to_be_bytes());
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, PullError> {
+        if data.len() != Self::LEN {
+            return Err(PullError::InvalidLength);
+        }
+        if data[0] != PRICE_FEED_MESSAGE_TAG {
+            return Err(PullError::InvalidTag);
+        }
+        let be8 = |at: usize| -> [u8; 8] { data[at..at + 8].try_into().unwrap() };
+        Ok(Self {
+            feed_id: data[1..33].try_into().unwrap(),
+            price: i64::from_be_bytes(be8(33)),
+            conf: u64::from_be_bytes(be8(41)),
+            exponent: i32::from_be_bytes(data[49..53].try_into().unwrap()),
+            publish_time: i64::from_be_bytes(be8(53)),
+            prev_publish_time: i64::from_be_bytes(be8(61)),
+            ema_price: i64::from_be_bytes(be8(69)),
+            ema_conf: u64::from_be_bytes(be8(77)),
+        })
+    }
+
+    /// Mesma semântica do `PriceUpdateV2::get_price_no_older_than` do Pyth.
+    /// A verificação da assinatura é responsabilidade do chamador (Ed25519
+    /// precompile on-chain sobre `update_digest`).
+    pub fn get_price_no_older_than(
+        &self,
+        unix_timestamp: i64,
+        maximum_age: u64,
+        feed_id: &[u8; 32],
+    ) -> Result<Price, PullError> {
+        if &self.feed_id != feed_id {
+            return Err(PullError::FeedIdMismatch);
+        }
+        let age = unix_timestamp.saturating_sub(self.publish_time);
+        if age > i64::try_from(maximum_age).unwrap_or(i64::MAX) {
+            return Err(PullError::PriceTooOld);
+        }
+        Ok(Price {
+            price: self.price,
+            conf: self.conf,
+            exponent: self.exponent,
+            publish_time: self.publish_time,
+        })
+    }
+}
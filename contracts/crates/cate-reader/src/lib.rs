@@ -0,0 +1,132 @@
+//! cate-reader
+//!
+//! Leitura do AssetRiskStatus direto da conta, sem CPI: o programa integrador
+//! recebe a conta do ativo e `RiskStatusLoader::load` confere dono,
+//! discriminator e derivação do PDA antes de expor o estado. Mais barato que
+//! `assert_trading_allowed`, mas não vê Config (pausa) nem AssetPolicy.
+//!
+//! A conta é zero-copy (`#[repr(C)]`): quem precisa só de um ou dois campos
+//! lê os bytes direto pelos offsets de [`layout`], sem copiar a struct.
+
+use anchor_lang::prelude::*;
+use workspace::{
+    AssetLifecycle, AssetRiskStatus, ErrorCode, ASSET_RISK_SEED, SEED_NAMESPACE,
+    ID as PROGRAM_ID,
+};
+
+/// Offsets dos campos do AssetRiskStatus nos dados da conta, já contando os
+/// 8 bytes do discriminator. Inteiros em little-endian; bools são u8 (0/1),
+/// `LIFECYCLE` é o discriminante de AssetLifecycle e `RISK_TIER` o de
+/// RiskTier, gravado a partir da `VERSION` 5 (antes lê zero; a faixa sai do
+/// score como em `AssetRiskStatus::risk_tier`). O layout só cresce consumindo
+/// `_reserved`: offsets publicados aqui não mudam.
+pub mod layout {
+    use core::mem::offset_of;
+    use workspace::AssetRiskStatus;
+
+    const DISCRIMINATOR_LEN: usize = 8;
+
+    /// Tamanho total da conta
+    pub const ACCOUNT_LEN: usize = DISCRIMINATOR_LEN + AssetRiskStatus::LEN;
+
+    pub const LAST_UPDATED: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, last_updated);
+    pub const CONFIDENCE_RATIO: usize =
+        DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, confidence_ratio);
+    pub const TIMESTAMP: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, timestamp);
+    pub const BREAKER_UNTIL: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, breaker_until);
+    pub const COOLING_UNTIL: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, cooling_until);
+    pub const VALID_UNTIL: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, valid_until);
+    pub const ASSET_ID: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, asset_id);
+    pub const DECISION_HASH: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, decision_hash);
+    pub const SUB_SCORES: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, sub_scores);
+    pub const SCORE_EMA: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, score_ema);
+    pub const BUMP: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, bump);
+    pub const RISK_SCORE: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, risk_score);
+    pub const IS_BLOCKED: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, is_blocked);
+    pub const LIFECYCLE: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, lifecycle);
+    pub const REDUCE_ONLY: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, reduce_only);
+    pub const ADMIN_REDUCE_ONLY: usize =
+        DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, admin_reduce_only);
+    pub const RISK_FLAGS: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, risk_flags);
+    pub const COOLING_DECISIONS_LEFT: usize =
+        DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, cooling_decisions_left);
+    pub const VERSION: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, version);
+    pub const RISK_TIER: usize = DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, risk_tier);
+    pub const DEADMAN_BLOCKED: usize =
+        DISCRIMINATOR_LEN + offset_of!(AssetRiskStatus, deadman_blocked);
+}
+
+/// AssetRiskStatus validado contra o programa CATE
+pub struct RiskStatusLoader {
+    address: Pubkey,
+    status: AssetRiskStatus,
+}
+
+impl RiskStatusLoader {
+    /// Falha com `InvalidAssetAccount` se a conta não é o PDA de risco de um
+    /// ativo do CATE (dono, discriminator, layout zero-copy e seeds)
+    pub fn load(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, PROGRAM_ID, ErrorCode::InvalidAssetAccount);
+        let data = info.try_borrow_data()?;
+        // Contas Borsh ainda não migradas têm outro tamanho e são recusadas
+        require!(data.len() == layout::ACCOUNT_LEN, ErrorCode::InvalidAssetAccount);
+        let status = AssetRiskStatus::from_account_data(&data)?;
+
+        // create_program_address com o bump gravado: mais barato que find_program_address
+        let expected = Pubkey::create_program_address(
+            &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&status.asset_id), &[status.bump]],
+            &PROGRAM_ID,
+        )
+        .map_err(|_| error!(ErrorCode::InvalidAssetAccount))?;
+        require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+
+        Ok(Self { address: info.key(), status })
+    }
+
+    /// Igual a `load`, exigindo também que a conta seja a do ativo esperado
+    pub fn load_asset(info: &AccountInfo, asset_id: &str) -> Result<Self> {
+        let loader = Self::load(info)?;
+        require!(
+            asset_id_seed(&loader.status.asset_id) == asset_id.as_bytes(),
+            ErrorCode::InvalidAssetAccount
+        );
+        Ok(loader)
+    }
+
+    pub fn address(&self) -> Pubkey {
+        self.address
+    }
+
+    pub fn status(&self) -> &AssetRiskStatus {
+        &self.status
+    }
+
+    /// Segundos desde a última atualização gravada
+    pub fn age(&self, now: i64) -> i64 {
+        now.saturating_sub(self.status.last_updated)
+    }
+
+    /// Trading irrestrito agora: ativo `Live`, sem bloqueio (inclusive do
+    /// dead-man switch), reduce-only, breaker armado, quarentena ou decisão
+    /// vencida e atualizado há no máximo `max_age` segundos
+    pub fn is_tradeable(&self, max_age: i64) -> Result<bool> {
+        Ok(self.is_tradeable_at(max_age, Clock::get()?.unix_timestamp))
+    }
+
+    pub fn is_tradeable_at(&self, max_age: i64, now: i64) -> bool {
+        let status = &self.status;
+        status.lifecycle() == AssetLifecycle::Live
+            && status.is_blocked == 0
+            && status.deadman_blocked == 0
+            && !status.is_expired(now)
+            && !status.is_reduce_only()
+            && !status.breaker_active(now)
+            && !status.is_cooling(now)
+            && self.age(now) <= max_age
+    }
+}
+
+fn asset_id_seed(asset_id: &[u8; 16]) -> &[u8] {
+    let len = asset_id.iter().position(|b| *b == 0).unwrap_or(asset_id.len());
+    &asset_id[..len]
+}
@@ -0,0 +1,143 @@
+//! cate-replay
+//!
+//! Lógica de replay protection do CATE extraída para reuso: janela de
+//! frescor do timestamp, retenção dos hashes usados e seleção de shard.
+//! O armazenamento fica atrás de `ReplayStore`, então backends diferentes
+//! (Vec em conta, ring buffer, bloom, por ativo) usam a mesma política.
+//!
+//! Sem dependências: funciona on-chain (BPF) e off-chain.
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// Timestamp fora da janela [now - max_past, now + max_future]
+    TimestampOutOfWindow,
+    /// Hash já registrado dentro do período de retenção
+    AlreadyUsed,
+    /// Backend sem espaço mesmo após remover entradas expiradas
+    StoreFull,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            ReplayError::TimestampOutOfWindow => "timestamp outside freshness window",
+            ReplayError::AlreadyUsed => "decision hash already used",
+            ReplayError::StoreFull => "replay store full",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Backend de armazenamento dos hashes já usados
+pub trait ReplayStore {
+    /// Hash registrado e ainda não removido
+    fn contains(&self, hash: &[u8; 32]) -> bool;
+
+    /// Remove registros com timestamp anterior a `cutoff`
+    fn prune(&mut self, cutoff: i64);
+
+    /// Registra o hash; deve retornar StoreFull se não houver espaço
+    fn insert(&mut self, hash: [u8; 32], timestamp: i64) -> Result<(), ReplayError>;
+
+    /// Registra o hash liberando espaço com registros anteriores a `cutoff`.
+    /// O padrão remove todos os vencidos antes de inserir; backends de
+    /// tamanho fixo podem sobrescrever só o necessário. Registro vencido que
+    /// fica no store é inofensivo: a janela de frescor já recusa o timestamp.
+    fn insert_evicting(
+        &mut self,
+        hash: [u8; 32],
+        timestamp: i64,
+        cutoff: i64,
+    ) -> Result<(), ReplayError> {
+        self.prune(cutoff);
+        self.insert(hash, timestamp)
+    }
+}
+
+/// Parâmetros da replay protection
+#[derive(Clone, Copy, Debug)]
+pub struct ReplayPolicy {
+    /// Idade máxima aceita para o timestamp assinado (segundos)
+    pub max_past_secs: i64,
+    /// Tolerância para relógio adiantado do signer (segundos)
+    pub max_future_secs: i64,
+    /// Por quanto tempo um hash usado é lembrado; deve ser >= max_past_secs
+    /// para que uma decisão removida já esteja fora da janela de frescor
+    pub retention_secs: i64,
+    /// Número de shards; o shard é o primeiro byte do hash módulo este valor
+    pub shard_count: u8,
+}
+
+impl ReplayPolicy {
+    /// Verifica apenas a janela de frescor
+    pub fn check_window(&self, timestamp: i64, now: i64) -> Result<(), ReplayError> {
+        if timestamp < now.saturating_sub(self.max_past_secs)
+            || timestamp > now.saturating_add(self.max_future_secs)
+        {
+            return Err(ReplayError::TimestampOutOfWindow);
+        }
+        Ok(())
+    }
+
+    pub fn shard_for(&self, hash: &[u8; 32]) -> u8 {
+        hash[0] % self.shard_count.max(1)
+    }
+
+    /// Janela de frescor + checagem de uso + registro, nessa ordem.
+    /// `store` deve ser o shard retornado por `shard_for(hash)`.
+    pub fn check_and_mark<S: ReplayStore + ?Sized>(
+        &self,
+        store: &mut S,
+        hash: [u8; 32],
+        timestamp: i64,
+        now: i64,
+    ) -> Result<(), ReplayError> {
+        self.check_window(timestamp, now)?;
+        if store.contains(&hash) {
+            return Err(ReplayError::AlreadyUsed);
+        }
+        store.insert_evicting(hash, timestamp, now.saturating_sub(self.retention_secs))
+    }
+}
+
+/// Dimensionamento de um bloom filter de tamanho fixo para uma taxa de falso
+/// positivo alvo
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BloomParams {
+    /// Bits marcados por hash (k)
+    pub num_hashes: u8,
+    /// Inserções por filtro até a taxa alvo ser ultrapassada
+    pub capacity: u32,
+}
+
+impl BloomParams {
+    /// k = ⌈log2(1/p)⌉ e n = m / (k / ln 2): aproximações inteiras das fórmulas
+    /// ótimas, arredondadas para o lado de menos falsos positivos.
+    /// `None` para taxa 0 ou >= 100%.
+    pub fn for_rate(num_bits: u32, false_positive_bps: u16) -> Option<Self> {
+        if false_positive_bps == 0 || false_positive_bps >= 10_000 {
+            return None;
+        }
+        let mut num_hashes = 1u8;
+        while (u32::from(false_positive_bps) << num_hashes) < 10_000 {
+            num_hashes += 1;
+        }
+        // 1 / ln 2 ≈ 1,4427
+        let capacity = u64::from(num_bits) * 10_000 / (14_427 * u64::from(num_hashes));
+        Some(Self { num_hashes, capacity: capacity as u32 })
+    }
+}
+
+/// Posição do `i`-ésimo bit de `hash` em um filtro de `num_bits`. Double
+/// hashing sobre os primeiros 16 bytes, que já são uniformes num hash de decisão.
+pub fn bloom_bit(hash: &[u8; 32], i: u8, num_bits: u32) -> u32 {
+    let mut h1 = [0u8; 8];
+    let mut h2 = [0u8; 8];
+    h1.copy_from_slice(&hash[..8]);
+    h2.copy_from_slice(&hash[8..16]);
+    let h1 = u64::from_le_bytes(h1);
+    let h2 = u64::from_le_bytes(h2) | 1;
+    (h1.wrapping_add(u64::from(i).wrapping_mul(h2)) % u64::from(num_bits.max(1))) as u32
+}
@@ -0,0 +1,442 @@
+//! cate-sdk
+//!
+//! Monta a transação de atualização do CATE: a instrução Ed25519 com os
+//! offsets que `verify_quorum` procura, seguida de `update_risk_status` com
+//! as contas derivadas do payload. Integradores não precisam refazer a conta
+//! dos offsets nem a derivação dos PDAs. Relayers que recebem decisões já
+//! assinadas usam `PresignedDecision` e `batch_update_instructions`; batches
+//! com muitas contas vão em transação v0 com a tabela de `lookup_table`.
+//!
+//! ```ignore
+//! let tx = build_update_risk_tx(
+//!     &payload,
+//!     &genesis_hash,
+//!     &[&engine_key],
+//!     &relayer,
+//!     &OptionalAccounts::default(),
+//!     blockhash,
+//! )?;
+//! ```
+
+pub mod lookup_table;
+
+use std::fmt;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use cate_client::{
+    asset_history_pda, asset_id_bytes, asset_id_string, asset_meta_pda, asset_policy_pda,
+    asset_risk_pda, config_pda, session_key_pda, used_decisions_pda_for, PROGRAM_ID,
+};
+use solana_sdk::ed25519_program;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+use workspace::message::{decision_hash, session_hash, DecisionPayload};
+use workspace::{accounts, instruction};
+
+/// Entradas Ed25519 conferidas pelo programa em uma instrução
+pub use workspace::MAX_PRECOMPILE_ENTRIES_SCANNED;
+
+/// Tamanho de cada entrada de offsets na instrução Ed25519
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// Índice que aponta para dados na própria instrução Ed25519
+const LOCAL_INSTRUCTION: u16 = u16::MAX;
+/// Teto de signers por decisão no programa (MAX_TRUSTED_SIGNERS)
+pub const MAX_SIGNERS: usize = 10;
+/// Teto de decisões por update_risk_status_batch no programa (MAX_BATCH_SIZE)
+pub const MAX_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SdkError {
+    /// Nenhum signer para assinar a decisão
+    NoSigners,
+    /// Mais signers do que o programa aceita em uma decisão
+    TooManySigners,
+    /// asset_id vazio ou fora do padding (bytes depois do primeiro zero)
+    InvalidAssetId,
+    /// Número de assinaturas diferente do número de signers
+    SignatureCountMismatch,
+    /// Batch vazio ou acima de MAX_BATCH_SIZE
+    InvalidBatchSize,
+    /// Assinaturas do batch acima de MAX_PRECOMPILE_ENTRIES_SCANNED: as
+    /// excedentes não seriam conferidas pelo programa
+    TooManySignatures,
+    /// Dados da conta não são uma address lookup table
+    InvalidLookupTable,
+    /// Mensagem v0 não compila (ex.: contas demais para as tabelas dadas)
+    MessageCompile,
+    /// Signers não batem com os exigidos pela mensagem
+    SigningFailed,
+}
+
+impl fmt::Display for SdkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            SdkError::NoSigners => "decision needs at least one signer",
+            SdkError::TooManySigners => "too many signers for one decision",
+            SdkError::InvalidAssetId => "asset id is empty or not zero-padded",
+            SdkError::SignatureCountMismatch => "signature and signer counts differ",
+            SdkError::InvalidBatchSize => "batch must hold between 1 and 10 decisions",
+            SdkError::TooManySignatures => "batch has more than 16 signatures to verify",
+            SdkError::InvalidLookupTable => "account is not an address lookup table",
+            SdkError::MessageCompile => "could not compile the v0 message",
+            SdkError::SigningFailed => "signers do not match the message",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for SdkError {}
+
+/// Contas opcionais de `update_risk_status`; `None` omite a conta. Os PDAs
+/// saem dos helpers do cate-client (ex.: `deadman_pda`, `stats_pda`).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OptionalAccounts {
+    pub deadman: Option<Pubkey>,
+    pub pending_decision: Option<Pubkey>,
+    pub pyth_price: Option<Pubkey>,
+    pub switchboard_feed: Option<Pubkey>,
+    pub roles: Option<Pubkey>,
+    pub portfolio: Option<Pubkey>,
+    pub stats: Option<Pubkey>,
+    pub replay_bloom: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub payer_fee_token: Option<Pubkey>,
+    pub treasury_fee_token: Option<Pubkey>,
+    pub fee_mint: Option<Pubkey>,
+    pub token_program: Option<Pubkey>,
+    /// SessionKey da chave que assinou, se a decisão veio de uma sessão
+    pub session_key: Option<Pubkey>,
+    /// Signer que paga a transação e recebe o keeper_tip; sem ele, a authority
+    pub keeper: Option<Pubkey>,
+    /// Deixa de fora o shard UsedDecisions do hash; só o ReplayMode::HashList o lê
+    pub omit_replay_shard: bool,
+}
+
+/// Uma assinatura a conferir pela instrução Ed25519
+#[derive(Clone, Copy)]
+pub struct Ed25519Entry {
+    pub pubkey: [u8; 32],
+    pub signature: [u8; 64],
+    pub message: [u8; 32],
+}
+
+/// Instrução Ed25519 que confere `entries`, todas com dados locais à própria
+/// instrução: cabeçalho, uma entrada de offsets por assinatura e, depois,
+/// pubkey || assinatura || mensagem de cada uma
+pub fn ed25519_verify_ix(entries: &[Ed25519Entry]) -> Instruction {
+    let header_len = 2 + SIGNATURE_OFFSETS_LEN * entries.len();
+    let entry_len = 32 + 64 + 32;
+    let mut data = Vec::with_capacity(header_len + entry_len * entries.len());
+    data.push(entries.len() as u8);
+    data.push(0); // padding
+
+    let mut payloads = Vec::with_capacity(entry_len * entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        let base = (header_len + entry_len * i) as u16;
+        let (pubkey_offset, signature_offset, message_offset) = (base, base + 32, base + 96);
+        for field in [
+            signature_offset,
+            LOCAL_INSTRUCTION,
+            pubkey_offset,
+            LOCAL_INSTRUCTION,
+            message_offset,
+            32,
+            LOCAL_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        payloads.extend_from_slice(&entry.pubkey);
+        payloads.extend_from_slice(&entry.signature);
+        payloads.extend_from_slice(&entry.message);
+    }
+    data.extend_from_slice(&payloads);
+
+    Instruction { program_id: ed25519_program::ID, accounts: Vec::new(), data }
+}
+
+/// Instrução Ed25519 com uma assinatura de cada signer sobre `message`.
+/// Retorna também as assinaturas, na ordem dos signers, para os argumentos
+/// de `update_risk_status`.
+pub fn ed25519_ix(message: &[u8; 32], signers: &[&Keypair]) -> (Instruction, Vec<[u8; 64]>) {
+    let entries: Vec<Ed25519Entry> = signers
+        .iter()
+        .map(|signer| Ed25519Entry {
+            pubkey: signer.pubkey().to_bytes(),
+            signature: signer.sign_message(message).into(),
+            message: *message,
+        })
+        .collect();
+    let signatures = entries.iter().map(|entry| entry.signature).collect();
+    (ed25519_verify_ix(&entries), signatures)
+}
+
+/// Decisão assinada off-chain pelos signers do engine, como chega a um
+/// relayer que não tem as chaves
+#[derive(Clone)]
+pub struct PresignedDecision {
+    pub payload: DecisionPayload,
+    pub signatures: Vec<[u8; 64]>,
+    pub signer_pubkeys: Vec<[u8; 32]>,
+}
+
+impl PresignedDecision {
+    pub fn decision_hash(&self, genesis_hash: &[u8; 32]) -> [u8; 32] {
+        decision_hash(&PROGRAM_ID, genesis_hash, &self.payload)
+    }
+
+    pub fn asset_id(&self) -> String {
+        asset_id_string(&self.payload.asset_id)
+    }
+
+    fn validate(&self) -> Result<(), SdkError> {
+        if self.signer_pubkeys.is_empty() {
+            return Err(SdkError::NoSigners);
+        }
+        if self.signer_pubkeys.len() > MAX_SIGNERS {
+            return Err(SdkError::TooManySigners);
+        }
+        if self.signatures.len() != self.signer_pubkeys.len() {
+            return Err(SdkError::SignatureCountMismatch);
+        }
+        if asset_id_bytes(&self.asset_id()) != Some(self.payload.asset_id) {
+            return Err(SdkError::InvalidAssetId);
+        }
+        Ok(())
+    }
+
+    fn ed25519_entries(&self, hash: &[u8; 32]) -> impl Iterator<Item = Ed25519Entry> + '_ {
+        let message = *hash;
+        self.signer_pubkeys
+            .iter()
+            .zip(self.signatures.iter())
+            .map(move |(pubkey, signature)| Ed25519Entry {
+                pubkey: *pubkey,
+                signature: *signature,
+                message,
+            })
+    }
+}
+
+/// Par [Ed25519, update_risk_status] para `payload` assinado por `signers`
+/// no cluster de `genesis_hash`. `authority` assina a atualização: precisa
+/// ser a authority do Config ou ter o papel Updater. Quem paga é o keeper.
+pub fn update_risk_instructions(
+    payload: &DecisionPayload,
+    genesis_hash: &[u8; 32],
+    signers: &[&Keypair],
+    authority: &Pubkey,
+    optional: &OptionalAccounts,
+) -> Result<[Instruction; 2], SdkError> {
+    let hash = decision_hash(&PROGRAM_ID, genesis_hash, payload);
+    let decision = PresignedDecision {
+        payload: payload.clone(),
+        signatures: signers.iter().map(|s| s.sign_message(&hash).into()).collect(),
+        signer_pubkeys: signers.iter().map(|s| s.pubkey().to_bytes()).collect(),
+    };
+    presigned_update_instructions(&decision, genesis_hash, authority, optional)
+}
+
+/// Como `update_risk_instructions`, com as assinaturas já prontas
+pub fn presigned_update_instructions(
+    decision: &PresignedDecision,
+    genesis_hash: &[u8; 32],
+    authority: &Pubkey,
+    optional: &OptionalAccounts,
+) -> Result<[Instruction; 2], SdkError> {
+    decision.validate()?;
+    let payload = &decision.payload;
+    let asset_id = decision.asset_id();
+    let hash = decision.decision_hash(genesis_hash);
+    let entries: Vec<Ed25519Entry> = decision.ed25519_entries(&hash).collect();
+
+    let accounts = accounts::UpdateRiskStatus {
+        config: config_pda().0,
+        used_decisions: (!optional.omit_replay_shard).then(|| used_decisions_pda_for(&hash).0),
+        asset_risk_status: asset_risk_pda(&asset_id).0,
+        authority: *authority,
+        keeper: optional.keeper.unwrap_or(*authority),
+        instructions_sysvar: sysvar::instructions::ID,
+        deadman: optional.deadman,
+        asset_policy: asset_policy_pda(&asset_id).0,
+        pending_decision: optional.pending_decision,
+        asset_history: asset_history_pda(&asset_id).0,
+        pyth_price: optional.pyth_price,
+        switchboard_feed: optional.switchboard_feed,
+        roles: optional.roles,
+        asset_meta: asset_meta_pda(&asset_id).0,
+        portfolio: optional.portfolio,
+        stats: optional.stats,
+        replay_bloom: optional.replay_bloom,
+        treasury: optional.treasury,
+        payer_fee_token: optional.payer_fee_token,
+        treasury_fee_token: optional.treasury_fee_token,
+        fee_mint: optional.fee_mint,
+        token_program: optional.token_program,
+        session_key: optional.session_key,
+        system_program: system_program::ID,
+    };
+    let data = instruction::UpdateRiskStatus {
+        asset_id,
+        risk_score: payload.risk_score,
+        is_blocked: payload.is_blocked,
+        reduce_only: payload.reduce_only,
+        risk_flags: payload.risk_flags,
+        sub_scores: payload.sub_scores,
+        confidence_ratio: payload.confidence_ratio,
+        publisher_count: payload.publisher_count,
+        publisher_ids: payload.publisher_ids,
+        timestamp: payload.timestamp,
+        nonce: payload.nonce,
+        sequence: payload.sequence,
+        valid_until: payload.valid_until,
+        decision_hash: hash,
+        signatures: decision.signatures.clone(),
+        signer_pubkeys: decision.signer_pubkeys.clone(),
+        secondary: None,
+    };
+    let update = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    };
+    Ok([ed25519_verify_ix(&entries), update])
+}
+
+/// Par [Ed25519, update_risk_status_batch]. Em remaining_accounts vão as
+/// contas dos ativos, na ordem das decisões, depois os shards, AssetMeta,
+/// AssetRiskHistory e o PDA da AssetPolicy sem repetição e por fim `extra`
+/// (ex.: SignerUsage, PriceUpdateV2 e SessionKey de outras sessões). Das
+/// opcionais, o batch só usa deadman, roles, stats, replay_bloom, as da taxa,
+/// `session_key`, `keeper` e `omit_replay_shard`.
+pub fn batch_update_instructions(
+    decisions: &[PresignedDecision],
+    genesis_hash: &[u8; 32],
+    authority: &Pubkey,
+    optional: &OptionalAccounts,
+    extra: &[AccountMeta],
+) -> Result<[Instruction; 2], SdkError> {
+    if decisions.is_empty() || decisions.len() > MAX_BATCH_SIZE {
+        return Err(SdkError::InvalidBatchSize);
+    }
+    let mut entries = Vec::new();
+    let mut assets = Vec::with_capacity(decisions.len());
+    let mut shared: Vec<AccountMeta> = Vec::new();
+    for decision in decisions {
+        decision.validate()?;
+        let asset_id = decision.asset_id();
+        let hash = decision.decision_hash(genesis_hash);
+        entries.extend(decision.ed25519_entries(&hash));
+        assets.push(AccountMeta::new(asset_risk_pda(&asset_id).0, false));
+        let shard = (!optional.omit_replay_shard)
+            .then(|| AccountMeta::new(used_decisions_pda_for(&hash).0, false));
+        let asset_meta = AccountMeta::new_readonly(asset_meta_pda(&asset_id).0, false);
+        let history = AccountMeta::new(asset_history_pda(&asset_id).0, false);
+        let policy = AccountMeta::new_readonly(asset_policy_pda(&asset_id).0, false);
+        for meta in shard.into_iter().chain([asset_meta, history, policy]) {
+            if !shared.iter().any(|m| m.pubkey == meta.pubkey) {
+                shared.push(meta);
+            }
+        }
+    }
+    // O programa só confere as primeiras entradas da instrução Ed25519
+    if entries.len() > MAX_PRECOMPILE_ENTRIES_SCANNED {
+        return Err(SdkError::TooManySignatures);
+    }
+
+    let mut metas = accounts::UpdateRiskStatusBatch {
+        config: config_pda().0,
+        authority: *authority,
+        keeper: optional.keeper.unwrap_or(*authority),
+        instructions_sysvar: sysvar::instructions::ID,
+        deadman: optional.deadman,
+        roles: optional.roles,
+        stats: optional.stats,
+        replay_bloom: optional.replay_bloom,
+        treasury: optional.treasury,
+        payer_fee_token: optional.payer_fee_token,
+        treasury_fee_token: optional.treasury_fee_token,
+        fee_mint: optional.fee_mint,
+        token_program: optional.token_program,
+        system_program: Some(system_program::ID),
+    }
+    .to_account_metas(None);
+    metas.extend(assets);
+    metas.extend(shared);
+    metas.extend(optional.session_key.map(|key| AccountMeta::new_readonly(key, false)));
+    metas.extend_from_slice(extra);
+
+    let data = instruction::UpdateRiskStatusBatch {
+        decisions: decisions
+            .iter()
+            .map(|d| workspace::BatchDecision {
+                payload: d.payload.clone(),
+                signatures: d.signatures.clone(),
+                signer_pubkeys: d.signer_pubkeys.clone(),
+                secondary: None,
+            })
+            .collect(),
+    };
+    let update = Instruction { program_id: PROGRAM_ID, accounts: metas, data: data.data() };
+    Ok([ed25519_verify_ix(&entries), update])
+}
+
+/// Par [Ed25519, register_session_key]: `root`, signer confiável, delega a
+/// `session_key` a assinatura de decisões até `expires_at` para `asset_ids`
+/// (vazio = todos). `payer` paga a conta da sessão.
+pub fn register_session_instructions(
+    root: &Keypair,
+    session_key: &Pubkey,
+    expires_at: i64,
+    asset_ids: Vec<[u8; 16]>,
+    genesis_hash: &[u8; 32],
+    payer: &Pubkey,
+) -> [Instruction; 2] {
+    let hash = session_hash(&PROGRAM_ID, genesis_hash, session_key, expires_at, &asset_ids);
+    let (verify, signatures) = ed25519_ix(&hash, &[root]);
+    let accounts = accounts::RegisterSessionKey {
+        config: config_pda().0,
+        session: session_key_pda(session_key).0,
+        payer: *payer,
+        instructions_sysvar: sysvar::instructions::ID,
+        system_program: system_program::ID,
+    };
+    let data = instruction::RegisterSessionKey {
+        session_key: *session_key,
+        root_signer: root.pubkey(),
+        expires_at,
+        asset_ids,
+        signature: signatures[0],
+    };
+    let register = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    };
+    [verify, register]
+}
+
+/// Transação assinada com o par de `update_risk_instructions`, paga por
+/// `authority`, que também faz de keeper
+pub fn build_update_risk_tx(
+    payload: &DecisionPayload,
+    genesis_hash: &[u8; 32],
+    signers: &[&Keypair],
+    authority: &Keypair,
+    optional: &OptionalAccounts,
+    recent_blockhash: Hash,
+) -> Result<Transaction, SdkError> {
+    let optional = OptionalAccounts { keeper: None, ..*optional };
+    let instructions =
+        update_risk_instructions(payload, genesis_hash, signers, &authority.pubkey(), &optional)?;
+    Ok(Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&authority.pubkey()),
+        &[authority],
+        recent_blockhash,
+    ))
+}
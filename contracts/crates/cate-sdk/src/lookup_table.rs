@@ -0,0 +1,139 @@
+//! Address lookup tables para as atualizações do CATE. Com dez ativos o
+//! batch passa do limite de contas de uma transação legacy; numa transação
+//! v0 as contas da tabela custam um byte cada em vez de 32.
+//!
+//! ```ignore
+//! let addresses = lookup_table_addresses(&["SOL/USD", "ETH/USD"]);
+//! let (table, ixs) = create_lookup_table_instructions(&authority, &payer, slot, &addresses);
+//! // ... envia `ixs`; a tabela vale a partir do slot seguinte ...
+//! let table = lookup_table_account(table, &rpc.get_account_data(&table)?)?;
+//! let tx = build_batch_update_v0_tx(
+//!     &decisions,
+//!     &genesis_hash,
+//!     &relayer,
+//!     &optional,
+//!     &[],
+//!     &[table],
+//!     blockhash,
+//! )?;
+//! ```
+
+use cate_client::{
+    asset_history_pda, asset_meta_pda, asset_policy_pda, asset_risk_pda, config_pda,
+    used_decisions_pda, REPLAY_SHARD_COUNT,
+};
+use solana_sdk::address_lookup_table::instruction::{create_lookup_table, extend_lookup_table};
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::sysvar;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::{batch_update_instructions, OptionalAccounts, PresignedDecision, SdkError};
+
+/// Endereços por extend_lookup_table, para a instrução caber numa transação
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 30;
+
+/// Contas comuns a toda atualização (Config, shards do replay store, sysvar
+/// de instruções e system program) seguidas das contas de cada ativo
+/// (AssetRiskStatus, AssetMeta, AssetRiskHistory e AssetPolicy), sem
+/// repetição. O id do programa fica de fora: programas invocados não são
+/// resolvidos por tabela.
+pub fn lookup_table_addresses(asset_ids: &[&str]) -> Vec<Pubkey> {
+    let mut addresses = vec![config_pda().0, sysvar::instructions::ID, system_program::ID];
+    addresses.extend((0..REPLAY_SHARD_COUNT).map(|shard| used_decisions_pda(shard).0));
+    for asset_id in asset_ids {
+        for address in [
+            asset_risk_pda(asset_id).0,
+            asset_meta_pda(asset_id).0,
+            asset_history_pda(asset_id).0,
+            asset_policy_pda(asset_id).0,
+        ] {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+    }
+    addresses
+}
+
+/// Cria a tabela (derivada de `authority` e `recent_slot`) e a preenche com
+/// `addresses`. Retorna o endereço da tabela e as instruções, uma por
+/// transação: create_lookup_table e depois um extend por bloco de endereços.
+pub fn create_lookup_table_instructions(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: u64,
+    addresses: &[Pubkey],
+) -> (Pubkey, Vec<Instruction>) {
+    let (create, table) = create_lookup_table(*authority, *payer, recent_slot);
+    let mut instructions = vec![create];
+    instructions.extend(extend_lookup_table_instructions(&table, authority, payer, &[], addresses));
+    (table, instructions)
+}
+
+/// Extends que acrescentam à tabela os `addresses` que ainda não estão em
+/// `existing` (ex.: contas de ativos listados depois da criação)
+pub fn extend_lookup_table_instructions(
+    table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    existing: &[Pubkey],
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    let mut missing: Vec<Pubkey> = Vec::new();
+    for address in addresses {
+        if !existing.contains(address) && !missing.contains(address) {
+            missing.push(*address);
+        }
+    }
+    missing
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| extend_lookup_table(*table, *authority, Some(*payer), chunk.to_vec()))
+        .collect()
+}
+
+/// Tabela lida da conta on-chain, no formato que `build_v0_tx` espera
+pub fn lookup_table_account(
+    address: Pubkey,
+    data: &[u8],
+) -> Result<AddressLookupTableAccount, SdkError> {
+    let table = AddressLookupTable::deserialize(data).map_err(|_| SdkError::InvalidLookupTable)?;
+    Ok(AddressLookupTableAccount { key: address, addresses: table.addresses.to_vec() })
+}
+
+/// Transação v0 paga por `payer` que resolve pelas `tables` as contas não
+/// assinantes. `signers` inclui o payer.
+pub fn build_v0_tx(
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+    tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, SdkError> {
+    let message = v0::Message::try_compile(&payer.pubkey(), instructions, tables, recent_blockhash)
+        .map_err(|_| SdkError::MessageCompile)?;
+    VersionedTransaction::try_new(VersionedMessage::V0(message), signers)
+        .map_err(|_| SdkError::SigningFailed)
+}
+
+/// Como `build_update_risk_tx`, para o par de `batch_update_instructions`
+/// numa transação v0 paga por `authority`
+pub fn build_batch_update_v0_tx(
+    decisions: &[PresignedDecision],
+    genesis_hash: &[u8; 32],
+    authority: &Keypair,
+    optional: &OptionalAccounts,
+    extra: &[AccountMeta],
+    tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, SdkError> {
+    let instructions =
+        batch_update_instructions(decisions, genesis_hash, &authority.pubkey(), optional, extra)?;
+    build_v0_tx(&instructions, authority, &[authority], tables, recent_blockhash)
+}
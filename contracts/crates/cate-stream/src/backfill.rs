@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use workspace::AssetRiskStatus;
+
+use crate::event::RiskStatusUpdate;
+use crate::StreamError;
+
+pub(crate) fn program_accounts_config() -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            AssetRiskStatus::DISCRIMINATOR,
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        with_context: Some(true),
+        ..Default::default()
+    }
+}
+
+pub(crate) fn decode(data: &[u8]) -> Result<AssetRiskStatus, StreamError> {
+    AssetRiskStatus::try_deserialize_tolerant(data).map_err(|e| StreamError::Decode(e.to_string()))
+}
+
+/// Último `(last_updated, decision_hash)` visto por conta, para deduplicar
+/// notificações repetidas e entregas do backfill.
+#[derive(Default)]
+pub(crate) struct SeenState {
+    seen: HashMap<Pubkey, (i64, [u8; 32])>,
+}
+
+impl SeenState {
+    /// Retorna true se o estado é novo para esta conta
+    pub fn observe(&mut self, address: &Pubkey, status: &AssetRiskStatus) -> bool {
+        let key = (status.last_updated, status.decision_hash);
+        match self.seen.insert(*address, key) {
+            Some(previous) => previous != key,
+            None => true,
+        }
+    }
+}
+
+/// Snapshot de todas as contas de risco; retorna apenas as que mudaram
+pub(crate) async fn backfill(
+    client: &RpcClient,
+    seen: &mut SeenState,
+) -> Result<(u64, Vec<RiskStatusUpdate>), StreamError> {
+    let slot = client.get_slot().await.map_err(|e| StreamError::Rpc(e.to_string()))?;
+    let accounts = client
+        .get_program_accounts_with_config(&workspace::ID, program_accounts_config())
+        .await
+        .map_err(|e| StreamError::Rpc(e.to_string()))?;
+
+    let mut updates = Vec::new();
+    for (address, account) in accounts {
+        let Ok(status) = decode(&account.data) else {
+            tracing::warn!("skipping undecodable risk account {}", address);
+            continue;
+        };
+        if seen.observe(&address, &status) {
+            updates.push(RiskStatusUpdate { address, slot, status, backfilled: true });
+        }
+    }
+    Ok((slot, updates))
+}
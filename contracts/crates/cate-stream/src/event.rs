@@ -0,0 +1,30 @@
+use anchor_lang::prelude::Pubkey;
+use workspace::AssetRiskStatus;
+
+/// Estado de um ativo observado em um slot
+#[derive(Clone)]
+pub struct RiskStatusUpdate {
+    pub address: Pubkey,
+    pub slot: u64,
+    pub status: AssetRiskStatus,
+    /// true quando veio do backfill e não de uma notificação ao vivo
+    pub backfilled: bool,
+}
+
+impl RiskStatusUpdate {
+    pub fn asset_id(&self) -> String {
+        let len = self.status.asset_id.iter().position(|b| *b == 0).unwrap_or(16);
+        String::from_utf8_lossy(&self.status.asset_id[..len]).into_owned()
+    }
+}
+
+#[derive(Clone)]
+pub enum CateEvent {
+    /// Conectado (ou reconectado) à origem
+    Connected { slot: u64 },
+    /// Conexão perdida; seguirá uma reconexão com backfill
+    Disconnected { reason: String },
+    /// Notificações podem ter sido perdidas entre estes slots; o backfill cobre o intervalo
+    Gap { from_slot: u64, to_slot: u64 },
+    RiskStatus(RiskStatusUpdate),
+}
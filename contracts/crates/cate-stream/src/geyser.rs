@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use tokio::sync::mpsc;
+use yellowstone_grpc_client::GeyserGrpcClient;
+use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
+use yellowstone_grpc_proto::geyser::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+};
+
+use crate::backfill::{backfill, decode, SeenState};
+use crate::event::{CateEvent, RiskStatusUpdate};
+use crate::{CateStream, StreamError};
+
+/// Mesmo loop do websocket, mas sobre Yellowstone gRPC (Geyser)
+pub(crate) async fn run(
+    config: CateStream,
+    endpoint: String,
+    x_token: Option<String>,
+    tx: mpsc::Sender<CateEvent>,
+) {
+    let client = RpcClient::new(config.rpc_url.clone());
+    let mut seen = SeenState::default();
+    let mut last_slot: Option<u64> = None;
+    let mut delay = config.reconnect_delay;
+
+    while !tx.is_closed() {
+        let slot_before = last_slot;
+        let reason = match session(&client, &endpoint, x_token.clone(), &tx, &mut seen, &mut last_slot).await {
+            Ok(()) => "geyser stream closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+        if last_slot != slot_before {
+            delay = config.reconnect_delay;
+        }
+        if tx.send(CateEvent::Disconnected { reason }).await.is_err() {
+            return;
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(config.max_reconnect_delay);
+    }
+}
+
+async fn session(
+    client: &RpcClient,
+    endpoint: &str,
+    x_token: Option<String>,
+    tx: &mpsc::Sender<CateEvent>,
+    seen: &mut SeenState,
+    last_slot: &mut Option<u64>,
+) -> Result<(), StreamError> {
+    let mut grpc = GeyserGrpcClient::build_from_shared(endpoint.to_string())
+        .and_then(|builder| builder.x_token(x_token))
+        .map_err(|e| StreamError::Subscription(e.to_string()))?
+        .connect()
+        .await
+        .map_err(|e| StreamError::Subscription(e.to_string()))?;
+
+    let mut accounts = HashMap::new();
+    accounts.insert(
+        "cate".to_string(),
+        SubscribeRequestFilterAccounts {
+            owner: vec![workspace::ID.to_string()],
+            ..Default::default()
+        },
+    );
+    let request = SubscribeRequest {
+        accounts,
+        commitment: Some(CommitmentLevel::Confirmed as i32),
+        ..Default::default()
+    };
+    let (_sink, mut updates) = grpc
+        .subscribe_with_request(Some(request))
+        .await
+        .map_err(|e| StreamError::Subscription(e.to_string()))?;
+
+    let (slot, snapshot) = backfill(client, seen).await?;
+    if tx.send(CateEvent::Connected { slot }).await.is_err() {
+        return Ok(());
+    }
+    if let Some(from_slot) = *last_slot {
+        if slot > from_slot + 1 {
+            let _ = tx.send(CateEvent::Gap { from_slot, to_slot: slot }).await;
+        }
+    }
+    for update in snapshot {
+        if tx.send(CateEvent::RiskStatus(update)).await.is_err() {
+            return Ok(());
+        }
+    }
+    *last_slot = Some(slot);
+
+    while let Some(message) = updates.next().await {
+        let message = message.map_err(|e| StreamError::Subscription(e.to_string()))?;
+        let Some(UpdateOneof::Account(update)) = message.update_oneof else {
+            continue;
+        };
+        let Some(account) = update.account else {
+            continue;
+        };
+        let Ok(address) = Pubkey::try_from(account.pubkey.as_slice()) else {
+            continue;
+        };
+        // Config, UsedDecisions etc. também pertencem ao programa: ignora silenciosamente
+        let Ok(status) = decode(&account.data) else {
+            continue;
+        };
+        *last_slot = Some(last_slot.map_or(update.slot, |s| s.max(update.slot)));
+        if !seen.observe(&address, &status) {
+            continue;
+        }
+        let event = RiskStatusUpdate { address, slot: update.slot, status, backfilled: false };
+        if tx.send(CateEvent::RiskStatus(event)).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
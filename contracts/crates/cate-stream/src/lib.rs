@@ -0,0 +1,108 @@
+//! cate-stream
+//!
+//! `Stream<Item = CateEvent>` over the CATE program accounts, with automatic
+//! reconnection, slot-gap detection and RPC backfill after each (re)connect.
+//!
+//! ```ignore
+//! let mut events = CateStream::websocket(rpc_url, ws_url).start();
+//! while let Some(event) = events.next().await {
+//!     match event {
+//!         CateEvent::RiskStatus(update) => { /* ... */ }
+//!         CateEvent::Gap { from_slot, to_slot } => { /* ... */ }
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+pub mod backfill;
+pub mod event;
+#[cfg(feature = "geyser")]
+pub mod geyser;
+pub mod websocket;
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+pub use event::{CateEvent, RiskStatusUpdate};
+
+#[derive(Debug, thiserror::Error)]
+pub enum StreamError {
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("subscription error: {0}")]
+    Subscription(String),
+    #[error("account decode error: {0}")]
+    Decode(String),
+}
+
+/// Origem das notificações
+#[derive(Clone, Debug)]
+pub enum Source {
+    WebSocket { ws_url: String },
+    #[cfg(feature = "geyser")]
+    Geyser { endpoint: String, x_token: Option<String> },
+}
+
+#[derive(Clone, Debug)]
+pub struct CateStream {
+    pub(crate) rpc_url: String,
+    pub(crate) source: Source,
+    pub(crate) reconnect_delay: Duration,
+    pub(crate) max_reconnect_delay: Duration,
+    pub(crate) buffer: usize,
+}
+
+impl CateStream {
+    pub fn websocket(rpc_url: impl Into<String>, ws_url: impl Into<String>) -> Self {
+        Self::new(rpc_url, Source::WebSocket { ws_url: ws_url.into() })
+    }
+
+    #[cfg(feature = "geyser")]
+    pub fn geyser(
+        rpc_url: impl Into<String>,
+        endpoint: impl Into<String>,
+        x_token: Option<String>,
+    ) -> Self {
+        Self::new(rpc_url, Source::Geyser { endpoint: endpoint.into(), x_token })
+    }
+
+    fn new(rpc_url: impl Into<String>, source: Source) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            source,
+            reconnect_delay: Duration::from_millis(500),
+            max_reconnect_delay: Duration::from_secs(30),
+            buffer: 1024,
+        }
+    }
+
+    /// Atraso inicial entre reconexões (dobra a cada falha até `max`)
+    pub fn reconnect_delay(mut self, initial: Duration, max: Duration) -> Self {
+        self.reconnect_delay = initial;
+        self.max_reconnect_delay = max;
+        self
+    }
+
+    pub fn buffer(mut self, buffer: usize) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    /// Inicia a tarefa de background e retorna o stream de eventos.
+    /// A tarefa termina quando o stream é descartado.
+    pub fn start(self) -> ReceiverStream<CateEvent> {
+        let (tx, rx) = mpsc::channel(self.buffer);
+        match self.source.clone() {
+            Source::WebSocket { ws_url } => {
+                tokio::spawn(websocket::run(self, ws_url, tx));
+            }
+            #[cfg(feature = "geyser")]
+            Source::Geyser { endpoint, x_token } => {
+                tokio::spawn(geyser::run(self, endpoint, x_token, tx));
+            }
+        }
+        ReceiverStream::new(rx)
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use tokio::sync::mpsc;
+
+use crate::backfill::{backfill, decode, program_accounts_config, SeenState};
+use crate::event::{CateEvent, RiskStatusUpdate};
+use crate::{CateStream, StreamError};
+
+/// Loop de reconexão: subscribe -> backfill -> notificações ao vivo
+pub(crate) async fn run(config: CateStream, ws_url: String, tx: mpsc::Sender<CateEvent>) {
+    let client = RpcClient::new(config.rpc_url.clone());
+    let mut seen = SeenState::default();
+    let mut last_slot: Option<u64> = None;
+    let mut delay = config.reconnect_delay;
+
+    while !tx.is_closed() {
+        let slot_before = last_slot;
+        let reason = match session(&client, &ws_url, &tx, &mut seen, &mut last_slot).await {
+            Ok(()) => "subscription closed".to_string(),
+            Err(e) => e.to_string(),
+        };
+        // Sessão chegou a conectar: volta ao atraso inicial
+        if last_slot != slot_before {
+            delay = config.reconnect_delay;
+        }
+        if tx.send(CateEvent::Disconnected { reason }).await.is_err() {
+            return;
+        }
+        tokio::time::sleep(delay).await;
+        delay = (delay * 2).min(config.max_reconnect_delay);
+    }
+}
+
+async fn session(
+    client: &RpcClient,
+    ws_url: &str,
+    tx: &mpsc::Sender<CateEvent>,
+    seen: &mut SeenState,
+    last_slot: &mut Option<u64>,
+) -> Result<(), StreamError> {
+    let pubsub = PubsubClient::new(ws_url)
+        .await
+        .map_err(|e| StreamError::Subscription(e.to_string()))?;
+    // Assina antes do backfill para não perder atualizações entre os dois
+    let (mut notifications, unsubscribe) = pubsub
+        .program_subscribe(&workspace::ID, Some(program_accounts_config()))
+        .await
+        .map_err(|e| StreamError::Subscription(e.to_string()))?;
+
+    let (slot, updates) = backfill(client, seen).await?;
+    if send(tx, CateEvent::Connected { slot }).await.is_err() {
+        return Ok(());
+    }
+    if let Some(from_slot) = *last_slot {
+        if slot > from_slot + 1 {
+            let _ = send(tx, CateEvent::Gap { from_slot, to_slot: slot }).await;
+        }
+    }
+    for update in updates {
+        if send(tx, CateEvent::RiskStatus(update)).await.is_err() {
+            return Ok(());
+        }
+    }
+    *last_slot = Some(slot);
+
+    while let Some(response) = notifications.next().await {
+        let slot = response.context.slot;
+        let keyed = response.value;
+        let Some(data) = keyed.account.data.decode() else {
+            continue;
+        };
+        let Ok(address) = keyed.pubkey.parse() else {
+            continue;
+        };
+        let status = match decode(&data) {
+            Ok(status) => status,
+            Err(e) => {
+                tracing::warn!("skipping risk account {}: {}", keyed.pubkey, e);
+                continue;
+            }
+        };
+        *last_slot = Some(last_slot.map_or(slot, |s| s.max(slot)));
+        if !seen.observe(&address, &status) {
+            continue;
+        }
+        let update = RiskStatusUpdate { address, slot, status, backfilled: false };
+        if send(tx, CateEvent::RiskStatus(update)).await.is_err() {
+            break;
+        }
+    }
+
+    unsubscribe().await;
+    Ok(())
+}
+
+async fn send(tx: &mpsc::Sender<CateEvent>, event: CateEvent) -> Result<(), ()> {
+    // Receptor lento: aplica backpressure em vez de descartar eventos
+    tokio::time::timeout(Duration::from_secs(60), tx.send(event))
+        .await
+        .map_err(|_| ())?
+        .map_err(|_| ())
+}
@@ -0,0 +1,32 @@
+//! Bytes arbitrários em Ed25519SignatureOffsets::from_bytes: nunca entra em
+//! pânico, aceita exatamente as entradas com 14 bytes ou mais e lê os sete
+//! campos u16 little-endian na ordem do programa nativo.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use workspace::Ed25519SignatureOffsets;
+
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(offsets) = Ed25519SignatureOffsets::from_bytes(data) else {
+        assert!(data.len() < SIGNATURE_OFFSETS_LEN, "rejected a complete entry");
+        return;
+    };
+    assert!(data.len() >= SIGNATURE_OFFSETS_LEN, "accepted a truncated entry");
+
+    let field = |i: usize| u16::from_le_bytes([data[2 * i], data[2 * i + 1]]);
+    let parsed = [
+        offsets.signature_offset,
+        offsets.signature_instruction_index,
+        offsets.public_key_offset,
+        offsets.public_key_instruction_index,
+        offsets.message_data_offset,
+        offsets.message_data_size,
+        offsets.message_instruction_index,
+    ];
+    for (i, value) in parsed.into_iter().enumerate() {
+        assert_eq!(value, field(i), "field {} misread", i);
+    }
+});
@@ -0,0 +1,131 @@
+//! verify_ed25519_instruction com um sysvar de instruções montado a partir
+//! da entrada: uma instrução Ed25519 com dados arbitrários, uma vizinha que
+//! os offsets podem referenciar e a instrução do CATE por último.
+//!
+//! Além de não entrar em pânico, toda aceitação precisa ser justificada por
+//! uma entrada de offsets cujas fatias (na instrução referenciada) contêm
+//! exatamente a pubkey, a assinatura e a mensagem de 32 bytes esperadas.
+
+#![no_main]
+
+use anchor_lang::prelude::AccountInfo;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::BorrowedInstruction;
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::solana_program::sysvar::instructions::{self, construct_instructions_data};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+
+const HEADER_LEN: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    ed25519_data: Vec<u8>,
+    /// Instrução vizinha: outra Ed25519 ou de outro programa
+    other_data: Vec<u8>,
+    other_is_ed25519: bool,
+    other_first: bool,
+    pubkey: [u8; 32],
+    message: [u8; 32],
+    signature: [u8; 64],
+    /// Acrescenta a tupla esperada ao fim dos dados da instrução Ed25519,
+    /// para que o fuzzer alcance o caminho de aceitação
+    plant: bool,
+}
+
+struct Ix {
+    program_id: Pubkey,
+    data: Vec<u8>,
+}
+
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Option<&[u8]> {
+    data.get(offset as usize..(offset as usize).checked_add(len)?)
+}
+
+/// Modelo de referência: alguma entrada de alguma instrução Ed25519 anterior
+/// aponta para a tupla esperada
+fn tuple_present(
+    ixs: &[Ix],
+    current: usize,
+    pubkey: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    ixs[..current].iter().enumerate().any(|(index, ix)| {
+        if ix.program_id != ed25519_program::ID || ix.data.len() < HEADER_LEN {
+            return false;
+        }
+        let data_of = |field: u16| {
+            let target = if field == THIS_INSTRUCTION { index } else { field as usize };
+            ixs.get(target).map(|ix| ix.data.as_slice())
+        };
+        (0..ix.data[0] as usize).any(|i| {
+            let start = HEADER_LEN + SIGNATURE_OFFSETS_LEN * i;
+            let Some(raw) = ix.data.get(start..start + SIGNATURE_OFFSETS_LEN) else {
+                return false;
+            };
+            let field = |n: usize| u16::from_le_bytes([raw[2 * n], raw[2 * n + 1]]);
+            if field(5) != 32 {
+                return false;
+            }
+            let found = |ix_field: usize, offset_field: usize, len: usize| {
+                data_of(field(ix_field)).and_then(|data| slice_at(data, field(offset_field), len))
+            };
+            found(1, 0, 64) == Some(signature)
+                && found(3, 2, 32) == Some(pubkey)
+                && found(6, 4, 32) == Some(message)
+        })
+    })
+}
+
+fuzz_target!(|input: Input| {
+    let mut ed25519_data = input.ed25519_data;
+    if input.plant {
+        ed25519_data.extend_from_slice(&input.pubkey);
+        ed25519_data.extend_from_slice(&input.signature);
+        ed25519_data.extend_from_slice(&input.message);
+    }
+    let ed25519 = Ix { program_id: ed25519_program::ID, data: ed25519_data };
+    let other_program =
+        if input.other_is_ed25519 { ed25519_program::ID } else { system_program::ID };
+    let other = Ix { program_id: other_program, data: input.other_data };
+    let cate = Ix { program_id: workspace::ID, data: Vec::new() };
+    let ixs = if input.other_first { [other, ed25519, cate] } else { [ed25519, other, cate] };
+    let current = ixs.len() - 1;
+
+    let borrowed: Vec<BorrowedInstruction> = ixs
+        .iter()
+        .map(|ix| BorrowedInstruction {
+            program_id: &ix.program_id,
+            accounts: Vec::new(),
+            data: &ix.data,
+        })
+        .collect();
+    let mut sysvar_data = construct_instructions_data(&borrowed);
+    // Os dois últimos bytes guardam o índice da instrução em execução
+    let len = sysvar_data.len();
+    sysvar_data[len - 2..].copy_from_slice(&(current as u16).to_le_bytes());
+
+    let key = instructions::ID;
+    let owner = anchor_lang::solana_program::sysvar::ID;
+    let mut lamports = 0;
+    let sysvar =
+        AccountInfo::new(&key, false, false, &mut lamports, &mut sysvar_data, &owner, false, 0);
+
+    let accepted = workspace::fuzzing::verify_ed25519_instruction(
+        &sysvar,
+        &input.pubkey,
+        &input.message,
+        &input.signature,
+    )
+    .is_ok();
+    if accepted {
+        assert!(
+            tuple_present(&ixs, current, &input.pubkey, &input.message, &input.signature),
+            "accepted a layout that does not carry the expected signature"
+        );
+    }
+});
@@ -0,0 +1,151 @@
+//! cate-transfer-hook
+//!
+//! Transfer hook Token-2022 para mints registrados no CATE: cada transferência
+//! lê o AssetRiskStatus do ativo e é rejeitada enquanto `is_blocked` ou o
+//! bloqueio do dead-man switch estiverem ligados ou a decisão gravada tiver
+//! passado de `valid_until`. O ativo vem do `AssetMint` do CATE; o endereço
+//! do PDA de risco fica gravado na ExtraAccountMetaList do mint e o
+//! Token-2022 o anexa sozinho.
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use cate_reader::RiskStatusLoader;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use workspace::{AssetMint, ASSET_MINT_SEED, ASSET_RISK_SEED, SEED_NAMESPACE};
+
+declare_id!("2n3R67asSaP2Gmhu6WtG6HiQkUTNw1YT126sSfBiE1bW");
+
+/// Seed fixada pela interface de transfer hook
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+#[program]
+pub mod cate_transfer_hook {
+    use super::*;
+
+    /// Cria a lista de contas extras do mint a partir do AssetMint do CATE.
+    /// Permissionless: o vínculo mint -> ativo é decidido pela authority do CATE.
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let metas = extra_account_metas(&ctx.accounts.asset_mint)?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &metas,
+        )?;
+        msg!("Transfer hook configured for mint {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// Ressincroniza a lista depois que o mint foi reassociado a outro ativo
+    pub fn update_extra_account_meta_list(ctx: Context<UpdateExtraAccountMetaList>) -> Result<()> {
+        let metas = extra_account_metas(&ctx.accounts.asset_mint)?;
+        ExtraAccountMetaList::update::<ExecuteInstruction>(
+            &mut ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?,
+            &metas,
+        )?;
+        msg!("Transfer hook resynced for mint {}", ctx.accounts.mint.key());
+        Ok(())
+    }
+
+    /// `Execute` da interface, chamado pelo Token-2022 em toda transferência
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        let loader = RiskStatusLoader::load(&ctx.accounts.asset_risk_status)?;
+        let status = loader.status();
+        require!(status.is_blocked == 0, HookError::AssetBlocked);
+        require!(status.deadman_blocked == 0, HookError::DeadmanBlocked);
+        require!(!status.is_expired(Clock::get()?.unix_timestamp), HookError::DecisionExpired);
+        Ok(())
+    }
+}
+
+/// Única conta extra: o AssetRiskStatus do ativo associado ao mint
+fn extra_account_metas(asset_mint: &AssetMint) -> Result<Vec<ExtraAccountMeta>> {
+    let len = asset_mint.asset_id.iter().position(|b| *b == 0).unwrap_or(16);
+    let (asset_risk_status, _) = Pubkey::find_program_address(
+        &[SEED_NAMESPACE, ASSET_RISK_SEED, &asset_mint.asset_id[..len]],
+        &workspace::ID,
+    );
+    Ok(vec![ExtraAccountMeta::new_with_pubkey(&asset_risk_status, false, false)?])
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: escrita em formato TLV por ExtraAccountMetaList::init
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(1)?,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.key().as_ref()],
+        bump = asset_mint.bump,
+        seeds::program = workspace::ID
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    /// CHECK: reescrita em formato TLV por ExtraAccountMetaList::update
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.key().as_ref()],
+        bump = asset_mint.bump,
+        seeds::program = workspace::ID
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+}
+
+/// Ordem fixada pela interface: source, mint, destination, owner, lista, extras
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: dono ou delegate da origem, já validado pelo Token-2022
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: PDA da lista; o Token-2022 resolve as extras a partir dela
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: validado por RiskStatusLoader (dono, discriminator e seeds)
+    pub asset_risk_status: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum HookError {
+    #[msg("Asset is blocked by the risk engine")]
+    AssetBlocked,
+    #[msg("Asset risk decision has expired")]
+    DecisionExpired,
+    #[msg("Asset is blocked by the triggered dead-man switch")]
+    DeadmanBlocked,
+}
@@ -0,0 +1,606 @@
+//! cate-vault
+//!
+//! Cofre de tokens SPL de um único dono que só negocia passando pelo gate do
+//! CATE: `execute_trade` chama `assert_trading_allowed_by_mint` com o piso de
+//! confiança e a janela de frescor do cofre e, aprovado, repassa a instrução
+//! ao DEX configurado assinando com o PDA do cofre. O CATE continua sem
+//! custódia; a execução fica neste programa.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use cate_client::cpi::{assert_trading_allowed_by_mint, GateByMintAccounts};
+use cate_client::RiskTier;
+
+declare_id!("EQaFV7vEqQbKLu8jhrDgTZsyZSrgLb2PgFNFC14RXuHY");
+
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const POSITION_SEED: &[u8] = b"position";
+
+pub const FULL_SIZE_BPS: u16 = 10_000;
+
+/// Low tamanho cheio, Medium um quarto, High e Critical nada
+pub const DEFAULT_TIER_SIZES: TierSizes =
+    TierSizes { low: FULL_SIZE_BPS, medium: 2_500, high: 0, critical: 0 };
+
+#[program]
+pub mod cate_vault {
+    use super::*;
+
+    /// Cria o cofre do dono com o DEX e os limites de gate
+    pub fn initialize_vault(
+        ctx: Context<InitializeVault>,
+        dex_program: Pubkey,
+        max_staleness: i64,
+        min_confidence_ratio: u64,
+    ) -> Result<()> {
+        require!(max_staleness > 0, VaultError::InvalidVaultParams);
+        let vault = &mut ctx.accounts.vault;
+        vault.bump = ctx.bumps.vault;
+        vault.owner = ctx.accounts.owner.key();
+        vault.dex_program = dex_program;
+        vault.max_staleness = max_staleness;
+        vault.min_confidence_ratio = min_confidence_ratio;
+        vault.max_position = 0;
+        vault.tier_sizes = DEFAULT_TIER_SIZES;
+        msg!("Vault initialized for {} routing to {}", vault.owner, dex_program);
+        Ok(())
+    }
+
+    /// Troca o DEX ou os limites do gate
+    pub fn set_vault_params(
+        ctx: Context<SetVaultParams>,
+        dex_program: Pubkey,
+        max_staleness: i64,
+        min_confidence_ratio: u64,
+    ) -> Result<()> {
+        require!(max_staleness > 0, VaultError::InvalidVaultParams);
+        let vault = &mut ctx.accounts.vault;
+        vault.dex_program = dex_program;
+        vault.max_staleness = max_staleness;
+        vault.min_confidence_ratio = min_confidence_ratio;
+        emit!(VaultParamsUpdated {
+            vault: vault.key(),
+            dex_program,
+            max_staleness,
+            min_confidence_ratio,
+        });
+        Ok(())
+    }
+
+    /// Teto de posição por ativo e a fração liberada em cada RiskTier do
+    /// CATE. `max_position = 0` desliga o teto.
+    pub fn set_position_limits(
+        ctx: Context<SetVaultParams>,
+        max_position: u64,
+        tier_sizes: TierSizes,
+    ) -> Result<()> {
+        require!(tier_sizes.is_valid(), VaultError::InvalidTierSizes);
+        let vault = &mut ctx.accounts.vault;
+        vault.max_position = max_position;
+        vault.tier_sizes = tier_sizes;
+        emit!(PositionLimitsUpdated {
+            vault: vault.key(),
+            max_position,
+            tier_sizes,
+        });
+        Ok(())
+    }
+
+    /// Deposita na conta de token do cofre (ATA do PDA, criada pelo cliente)
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault_token.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+        emit!(VaultDeposited {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Saque sem gate: o dono sempre pode retirar os próprios fundos
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        let owner = ctx.accounts.owner.key();
+        let seeds: &[&[u8]] = &[VAULT_SEED, owner.as_ref(), &[ctx.accounts.vault.bump]];
+        let signer_seeds = &[seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.owner_token.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+        emit!(VaultWithdrawn {
+            vault: ctx.accounts.vault.key(),
+            mint: ctx.accounts.mint.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Gate do CATE para o ativo de `mint` e, aprovado, CPI ao DEX com
+    /// `dex_data` e as remaining_accounts na ordem recebida.
+    /// Modo advisory não libera o cofre; em reduce-only o saldo do ativo
+    /// no cofre não pode crescer com o trade. Se a posição crescer, o saldo
+    /// final não passa do teto do RiskTier atual.
+    pub fn execute_trade<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTrade<'info>>,
+        mint: Pubkey,
+        dex_data: Vec<u8>,
+    ) -> Result<()> {
+        let vault = &ctx.accounts.vault;
+        let gate = assert_trading_allowed_by_mint(
+            GateByMintAccounts {
+                cate_program: ctx.accounts.cate_program.to_account_info(),
+                config: ctx.accounts.cate_config.to_account_info(),
+                asset_mint: ctx.accounts.asset_mint.to_account_info(),
+                asset_risk_status: ctx.accounts.asset_risk_status.to_account_info(),
+                asset_policy: ctx.accounts.asset_policy.as_ref().map(|a| a.to_account_info()),
+                portfolio: ctx.accounts.portfolio.as_ref().map(|a| a.to_account_info()),
+                integration_stats: None,
+                instructions_sysvar: ctx
+                    .accounts
+                    .instructions_sysvar
+                    .as_ref()
+                    .map(|a| a.to_account_info()),
+                consumer: ctx.accounts.cate_consumer.as_ref().map(|a| a.to_account_info()),
+                global_schedule: ctx.accounts.global_schedule.as_ref().map(|a| a.to_account_info()),
+                asset_schedule: ctx.accounts.asset_schedule.as_ref().map(|a| a.to_account_info()),
+            },
+            mint,
+            Some(vault.max_staleness),
+            Some(vault.min_confidence_ratio),
+        )?;
+        require!(gate.allowed, VaultError::TradingNotAllowed);
+
+        let balance_before = ctx.accounts.vault_asset_token.amount;
+        let vault_key = vault.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == vault_key,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: vault.dex_program,
+            accounts,
+            data: dex_data,
+        };
+        let owner = vault.owner;
+        let seeds: &[&[u8]] = &[VAULT_SEED, owner.as_ref(), &[vault.bump]];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[seeds])?;
+
+        ctx.accounts.vault_asset_token.reload()?;
+        let balance_after = ctx.accounts.vault_asset_token.amount;
+        if gate.reduce_only {
+            require!(balance_after <= balance_before, VaultError::ReduceOnlyViolation);
+        }
+        if balance_after > balance_before {
+            let cap = ctx.accounts.vault.position_cap(gate.risk_tier);
+            require!(balance_after <= cap, VaultError::PositionLimitExceeded);
+        }
+
+        let clock = Clock::get()?;
+        let position = &mut ctx.accounts.position;
+        position.bump = ctx.bumps.position;
+        position.vault = vault_key;
+        position.mint = mint;
+        position.amount = balance_after;
+        position.updated_at = clock.unix_timestamp;
+
+        emit!(TradeExecuted {
+            vault: vault_key,
+            mint,
+            dex_program: ctx.accounts.vault.dex_program,
+            risk_score: gate.risk_score,
+            confidence_ratio: gate.confidence_ratio,
+            balance_before,
+            balance_after,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Gate do CATE mais o teto de posição para um saldo final de
+    /// `amount_after`, sem executar nada. Retorna o teto do RiskTier atual.
+    pub fn pre_trade_check(
+        ctx: Context<PreTradeCheck>,
+        mint: Pubkey,
+        amount_after: u64,
+    ) -> Result<u64> {
+        let vault = &ctx.accounts.vault;
+        let gate = assert_trading_allowed_by_mint(
+            GateByMintAccounts {
+                cate_program: ctx.accounts.cate_program.to_account_info(),
+                config: ctx.accounts.cate_config.to_account_info(),
+                asset_mint: ctx.accounts.asset_mint.to_account_info(),
+                asset_risk_status: ctx.accounts.asset_risk_status.to_account_info(),
+                asset_policy: ctx.accounts.asset_policy.as_ref().map(|a| a.to_account_info()),
+                portfolio: ctx.accounts.portfolio.as_ref().map(|a| a.to_account_info()),
+                integration_stats: None,
+                instructions_sysvar: ctx
+                    .accounts
+                    .instructions_sysvar
+                    .as_ref()
+                    .map(|a| a.to_account_info()),
+                consumer: ctx.accounts.cate_consumer.as_ref().map(|a| a.to_account_info()),
+                global_schedule: ctx.accounts.global_schedule.as_ref().map(|a| a.to_account_info()),
+                asset_schedule: ctx.accounts.asset_schedule.as_ref().map(|a| a.to_account_info()),
+            },
+            mint,
+            Some(vault.max_staleness),
+            Some(vault.min_confidence_ratio),
+        )?;
+        require!(gate.allowed, VaultError::TradingNotAllowed);
+
+        let current = ctx.accounts.position.as_ref().map_or(0, |position| position.amount);
+        if gate.reduce_only {
+            require!(amount_after <= current, VaultError::ReduceOnlyViolation);
+        }
+        let cap = vault.position_cap(gate.risk_tier);
+        if amount_after > current {
+            require!(amount_after <= cap, VaultError::PositionLimitExceeded);
+        }
+        Ok(cap)
+    }
+}
+
+#[account]
+pub struct Vault {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub dex_program: Pubkey,
+    pub max_staleness: i64,        // Idade máxima da decisão do CATE, em segundos
+    pub min_confidence_ratio: u64, // Piso repassado a assert_trading_allowed
+    pub max_position: u64,         // Teto de saldo por ativo (0 = sem teto)
+    pub tier_sizes: TierSizes,     // Fração do teto por RiskTier do CATE
+}
+
+impl Vault {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8 + 8 + TierSizes::LEN;
+
+    /// Maior saldo permitido no ativo na faixa atual do CATE
+    pub fn position_cap(&self, risk_tier: RiskTier) -> u64 {
+        if self.max_position == 0 {
+            return u64::MAX;
+        }
+        let size_bps = self.tier_sizes.size_bps(risk_tier);
+        (u128::from(self.max_position) * u128::from(size_bps) / u128::from(FULL_SIZE_BPS)) as u64
+    }
+}
+
+/// Fração do teto, em bps, liberada em cada RiskTier. As faixas são as do
+/// Config do CATE; o cofre não tem limites de score próprios.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TierSizes {
+    pub low: u16,
+    pub medium: u16,
+    pub high: u16,
+    pub critical: u16,
+}
+
+impl TierSizes {
+    pub const LEN: usize = 2 * 4;
+
+    /// Frações até 100%
+    pub fn is_valid(&self) -> bool {
+        [self.low, self.medium, self.high, self.critical].iter().all(|bps| *bps <= FULL_SIZE_BPS)
+    }
+
+    pub fn size_bps(&self, risk_tier: RiskTier) -> u16 {
+        match risk_tier {
+            RiskTier::Low => self.low,
+            RiskTier::Medium => self.medium,
+            RiskTier::High => self.high,
+            RiskTier::Critical => self.critical,
+        }
+    }
+}
+
+/// Exposição do cofre em um ativo: saldo, em unidades do token, da conta do
+/// cofre após o último trade
+#[account]
+pub struct UserPosition {
+    pub bump: u8,
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub updated_at: i64,
+}
+
+impl UserPosition {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + 8;
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Vault::LEN,
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVaultParams<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub owner: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub owner: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub owner_token: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault,
+        token::token_program = token_program
+    )]
+    pub vault_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct ExecuteTrade<'info> {
+    #[account(
+        seeds = [VAULT_SEED, owner.key().as_ref()],
+        bump = vault.bump,
+        has_one = owner
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + UserPosition::LEN,
+        seeds = [POSITION_SEED, vault.key().as_ref(), mint.as_ref()],
+        bump
+    )]
+    pub position: Account<'info, UserPosition>,
+
+    /// Conta do cofre no ativo gateado, relida após o trade para o reduce-only
+    #[account(
+        constraint = vault_asset_token.mint == mint @ VaultError::InvalidVaultTokenAccount,
+        constraint = vault_asset_token.owner == vault.key() @ VaultError::InvalidVaultTokenAccount
+    )]
+    pub vault_asset_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: programa escolhido pelo dono do cofre
+    #[account(address = vault.dex_program, executable)]
+    pub dex_program: UncheckedAccount<'info>,
+
+    /// CHECK: programa CATE
+    #[account(address = cate_client::PROGRAM_ID)]
+    pub cate_program: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE (seeds do Config)
+    pub cate_config: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE contra `mint`
+    pub asset_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE contra o asset_id do AssetMint
+    pub asset_risk_status: UncheckedAccount<'info>,
+
+    /// CHECK: opcional, validado pelo CATE
+    pub asset_policy: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PortfolioRisk do CATE, exigido por ele com portfolio_breaker
+    pub portfolio: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Consumer do cofre no CATE, exigido por ele com consumer_access
+    #[account(mut)]
+    pub cate_consumer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, exigido pelo CATE junto com cate_consumer
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule global do CATE, exigido por ele se houver horários
+    pub global_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule do ativo no CATE, nas mesmas condições
+    pub asset_schedule: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct PreTradeCheck<'info> {
+    pub vault: Account<'info, Vault>,
+
+    /// Ausente = posição zerada
+    #[account(
+        seeds = [POSITION_SEED, vault.key().as_ref(), mint.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, UserPosition>>,
+
+    /// CHECK: programa CATE
+    #[account(address = cate_client::PROGRAM_ID)]
+    pub cate_program: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE (seeds do Config)
+    pub cate_config: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE contra `mint`
+    pub asset_mint: UncheckedAccount<'info>,
+
+    /// CHECK: validado pelo CATE contra o asset_id do AssetMint
+    pub asset_risk_status: UncheckedAccount<'info>,
+
+    /// CHECK: opcional, validado pelo CATE
+    pub asset_policy: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PortfolioRisk do CATE, exigido por ele com portfolio_breaker
+    pub portfolio: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Consumer do cofre no CATE, exigido por ele com consumer_access
+    #[account(mut)]
+    pub cate_consumer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, exigido pelo CATE junto com cate_consumer
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule global do CATE, exigido por ele se houver horários
+    pub global_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule do ativo no CATE, nas mesmas condições
+    pub asset_schedule: Option<UncheckedAccount<'info>>,
+}
+
+#[event]
+pub struct VaultParamsUpdated {
+    pub vault: Pubkey,
+    pub dex_program: Pubkey,
+    pub max_staleness: i64,
+    pub min_confidence_ratio: u64,
+}
+
+#[event]
+pub struct PositionLimitsUpdated {
+    pub vault: Pubkey,
+    pub max_position: u64,
+    pub tier_sizes: TierSizes,
+}
+
+#[event]
+pub struct VaultDeposited {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VaultWithdrawn {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TradeExecuted {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub dex_program: Pubkey,
+    pub risk_score: u8,
+    pub confidence_ratio: u64,
+    pub balance_before: u64,
+    pub balance_after: u64,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Vault max staleness must be positive")]
+    InvalidVaultParams,
+    #[msg("Amount must be positive")]
+    InvalidAmount,
+    #[msg("Token account is not the vault's account for this mint")]
+    InvalidVaultTokenAccount,
+    #[msg("Risk gate returned an advisory warning; the vault only trades on a clean gate")]
+    TradingNotAllowed,
+    #[msg("Asset is reduce-only and the trade increased the vault's position")]
+    ReduceOnlyViolation,
+    #[msg("Tier sizes must be at most 10000 bps each")]
+    InvalidTierSizes,
+    #[msg("Trade would take the position above the cap for the current risk tier")]
+    PositionLimitExceeded,
+}
@@ -0,0 +1,53 @@
+//! mock-multisig
+//!
+//! Só para testes: imita o cofre de um multisig (Squads) repassando uma
+//! instrução qualquer com o PDA `vault` como signer via CPI. Não há
+//! aprovação de membros; o que importa é que a authority do CATE seja um
+//! PDA que nunca assina a transação externa nem paga aluguel.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+declare_id!("FxbpYFL2KgfN9X9H3YMEEfZ43P4ViKFRhY4uikVDM1Bc");
+
+pub const VAULT_SEED: &[u8] = b"vault";
+
+#[program]
+pub mod mock_multisig {
+    use super::*;
+
+    /// remaining_accounts: contas da instrução alvo, na ordem dela; o vault
+    /// entra como não-signer e é promovido a signer no CPI
+    pub fn execute(ctx: Context<Execute>, data: Vec<u8>) -> Result<()> {
+        let vault_key = ctx.accounts.vault.key();
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == vault_key,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: ctx.accounts.target_program.key(),
+            accounts,
+            data,
+        };
+        let seeds: &[&[u8]] = &[VAULT_SEED, &[ctx.bumps.vault]];
+        invoke_signed(&instruction, ctx.remaining_accounts, &[seeds])?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// CHECK: PDA sem dados; só assina via invoke_signed
+    #[account(seeds = [VAULT_SEED], bump)]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: programa chamado; quem autoriza é o próprio alvo
+    #[account(executable)]
+    pub target_program: UncheckedAccount<'info>,
+}
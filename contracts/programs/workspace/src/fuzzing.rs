@@ -0,0 +1,19 @@
+//! Pontos de entrada para os alvos de contracts/fuzz, compilados só com a
+//! feature `fuzzing`. A verificação continua privada no programa.
+
+use anchor_lang::prelude::*;
+
+/// `verify_ed25519_instruction` contra um sysvar de instruções montado pelo alvo
+pub fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_pubkey: &[u8; 32],
+    expected_message: &[u8; 32],
+    expected_signature: &[u8; 64],
+) -> Result<()> {
+    crate::verify_ed25519_instruction(
+        instructions_sysvar,
+        expected_pubkey,
+        expected_message,
+        expected_signature,
+    )
+}
@@ -0,0 +1,128 @@
+//! Helpers de gating para order flow de programas integradores.
+//!
+//! Em vez de allow/deny binário, calcula o tamanho máximo de fill permitido
+//! a partir do AssetRiskStatus e dos limites do integrador, para que market
+//! makers reduzam o tamanho da ordem em vez de tê-la rejeitada.
+//! `effective_status` aplica a janela de frescor do Config/AssetPolicy.
+
+use crate::{AssetLifecycle, AssetPolicy, AssetRiskStatus, Config, EffectiveStatus, ScoreSource};
+
+/// 100% em basis points
+pub const FULL_FACTOR_BPS: u16 = 10_000;
+
+/// Limites aplicados pelo integrador sobre o estado de risco
+#[derive(Clone, Copy, Debug)]
+pub struct FillLimits {
+    /// Teto de notional por ordem (None = sem teto)
+    pub max_notional: Option<u64>,
+    /// Fração do teto liberada durante a reabertura de um ativo (10000 = aberto)
+    pub reopen_factor_bps: u16,
+    /// Acima deste score o teto é reduzido linearmente até zero em `block_score`
+    pub caution_score: u8,
+    /// Score a partir do qual nenhum fill é permitido
+    pub block_score: u8,
+    /// Score comparado com as zonas: o bruto ou a média móvel
+    pub score_source: ScoreSource,
+}
+
+impl Default for FillLimits {
+    /// Zonas do RISK_SCORE_MODEL.md: ALLOW <= 30, BLOCK >= 70
+    fn default() -> Self {
+        Self {
+            max_notional: None,
+            reopen_factor_bps: FULL_FACTOR_BPS,
+            caution_score: 30,
+            block_score: 70,
+            score_source: ScoreSource::Raw,
+        }
+    }
+}
+
+/// Maior fill (em notional) permitido para uma ordem de `order_notional`.
+/// Retorna 0 quando o ativo está bloqueado, em reduce-only (nenhuma exposição
+/// nova), fora de `Live` ou o score está na zona de bloqueio.
+pub fn max_allowed_fill(status: &AssetRiskStatus, limits: &FillLimits, order_notional: u64) -> u64 {
+    let risk_score = status.score(limits.score_source);
+    if status.is_blocked != 0
+        || status.is_deadman_blocked()
+        || status.is_reduce_only()
+        || status.lifecycle() != AssetLifecycle::Live
+        || risk_score >= limits.block_score
+    {
+        return 0;
+    }
+
+    let mut cap = limits.max_notional.unwrap_or(u64::MAX);
+    cap = scale_bps(cap, limits.reopen_factor_bps.min(FULL_FACTOR_BPS));
+
+    // Zona de cautela: reduz linearmente entre caution_score e block_score
+    if risk_score > limits.caution_score && limits.block_score > limits.caution_score {
+        let span = (limits.block_score - limits.caution_score) as u64;
+        let remaining = (limits.block_score - risk_score) as u64;
+        let factor_bps = remaining * FULL_FACTOR_BPS as u64 / span;
+        cap = scale_bps(cap, factor_bps as u16);
+    }
+
+    order_notional.min(cap)
+}
+
+fn scale_bps(value: u64, bps: u16) -> u64 {
+    ((value as u128 * bps as u128) / FULL_FACTOR_BPS as u128) as u64
+}
+
+/// Janela de frescor aplicável: a do ativo, se configurada, senão a global
+pub fn staleness_window(config: &Config, policy: Option<&AssetPolicy>) -> i64 {
+    policy
+        .and_then(|p| p.max_staleness_secs)
+        .unwrap_or(config.max_staleness_secs)
+}
+
+/// Estado do ativo tratando dados velhos (last_updated fora da janela),
+/// decisão vencida (valid_until), circuit breaker armado, quarentena
+/// pós-desbloqueio, dead-man switch acionado, ativo fora de operação, engine
+/// sem heartbeat dentro do SLA e programa pausado como bloqueio. Usado por
+/// get_effective_status e por leitores off-chain que carregam as contas
+/// diretamente.
+pub fn effective_status(
+    status: &AssetRiskStatus,
+    config: &Config,
+    policy: Option<&AssetPolicy>,
+    now: i64,
+) -> EffectiveStatus {
+    let max_staleness_secs = staleness_window(config, policy);
+    let is_stale = now.saturating_sub(status.last_updated) > max_staleness_secs;
+    let expired = status.is_expired(now);
+    let breaker_active = status.breaker_active(now);
+    let cooling = status.is_cooling(now);
+    let engine_degraded = config.engine_degraded(now);
+    let not_trading =
+        matches!(status.lifecycle(), AssetLifecycle::Listed | AssetLifecycle::Delisted);
+    EffectiveStatus {
+        asset_id: status.asset_id,
+        risk_score: status.risk_score,
+        ema_score: status.ema_score(),
+        is_blocked: status.is_blocked != 0
+            || status.is_deadman_blocked()
+            || is_stale
+            || expired
+            || breaker_active
+            || cooling
+            || engine_degraded
+            || not_trading
+            || config.paused,
+        reported_blocked: status.is_blocked != 0,
+        is_stale,
+        expired,
+        breaker_active,
+        cooling,
+        engine_degraded,
+        reduce_only: status.is_reduce_only(),
+        lifecycle: status.lifecycle(),
+        risk_flags: status.risk_flags,
+        sub_scores: status.sub_scores,
+        confidence_ratio: status.confidence_ratio,
+        last_updated: status.last_updated,
+        max_staleness_secs,
+        risk_tier: status.risk_tier(),
+    }
+}
@@ -1,517 +1,9322 @@
-use anchor_lang::prelude::*;
-use anchor_lang::solana_program::ed25519_program;
-use anchor_lang::solana_program::sysvar::instructions::{self, load_instruction_at_checked};
-
-declare_id!("2CVGjnZ2BRebSeDHdo3VZknm5jVjxZmWu9m95M14sTN3");
-
-/// Headers da instrução Ed25519
-const ED25519_SIG_LEN: usize = 64;
-const ED25519_PUBKEY_LEN: usize = 32;
-const ED25519_INSTRUCTION_LEN: usize = 2; // num_signatures + padding
-const SIGNATURE_OFFSETS_LEN: usize = 14; // 7 campos de u16 = 14 bytes
-
-#[program]
-pub mod workspace {
-    use super::*;
-
-    pub fn initialize_config(ctx: Context<InitializeConfig>, trusted_signer: Pubkey) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        config.bump = ctx.bumps.config;
-        config.authority = ctx.accounts.authority.key();
-        config.is_initialized = true;
-        config.trusted_signer = trusted_signer;
-        config.nonce = 0; // Inicializa nonce para replay protection
-        
-        msg!("CATE Trust Layer v2 initialized with authority: {}, trusted_signer: {}", 
-            config.authority, config.trusted_signer);
-        Ok(())
-    }
-
-    pub fn update_trusted_signer(ctx: Context<UpdateTrustedSigner>, new_signer: Pubkey) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        let old_signer = config.trusted_signer;
-        config.trusted_signer = new_signer;
-        
-        msg!("Trusted signer updated from {} to {}", old_signer, new_signer);
-        Ok(())
-    }
-
-    pub fn update_risk_status(
-        ctx: Context<UpdateRiskStatus>,
-        asset_id: String,
-        risk_score: u8,
-        is_blocked: bool,
-        confidence_ratio: u64,
-        publisher_count: u8,
-        timestamp: i64, // NOVO: Previne replay attacks
-        decision_hash: [u8; 32],
-        signature: [u8; 64],
-        signer_pubkey: [u8; 32],
-    ) -> Result<()> {
-        // Validations básicas
-        require!(asset_id.len() <= 16, ErrorCode::AssetIdTooLong);
-        require!(!asset_id.is_empty(), ErrorCode::AssetIdEmpty);
-        require!(risk_score <= 100, ErrorCode::InvalidRiskScore);
-        require!(confidence_ratio <= 10000, ErrorCode::InvalidConfidenceRatio);
-        
-        // Verifica timestamp (evita assinaturas muito antigas)
-        let current_time = Clock::get()?.unix_timestamp;
-        require!(
-            timestamp >= current_time - 300 && timestamp <= current_time + 60,
-            ErrorCode::InvalidTimestamp
-        );
-
-        // Verifica signer
-        let config = &ctx.accounts.config;
-        let signer_pubkey_key = Pubkey::new_from_array(signer_pubkey);
-        require!(
-            signer_pubkey_key == config.trusted_signer,
-            ErrorCode::InvalidSigner
-        );
-        
-        // Verifica Ed25519 de forma SEGURA via CPI check
-        // A instrução Ed25519 deve estar em current_index - 1
-        verify_ed25519_instruction(
-            &ctx.accounts.instructions_sysvar,
-            &signer_pubkey,
-            &decision_hash,
-            &signature,
-        )?;
-
-        // Replay protection: verifica se este hash já foi usado
-        require!(
-            !ctx.accounts.used_decisions.is_used(decision_hash),
-            ErrorCode::DecisionAlreadyUsed
-        );
-        
-        // Marca como usado
-        ctx.accounts.used_decisions.mark_used(decision_hash, timestamp)?;
-
-        let asset_risk = &mut ctx.accounts.asset_risk_status;
-        
-        // Asset ID com padding seguro
-        let mut asset_id_bytes = [0u8; 16];
-        let bytes = asset_id.as_bytes();
-        asset_id_bytes[..bytes.len().min(16)].copy_from_slice(&bytes[..bytes.len().min(16)]);
-        asset_risk.asset_id = asset_id_bytes;
-        
-        asset_risk.bump = ctx.bumps.asset_risk_status;
-        asset_risk.risk_score = risk_score;
-        asset_risk.is_blocked = is_blocked;
-        asset_risk.last_updated = current_time;
-        asset_risk.confidence_ratio = confidence_ratio;
-        asset_risk.publisher_count = publisher_count;
-        asset_risk.timestamp = timestamp; // Armazena para auditoria
-        
-        asset_risk.decision_hash = decision_hash;
-        asset_risk.signature = signature;
-        asset_risk.signer_pubkey = signer_pubkey;
-        
-        msg!(
-            "Updated risk status for {}: score={}, blocked={}, confidence={}bps, publishers={}, ts={}",
-            asset_id, risk_score, is_blocked, confidence_ratio, publisher_count, timestamp
-        );
-        
-        Ok(())
-    }
-
-    pub fn verify_decision(
-        ctx: Context<VerifyDecision>,
-        _asset_id: String,
-        timestamp: i64,
-        decision_hash: [u8; 32],
-        signature: [u8; 64],
-        signer_pubkey: [u8; 32],
-    ) -> Result<()> {
-        let config = &ctx.accounts.config;
-        let signer_pubkey_key = Pubkey::new_from_array(signer_pubkey);
-        
-        require!(
-            signer_pubkey_key == config.trusted_signer,
-            ErrorCode::InvalidSigner
-        );
-        
-        verify_ed25519_instruction(
-            &ctx.accounts.instructions_sysvar,
-            &signer_pubkey,
-            &decision_hash,
-            &signature,
-        )?;
-        
-        // Verifica se não está expirado (5 minutos de tolerância)
-        let current_time = Clock::get()?.unix_timestamp;
-        require!(
-            timestamp >= current_time - 300,
-            ErrorCode::DecisionExpired
-        );
-
-        msg!("Decision verification: VALID for timestamp {}", timestamp);
-        Ok(())
-    }
-
-    pub fn get_risk_status(ctx: Context<GetRiskStatus>, _asset_id: String) -> Result<AssetRiskStatus> {
-        let asset_risk = &ctx.accounts.asset_risk_status;
-        Ok(asset_risk.clone().into_inner())
-    }
-}
-
-// ============================================================================
-// Verificação Segura de Ed25519
-// ============================================================================
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct Ed25519SignatureOffsets {
-    pub signature_offset: u16,
-    pub signature_instruction_index: u16,
-    pub public_key_offset: u16,
-    pub public_key_instruction_index: u16,
-    pub message_data_offset: u16,
-    pub message_data_size: u16,
-    pub message_instruction_index: u16,
-}
-
-impl Ed25519SignatureOffsets {
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < SIGNATURE_OFFSETS_LEN {
-            return Err(ErrorCode::InvalidEd25519Data.into());
-        }
-        
-        Ok(Self {
-            signature_offset: u16::from_le_bytes([bytes[0], bytes[1]]),
-            signature_instruction_index: u16::from_le_bytes([bytes[2], bytes[3]]),
-            public_key_offset: u16::from_le_bytes([bytes[4], bytes[5]]),
-            public_key_instruction_index: u16::from_le_bytes([bytes[6], bytes[7]]),
-            message_data_offset: u16::from_le_bytes([bytes[8], bytes[9]]),
-            message_data_size: u16::from_le_bytes([bytes[10], bytes[11]]),
-            message_instruction_index: u16::from_le_bytes([bytes[12], bytes[13]]),
-        })
-    }
-}
-
-fn verify_ed25519_instruction(
-    instructions_sysvar: &AccountInfo,
-    expected_pubkey: &[u8; 32],
-    expected_message: &[u8; 32],
-    expected_signature: &[u8; 64],
-) -> Result<()> {
-    let current_index = instructions::load_current_index_checked(instructions_sysvar)? as usize;
-    
-    // Deve haver uma instrução anterior
-    require!(current_index > 0, ErrorCode::MissingEd25519Instruction);
-    
-    // Carrega a instrução anterior
-    let ed25519_ix = load_instruction_at_checked(current_index - 1, instructions_sysvar)?;
-    
-    // Verifica se é o programa Ed25519 oficial
-    require!(
-        ed25519_ix.program_id == ed25519_program::ID,
-        ErrorCode::InvalidEd25519Program
-    );
-    
-    // Parse dos dados da instrução Ed25519
-    let data = &ed25519_ix.data;
-    require!(data.len() >= ED25519_INSTRUCTION_LEN, ErrorCode::InvalidEd25519Data);
-    
-    let num_signatures = data[0] as usize;
-    let padding = data[1];
-    
-    require!(num_signatures >= 1, ErrorCode::InvalidEd25519Data);
-    require!(padding == 0, ErrorCode::InvalidEd25519Data);
-    
-    // Calcula o tamanho esperado: header + (offsets * num_signatures) + dados
-    let expected_min_len = ED25519_INSTRUCTION_LEN + (SIGNATURE_OFFSETS_LEN * num_signatures);
-    require!(data.len() >= expected_min_len, ErrorCode::InvalidEd25519Data);
-    
-    // Para cada assinatura, verifica se os dados correspondem ao esperado
-    for i in 0..num_signatures {
-        let offset_start = ED25519_INSTRUCTION_LEN + (SIGNATURE_OFFSETS_LEN * i);
-        let offset_end = offset_start + SIGNATURE_OFFSETS_LEN;
-        
-        let offsets = Ed25519SignatureOffsets::from_bytes(&data[offset_start..offset_end])?;
-        
-        // Verifica se os dados estão na instrução atual (índice = u16::MAX significa dados na mesma instrução)
-        require!(
-            offsets.signature_instruction_index == u16::MAX ||
-            offsets.signature_instruction_index == (current_index - 1) as u16,
-            ErrorCode::InvalidInstructionIndex
-        );
-        
-        // Verifica bounds dos offsets
-        let sig_start = offsets.signature_offset as usize;
-        let sig_end = sig_start.checked_add(ED25519_SIG_LEN)
-            .ok_or(ErrorCode::InvalidEd25519Data)?;
-        require!(sig_end <= data.len(), ErrorCode::SignatureOffsetOverflow);
-        
-        let pubkey_start = offsets.public_key_offset as usize;
-        let pubkey_end = pubkey_start.checked_add(ED25519_PUBKEY_LEN)
-            .ok_or(ErrorCode::InvalidEd25519Data)?;
-        require!(pubkey_end <= data.len(), ErrorCode::PubkeyOffsetOverflow);
-        
-        let msg_start = offsets.message_data_offset as usize;
-        let msg_size = offsets.message_data_size as usize;
-        let msg_end = msg_start.checked_add(msg_size)
-            .ok_or(ErrorCode::InvalidEd25519Data)?;
-        require!(msg_end <= data.len(), ErrorCode::MessageOffsetOverflow);
-        require!(msg_size == 32, ErrorCode::InvalidMessageSize);
-        
-        // Verifica se os dados batem com o esperado
-        let ix_signature = &data[sig_start..sig_end];
-        let ix_pubkey = &data[pubkey_start..pubkey_end];
-        let ix_message = &data[msg_start..msg_end];
-        
-        // Comparação constant-time (mitiga timing attacks)
-        if secure_compare(ix_pubkey, expected_pubkey) 
-            && secure_compare(ix_signature, expected_signature)
-            && secure_compare(ix_message, expected_message) {
-            msg!("Ed25519 signature {} verified successfully", i);
-            return Ok(());
-        }
-    }
-    
-    // Se chegou aqui, nenhuma assinatura correspondeu
-    Err(ErrorCode::SignatureVerificationFailed.into())
-}
-
-/// Comparação constant-time para prevenir timing attacks
-fn secure_compare(a: &[u8], b: &[u8]) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-    let mut result = 0u8;
-    for (x, y) in a.iter().zip(b.iter()) {
-        result |= x ^ y;
-    }
-    result == 0
-}
-
-// ============================================================================
-// Conta para Replay Protection
-// ============================================================================
-
-#[account]
-pub struct UsedDecisions {
-    pub bump: u8,
-    pub decisions: Vec<DecisionRecord>,
-    pub max_size: u16,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct DecisionRecord {
-    pub hash: [u8; 32],
-    pub timestamp: i64,
-}
-
-impl UsedDecisions {
-    pub const LEN: usize = 1 + 4 + (34 * 100); // bump + vec len + 100 records
-    
-    pub fn is_used(&self, hash: [u8; 32]) -> bool {
-        self.decisions.iter().any(|d| d.hash == hash)
-    }
-    
-    pub fn mark_used(&mut self, hash: [u8; 32], timestamp: i64) -> Result<()> {
-        // Remove entradas antigas (mais de 1 hora) para economizar espaço
-        let current_time = timestamp;
-        self.decisions.retain(|d| current_time - d.timestamp < 3600);
-        
-        require!(
-            (self.decisions.len() as u16) < self.max_size,
-            ErrorCode::DecisionHistoryFull
-        );
-        
-        self.decisions.push(DecisionRecord { hash, timestamp });
-        Ok(())
-    }
-}
-
-// ============================================================================
-// Accounts
-// ============================================================================
-
-#[account]
-pub struct Config {
-    pub bump: u8,
-    pub authority: Pubkey,
-    pub is_initialized: bool,
-    pub trusted_signer: Pubkey,
-    pub nonce: u64, // Para tracking de operações
-}
-
-impl Config {
-    pub const LEN: usize = 1 + 32 + 1 + 32 + 8; // + nonce
-}
-
-#[account]
-
-pub struct AssetRiskStatus {
-    pub bump: u8,
-    pub asset_id: [u8; 16],
-    pub risk_score: u8,
-    pub is_blocked: bool,
-    pub last_updated: i64,
-    pub confidence_ratio: u64,
-    pub publisher_count: u8,
-    pub timestamp: i64, // NOVO: quando foi assinado
-    pub decision_hash: [u8; 32],
-    pub signature: [u8; 64],
-    pub signer_pubkey: [u8; 32],
-}
-
-impl AssetRiskStatus {
-    pub const LEN: usize = 1 + 16 + 1 + 1 + 8 + 8 + 1 + 8 + 32 + 64 + 32; // + timestamp
-}
-
-// ============================================================================
-// Contexts
-// ============================================================================
-
-#[derive(Accounts)]
-pub struct InitializeConfig<'info> {
-    #[account(
-        init,
-        seeds = [b"config"],
-        bump,
-        payer = authority,
-        space = 8 + Config::LEN
-    )]
-    pub config: Account<'info, Config>,
-    
-    #[account(
-        init,
-        payer = authority,
-        seeds = [b"used_decisions"],
-        bump,
-        space = 8 + UsedDecisions::LEN
-    )]
-    pub used_decisions: Account<'info, UsedDecisions>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateTrustedSigner<'info> {
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.is_initialized @ ErrorCode::NotInitialized,
-        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
-    )]
-    pub config: Account<'info, Config>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(asset_id: String, timestamp: i64, decision_hash: [u8; 32])]
-pub struct UpdateRiskStatus<'info> {
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.is_initialized @ ErrorCode::NotInitialized,
-        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
-    )]
-    pub config: Account<'info, Config>,
-    
-    #[account(
-        mut,
-        seeds = [b"used_decisions"],
-        bump = used_decisions.bump
-    )]
-    pub used_decisions: Account<'info, UsedDecisions>,
-    
-    #[account(
-        init_if_needed,
-        seeds = [b"asset_risk", asset_id.as_bytes()],
-        bump,
-        payer = authority,
-        space = 8 + AssetRiskStatus::LEN
-    )]
-    pub asset_risk_status: Account<'info, AssetRiskStatus>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Instructions sysvar verification
-    #[account(address = instructions::ID)]
-    pub instructions_sysvar: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct VerifyDecision<'info> {
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump,
-        constraint = config.is_initialized @ ErrorCode::NotInitialized
-    )]
-    pub config: Account<'info, Config>,
-    
-    /// CHECK: Instructions sysvar verification
-    #[account(address = instructions::ID)]
-    pub instructions_sysvar: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(asset_id: String)]
-pub struct GetRiskStatus<'info> {
-    #[account(
-        seeds = [b"asset_risk", asset_id.as_bytes()],
-        bump = asset_risk_status.bump
-    )]
-    pub asset_risk_status: Account<'info, AssetRiskStatus>,
-}
-
-// ============================================================================
-// Errors
-// ============================================================================
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Asset ID exceeds maximum length of 16 characters")]
-    AssetIdTooLong,
-    #[msg("Asset ID cannot be empty")]
-    AssetIdEmpty,
-    #[msg("Risk score must be between 0 and 100")]
-    InvalidRiskScore,
-    #[msg("Confidence ratio must be between 0 and 10000 basis points")]
-    InvalidConfidenceRatio,
-    #[msg("Invalid timestamp")]
-    InvalidTimestamp,
-    #[msg("Program has not been initialized")]
-    NotInitialized,
-    #[msg("Unauthorized: caller is not the authority")]
-    Unauthorized,
-    #[msg("Invalid signer: does not match trusted signer")]
-    InvalidSigner,
-    #[msg("Invalid Ed25519 signature")]
-    InvalidSignature,
-    #[msg("Missing Ed25519 verification instruction")]
-    MissingEd25519Instruction,
-    #[msg("Invalid Ed25519 program")]
-    InvalidEd25519Program,
-    #[msg("Invalid Ed25519 instruction data")]
-    InvalidEd25519Data,
-    #[msg("Invalid instruction index in Ed25519 data")]
-    InvalidInstructionIndex,
-    #[msg("Signature offset overflow")]
-    SignatureOffsetOverflow,
-    #[msg("Public key offset overflow")]
-    PubkeyOffsetOverflow,
-    #[msg("Message offset overflow")]
-    MessageOffsetOverflow,
-    #[msg("Invalid message size")]
-    InvalidMessageSize,
-    #[msg("Signature verification failed")]
-    SignatureVerificationFailed,
-    #[msg("Decision hash already used")]
-    DecisionAlreadyUsed,
-    #[msg("Decision history full")]
-    DecisionHistoryFull,
-    #[msg("Decision expired")]
-    DecisionExpired,
-}
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT};
+use anchor_lang::solana_program::{ed25519_program, secp256k1_program};
+use anchor_lang::solana_program::sysvar::instructions::{self, load_instruction_at_checked};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use cate_replay::{bloom_bit, BloomParams, ReplayError, ReplayPolicy, ReplayStore};
+
+declare_id!("2CVGjnZ2BRebSeDHdo3VZknm5jVjxZmWu9m95M14sTN3");
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+pub mod gating;
+pub mod message;
+pub mod pyth;
+pub mod switchboard;
+pub mod wormhole;
+
+pub use message::{
+    publisher_id, DecisionPayload, PublisherId, MAX_PUBLISHER_IDS, MAX_SESSION_ASSETS,
+    RISK_CATEGORY_COUNT, RISK_FLAGS_ALL, RISK_FLAG_DEPEG, RISK_FLAG_LIQUIDITY, RISK_FLAG_ORACLE,
+    RISK_FLAG_VOLATILITY, SUB_SCORE_DEPEG, SUB_SCORE_LIQUIDITY, SUB_SCORE_ORACLE,
+    SUB_SCORE_VOLATILITY,
+};
+
+/// Headers da instrução Ed25519
+const ED25519_SIG_LEN: usize = 64;
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_INSTRUCTION_LEN: usize = 2; // num_signatures + padding
+const SIGNATURE_OFFSETS_LEN: usize = 14; // 7 campos de u16 = 14 bytes
+
+/// Headers da instrução Secp256k1
+const SECP256K1_SIG_LEN: usize = 64; // r || s; o recovery id vem logo depois
+const SECP256K1_ADDRESS_LEN: usize = 20; // Endereço estilo Ethereum
+const SECP256K1_INSTRUCTION_LEN: usize = 1; // num_signatures
+const SECP256K1_OFFSETS_LEN: usize = 11; // 4 u16 + 3 u8
+
+/// Entradas conferidas por instrução de precompile; as seguintes são ignoradas.
+/// Cobre uma assinatura por signer confiável com folga para entradas alheias.
+pub const MAX_PRECOMPILE_ENTRIES_SCANNED: usize = 16;
+
+/// Namespace opcional para as seeds dos PDAs (deployments white-label / forks).
+/// Definido em tempo de compilação via `CATE_SEED_NAMESPACE`; vazio mantém os PDAs originais.
+pub const SEED_NAMESPACE: &[u8] = match option_env!("CATE_SEED_NAMESPACE") {
+    Some(namespace) => namespace.as_bytes(),
+    None => b"",
+};
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const USED_DECISIONS_SEED: &[u8] = b"used_decisions";
+pub const ASSET_RISK_SEED: &[u8] = b"asset_risk";
+pub const DEADMAN_SEED: &[u8] = b"deadman";
+pub const ASSET_POLICY_SEED: &[u8] = b"asset_policy";
+pub const SIGNER_USAGE_SEED: &[u8] = b"signer_usage";
+pub const ASSET_HISTORY_SEED: &[u8] = b"asset_history";
+pub const PENDING_CHANGE_SEED: &[u8] = b"pending_change";
+pub const ASSET_MINT_SEED: &[u8] = b"asset_risk_mint";
+pub const INTEGRATION_STATS_SEED: &[u8] = b"integration_stats";
+pub const PENDING_DECISION_SEED: &[u8] = b"pending_decision";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const ROLES_SEED: &[u8] = b"roles";
+pub const ASSET_META_SEED: &[u8] = b"asset_meta";
+pub const ASSET_GROUP_SEED: &[u8] = b"asset_group";
+pub const PORTFOLIO_SEED: &[u8] = b"portfolio";
+pub const STATS_SEED: &[u8] = b"stats";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const REPLAY_BLOOM_SEED: &[u8] = b"replay_bloom";
+pub const CONSUMER_SEED: &[u8] = b"consumer";
+pub const SCHEDULE_SEED: &[u8] = b"schedule";
+pub const WORMHOLE_EMITTER_SEED: &[u8] = b"emitter";
+pub const WORMHOLE_MESSAGE_SEED: &[u8] = b"wormhole_message";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const SESSION_KEY_SEED: &[u8] = b"session_key";
+
+/// Quantos scores recentes cada AssetRiskHistory guarda
+pub const RISK_HISTORY_LEN: usize = 24;
+
+/// Número de shards do replay store; o shard é escolhido pelo primeiro byte do hash
+pub const REPLAY_SHARD_COUNT: u8 = 8;
+
+/// Política de replay: aceita timestamps de até 5 min atrás / 1 min à frente
+/// e lembra hashes usados por 1 hora. A janela é o padrão do Config, ajustável
+/// via PolicyChange::TimestampWindow; a retenção é fixa e limita a janela.
+pub const REPLAY_POLICY: ReplayPolicy = ReplayPolicy {
+    max_past_secs: 300,
+    max_future_secs: 60,
+    retention_secs: 3600,
+    shard_count: REPLAY_SHARD_COUNT,
+};
+
+/// Capacidade de cada shard do replay store
+const MAX_DECISIONS_PER_SHARD: u16 = 100;
+
+/// Teto de max_size em resize_replay_shard: a conta inteira é desserializada
+/// no heap de 32 KB junto com o resto da instrução
+pub const MAX_REPLAY_SHARD_SIZE: u16 = 600;
+
+/// Tamanho de cada um dos dois filtros do ReplayBloom (32768 bits)
+pub const BLOOM_FILTER_BYTES: usize = 4096;
+
+/// Máximo de decisões em update_risk_status_batch
+const MAX_BATCH_SIZE: usize = 10;
+
+/// Tamanho máximo do conjunto de signers confiáveis
+const MAX_TRUSTED_SIGNERS: usize = 10;
+pub const MAX_ROLE_GRANTS: usize = 16;
+pub const MAX_NOTIONAL_TIERS: usize = 8;
+pub const MAX_RAMP_STEPS: usize = 4;
+pub const MAX_PORTFOLIO_ASSETS: usize = 32;
+/// Ativos com contador próprio em Stats; os demais só entram no total
+pub const MAX_STATS_ASSETS: usize = 64;
+
+/// Atraso mínimo padrão entre propor e ativar um novo conjunto de signers
+pub const DEFAULT_SIGNER_ROTATION_DELAY_SECS: i64 = 86_400;
+
+/// Duração máxima de uma delegação de chave de sessão
+pub const MAX_SESSION_DURATION_SECS: i64 = 7 * 86_400;
+
+/// Antecedência com que decisões de um signer perto do `expires_at` emitem
+/// SignerExpiring
+pub const SIGNER_EXPIRY_WARNING_SECS: i64 = 7 * 86_400;
+
+/// Peso padrão de cada decisão nova na média móvel do score
+pub const DEFAULT_SCORE_EMA_ALPHA_BPS: u16 = 3_000;
+
+/// Versões de layout gravadas em Config.version e AssetRiskStatus.version;
+/// 0 é a conta criada antes do campo existir. AssetRiskStatus é zero-copy a
+/// partir da 4; as anteriores são Borsh (AssetRiskStatusV3).
+pub const CONFIG_VERSION: u8 = 8;
+pub const ASSET_RISK_VERSION: u8 = 5;
+/// Primeira versão do AssetRiskStatus com `risk_tier` gravado
+pub const RISK_TIER_VERSION: u8 = 5;
+
+
+/// Atraso padrão entre enfileirar e aplicar uma mudança de política
+pub const DEFAULT_POLICY_DELAY_SECS: i64 = 86_400;
+
+/// Janela padrão de frescor para leituras (get_effective_status)
+pub const DEFAULT_MAX_STALENESS_SECS: i64 = 3600;
+
+/// Contadores de uso por chave reiniciam a cada dia UTC
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Dead-man switch: limite de ativos cobertos explicitamente
+const MAX_DEADMAN_ASSETS: usize = 16;
+
+/// Máximo de ativos por grupo: block_group precisa de todos na mesma transação
+const MAX_GROUP_MEMBERS: usize = 24;
+
+/// Máximo de janelas em cada lista de um TradingSchedule
+pub const MAX_SCHEDULE_WINDOWS: usize = 8;
+
+#[program]
+pub mod workspace {
+    use super::*;
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        trusted_signer: Pubkey,
+        genesis_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let config = &mut ctx.accounts.config;
+        **config = Config::fresh(
+            ctx.bumps.config,
+            ctx.accounts.authority.key(),
+            trusted_signer,
+            genesis_hash,
+            clock.slot,
+        );
+        config.nonce = 0; // Inicializa nonce para replay protection
+
+        // Shard 0 do replay store; os demais via initialize_replay_shard
+        let used_decisions = &mut ctx.accounts.used_decisions;
+        used_decisions.bump = ctx.bumps.used_decisions;
+        used_decisions.shard = 0;
+        used_decisions.max_size = MAX_DECISIONS_PER_SHARD;
+        used_decisions.generation = config.generation;
+        used_decisions.reset_at = clock.unix_timestamp;
+        
+        emit!(ConfigInitialized {
+            authority: config.authority,
+            trusted_signer,
+            genesis_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("CATE Trust Layer v2 initialized with authority: {}, trusted_signer: {}", 
+            config.authority, trusted_signer);
+        Ok(())
+    }
+
+    /// Recuperação: reescreve um Config corrompido ou de layout antigo sem
+    /// redeploy. Só a authority gravada nos bytes iniciais (layout estável)
+    /// pode chamar. A nova geração invalida todos os shards do replay store,
+    /// que precisam ser fechados e recriados, e os ids de mudanças de política
+    /// recomeçam acima de qualquer PendingPolicyChange anterior.
+    pub fn reinitialize_config(
+        ctx: Context<ReinitializeConfig>,
+        trusted_signer: Pubkey,
+        genesis_hash: [u8; 32],
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let mut config = Config::fresh(
+            ctx.bumps.config,
+            ctx.accounts.authority.key(),
+            trusted_signer,
+            genesis_hash,
+            clock.slot,
+        );
+        config.nonce = clock.slot;
+
+        let info = ctx.accounts.config.to_account_info();
+        let mut data = info.try_borrow_mut_data()?;
+        data.fill(0);
+        config.try_serialize(&mut &mut data[..])?;
+
+        emit!(ConfigReinitialized {
+            authority: config.authority,
+            trusted_signer,
+            genesis_hash,
+            generation: config.generation,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Config reinitialized, generation {}", config.generation);
+        Ok(())
+    }
+
+    /// Leva o Config ao layout atual: realoca, lê o layout antigo com os campos
+    /// finais zerados e grava `version`. Layout que mudou no meio da struct não
+    /// é legível assim; para ele, reinitialize_config.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let info = ctx.accounts.config.to_account_info();
+        let mut config = Config::try_deserialize_tolerant(&info.try_borrow_data()?)?;
+        let from_version = config.version;
+        require!(from_version < CONFIG_VERSION, ErrorCode::AlreadyMigrated);
+        config.version = CONFIG_VERSION;
+        config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(ConfigMigrated {
+            from_version,
+            to_version: CONFIG_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Config migrated from version {} to {}", from_version, CONFIG_VERSION);
+        Ok(())
+    }
+
+    /// Converte a conta Borsh de um ativo (versões 0 a 3) para o layout
+    /// zero-copy no mesmo PDA. Contas no layout v1 passam por retire_v1_asset.
+    pub fn migrate_asset(ctx: Context<MigrateAsset>, asset_id: String) -> Result<()> {
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let info = ctx.accounts.asset_risk_status.to_account_info();
+        // O realloc completou os bytes Borsh com zeros até o tamanho novo
+        let legacy = AssetRiskStatusV3::try_from_data(&info.try_borrow_data()?)?;
+        require!(legacy.asset_id == asset_id_bytes, ErrorCode::InvalidAssetAccount);
+        let from_version = legacy.version;
+        // Faixa pelos limites padrão até a próxima decisão do ativo
+        let asset_risk = AssetRiskStatus::from(legacy);
+        info.try_borrow_mut_data()?[8..8 + AssetRiskStatus::LEN]
+            .copy_from_slice(bytemuck::bytes_of(&asset_risk));
+
+        emit!(AssetMigrated {
+            asset_id: asset_id_bytes,
+            from_version,
+            to_version: ASSET_RISK_VERSION,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("{} migrated from version {} to {}", asset_id, from_version, ASSET_RISK_VERSION);
+        Ok(())
+    }
+
+    /// Propõe a troca para um único signer (1-of-1). Só vale a partir de
+    /// `effective_at`, que deve respeitar `config.signer_rotation_delay_secs`.
+    pub fn propose_signer(
+        ctx: Context<UpdateTrustedSigner>,
+        new_signer: Pubkey,
+        effective_at: i64,
+    ) -> Result<()> {
+        propose_signers(&mut ctx.accounts.config, vec![new_signer], Vec::new(), 1, effective_at)
+    }
+
+    /// Propõe um conjunto com o mesmo timelock de `propose_signer`. `weights`
+    /// é paralelo a `signers` (vazio = todos com peso 1) e `quorum` passa a ser
+    /// o peso mínimo somado: chave principal 2 e reservas 1 com quorum 2.
+    pub fn propose_signer_set(
+        ctx: Context<UpdateTrustedSigner>,
+        signers: Vec<Pubkey>,
+        weights: Vec<u8>,
+        quorum: u8,
+        effective_at: i64,
+    ) -> Result<()> {
+        propose_signers(&mut ctx.accounts.config, signers, weights, quorum, effective_at)
+    }
+
+    /// Variante para governança (SPL Governance, multisig): o atraso conta da
+    /// execução da proposta, cujo momento não é conhecido quando ela é criada
+    pub fn propose_signer_set_after(
+        ctx: Context<UpdateTrustedSigner>,
+        signers: Vec<Pubkey>,
+        weights: Vec<u8>,
+        quorum: u8,
+        delay_secs: i64,
+    ) -> Result<()> {
+        let effective_at = Clock::get()?.unix_timestamp.saturating_add(delay_secs);
+        propose_signers(&mut ctx.accounts.config, signers, weights, quorum, effective_at)
+    }
+
+    /// Troca só o quorum, mantendo o conjunto e os pesos atuais; passa pelo
+    /// mesmo timelock e ativação de uma rotação
+    pub fn propose_quorum_after(
+        ctx: Context<UpdateTrustedSigner>,
+        quorum: u8,
+        delay_secs: i64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let signers = config.trusted_signers.clone();
+        let weights = signers.iter().map(|s| config.signer_weight(s)).collect();
+        let effective_at = Clock::get()?.unix_timestamp.saturating_add(delay_secs);
+        propose_signers(config, signers, weights, quorum, effective_at)
+    }
+
+    pub fn activate_signer(ctx: Context<UpdateTrustedSigner>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        let pending = config.pending_signer_set.take().ok_or(ErrorCode::NoPendingSigner)?;
+        require!(current_time >= pending.effective_at, ErrorCode::SignerNotYetEffective);
+
+        // Conjunto anterior segue aceito durante a janela de graça: a idade máxima
+        // aceita de um timestamp, depois disso nenhuma decisão em trânsito seria válida
+        config.old_signers = std::mem::replace(&mut config.trusted_signers, pending.signers);
+        config.rotation_deadline = current_time.saturating_add(config.max_past_secs);
+        config.quorum = pending.quorum;
+        // Os anteriores mantêm o peso durante a graça; os de rotações mais
+        // antigas saem da tabela
+        let mut weights = std::mem::take(&mut config.pending_signer_weights);
+        weights.extend(config.signer_weights.iter().filter(|entry| {
+            config.old_signers.contains(&entry.signer)
+                && !config.trusted_signers.contains(&entry.signer)
+        }));
+        config.signer_weights = weights;
+
+        emit!(TrustedSignerRotated {
+            signers: config.trusted_signers.clone(),
+            weights: config.trusted_signers.iter().map(|s| config.signer_weight(s)).collect(),
+            quorum: config.quorum,
+            grace_until: config.rotation_deadline,
+            timestamp: current_time,
+        });
+        msg!(
+            "Trusted signer set activated: weight {} of {} signers, old keys accepted until {}",
+            config.quorum, config.trusted_signers.len(), config.rotation_deadline
+        );
+        Ok(())
+    }
+
+    /// Escape hatch: descarta a rotação pendente
+    pub fn cancel_proposed_signer(ctx: Context<UpdateTrustedSigner>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending = config.pending_signer_set.take().ok_or(ErrorCode::NoPendingSigner)?;
+        config.pending_signer_weights.clear();
+
+        emit!(SignerRotationCancelled {
+            signers: pending.signers,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Pending signer rotation cancelled");
+        Ok(())
+    }
+
+    /// A authority pode ser o PDA de um multisig (ex.: vault do Squads), que
+    /// assina via CPI: as instruções admin só exigem a assinatura dela e o
+    /// aluguel de contas novas sai de um `payer` separado
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_authority = Some(new_authority);
+
+        emit!(AuthorityTransferProposed {
+            authority: config.authority,
+            pending_authority: new_authority,
+        });
+        msg!("Authority transfer proposed from {} to {}", config.authority, new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let old_authority = config.authority;
+        config.authority = ctx.accounts.new_authority.key();
+        config.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            old_authority,
+            new_authority: config.authority,
+        });
+        msg!("Authority transferred from {} to {}", old_authority, config.authority);
+        Ok(())
+    }
+
+    /// Kill switch: congela todas as atualizações de risco e o gate de trading
+    pub fn pause(ctx: Context<SetPaused>) -> Result<()> {
+        set_paused(ctx, true)
+    }
+
+    /// Só o admin retoma; pauser e guardian podem apenas pausar
+    pub fn unpause(ctx: Context<SetPaused>) -> Result<()> {
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            ctx.accounts.config.authority,
+            ErrorCode::Unauthorized
+        );
+        set_paused(ctx, false)
+    }
+
+    /// Concede um papel operacional a uma chave. Rotação de signers, políticas
+    /// e upgrades continuam exclusivos da authority (admin).
+    pub fn grant_role(ctx: Context<ManageRoles>, role: Role, key: Pubkey) -> Result<()> {
+        let roles = &mut ctx.accounts.roles;
+        roles.bump = ctx.bumps.roles;
+        if !roles.has_role(&key, role) {
+            require!(roles.grants.len() < MAX_ROLE_GRANTS, ErrorCode::RoleTableFull);
+            roles.grants.push(RoleGrant { key, role });
+        }
+
+        emit!(RoleGranted { role, key, timestamp: Clock::get()?.unix_timestamp });
+        msg!("Role {:?} granted to {}", role, key);
+        Ok(())
+    }
+
+    pub fn revoke_role(ctx: Context<ManageRoles>, role: Role, key: Pubkey) -> Result<()> {
+        let roles = &mut ctx.accounts.roles;
+        let before = roles.grants.len();
+        roles.grants.retain(|grant| !(grant.key == key && grant.role == role));
+        require!(roles.grants.len() < before, ErrorCode::RoleNotGranted);
+
+        emit!(RoleRevoked { role, key, timestamp: Clock::get()?.unix_timestamp });
+        msg!("Role {:?} revoked from {}", role, key);
+        Ok(())
+    }
+
+    /// Enfileira uma mudança de parâmetro de política. Ela fica visível no PDA
+    /// `pending_change` por `config.policy_delay_secs` antes de poder ser aplicada,
+    /// dando aviso prévio a integradores. O id é o `config.nonce` atual.
+    pub fn queue_policy_change(ctx: Context<QueuePolicyChange>, change: PolicyChange) -> Result<()> {
+        change.validate()?;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let config = &mut ctx.accounts.config;
+        let pending = &mut ctx.accounts.pending_change;
+        pending.bump = ctx.bumps.pending_change;
+        pending.id = config.nonce;
+        pending.change = change.clone();
+        pending.queued_at = current_time;
+        pending.eta = current_time.saturating_add(config.policy_delay_secs);
+        config.nonce += 1;
+
+        emit!(PolicyChangeQueued {
+            id: pending.id,
+            change,
+            eta: pending.eta,
+            timestamp: current_time,
+        });
+        msg!("Policy change {} queued, applicable at {}", pending.id, pending.eta);
+        Ok(())
+    }
+
+    /// Aplica uma mudança de Config vencida. Permissionless: qualquer um pode
+    /// executar após o eta; o aluguel do PDA volta para a authority.
+    pub fn apply_policy_change(ctx: Context<ApplyPolicyChange>, id: u64) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let pending = &ctx.accounts.pending_change;
+        require!(current_time >= pending.eta, ErrorCode::PolicyChangeNotReady);
+
+        let change = pending.change.clone();
+        apply_config_change(&mut ctx.accounts.config, &change, current_time)?;
+
+        emit!(PolicyChangeApplied { id, change, timestamp: current_time });
+        msg!("Policy change {} applied", id);
+        Ok(())
+    }
+
+    /// Aplica uma mudança de AssetPolicy vencida (cria a conta se necessário)
+    pub fn apply_asset_policy_change(
+        ctx: Context<ApplyAssetPolicyChange>,
+        id: u64,
+        asset_id: String,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let pending = &ctx.accounts.pending_change;
+        require!(current_time >= pending.eta, ErrorCode::PolicyChangeNotReady);
+
+        let PolicyChange::AssetPolicy {
+            asset_id: change_asset_id,
+            advisory_only,
+            block_threshold,
+            max_staleness_secs,
+            pyth_check,
+            switchboard_check,
+        } = pending.change.clone()
+        else {
+            return err!(ErrorCode::WrongPolicyChangeKind);
+        };
+        require!(
+            change_asset_id == asset_id_to_bytes(&asset_id)?,
+            ErrorCode::InvalidAssetAccount
+        );
+
+        let policy = &mut ctx.accounts.asset_policy;
+        policy.bump = ctx.bumps.asset_policy;
+        policy.asset_id = change_asset_id;
+        policy.advisory_only = advisory_only;
+        policy.block_threshold = block_threshold;
+        policy.max_staleness_secs = max_staleness_secs;
+        policy.pyth_check = pyth_check.clone();
+        policy.switchboard_check = switchboard_check.clone();
+        policy.updated_at = current_time;
+
+        emit!(AssetPolicyUpdated {
+            asset_id: policy.asset_id,
+            advisory_only,
+            block_threshold,
+            max_staleness_secs,
+            pyth_check,
+            switchboard_check,
+        });
+        emit!(PolicyChangeApplied {
+            id,
+            change: pending.change.clone(),
+            timestamp: current_time,
+        });
+        msg!(
+            "Asset policy for {}: advisory_only={}, block_threshold={:?}, max_staleness={:?}",
+            asset_id, advisory_only, block_threshold, max_staleness_secs
+        );
+        Ok(())
+    }
+
+    pub fn cancel_policy_change(ctx: Context<CancelPolicyChange>, id: u64) -> Result<()> {
+        emit!(PolicyChangeCancelled {
+            id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Policy change {} cancelled", id);
+        Ok(())
+    }
+
+    /// Cria a fila de revisão do ativo, exigida para estacionar decisões
+    /// quando o modo de dois engines está ativo
+    pub fn initialize_pending_decision(
+        ctx: Context<InitializePendingDecision>,
+        asset_id: String,
+    ) -> Result<()> {
+        let pending = &mut ctx.accounts.pending_decision;
+        pending.bump = ctx.bumps.pending_decision;
+        pending.asset_id = asset_id_to_bytes(&asset_id)?;
+        pending.payload = None;
+        msg!("Pending decision account initialized for {}", asset_id);
+        Ok(())
+    }
+
+    /// Revisão do guardian: aprovar grava a decisão estacionada, rejeitar a descarta.
+    /// Só aprova se ela ainda for mais nova que o estado atual do ativo.
+    pub fn resolve_pending_decision(
+        ctx: Context<ResolvePendingDecision>,
+        _asset_id: String,
+        approve: bool,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let pending = &mut ctx.accounts.pending_decision;
+        let payload = pending.payload.take().ok_or(ErrorCode::NoPendingDecision)?;
+
+        if approve {
+            let config = &ctx.accounts.config;
+            let mut asset_risk = ctx.accounts.asset_risk_status.load_mut()?;
+            require!(!config.paused, ErrorCode::ProgramPaused);
+            require!(
+                config.portfolio_breaker.is_none() || ctx.accounts.portfolio.is_some(),
+                ErrorCode::PortfolioAccountMissing
+            );
+            require!(asset_risk.lifecycle() != AssetLifecycle::Delisted, ErrorCode::AssetDelisted);
+            require!(
+                payload.timestamp > asset_risk.timestamp,
+                ErrorCode::PendingDecisionSuperseded
+            );
+            require!(!payload.is_expired(current_time), ErrorCode::DecisionExpired);
+            // O piso pode ter mudado enquanto a decisão esperava o guardian
+            let below_floor = config.below_decision_floor(&payload);
+            require!(!below_floor || config.block_below_floor, ErrorCode::DecisionBelowFloor);
+            require!(
+                payload.is_blocked || asset_risk.unblock_allowed(payload.timestamp),
+                ErrorCode::DecisionPredatesEmergencyBlock
+            );
+
+            let is_blocked = payload.is_blocked
+                || below_floor
+                || asset_risk.is_deadman_blocked()
+                || ctx
+                    .accounts
+                    .asset_policy
+                    .as_ref()
+                    .is_some_and(|p| p.forces_block(payload.risk_score));
+            asset_risk.track_cooling(is_blocked, config.unblock_cooldown.as_ref(), current_time);
+            asset_risk.track_ema(payload.risk_score, config.score_ema_alpha_bps);
+            write_decision(
+                &mut asset_risk,
+                &payload,
+                &config.risk_tier_thresholds(),
+                is_blocked,
+                pending.decision_hash,
+                pending.signature,
+                pending.signer_pubkey,
+                pending.signer_count,
+                current_time,
+            );
+            if let Some(history) = ctx.accounts.asset_history.as_ref() {
+                let mut history = history.load_mut()?;
+                if history.asset_id == payload.asset_id {
+                    history.push(&asset_risk);
+                }
+            }
+            if let Some(portfolio) = ctx.accounts.portfolio.as_mut() {
+                record_portfolio_score(
+                    config,
+                    portfolio,
+                    &payload.asset_id,
+                    asset_risk.risk_score,
+                    current_time,
+                );
+            }
+        }
+
+        emit!(PendingDecisionResolved {
+            asset_id: payload.asset_id,
+            decision_hash: pending.decision_hash,
+            approved: approve,
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: current_time,
+        });
+        msg!("Pending decision {}", if approve { "approved" } else { "rejected" });
+        Ok(())
+    }
+
+    pub fn initialize_signer_usage(ctx: Context<InitializeSignerUsage>, signer: Pubkey) -> Result<()> {
+        let usage = &mut ctx.accounts.signer_usage;
+        usage.bump = ctx.bumps.signer_usage;
+        usage.signer = signer;
+        usage.day = Clock::get()?.unix_timestamp / SECONDS_PER_DAY;
+        msg!("Signer usage account initialized for {}", signer);
+        Ok(())
+    }
+
+    /// Libera uma chave que atingiu o teto antes da virada do dia
+    pub fn reset_signer_usage(ctx: Context<ResetSignerUsage>, signer: Pubkey) -> Result<()> {
+        let usage = &mut ctx.accounts.signer_usage;
+        usage.unblock_count = 0;
+        usage.score_decrease = 0;
+
+        emit!(SignerUsageReset {
+            signer,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Signer usage reset for {}", signer);
+        Ok(())
+    }
+
+    /// Transição de ciclo de vida do ativo. Pode criar a conta do ativo em
+    /// `Listed` antes da primeira decisão.
+    pub fn set_asset_lifecycle(
+        ctx: Context<SetAssetLifecycle>,
+        asset_id: String,
+        lifecycle: AssetLifecycle,
+    ) -> Result<()> {
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let mut asset_risk = match ctx.accounts.asset_risk_status.load_mut() {
+            Ok(asset_risk) => asset_risk,
+            Err(_) => ctx.accounts.asset_risk_status.load_init()?,
+        };
+        if asset_risk.asset_id[0] == 0 {
+            asset_risk.bump = ctx.bumps.asset_risk_status;
+            asset_risk.version = ASSET_RISK_VERSION;
+            asset_risk.asset_id = asset_id_bytes;
+            asset_risk.set_lifecycle(AssetLifecycle::Listed);
+        }
+
+        let from = asset_risk.lifecycle();
+        require!(from.can_transition_to(lifecycle), ErrorCode::InvalidLifecycleTransition);
+        asset_risk.set_lifecycle(lifecycle);
+
+        emit!(AssetLifecycleChanged {
+            asset_id: asset_id_bytes,
+            from,
+            to: lifecycle,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Asset {} lifecycle: {:?} -> {:?}", asset_id, from, lifecycle);
+        Ok(())
+    }
+
+    /// Reduce-only administrativo, independente do sinal do engine
+    pub fn set_reduce_only(
+        ctx: Context<SetReduceOnly>,
+        asset_id: String,
+        reduce_only: bool,
+    ) -> Result<()> {
+        let mut asset_risk = ctx.accounts.asset_risk_status.load_mut()?;
+        asset_risk.admin_reduce_only = u8::from(reduce_only);
+
+        emit!(ReduceOnlySet {
+            asset_id: asset_risk.asset_id,
+            reduce_only,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Admin reduce-only for {}: {}", asset_id, reduce_only);
+        Ok(())
+    }
+
+    /// Horário de negociação e manutenção do ativo (`asset_id` vazio = global),
+    /// substituindo as listas anteriores. Janelas semanais em UTC.
+    pub fn set_trading_schedule(
+        ctx: Context<SetTradingSchedule>,
+        asset_id: String,
+        open_hours: Vec<WeeklyWindow>,
+        closed_hours: Vec<WeeklyWindow>,
+        maintenance_windows: Vec<MaintenanceWindow>,
+    ) -> Result<()> {
+        let asset_id_bytes = schedule_scope(&asset_id)?;
+        for list_len in [open_hours.len(), closed_hours.len(), maintenance_windows.len()] {
+            require!(list_len <= MAX_SCHEDULE_WINDOWS, ErrorCode::TooManyScheduleWindows);
+        }
+        require!(
+            open_hours.iter().chain(closed_hours.iter()).all(WeeklyWindow::is_valid)
+                && maintenance_windows.iter().all(|window| window.start < window.end),
+            ErrorCode::InvalidScheduleWindow
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let schedule = &mut ctx.accounts.schedule;
+        if schedule.updated_at == 0 {
+            schedule.bump = ctx.bumps.schedule;
+            schedule.asset_id = asset_id_bytes;
+            let config = &mut ctx.accounts.config;
+            config.trading_schedules = config.trading_schedules.saturating_add(1);
+        }
+        schedule.open_hours = open_hours.clone();
+        schedule.closed_hours = closed_hours.clone();
+        schedule.maintenance_windows = maintenance_windows.clone();
+        schedule.updated_at = current_time;
+
+        emit!(TradingScheduleUpdated {
+            asset_id: asset_id_bytes,
+            open_hours,
+            closed_hours,
+            maintenance_windows,
+            timestamp: current_time,
+        });
+        let scope = if asset_id.is_empty() { "all assets" } else { asset_id.as_str() };
+        msg!("Trading schedule set for {}", scope);
+        Ok(())
+    }
+
+    /// Remove o horário do ativo (`asset_id` vazio = global); o rent volta à authority
+    pub fn close_trading_schedule(
+        ctx: Context<CloseTradingSchedule>,
+        asset_id: String,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.trading_schedules = config.trading_schedules.saturating_sub(1);
+
+        emit!(TradingScheduleClosed {
+            asset_id: ctx.accounts.schedule.asset_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        let scope = if asset_id.is_empty() { "all assets" } else { asset_id.as_str() };
+        msg!("Trading schedule closed for {}", scope);
+        Ok(())
+    }
+
+    /// Bloqueio de emergência por um guardian, sem decisão assinada. Só bloqueia:
+    /// o desbloqueio exige decisão do engine assinada depois deste instante.
+    pub fn emergency_block(ctx: Context<EmergencyBlock>, asset_id: String) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut asset_risk = ctx.accounts.asset_risk_status.load_mut()?;
+        asset_risk.is_blocked = 1;
+        asset_risk.emergency_blocked_at = current_time;
+        asset_risk.last_updated = current_time;
+
+        emit!(EmergencyBlocked {
+            asset_id: asset_risk.asset_id,
+            guardian: ctx.accounts.guardian.key(),
+            timestamp: current_time,
+        });
+        msg!("Emergency block on {} by {}", asset_id, ctx.accounts.guardian.key());
+        Ok(())
+    }
+
+    /// Define os membros de um grupo de ativos (ex.: "majors", "lsts"),
+    /// substituindo a lista anterior
+    pub fn set_asset_group(
+        ctx: Context<SetAssetGroup>,
+        group_id: String,
+        assets: Vec<String>,
+    ) -> Result<()> {
+        require!(assets.len() <= MAX_GROUP_MEMBERS, ErrorCode::TooManyGroupMembers);
+        let mut members: Vec<[u8; 16]> = Vec::with_capacity(assets.len());
+        for asset_id in assets.iter() {
+            let asset_id_bytes = asset_id_to_bytes(asset_id)?;
+            if !members.contains(&asset_id_bytes) {
+                members.push(asset_id_bytes);
+            }
+        }
+
+        let group = &mut ctx.accounts.asset_group;
+        group.bump = ctx.bumps.asset_group;
+        group.group_id = asset_id_to_bytes(&group_id)?;
+        group.members = members;
+
+        emit!(AssetGroupUpdated {
+            group_id: group.group_id,
+            member_count: group.members.len() as u8,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Asset group {} set with {} members", group_id, group.members.len());
+        Ok(())
+    }
+
+    /// Peso do ativo no score agregado da carteira; 0 remove. O ativo precisa
+    /// estar registrado e entra com o score atual, se já tiver AssetRiskStatus.
+    pub fn set_portfolio_weight(
+        ctx: Context<SetPortfolioWeight>,
+        asset_id: String,
+        weight: u16,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let risk_score = match ctx.accounts.asset_risk_status.as_ref() {
+            Some(asset_risk) => asset_risk.load()?.risk_score,
+            None => 0,
+        };
+
+        let portfolio = &mut ctx.accounts.portfolio;
+        portfolio.bump = ctx.bumps.portfolio;
+        portfolio.set_weight(asset_id_bytes, weight, risk_score)?;
+        portfolio.updated_at = current_time;
+
+        emit!(PortfolioWeightSet {
+            asset_id: asset_id_bytes,
+            weight,
+            aggregate_score: portfolio.aggregate_score(),
+            timestamp: current_time,
+        });
+        msg!("Portfolio weight for {} set to {}", asset_id, weight);
+        Ok(())
+    }
+
+    /// emergency_block de todos os membros do grupo em uma transação.
+    /// remaining_accounts: o AssetRiskStatus de cada membro, em qualquer ordem;
+    /// falta de qualquer membro falha a instrução inteira.
+    pub fn block_group<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BlockGroup<'info>>,
+        group_id: String,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let group = &ctx.accounts.asset_group;
+        let guardian = ctx.accounts.guardian.key();
+
+        let mut blocked: Vec<[u8; 16]> = Vec::with_capacity(group.members.len());
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+            let loader: AccountLoader<'info, AssetRiskStatus> = AccountLoader::try_from(info)?;
+            let mut asset_risk = loader.load_mut()?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&asset_risk.asset_id)],
+                ctx.program_id,
+            );
+            require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+            require!(group.members.contains(&asset_risk.asset_id), ErrorCode::AssetNotInGroup);
+            require!(!blocked.contains(&asset_risk.asset_id), ErrorCode::InvalidAssetAccount);
+
+            // Zero-copy: escrito direto nos dados da conta, sem exit
+            asset_risk.is_blocked = 1;
+            asset_risk.emergency_blocked_at = current_time;
+            asset_risk.last_updated = current_time;
+
+            emit!(EmergencyBlocked {
+                asset_id: asset_risk.asset_id,
+                guardian,
+                timestamp: current_time,
+            });
+            blocked.push(asset_risk.asset_id);
+        }
+        require!(blocked.len() == group.members.len(), ErrorCode::GroupMemberMissing);
+
+        emit!(GroupBlocked {
+            group_id: group.group_id,
+            guardian,
+            blocked_assets: blocked.len() as u8,
+            timestamp: current_time,
+        });
+        msg!("Group {} blocked by {}: {} assets", group_id, guardian, blocked.len());
+        Ok(())
+    }
+
+    /// Intervenção humana: força bloqueio ou desbloqueio fora do engine. Fica
+    /// registrada em `last_override()` para integradores distinguirem da decisão
+    /// assinada; a próxima decisão do engine volta a valer normalmente.
+    pub fn override_risk_status(
+        ctx: Context<OverrideRiskStatus>,
+        asset_id: String,
+        is_blocked: bool,
+        reason_code: u16,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut asset_risk = ctx.accounts.asset_risk_status.load_mut()?;
+        let record = OverrideRecord {
+            authority: ctx.accounts.authority.key(),
+            timestamp: current_time,
+            reason_code,
+            is_blocked,
+        };
+        if asset_risk.is_blocked != 0 && !is_blocked {
+            asset_risk.unblocked_at = current_time;
+        }
+        asset_risk.is_blocked = u8::from(is_blocked);
+        asset_risk.last_updated = current_time;
+        asset_risk.set_last_override(&record);
+        // Intervenção manual não passa pela quarentena nem herda a validade
+        // da decisão anterior
+        asset_risk.cooling_until = 0;
+        asset_risk.cooling_decisions_left = 0;
+        asset_risk.valid_until = 0;
+
+        emit!(RiskStatusOverridden {
+            asset_id: asset_risk.asset_id,
+            is_blocked,
+            reason_code,
+            authority: record.authority,
+            timestamp: current_time,
+        });
+        msg!(
+            "Override on {}: blocked={} reason={} by {}",
+            asset_id,
+            is_blocked,
+            reason_code,
+            record.authority
+        );
+        Ok(())
+    }
+
+    /// Aposenta a conta v1 do ativo (layout sem timestamp/nonce) no mesmo PDA.
+    /// O keeper envia na mesma transação a decisão v2 re-assinada, que recria a
+    /// conta via init_if_needed: consumidores nunca veem o ativo sem estado.
+    pub fn retire_v1_asset(ctx: Context<RetireV1Asset>, asset_id: String) -> Result<()> {
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let info = ctx.accounts.v1_asset_risk.to_account_info();
+        let legacy = AssetRiskStatusV1::try_from_account(&info, ctx.program_id)?;
+        require!(legacy.asset_id == asset_id_bytes, ErrorCode::NotV1Account);
+
+        anchor_lang::common::close(info, ctx.accounts.authority.to_account_info())?;
+
+        emit!(V1AssetRetired {
+            asset_id: legacy.asset_id,
+            risk_score: legacy.risk_score,
+            is_blocked: legacy.is_blocked,
+            confidence_ratio: legacy.confidence_ratio,
+            publisher_count: legacy.publisher_count,
+            decision_hash: legacy.decision_hash,
+            last_updated: legacy.last_updated,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Retired v1 account for {}", asset_id);
+        Ok(())
+    }
+
+    /// Fecha a conta de um ativo já deslistado e devolve o rent a `recipient`.
+    /// Histórico, política e mint do ativo têm seus próprios fechamentos.
+    pub fn close_asset_risk(ctx: Context<CloseAssetRisk>, asset_id: String) -> Result<()> {
+        let asset_risk = ctx.accounts.asset_risk_status.load()?;
+        require!(asset_risk.lifecycle() == AssetLifecycle::Delisted, ErrorCode::AssetNotDelisted);
+
+        emit!(AssetRiskClosed {
+            asset_id: asset_risk.asset_id,
+            recipient: ctx.accounts.recipient.key(),
+            lamports: ctx.accounts.asset_risk_status.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Closed asset risk account for {}", asset_id);
+        Ok(())
+    }
+
+    pub fn reset_circuit_breaker(ctx: Context<ResetCircuitBreaker>, asset_id: String) -> Result<()> {
+        let mut asset_risk = ctx.accounts.asset_risk_status.load_mut()?;
+        asset_risk.breaker_until = 0;
+
+        emit!(CircuitBreakerReset {
+            asset_id: asset_risk.asset_id,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Circuit breaker reset for {}", asset_id);
+        Ok(())
+    }
+
+    /// Cria um shard do replay store. O shard 0 nasce em initialize_config e só
+    /// passa por aqui depois de fechado por close_used_decisions.
+    pub fn initialize_replay_shard(ctx: Context<InitializeReplayShard>, shard: u8) -> Result<()> {
+        require!(shard < REPLAY_SHARD_COUNT, ErrorCode::InvalidReplayShard);
+
+        let used_decisions = &mut ctx.accounts.used_decisions;
+        used_decisions.bump = ctx.bumps.used_decisions;
+        used_decisions.shard = shard;
+        used_decisions.max_size = MAX_DECISIONS_PER_SHARD;
+        used_decisions.generation = ctx.accounts.config.generation;
+        // Decisões assinadas antes da criação podem estar no shard anterior
+        used_decisions.reset_at = Clock::get()?.unix_timestamp;
+
+        emit!(ReplayShardInitialized { shard });
+        msg!("Replay store shard {} initialized", shard);
+        Ok(())
+    }
+
+    /// Cresce ou encolhe um shard do replay store sem recriá-lo. Cada chamada
+    /// cresce no máximo 10 KB (256 registros), limite do runtime para realloc;
+    /// ao encolher, o aluguel excedente volta ao `payer`.
+    pub fn resize_replay_shard(
+        ctx: Context<ResizeReplayShard>,
+        shard: u8,
+        max_size: u16,
+    ) -> Result<()> {
+        let used_decisions = &mut ctx.accounts.used_decisions;
+        require!(
+            max_size > 0
+                && max_size <= MAX_REPLAY_SHARD_SIZE
+                && max_size as usize >= used_decisions.decisions.len(),
+            ErrorCode::InvalidReplayShardSize
+        );
+        let old_max_size = used_decisions.max_size;
+        used_decisions.max_size = max_size;
+
+        emit!(ReplayShardResized { shard, old_max_size, max_size });
+        msg!("Replay store shard {} resized from {} to {}", shard, old_max_size, max_size);
+        Ok(())
+    }
+
+    /// Cria o bloom filter do ReplayMode::Bloom. `false_positive_bps` define
+    /// quantos bits cada hash marca e quantas decisões cabem por janela;
+    /// `window_secs` precisa cobrir a janela de frescor inteira.
+    pub fn initialize_replay_bloom(
+        ctx: Context<InitializeReplayBloom>,
+        false_positive_bps: u16,
+        window_secs: i64,
+    ) -> Result<()> {
+        let config = &ctx.accounts.config;
+        require!(
+            window_secs > 0
+                && window_secs >= config.max_past_secs.saturating_add(config.max_future_secs),
+            ErrorCode::ReplayBloomWindowTooShort
+        );
+        let params = BloomParams::for_rate(ReplayBloom::NUM_BITS, false_positive_bps)
+            .ok_or(ErrorCode::InvalidBloomParams)?;
+
+        let mut bloom = ctx.accounts.replay_bloom.load_init()?;
+        bloom.bump = ctx.bumps.replay_bloom;
+        bloom.num_hashes = params.num_hashes;
+        bloom.capacity = params.capacity;
+        bloom.window_secs = window_secs;
+        bloom.window = Clock::get()?.unix_timestamp.div_euclid(window_secs);
+
+        emit!(ReplayBloomInitialized {
+            false_positive_bps,
+            num_hashes: params.num_hashes,
+            capacity: params.capacity,
+            window_secs,
+        });
+        msg!(
+            "Replay bloom initialized: {} hashes, {} decisions per {}s window",
+            params.num_hashes, params.capacity, window_secs
+        );
+        Ok(())
+    }
+
+    /// Fecha um shard cheio, corrompido ou de geração antiga; recrie com
+    /// initialize_replay_shard. Não desserializa a conta, só confere o dono.
+    pub fn close_used_decisions(ctx: Context<CloseUsedDecisions>, shard: u8) -> Result<()> {
+        let info = ctx.accounts.used_decisions.to_account_info();
+        require_keys_eq!(*info.owner, *ctx.program_id, ErrorCode::InvalidReplayShard);
+        require!(
+            info.try_borrow_data()?.starts_with(UsedDecisions::DISCRIMINATOR),
+            ErrorCode::InvalidReplayShard
+        );
+        anchor_lang::common::close(info, ctx.accounts.authority.to_account_info())?;
+
+        emit!(ReplayShardClosed { shard, timestamp: Clock::get()?.unix_timestamp });
+        msg!("Replay store shard {} closed", shard);
+        Ok(())
+    }
+
+    /// Cria ou ajusta o cofre que paga a recompensa do crank de limpeza.
+    /// O cofre é abastecido por transferências comuns de SOL para o PDA.
+    pub fn configure_fee_vault(ctx: Context<ConfigureFeeVault>, prune_bounty: u64) -> Result<()> {
+        let vault = &mut ctx.accounts.fee_vault;
+        vault.bump = ctx.bumps.fee_vault;
+        vault.prune_bounty = prune_bounty;
+
+        emit!(FeeVaultConfigured {
+            prune_bounty,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Prune bounty set to {} lamports", prune_bounty);
+        Ok(())
+    }
+
+    /// Crank permissionless: remove registros fora da retenção do shard e paga
+    /// `prune_bounty` ao chamador, limitado ao saldo acima do rent do cofre
+    pub fn prune_used_decisions(ctx: Context<PruneUsedDecisions>, shard: u8) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let replay_policy = ctx.accounts.config.replay_policy();
+        let used_decisions = &mut ctx.accounts.used_decisions;
+        let before = used_decisions.decisions.len();
+        used_decisions.prune(current_time.saturating_sub(replay_policy.retention_secs));
+        let removed = before - used_decisions.decisions.len();
+        // Sem registros vencidos não há recompensa, senão o cofre seria drenado
+        require!(removed > 0, ErrorCode::NothingToPrune);
+
+        let vault = ctx.accounts.fee_vault.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(vault.data_len());
+        let bounty = ctx
+            .accounts
+            .fee_vault
+            .prune_bounty
+            .min(vault.lamports().saturating_sub(rent_floor));
+        if bounty > 0 {
+            **vault.try_borrow_mut_lamports()? -= bounty;
+            **ctx.accounts.caller.to_account_info().try_borrow_mut_lamports()? += bounty;
+            let fee_vault = &mut ctx.accounts.fee_vault;
+            fee_vault.total_paid = fee_vault.total_paid.saturating_add(bounty);
+        }
+
+        emit!(UsedDecisionsPruned {
+            shard,
+            removed: removed as u16,
+            remaining: ctx.accounts.used_decisions.decisions.len() as u16,
+            bounty,
+            caller: ctx.accounts.caller.key(),
+            timestamp: current_time,
+        });
+        msg!("Pruned {} records from shard {} (bounty {} lamports)", removed, shard, bounty);
+        Ok(())
+    }
+
+    /// Cria o Treasury que recebe a taxa por atualização (Config.update_fee)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        ctx.accounts.treasury.bump = ctx.bumps.treasury;
+        msg!("Treasury initialized");
+        Ok(())
+    }
+
+    /// Saca do Treasury para a authority: lamports acima do rent ou, com as
+    /// contas SPL, tokens da conta do Treasury para `recipient_token`
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let treasury = accounts.treasury.to_account_info();
+        let (mint, recipient) = match (
+            accounts.treasury_token.as_ref(),
+            accounts.recipient_token.as_ref(),
+            accounts.mint.as_ref(),
+            accounts.token_program.as_ref(),
+        ) {
+            (None, None, None, None) => {
+                let rent_floor = Rent::get()?.minimum_balance(treasury.data_len());
+                require!(
+                    amount <= treasury.lamports().saturating_sub(rent_floor),
+                    ErrorCode::TreasuryInsufficientFunds
+                );
+                **treasury.try_borrow_mut_lamports()? -= amount;
+                **accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
+                (None, accounts.authority.key())
+            }
+            (Some(from), Some(to), Some(mint), Some(token_program)) => {
+                require!(amount <= from.amount, ErrorCode::TreasuryInsufficientFunds);
+                let bump = [accounts.treasury.bump];
+                let signer_seeds: &[&[&[u8]]] = &[&[SEED_NAMESPACE, TREASURY_SEED, &bump]];
+                let cpi_accounts = TransferChecked {
+                    from: from.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: treasury,
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                transfer_checked(cpi_ctx, amount, mint.decimals)?;
+                (Some(mint.key()), to.key())
+            }
+            _ => return err!(ErrorCode::FeeAccountsMissing),
+        };
+
+        emit!(TreasuryWithdrawn {
+            mint,
+            amount,
+            recipient,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Withdrew {} from treasury", amount);
+        Ok(())
+    }
+
+    /// Cria o fundo de seguro. `fee_share_bps` é a fração das taxas em
+    /// lamports do Treasury repassada por sweep_fees_to_insurance; conta só
+    /// o que for cobrado depois da criação
+    pub fn initialize_insurance_fund(
+        ctx: Context<InitializeInsuranceFund>,
+        fee_share_bps: u16,
+    ) -> Result<()> {
+        require!(fee_share_bps <= 10_000, ErrorCode::InvalidInsuranceFeeShare);
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.bump = ctx.bumps.insurance_fund;
+        fund.fee_share_bps = fee_share_bps;
+        fund.fees_swept_watermark = ctx.accounts.treasury.lamports_collected;
+
+        emit!(InsuranceFeeShareUpdated {
+            fee_share_bps,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Insurance fund initialized ({} bps of fees)", fee_share_bps);
+        Ok(())
+    }
+
+    /// Muda a fração das taxas; vale para o que ainda não foi varrido
+    pub fn set_insurance_fee_share(
+        ctx: Context<SetInsuranceFeeShare>,
+        fee_share_bps: u16,
+    ) -> Result<()> {
+        require!(fee_share_bps <= 10_000, ErrorCode::InvalidInsuranceFeeShare);
+        ctx.accounts.insurance_fund.fee_share_bps = fee_share_bps;
+
+        emit!(InsuranceFeeShareUpdated {
+            fee_share_bps,
+            updated_by: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Insurance fee share set to {} bps", fee_share_bps);
+        Ok(())
+    }
+
+    /// Depósito voluntário de lamports no fundo (permissionless)
+    pub fn contribute_insurance(ctx: Context<ContributeInsurance>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidInsuranceAmount);
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.contributor.to_account_info(),
+            to: ctx.accounts.insurance_fund.to_account_info(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+        let fund = &mut ctx.accounts.insurance_fund;
+        fund.total_contributed = fund.total_contributed.saturating_add(amount);
+
+        emit!(InsuranceContributed {
+            contributor: ctx.accounts.contributor.key(),
+            amount,
+            from_fees: false,
+            balance: fund.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Contributed {} lamports to insurance", amount);
+        Ok(())
+    }
+
+    /// Repassa ao fundo `fee_share_bps` das taxas em lamports cobradas desde o
+    /// último sweep (permissionless). Limitado ao saldo do Treasury acima do
+    /// rent: o que a authority já sacou não é reposto
+    pub fn sweep_fees_to_insurance(ctx: Context<SweepFeesToInsurance>) -> Result<()> {
+        let collected = ctx.accounts.treasury.lamports_collected;
+        let fund = &mut ctx.accounts.insurance_fund;
+        let pending = collected.saturating_sub(fund.fees_swept_watermark);
+        let share = (pending as u128 * fund.fee_share_bps as u128 / 10_000) as u64;
+
+        let treasury = ctx.accounts.treasury.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(treasury.data_len());
+        let amount = share.min(treasury.lamports().saturating_sub(rent_floor));
+        require!(amount > 0, ErrorCode::NothingToSweep);
+
+        **treasury.try_borrow_mut_lamports()? -= amount;
+        **fund.to_account_info().try_borrow_mut_lamports()? += amount;
+        fund.fees_swept_watermark = collected;
+        fund.total_from_fees = fund.total_from_fees.saturating_add(amount);
+
+        emit!(InsuranceContributed {
+            contributor: ctx.accounts.treasury.key(),
+            amount,
+            from_fees: true,
+            balance: fund.to_account_info().lamports(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Swept {} lamports of fees to insurance", amount);
+        Ok(())
+    }
+
+    /// Indeniza um integrador por perda em classificação errada comprovada do
+    /// CATE. `decision_hash` identifica a decisão contestada e `evidence_hash`
+    /// o dossiê off-chain que a authority analisou
+    pub fn pay_insurance_claim(
+        ctx: Context<PayInsuranceClaim>,
+        asset_id: String,
+        decision_hash: [u8; 32],
+        evidence_hash: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidInsuranceAmount);
+        let asset_id = asset_id_to_bytes(&asset_id)?;
+        let fund = &mut ctx.accounts.insurance_fund;
+        let info = fund.to_account_info();
+        let rent_floor = Rent::get()?.minimum_balance(info.data_len());
+        require!(
+            amount <= info.lamports().saturating_sub(rent_floor),
+            ErrorCode::InsuranceInsufficientFunds
+        );
+        **info.try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.recipient.try_borrow_mut_lamports()? += amount;
+
+        let clock = Clock::get()?;
+        fund.total_paid_out = fund.total_paid_out.saturating_add(amount);
+        fund.payout_count = fund.payout_count.saturating_add(1);
+        fund.last_payout_at = clock.unix_timestamp;
+
+        emit!(InsuranceClaimPaid {
+            recipient: ctx.accounts.recipient.key(),
+            asset_id,
+            decision_hash,
+            evidence_hash,
+            amount,
+            approved_by: ctx.accounts.authority.key(),
+            payout_count: fund.payout_count,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!("Paid insurance claim of {} lamports", amount);
+        Ok(())
+    }
+
+    /// Registra uma chave de sessão: o signer confiável `root_signer` assina
+    /// session_hash numa instrução Ed25519/Secp256k1 anterior, autorizando
+    /// `session_key` a assinar decisões em seu nome até `expires_at` e só para
+    /// `asset_ids` (vazio = todos). A chave raiz fica fora da infraestrutura
+    /// quente; qualquer um pode pagar o registro
+    pub fn register_session_key(
+        ctx: Context<RegisterSessionKey>,
+        session_key: Pubkey,
+        root_signer: Pubkey,
+        expires_at: i64,
+        asset_ids: Vec<[u8; 16]>,
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let config = &ctx.accounts.config;
+        require!(config.is_trusted_signer(&root_signer, current_time), ErrorCode::InvalidSigner);
+        // Uma chave do conjunto já assina por si
+        require!(
+            !config.is_trusted_signer(&session_key, current_time),
+            ErrorCode::InvalidSessionKey
+        );
+        require!(
+            expires_at > current_time
+                && expires_at <= current_time.saturating_add(MAX_SESSION_DURATION_SECS),
+            ErrorCode::InvalidSessionExpiry
+        );
+        require!(
+            asset_ids.len() <= MAX_SESSION_ASSETS && asset_ids.iter().all(|id| id[0] != 0),
+            ErrorCode::InvalidSessionAssets
+        );
+
+        let hash = message::session_hash(
+            ctx.program_id,
+            &config.genesis_hash,
+            &session_key,
+            expires_at,
+            &asset_ids,
+        );
+        verify_signer(
+            config,
+            &ctx.accounts.instructions_sysvar,
+            &root_signer.to_bytes(),
+            &hash,
+            &signature,
+        )?;
+
+        let session = &mut ctx.accounts.session;
+        session.bump = ctx.bumps.session;
+        session.session_key = session_key;
+        session.root_signer = root_signer;
+        session.expires_at = expires_at;
+        session.asset_ids = asset_ids.clone();
+        session.revoked = false;
+        session.payer = ctx.accounts.payer.key();
+        session.registered_at = current_time;
+
+        emit!(SessionKeyRegistered {
+            session_key,
+            root_signer,
+            expires_at,
+            asset_ids,
+            timestamp: current_time,
+        });
+        msg!("Session key {} delegated by {} until {}", session_key, root_signer, expires_at);
+        Ok(())
+    }
+
+    /// Revoga uma chave de sessão antes do vencimento. A conta fica até
+    /// `expires_at` para que a mesma delegação não seja registrada de novo
+    pub fn revoke_session_key(ctx: Context<RevokeSessionKey>) -> Result<()> {
+        let session = &mut ctx.accounts.session;
+        session.revoked = true;
+
+        emit!(SessionKeyRevoked {
+            session_key: session.session_key,
+            root_signer: session.root_signer,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Session key {} revoked", session.session_key);
+        Ok(())
+    }
+
+    /// Fecha uma sessão vencida e devolve o aluguel a quem a registrou.
+    /// Permissionless; a delegação vencida não pode mais ser registrada
+    pub fn close_session_key(ctx: Context<CloseSessionKey>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= ctx.accounts.session.expires_at,
+            ErrorCode::SessionKeyActive
+        );
+        msg!("Session key {} closed", ctx.accounts.session.session_key);
+        Ok(())
+    }
+
+    /// remaining_accounts: SignerUsage de cada signer, exigidos quando há
+    /// tetos de uso configurados e a decisão reduz risco
+    pub fn update_risk_status<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateRiskStatus<'info>>,
+        asset_id: String,
+        risk_score: u8,
+        is_blocked: bool,
+        reduce_only: bool,
+        risk_flags: u8,
+        sub_scores: [u8; RISK_CATEGORY_COUNT],
+        confidence_ratio: u64,
+        publisher_count: u8,
+        publisher_ids: [PublisherId; MAX_PUBLISHER_IDS],
+        timestamp: i64, // NOVO: Previne replay attacks
+        nonce: u64,
+        sequence: u64,
+        valid_until: i64,
+        decision_hash: [u8; 32],
+        signatures: Vec<[u8; 64]>,
+        signer_pubkeys: Vec<[u8; 32]>,
+        secondary: Option<SecondaryAssessment>,
+    ) -> Result<()> {
+        let payload = DecisionPayload {
+            asset_id: asset_id_to_bytes(&asset_id)?,
+            risk_score,
+            is_blocked,
+            reduce_only,
+            risk_flags,
+            sub_scores,
+            confidence_ratio,
+            publisher_count,
+            publisher_ids,
+            timestamp,
+            nonce,
+            sequence,
+            valid_until,
+        };
+        let decision = SignedDecision {
+            payload: &payload,
+            decision_hash,
+            signatures: &signatures,
+            signer_pubkeys: &signer_pubkeys,
+            secondary: secondary.as_ref(),
+        };
+        update_single(ctx, &asset_id, &decision, false)
+    }
+
+    /// Variante de update_risk_status que exige o feed Switchboard On-Demand do
+    /// ativo (AssetPolicy.switchboard_check): confere frescor e valor on-chain e
+    /// grava a confiança do oráculo ao lado da declarada pelo engine
+    pub fn update_risk_status_switchboard<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateRiskStatus<'info>>,
+        asset_id: String,
+        risk_score: u8,
+        is_blocked: bool,
+        reduce_only: bool,
+        risk_flags: u8,
+        sub_scores: [u8; RISK_CATEGORY_COUNT],
+        confidence_ratio: u64,
+        publisher_count: u8,
+        publisher_ids: [PublisherId; MAX_PUBLISHER_IDS],
+        timestamp: i64,
+        nonce: u64,
+        sequence: u64,
+        valid_until: i64,
+        decision_hash: [u8; 32],
+        signatures: Vec<[u8; 64]>,
+        signer_pubkeys: Vec<[u8; 32]>,
+        secondary: Option<SecondaryAssessment>,
+    ) -> Result<()> {
+        let payload = DecisionPayload {
+            asset_id: asset_id_to_bytes(&asset_id)?,
+            risk_score,
+            is_blocked,
+            reduce_only,
+            risk_flags,
+            sub_scores,
+            confidence_ratio,
+            publisher_count,
+            publisher_ids,
+            timestamp,
+            nonce,
+            sequence,
+            valid_until,
+        };
+        let decision = SignedDecision {
+            payload: &payload,
+            decision_hash,
+            signatures: &signatures,
+            signer_pubkeys: &signer_pubkeys,
+            secondary: secondary.as_ref(),
+        };
+        update_single(ctx, &asset_id, &decision, true)
+    }
+
+    /// Várias decisões em uma instrução, verificadas contra uma única instrução
+    /// Ed25519 com múltiplas assinaturas.
+    /// remaining_accounts: [AssetRiskStatus de cada decisão, na ordem] ++ [shards do replay store usados]
+    /// ++ [AssetMeta e AssetRiskHistory de cada ativo] ++ [PortfolioRisk, exigido com
+    /// portfolio_breaker] ++ [PDA da AssetPolicy de cada ativo, mesmo sem política] ++
+    /// [SignerUsage dos signers, PendingDecision já inicializados, SessionKey das chaves de
+    /// sessão que assinaram e PriceUpdateV2 do Pyth dos ativos com pyth_check, opcionais, em
+    /// qualquer ordem]
+    pub fn update_risk_status_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateRiskStatusBatch<'info>>,
+        decisions: Vec<BatchDecision>,
+    ) -> Result<()> {
+        require!(!decisions.is_empty(), ErrorCode::EmptyBatch);
+        require!(decisions.len() <= MAX_BATCH_SIZE, ErrorCode::BatchTooLarge);
+        require!(
+            ctx.remaining_accounts.len() > decisions.len(),
+            ErrorCode::InvalidAssetAccount
+        );
+        // Um ativo por batch: a segunda decisão veria o estado gravado pela primeira
+        for (i, decision) in decisions.iter().enumerate() {
+            require!(
+                !decisions[..i].iter().any(|d| d.payload.asset_id == decision.payload.asset_id),
+                ErrorCode::DuplicateBatchAsset
+            );
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        let (asset_infos, extra_infos) = ctx.remaining_accounts.split_at(decisions.len());
+
+        // Carrega os shards uma vez: decisões no mesmo shard compartilham a conta.
+        // Políticas são lidas por endereço, na decisão de cada ativo
+        let mut shards: Vec<Account<'info, UsedDecisions>> = Vec::with_capacity(extra_infos.len());
+        let mut signer_usage: Vec<Account<'info, SignerUsage>> = Vec::new();
+        let mut histories: Vec<AccountLoader<'info, AssetRiskHistory>> = Vec::new();
+        let mut pending: Vec<Account<'info, PendingDecision>> = Vec::new();
+        let mut oracle_prices: Vec<pyth::PythPrice> = Vec::new();
+        let mut metas: Vec<Account<'info, AssetMeta>> = Vec::new();
+        let mut sessions: Vec<Account<'info, SessionKey>> = Vec::new();
+        let mut portfolio: Option<Account<'info, PortfolioRisk>> = None;
+        for info in extra_infos.iter() {
+            // PDA de AssetPolicy ainda não criado
+            if info.data_is_empty() {
+                continue;
+            }
+            if *info.owner == pyth::PYTH_RECEIVER_ID {
+                oracle_prices.push(pyth::PythPrice::try_from_account(info)?);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(PendingDecision::DISCRIMINATOR) {
+                require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+                let parked: Account<'info, PendingDecision> = Account::try_from(info)?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[SEED_NAMESPACE, PENDING_DECISION_SEED, asset_id_seed(&parked.asset_id)],
+                    ctx.program_id,
+                );
+                require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+                pending.push(parked);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(AssetRiskHistory::DISCRIMINATOR) {
+                require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+                let history: AccountLoader<'info, AssetRiskHistory> =
+                    AccountLoader::try_from(info)?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[SEED_NAMESPACE, ASSET_HISTORY_SEED, asset_id_seed(&history.load()?.asset_id)],
+                    ctx.program_id,
+                );
+                require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+                histories.push(history);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(SignerUsage::DISCRIMINATOR) {
+                signer_usage.push(load_signer_usage(ctx.program_id, info)?);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(PortfolioRisk::DISCRIMINATOR) {
+                require!(info.is_writable && portfolio.is_none(), ErrorCode::InvalidAssetAccount);
+                let (expected, _) =
+                    Pubkey::find_program_address(&[SEED_NAMESPACE, PORTFOLIO_SEED], ctx.program_id);
+                require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+                portfolio = Some(Account::try_from(info)?);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(AssetMeta::DISCRIMINATOR) {
+                let meta: Account<'info, AssetMeta> = Account::try_from(info)?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[SEED_NAMESPACE, ASSET_META_SEED, asset_id_seed(&meta.asset_id)],
+                    ctx.program_id,
+                );
+                require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+                metas.push(meta);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(SessionKey::DISCRIMINATOR) {
+                let session: Account<'info, SessionKey> = Account::try_from(info)?;
+                let (expected, _) = Pubkey::find_program_address(
+                    &[SEED_NAMESPACE, SESSION_KEY_SEED, session.session_key.as_ref()],
+                    ctx.program_id,
+                );
+                require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+                sessions.push(session);
+                continue;
+            }
+            if info.try_borrow_data()?.starts_with(AssetPolicy::DISCRIMINATOR) {
+                continue;
+            }
+            require!(info.is_writable, ErrorCode::InvalidReplayShard);
+            let shard: Account<'info, UsedDecisions> = Account::try_from(info)?;
+            let (expected, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard.shard]],
+                ctx.program_id,
+            );
+            require_keys_eq!(expected, info.key(), ErrorCode::InvalidReplayShard);
+            shards.push(shard);
+        }
+        require!(
+            ctx.accounts.config.portfolio_breaker.is_none() || portfolio.is_some(),
+            ErrorCode::PortfolioAccountMissing
+        );
+
+        let mut replay_bloom =
+            ctx.accounts.replay_bloom.as_ref().map(|b| b.load_mut()).transpose()?;
+        // Ativos gravados e o last_updated anterior, para a gorjeta do keeper
+        let mut refreshed: Vec<([u8; 16], i64)> = Vec::new();
+        for (decision, info) in decisions.iter().zip(asset_infos.iter()) {
+            require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+            let loader: AccountLoader<'info, AssetRiskStatus> = AccountLoader::try_from(info)?;
+            let mut asset_risk = loader.load_mut()?;
+
+            // A conta deve ser o PDA do ativo assinado no payload
+            require!(
+                asset_risk.asset_id == decision.payload.asset_id,
+                ErrorCode::InvalidAssetAccount
+            );
+            let (expected, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&decision.payload.asset_id)],
+                ctx.program_id,
+            );
+            require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+            require!(
+                metas.iter().any(|m| m.asset_id == decision.payload.asset_id),
+                ErrorCode::AssetNotRegistered
+            );
+            // Histórico obrigatório: sem ele o circuit breaker não seria avaliado
+            let history = histories
+                .iter()
+                .find(|h| h.load().is_ok_and(|h| h.asset_id == decision.payload.asset_id))
+                .ok_or(ErrorCode::InvalidAssetAccount)?;
+
+            let decision_hash = message::decision_hash(
+                ctx.program_id,
+                &ctx.accounts.config.genesis_hash,
+                &decision.payload,
+            );
+            let shard_index = replay_shard(&decision_hash);
+            // Só o ReplayMode::HashList exige o shard da decisão
+            let used_decisions = shards
+                .iter_mut()
+                .find(|s| s.shard == shard_index)
+                .map(|s| &mut **s);
+            let (policy_address, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id_seed(&decision.payload.asset_id)],
+                ctx.program_id,
+            );
+            let policy_info = extra_infos
+                .iter()
+                .find(|i| i.key() == policy_address)
+                .ok_or(ErrorCode::InvalidAssetAccount)?;
+            let policy = read_asset_policy(ctx.program_id, policy_info)?;
+            let parked = pending
+                .iter_mut()
+                .find(|p| p.asset_id == decision.payload.asset_id);
+            // Como no fluxo simples, uma sessão por decisão; a delegação é
+            // conferida em verify_quorum
+            let session = sessions
+                .iter()
+                .find(|s| decision.signer_pubkeys.contains(&s.session_key.to_bytes()))
+                .map(|s| &**s);
+            check_oracle_confidence(
+                policy.as_ref(),
+                &oracle_prices,
+                &decision.payload,
+                current_time,
+            )?;
+
+            // Sem DecisionRejected: o erro reverte o batch inteiro, e o evento junto
+            let previous_update = asset_risk.last_updated;
+            let outcome = apply_decision(
+                ctx.program_id,
+                &ctx.accounts.config,
+                &ctx.accounts.instructions_sysvar,
+                used_decisions,
+                replay_bloom.as_deref_mut(),
+                &mut asset_risk,
+                policy.as_ref(),
+                &mut signer_usage,
+                parked.map(|p| &mut **p),
+                session,
+                &SignedDecision {
+                    payload: &decision.payload,
+                    decision_hash,
+                    signatures: &decision.signatures,
+                    signer_pubkeys: &decision.signer_pubkeys,
+                    secondary: decision.secondary.as_ref(),
+                },
+                current_time,
+            )?;
+            if let Some(stats) = ctx.accounts.stats.as_mut() {
+                stats.record_outcome(&decision.payload.asset_id, outcome);
+            }
+            if outcome == DecisionOutcome::Parked {
+                continue;
+            }
+            refreshed.push((decision.payload.asset_id, previous_update));
+            let mut history = history.load_mut()?;
+            history.push(&asset_risk);
+            check_circuit_breaker(&ctx.accounts.config, &history, &mut asset_risk, current_time);
+            drop(history);
+
+            if let Some(deadman) = ctx.accounts.deadman.as_mut() {
+                deadman.rearm_if_sentinel(&decision.payload.asset_id, current_time);
+            }
+            if let Some(portfolio) = portfolio.as_mut() {
+                record_portfolio_score(
+                    &ctx.accounts.config,
+                    portfolio,
+                    &decision.payload.asset_id,
+                    asset_risk.risk_score,
+                    current_time,
+                );
+            }
+        }
+        drop(replay_bloom);
+
+        for shard in shards.iter() {
+            shard.exit(ctx.program_id)?;
+        }
+        for usage in signer_usage.iter() {
+            usage.exit(ctx.program_id)?;
+        }
+        for parked in pending.iter() {
+            parked.exit(ctx.program_id)?;
+        }
+        if let Some(portfolio) = portfolio.as_ref() {
+            portfolio.exit(ctx.program_id)?;
+        }
+
+        let accounts = &mut ctx.accounts;
+        collect_update_fee(
+            &accounts.config,
+            accounts.treasury.as_mut(),
+            accounts.keeper.to_account_info(),
+            accounts.system_program.as_ref().map(|p| p.to_account_info()),
+            FeeTokenAccounts {
+                payer_token: accounts.payer_fee_token.as_ref(),
+                treasury_token: accounts.treasury_fee_token.as_ref(),
+                mint: accounts.fee_mint.as_ref(),
+                token_program: accounts.token_program.as_ref(),
+            },
+            // Decisões estacionadas não são cobradas no batch
+            refreshed.len() as u64,
+            current_time,
+        )?;
+        for (asset_id, previous_update) in refreshed.iter() {
+            pay_keeper_tip(
+                &accounts.config,
+                accounts.treasury.as_mut(),
+                accounts.keeper.to_account_info(),
+                asset_id,
+                *previous_update,
+                current_time,
+            )?;
+        }
+
+        msg!("Batch risk update applied: {} decisions", decisions.len());
+        Ok(())
+    }
+
+    /// Verifica quorum e janela sem gravar a decisão. Com `stats`, a falha é
+    /// registrada como rejeição e a instrução conclui: um erro reverteria o
+    /// registro. O motivo sai no evento DecisionRejected.
+    pub fn verify_decision(
+        ctx: Context<VerifyDecision>,
+        payload: DecisionPayload,
+        signatures: Vec<[u8; 64]>,
+        signer_pubkeys: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let timestamp = payload.timestamp;
+        let decision_hash =
+            message::decision_hash(ctx.program_id, &ctx.accounts.config.genesis_hash, &payload);
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let result = verify_quorum(
+            &ctx.accounts.config,
+            &ctx.accounts.instructions_sysvar,
+            &decision_hash,
+            &signatures,
+            &signer_pubkeys,
+            None,
+            &payload.asset_id,
+            current_time,
+        )
+        .and_then(|signers| {
+            check_signer_expiry(&ctx.accounts.config, &signers, timestamp, current_time)?;
+            // Mesma janela de frescor aplicada em update_risk_status
+            ctx.accounts
+                .config
+                .replay_policy()
+                .check_window(timestamp, current_time)
+                .map_err(replay_error)?;
+            Ok(signers.len() as u8)
+        });
+
+        let signer_count = match result {
+            Ok(signer_count) => signer_count,
+            Err(err) => {
+                emit_rejection(&payload.asset_id, &decision_hash, &err, current_time);
+                let Some(stats) = ctx.accounts.stats.as_mut() else {
+                    return Err(err);
+                };
+                stats.record_rejection(&payload.asset_id, &err, Clock::get()?.slot, current_time);
+                msg!("Decision verification: REJECTED ({})", err);
+                return Ok(());
+            }
+        };
+        if let Some(stats) = ctx.accounts.stats.as_mut() {
+            stats.verifications += 1;
+        }
+
+        emit!(DecisionVerified {
+            asset_id: payload.asset_id,
+            decision_hash,
+            decision_timestamp: timestamp,
+            signer_count,
+        });
+        msg!("Decision verification: VALID for timestamp {}", timestamp);
+        Ok(())
+    }
+
+    /// Sinal de vida do engine: um único signer confiável assina o timestamp,
+    /// sem quorum nem replay store. Só avança `last_heartbeat`.
+    pub fn heartbeat(
+        ctx: Context<Heartbeat>,
+        timestamp: i64,
+        signer_pubkey: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let config = &mut ctx.accounts.config;
+        require!(
+            config.is_trusted_signer(&Pubkey::new_from_array(signer_pubkey), current_time),
+            ErrorCode::InvalidSigner
+        );
+        config
+            .replay_policy()
+            .check_window(timestamp, current_time)
+            .map_err(replay_error)?;
+        require!(timestamp > config.last_heartbeat, ErrorCode::StaleHeartbeat);
+
+        let hash = message::heartbeat_hash(ctx.program_id, &config.genesis_hash, timestamp);
+        verify_signer(
+            config,
+            &ctx.accounts.instructions_sysvar,
+            &signer_pubkey,
+            &hash,
+            &signature,
+        )?;
+        config.last_heartbeat = timestamp;
+
+        emit!(EngineHeartbeat { signer: Pubkey::new_from_array(signer_pubkey), timestamp });
+        Ok(())
+    }
+
+    /// Saúde do engine pelo último heartbeat, via return data
+    pub fn get_engine_health(ctx: Context<GetEngineHealth>) -> Result<EngineHealth> {
+        Ok(EngineHealth::new(&ctx.accounts.config, Clock::get()?.unix_timestamp))
+    }
+
+    /// Conta inteira via return data; para CPI prefira view_risk_status
+    pub fn get_risk_status(ctx: Context<GetRiskStatus>, _asset_id: String) -> Result<AssetRiskStatus> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        Ok(*ctx.accounts.asset_risk_status.load()?)
+    }
+
+    /// Leitura compacta para CPI: o RiskView vai em return data e o chamador
+    /// lê com `get_return_data` sem desserializar a conta inteira
+    pub fn view_risk_status(ctx: Context<ViewRiskStatus>, _asset_id: String) -> Result<RiskView> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        Ok(RiskView::new(&ctx.accounts.asset_risk_status.load()?, clock.unix_timestamp))
+    }
+
+    /// Leitura com frescor aplicado: dados mais antigos que a janela (do ativo
+    /// ou global), breaker armado ou programa pausado são reportados como bloqueados.
+    pub fn get_effective_status(
+        ctx: Context<GetEffectiveStatus>,
+        _asset_id: String,
+    ) -> Result<EffectiveStatus> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        Ok(gating::effective_status(
+            &ctx.accounts.asset_risk_status.load()?,
+            &ctx.accounts.config,
+            ctx.accounts.asset_policy.as_deref(),
+            clock.unix_timestamp,
+        ))
+    }
+
+    /// Gate barato para CPI: falha com código específico se o ativo estiver
+    /// bloqueado, desatualizado ou abaixo do piso de confiança.
+    /// Ativos em modo advisory nunca falham: retornam o código como aviso.
+    /// O ciclo de vida é administrativo e falha mesmo em modo advisory.
+    /// `reduce_only = true` (engine, authority ou `Delisting`) permite apenas
+    /// operações que reduzem posição; cabe ao integrador aplicar.
+    pub fn assert_trading_allowed(
+        ctx: Context<AssertTradingAllowed>,
+        _asset_id: String,
+        max_staleness: Option<i64>,
+        min_confidence_ratio: Option<u64>,
+    ) -> Result<TradingGate> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        check_trading_schedule(
+            &ctx.accounts.config,
+            ctx.accounts.global_schedule.as_deref(),
+            ctx.accounts.asset_schedule.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        let gate = trading_gate(
+            &ctx.accounts.config,
+            &ctx.accounts.asset_risk_status.load()?,
+            ctx.accounts.asset_policy.as_deref(),
+            ctx.accounts.portfolio.as_deref(),
+            max_staleness,
+            min_confidence_ratio,
+            clock.unix_timestamp,
+        )?;
+        record_integration_usage(
+            ctx.accounts.integration_stats.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &gate,
+            &clock,
+        )?;
+        Ok(gate)
+    }
+
+    /// Cria o contador de uso do gate para um programa consumidor em uma epoch.
+    /// Quem paga o rent é o integrador: a coleta é opt-in por conta.
+    pub fn initialize_integration_stats(
+        ctx: Context<InitializeIntegrationStats>,
+        consumer: Pubkey,
+        epoch: u64,
+    ) -> Result<()> {
+        require!(epoch >= Clock::get()?.epoch, ErrorCode::InvalidIntegrationStatsAccount);
+        let stats = &mut ctx.accounts.integration_stats;
+        stats.bump = ctx.bumps.integration_stats;
+        stats.consumer = consumer;
+        stats.epoch = epoch;
+        msg!("Integration stats initialized for {} at epoch {}", consumer, epoch);
+        Ok(())
+    }
+
+    /// Inclui ou retira um programa da allowlist de consumidores do gate.
+    /// A assinatura paga, se houver, continua valendo.
+    pub fn set_consumer_allowlisted(
+        ctx: Context<SetConsumerAllowlisted>,
+        program_id: Pubkey,
+        allowlisted: bool,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let consumer = &mut ctx.accounts.consumer;
+        if consumer.registered_at == 0 {
+            consumer.bump = ctx.bumps.consumer;
+            consumer.program_id = program_id;
+            consumer.registered_at = current_time;
+        }
+        consumer.allowlisted = allowlisted;
+
+        emit!(ConsumerAllowlistUpdated { program_id, allowlisted, timestamp: current_time });
+        msg!("Consumer {} allowlisted: {}", program_id, allowlisted);
+        Ok(())
+    }
+
+    /// Permissionless: paga `epochs` epochs de acesso ao gate para `program_id`
+    /// ao Treasury, somadas ao fim da assinatura vigente
+    pub fn subscribe_consumer(
+        ctx: Context<SubscribeConsumer>,
+        program_id: Pubkey,
+        epochs: u64,
+    ) -> Result<()> {
+        let fee_per_epoch = ctx
+            .accounts
+            .config
+            .consumer_access
+            .as_ref()
+            .map_or(0, |access| access.fee_per_epoch);
+        require!(fee_per_epoch > 0, ErrorCode::ConsumerSubscriptionsDisabled);
+        require!(epochs > 0, ErrorCode::InvalidSubscriptionLength);
+        let amount = fee_per_epoch.checked_mul(epochs).ok_or(ErrorCode::InvalidSubscriptionLength)?;
+
+        let cpi_accounts = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.lamports_collected = treasury.lamports_collected.saturating_add(amount);
+
+        let clock = Clock::get()?;
+        let consumer = &mut ctx.accounts.consumer;
+        if consumer.registered_at == 0 {
+            consumer.bump = ctx.bumps.consumer;
+            consumer.program_id = program_id;
+            consumer.registered_at = clock.unix_timestamp;
+        }
+        consumer.paid_until_epoch = consumer
+            .paid_until_epoch
+            .max(clock.epoch)
+            .checked_add(epochs)
+            .ok_or(ErrorCode::InvalidSubscriptionLength)?;
+        consumer.total_paid = consumer.total_paid.saturating_add(amount);
+
+        emit!(ConsumerSubscribed {
+            program_id,
+            payer: ctx.accounts.payer.key(),
+            epochs,
+            amount,
+            paid_until_epoch: consumer.paid_until_epoch,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!(
+            "Consumer {} subscribed until epoch {} ({} lamports)",
+            program_id, consumer.paid_until_epoch, amount
+        );
+        Ok(())
+    }
+
+    /// Remove o consumidor; o rent volta à authority e o programa perde o acesso
+    pub fn close_consumer(ctx: Context<CloseConsumer>, program_id: Pubkey) -> Result<()> {
+        emit!(ConsumerClosed { program_id, timestamp: Clock::get()?.unix_timestamp });
+        msg!("Consumer {} closed", program_id);
+        Ok(())
+    }
+
+    /// Cria os contadores globais de decisões; opt-in, quem paga é o operador
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        ctx.accounts.stats.bump = ctx.bumps.stats;
+        msg!("Stats account initialized");
+        Ok(())
+    }
+
+    /// Associa o mint SPL a um asset_id, para integradores que consultam pelo token
+    pub fn register_asset_mint(
+        ctx: Context<RegisterAssetMint>,
+        asset_id: String,
+        mint: Pubkey,
+    ) -> Result<()> {
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let asset_mint = &mut ctx.accounts.asset_mint;
+        asset_mint.bump = ctx.bumps.asset_mint;
+        asset_mint.mint = mint;
+        asset_mint.asset_id = asset_id_bytes;
+
+        emit!(AssetMintRegistered {
+            mint,
+            asset_id: asset_id_bytes,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Mint {} registered for asset {}", mint, asset_id);
+        Ok(())
+    }
+
+    /// Remove a associação (mint errado ou ativo migrado); o rent volta à authority
+    pub fn unregister_asset_mint(ctx: Context<UnregisterAssetMint>, mint: Pubkey) -> Result<()> {
+        emit!(AssetMintUnregistered {
+            mint,
+            asset_id: ctx.accounts.asset_mint.asset_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    /// Cadastra o ativo: update_risk_status só aceita decisões de ativos
+    /// com AssetMeta
+    pub fn register_asset(
+        ctx: Context<RegisterAsset>,
+        asset_id: String,
+        mint: Pubkey,
+        pyth_feed: [u8; 32],
+        category: AssetCategory,
+        decimals: u8,
+    ) -> Result<()> {
+        let asset_id_bytes = asset_id_to_bytes(&asset_id)?;
+        let current_time = Clock::get()?.unix_timestamp;
+        let meta = &mut ctx.accounts.asset_meta;
+        meta.bump = ctx.bumps.asset_meta;
+        meta.asset_id = asset_id_bytes;
+        meta.mint = mint;
+        meta.pyth_feed = pyth_feed;
+        meta.category = category;
+        meta.decimals = decimals;
+        meta.registered_at = current_time;
+
+        emit!(AssetRegistered {
+            asset_id: asset_id_bytes,
+            mint,
+            pyth_feed,
+            category,
+            decimals,
+            timestamp: current_time,
+        });
+        msg!("Asset {} registered ({:?}, mint {})", asset_id, category, mint);
+        Ok(())
+    }
+
+    /// Remove o cadastro; novas decisões do ativo passam a ser recusadas
+    pub fn unregister_asset(ctx: Context<UnregisterAsset>, asset_id: String) -> Result<()> {
+        emit!(AssetUnregistered {
+            asset_id: ctx.accounts.asset_meta.asset_id,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!("Asset {} unregistered", asset_id);
+        Ok(())
+    }
+
+    /// `get_effective_status` endereçado pelo mint
+    pub fn get_effective_status_by_mint(
+        ctx: Context<GetEffectiveStatusByMint>,
+        _mint: Pubkey,
+    ) -> Result<EffectiveStatus> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        Ok(gating::effective_status(
+            &ctx.accounts.asset_risk_status.load()?,
+            &ctx.accounts.config,
+            ctx.accounts.asset_policy.as_deref(),
+            clock.unix_timestamp,
+        ))
+    }
+
+    /// `assert_trading_allowed` endereçado pelo mint; mesmos códigos de erro
+    pub fn assert_trading_allowed_by_mint(
+        ctx: Context<AssertTradingAllowedByMint>,
+        _mint: Pubkey,
+        max_staleness: Option<i64>,
+        min_confidence_ratio: Option<u64>,
+    ) -> Result<TradingGate> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        check_trading_schedule(
+            &ctx.accounts.config,
+            ctx.accounts.global_schedule.as_deref(),
+            ctx.accounts.asset_schedule.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        let gate = trading_gate(
+            &ctx.accounts.config,
+            &ctx.accounts.asset_risk_status.load()?,
+            ctx.accounts.asset_policy.as_deref(),
+            ctx.accounts.portfolio.as_deref(),
+            max_staleness,
+            min_confidence_ratio,
+            clock.unix_timestamp,
+        )?;
+        record_integration_usage(
+            ctx.accounts.integration_stats.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &gate,
+            &clock,
+        )?;
+        Ok(gate)
+    }
+
+    /// Gate do roteador para uma ordem concreta: janela de frescor do
+    /// ativo/global, piso de confiança e teto de notional por faixa de score
+    /// do Config, reduzido pela rampa de reabertura logo após um desbloqueio.
+    /// Em reduce-only só `Sell` passa. Ativos em modo advisory
+    /// não falham: a violação volta em `warning_code`. `score_source`
+    /// escolhe se a faixa sai do score bruto ou da média móvel.
+    pub fn pre_trade_check(
+        ctx: Context<GetEffectiveStatus>,
+        _asset_id: String,
+        side: TradeSide,
+        notional: u64,
+        score_source: ScoreSource,
+    ) -> Result<TradingGate> {
+        let clock = Clock::get()?;
+        authorize_consumer(
+            &ctx.accounts.config,
+            ctx.accounts.consumer.as_deref_mut(),
+            ctx.accounts.instructions_sysvar.as_ref(),
+            &clock,
+        )?;
+        let current_time = clock.unix_timestamp;
+        check_trading_schedule(
+            &ctx.accounts.config,
+            ctx.accounts.global_schedule.as_deref(),
+            ctx.accounts.asset_schedule.as_deref(),
+            current_time,
+        )?;
+        pre_trade_gate(
+            &ctx.accounts.config,
+            &ctx.accounts.asset_risk_status.load()?,
+            ctx.accounts.asset_policy.as_deref(),
+            ctx.accounts.portfolio.as_deref(),
+            side,
+            notional,
+            score_source,
+            current_time,
+        )
+    }
+
+    pub fn configure_deadman(
+        ctx: Context<ConfigureDeadman>,
+        sentinel_asset_id: String,
+        window_seconds: i64,
+        block_all: bool,
+        assets: Vec<String>,
+    ) -> Result<()> {
+        require!(window_seconds > 0, ErrorCode::InvalidDeadmanWindow);
+        require!(assets.len() <= MAX_DEADMAN_ASSETS, ErrorCode::TooManyDeadmanAssets);
+
+        let mut covered = Vec::with_capacity(assets.len());
+        for asset_id in assets.iter() {
+            covered.push(asset_id_to_bytes(asset_id)?);
+        }
+
+        let deadman = &mut ctx.accounts.deadman;
+        // Acionamento em curso só é encerrado por rearm_deadman, que libera os ativos
+        require!(!deadman.triggered, ErrorCode::DeadmanStillTriggered);
+        deadman.bump = ctx.bumps.deadman;
+        deadman.sentinel_asset_id = asset_id_to_bytes(&sentinel_asset_id)?;
+        deadman.window_seconds = window_seconds;
+        deadman.block_all = block_all;
+        deadman.assets = covered;
+        // Começa a contar a partir da configuração
+        deadman.last_sentinel_update = Clock::get()?.unix_timestamp;
+
+        emit!(DeadmanConfigured {
+            sentinel_asset_id: deadman.sentinel_asset_id,
+            window_seconds,
+            block_all,
+            asset_count: deadman.assets.len() as u8,
+        });
+        msg!(
+            "Dead-man switch configured: sentinel={}, window={}s, block_all={}, assets={}",
+            sentinel_asset_id, window_seconds, block_all, deadman.assets.len()
+        );
+        Ok(())
+    }
+
+    /// Permissionless: qualquer um pode acionar após a janela expirar.
+    /// Os AssetRiskStatus a bloquear são passados em remaining_accounts.
+    pub fn trigger_deadman<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TriggerDeadman<'info>>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let deadman = &mut ctx.accounts.deadman;
+        require!(
+            current_time.saturating_sub(deadman.last_sentinel_update) > deadman.window_seconds,
+            ErrorCode::DeadmanNotExpired
+        );
+
+        let mut blocked = 0u32;
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+            let loader: AccountLoader<'info, AssetRiskStatus> = AccountLoader::try_from(info)?;
+            let mut asset_risk = loader.load_mut()?;
+
+            // Garante que é o PDA canônico do ativo
+            let (expected, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&asset_risk.asset_id)],
+                ctx.program_id,
+            );
+            require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+
+            require!(
+                deadman.block_all || deadman.covers(&asset_risk.asset_id),
+                ErrorCode::DeadmanAssetNotCovered
+            );
+
+            asset_risk.is_blocked = 1;
+            asset_risk.deadman_blocked = 1;
+            asset_risk.last_updated = current_time;
+            blocked += 1;
+        }
+
+        deadman.triggered = true;
+        deadman.triggered_at = current_time;
+
+        emit!(DeadmanTriggered {
+            last_sentinel_update: deadman.last_sentinel_update,
+            blocked_assets: blocked,
+            timestamp: current_time,
+        });
+        msg!(
+            "Dead-man switch triggered: last sentinel update {}, blocked {} assets",
+            deadman.last_sentinel_update, blocked
+        );
+        Ok(())
+    }
+
+    /// Authority: encerra o acionamento do dead-man switch. Os AssetRiskStatus
+    /// bloqueados por ele vão em remaining_accounts e voltam a ser
+    /// desbloqueáveis pela próxima decisão assinada. Cada um precisa ter
+    /// recebido decisão depois do acionamento e dentro da janela do switch.
+    pub fn rearm_deadman<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RearmDeadman<'info>>,
+    ) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let deadman = &ctx.accounts.deadman;
+        let mut released = 0u32;
+        for info in ctx.remaining_accounts.iter() {
+            require!(info.is_writable, ErrorCode::InvalidAssetAccount);
+            let loader: AccountLoader<'info, AssetRiskStatus> = AccountLoader::try_from(info)?;
+            let mut asset_risk = loader.load_mut()?;
+
+            // Garante que é o PDA canônico do ativo
+            let (expected, _) = Pubkey::find_program_address(
+                &[SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&asset_risk.asset_id)],
+                ctx.program_id,
+            );
+            require_keys_eq!(expected, info.key(), ErrorCode::InvalidAssetAccount);
+
+            // trigger_deadman grava last_updated no acionamento: só uma decisão
+            // posterior e ainda dentro da janela o move adiante
+            require!(
+                asset_risk.last_updated > deadman.triggered_at
+                    && current_time.saturating_sub(asset_risk.last_updated)
+                        <= deadman.window_seconds,
+                ErrorCode::DeadmanNoFreshDecision
+            );
+            asset_risk.deadman_blocked = 0;
+            released += 1;
+        }
+
+        let deadman = &mut ctx.accounts.deadman;
+        deadman.triggered = false;
+        deadman.last_sentinel_update = current_time;
+
+        emit!(DeadmanRearmed {
+            released_assets: released,
+            authority: ctx.accounts.authority.key(),
+            timestamp: current_time,
+        });
+        msg!("Dead-man switch re-armed, released {} assets", released);
+        Ok(())
+    }
+
+    /// Permissionless: publica o último AssetRiskStatus como mensagem Wormhole
+    /// (cate_decision::Attestation) para os deployments EVM. O payer paga a
+    /// taxa do core bridge; a mensagem fica num PDA por sequence do emitter.
+    pub fn publish_risk_attestation(
+        ctx: Context<PublishRiskAttestation>,
+        asset_id: String,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let attestation = accounts.asset_risk_status.load()?.attestation();
+        require!(attestation.timestamp > 0, ErrorCode::NoDecisionToAttest);
+
+        let sequence = wormhole::next_sequence(&accounts.wormhole_sequence)?;
+        let sequence_bytes = sequence.to_le_bytes();
+        let (message, message_bump) = Pubkey::find_program_address(
+            &[SEED_NAMESPACE, WORMHOLE_MESSAGE_SEED, &sequence_bytes],
+            ctx.program_id,
+        );
+        require_keys_eq!(
+            accounts.wormhole_message.key(),
+            message,
+            ErrorCode::InvalidWormholeAccount
+        );
+
+        let fee = wormhole::message_fee(&accounts.wormhole_bridge)?;
+        if fee > 0 {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: accounts.payer.to_account_info(),
+                to: accounts.wormhole_fee_collector.to_account_info(),
+            };
+            anchor_lang::system_program::transfer(
+                CpiContext::new(accounts.system_program.to_account_info(), cpi_accounts),
+                fee,
+            )?;
+        }
+
+        let emitter_seeds: &[&[u8]] =
+            &[SEED_NAMESPACE, WORMHOLE_EMITTER_SEED, &[ctx.bumps.wormhole_emitter]];
+        let message_seeds: &[&[u8]] =
+            &[SEED_NAMESPACE, WORMHOLE_MESSAGE_SEED, &sequence_bytes, &[message_bump]];
+        wormhole::post_message(
+            &accounts.wormhole_program,
+            wormhole::PostMessageAccounts {
+                bridge: accounts.wormhole_bridge.to_account_info(),
+                message: accounts.wormhole_message.to_account_info(),
+                emitter: accounts.wormhole_emitter.to_account_info(),
+                sequence: accounts.wormhole_sequence.to_account_info(),
+                payer: accounts.payer.to_account_info(),
+                fee_collector: accounts.wormhole_fee_collector.to_account_info(),
+                clock: accounts.clock.to_account_info(),
+                rent: accounts.rent.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+            },
+            &attestation.to_bytes(),
+            &[emitter_seeds, message_seeds],
+        )?;
+
+        emit!(RiskAttestationPublished {
+            asset_id: attestation.asset_id,
+            decision_hash: attestation.decision_hash,
+            emitter: accounts.wormhole_emitter.key(),
+            sequence,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        msg!(
+            "Risk attestation for {} published: score={}, sequence={}",
+            asset_id, attestation.risk_score, sequence
+        );
+        Ok(())
+    }
+}
+
+fn propose_signers(
+    config: &mut Config,
+    signers: Vec<Pubkey>,
+    mut weights: Vec<u8>,
+    quorum: u8,
+    effective_at: i64,
+) -> Result<()> {
+    require!(!signers.is_empty(), ErrorCode::InvalidQuorum);
+    require!(signers.len() <= MAX_TRUSTED_SIGNERS, ErrorCode::TooManySigners);
+    if weights.is_empty() {
+        weights = vec![1; signers.len()];
+    }
+    require!(
+        weights.len() == signers.len() && weights.iter().all(|w| *w >= 1),
+        ErrorCode::InvalidSignerWeight
+    );
+    let total_weight: u16 = weights.iter().map(|w| *w as u16).sum();
+    require!(
+        quorum >= 1 && quorum as u16 <= total_weight,
+        ErrorCode::InvalidQuorum
+    );
+    for (i, signer) in signers.iter().enumerate() {
+        require!(!signers[..i].contains(signer), ErrorCode::DuplicateSigner);
+    }
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        effective_at >= current_time.saturating_add(config.signer_rotation_delay_secs),
+        ErrorCode::SignerActivationTooEarly
+    );
+
+    emit!(SignerRotationProposed {
+        signers: signers.clone(),
+        weights: weights.clone(),
+        quorum,
+        effective_at,
+        timestamp: current_time,
+    });
+    msg!(
+        "Signer rotation proposed: weight {} of {} effective at {}",
+        quorum, total_weight, effective_at
+    );
+    config.pending_signer_weights = signers
+        .iter()
+        .zip(weights)
+        .filter(|(_, weight)| *weight != 1)
+        .map(|(signer, weight)| SignerWeight { signer: *signer, weight })
+        .collect();
+    config.pending_signer_set = Some(PendingSignerSet { signers, quorum, effective_at });
+    Ok(())
+}
+
+/// Corpo comum de update_risk_status e update_risk_status_switchboard
+fn update_single<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateRiskStatus<'info>>,
+    asset_id: &str,
+    decision: &SignedDecision,
+    require_switchboard: bool,
+) -> Result<()> {
+    let payload = decision.payload;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let mut signer_usage = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        signer_usage.push(load_signer_usage(ctx.program_id, info)?);
+    }
+
+    let accounts = &mut ctx.accounts;
+    require!(
+        accounts.config.portfolio_breaker.is_none() || accounts.portfolio.is_some(),
+        ErrorCode::PortfolioAccountMissing
+    );
+    let asset_policy = read_asset_policy(ctx.program_id, &accounts.asset_policy)?;
+    let switchboard = read_switchboard(
+        asset_policy.as_ref(),
+        accounts.switchboard_feed.as_ref(),
+        require_switchboard,
+        current_time,
+    )?;
+    let oracle_prices = match accounts.pyth_price.as_ref() {
+        Some(info) => vec![pyth::PythPrice::try_from_account(info)?],
+        None => Vec::new(),
+    };
+    check_oracle_confidence(
+        asset_policy.as_ref(),
+        &oracle_prices,
+        payload,
+        current_time,
+    )
+    .inspect_err(|err| {
+        emit_rejection(&payload.asset_id, &decision.decision_hash, err, current_time)
+    })?;
+
+    // Conta zero-copy criada agora por init_if_needed ainda não tem discriminator
+    let mut asset_risk = match accounts.asset_risk_status.load_mut() {
+        Ok(asset_risk) => asset_risk,
+        Err(_) => accounts.asset_risk_status.load_init()?,
+    };
+    asset_risk.bump = ctx.bumps.asset_risk_status;
+    asset_risk.version = ASSET_RISK_VERSION;
+    let previous_update = asset_risk.last_updated;
+    let mut replay_bloom = accounts.replay_bloom.as_ref().map(|b| b.load_mut()).transpose()?;
+    let outcome = apply_decision(
+        ctx.program_id,
+        &accounts.config,
+        &accounts.instructions_sysvar,
+        accounts.used_decisions.as_deref_mut(),
+        replay_bloom.as_deref_mut(),
+        &mut asset_risk,
+        asset_policy.as_ref(),
+        &mut signer_usage,
+        accounts.pending_decision.as_deref_mut(),
+        accounts.session_key.as_deref(),
+        decision,
+        current_time,
+    )
+    .inspect_err(|err| {
+        emit_rejection(&payload.asset_id, &decision.decision_hash, err, current_time)
+    })?;
+    drop(replay_bloom);
+    for usage in signer_usage.iter() {
+        usage.exit(ctx.program_id)?;
+    }
+    if let Some(stats) = accounts.stats.as_mut() {
+        stats.record_outcome(&payload.asset_id, outcome);
+    }
+    // Decisão estacionada também é cobrada: o trabalho de verificação foi feito
+    collect_update_fee(
+        &accounts.config,
+        accounts.treasury.as_mut(),
+        accounts.keeper.to_account_info(),
+        Some(accounts.system_program.to_account_info()),
+        FeeTokenAccounts {
+            payer_token: accounts.payer_fee_token.as_ref(),
+            treasury_token: accounts.treasury_fee_token.as_ref(),
+            mint: accounts.fee_mint.as_ref(),
+            token_program: accounts.token_program.as_ref(),
+        },
+        1,
+        current_time,
+    )?;
+    if outcome == DecisionOutcome::Parked {
+        return Ok(());
+    }
+    if let Some((feed, reading)) = switchboard {
+        record_switchboard(&mut asset_risk, feed, reading, current_time)?;
+    }
+
+    // Histórico: conta criada na primeira atualização do ativo
+    let mut history = match accounts.asset_history.load_mut() {
+        Ok(history) => history,
+        Err(_) => accounts.asset_history.load_init()?,
+    };
+    if history.asset_id[0] == 0 {
+        history.bump = ctx.bumps.asset_history;
+        history.asset_id = payload.asset_id;
+    }
+    history.push(&asset_risk);
+    check_circuit_breaker(&accounts.config, &history, &mut asset_risk, current_time);
+    drop(history);
+
+    // Dead-man switch: decisão válida do ativo sentinela rearma o timer
+    if let Some(deadman) = accounts.deadman.as_mut() {
+        deadman.rearm_if_sentinel(&payload.asset_id, current_time);
+    }
+    if let Some(portfolio) = accounts.portfolio.as_mut() {
+        record_portfolio_score(
+            &accounts.config,
+            portfolio,
+            &payload.asset_id,
+            asset_risk.risk_score,
+            current_time,
+        );
+    }
+    pay_keeper_tip(
+        &accounts.config,
+        accounts.treasury.as_mut(),
+        accounts.keeper.to_account_info(),
+        &payload.asset_id,
+        previous_update,
+        current_time,
+    )?;
+    
+    msg!(
+        "Updated risk status for {}: score={}, blocked={}, reduce_only={}, confidence={}bps, publishers={}, ts={}",
+        asset_id,
+        payload.risk_score,
+        payload.is_blocked,
+        payload.reduce_only,
+        payload.confidence_ratio,
+        payload.publisher_count,
+        payload.timestamp
+    );
+    
+    Ok(())
+}
+
+/// Contas SPL da taxa por atualização; exigidas quando `UpdateFee.mint` está definido
+struct FeeTokenAccounts<'a, 'info> {
+    payer_token: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    treasury_token: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+}
+
+/// Cobra `Config.update_fee` de cada atualização aceita e credita o Treasury
+fn collect_update_fee<'info>(
+    config: &Config,
+    treasury: Option<&mut Account<'info, Treasury>>,
+    payer: AccountInfo<'info>,
+    system_program: Option<AccountInfo<'info>>,
+    tokens: FeeTokenAccounts<'_, 'info>,
+    updates: u64,
+    current_time: i64,
+) -> Result<()> {
+    let Some(fee) = config.update_fee.as_ref() else {
+        return Ok(());
+    };
+    if updates == 0 {
+        return Ok(());
+    }
+    let treasury = treasury.ok_or(ErrorCode::FeeAccountsMissing)?;
+    let amount = fee.amount.saturating_mul(updates);
+    match fee.mint {
+        None => {
+            let system_program = system_program.ok_or(ErrorCode::FeeAccountsMissing)?;
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: treasury.to_account_info(),
+            };
+            anchor_lang::system_program::transfer(
+                CpiContext::new(system_program, cpi_accounts),
+                amount,
+            )?;
+            treasury.lamports_collected = treasury.lamports_collected.saturating_add(amount);
+        }
+        Some(fee_mint) => {
+            let (Some(from), Some(to), Some(mint), Some(token_program)) =
+                (tokens.payer_token, tokens.treasury_token, tokens.mint, tokens.token_program)
+            else {
+                return err!(ErrorCode::FeeAccountsMissing);
+            };
+            require_keys_eq!(mint.key(), fee_mint, ErrorCode::InvalidFeeAccount);
+            require_keys_eq!(to.owner, treasury.key(), ErrorCode::InvalidFeeAccount);
+            let cpi_accounts = TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: payer.clone(),
+            };
+            transfer_checked(
+                CpiContext::new(token_program.to_account_info(), cpi_accounts),
+                amount,
+                mint.decimals,
+            )?;
+            treasury.tokens_collected = treasury.tokens_collected.saturating_add(amount);
+        }
+    }
+
+    emit!(UpdateFeeCollected {
+        payer: payer.key(),
+        mint: fee.mint,
+        amount,
+        updates,
+        timestamp: current_time,
+    });
+    Ok(())
+}
+
+/// Paga `Config.keeper_tip` ao keeper que enviou a decisão para um ativo cujo dado
+/// tinha mais de `min_age_secs`. Sem Treasury a atualização segue sem gorjeta;
+/// o valor é limitado ao saldo acima do rent, como a recompensa do prune.
+fn pay_keeper_tip<'info>(
+    config: &Config,
+    treasury: Option<&mut Account<'info, Treasury>>,
+    keeper: AccountInfo<'info>,
+    asset_id: &[u8; 16],
+    previous_update: i64,
+    current_time: i64,
+) -> Result<()> {
+    let (Some(tip), Some(treasury)) = (config.keeper_tip.as_ref(), treasury) else {
+        return Ok(());
+    };
+    // Primeira decisão do ativo não tem dado velho a renovar
+    let age_secs = current_time.saturating_sub(previous_update);
+    if previous_update == 0 || age_secs < tip.min_age_secs {
+        return Ok(());
+    }
+
+    let treasury_info = treasury.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let lamports = tip.lamports.min(treasury_info.lamports().saturating_sub(rent_floor));
+    if lamports == 0 {
+        return Ok(());
+    }
+    **treasury_info.try_borrow_mut_lamports()? -= lamports;
+    **keeper.try_borrow_mut_lamports()? += lamports;
+    treasury.tips_paid = treasury.tips_paid.saturating_add(lamports);
+
+    emit!(KeeperTipPaid {
+        keeper: keeper.key(),
+        asset_id: *asset_id,
+        age_secs,
+        lamports,
+        timestamp: current_time,
+    });
+    Ok(())
+}
+
+/// Atualiza o agregado e emite PortfolioBreakerChanged se o breaker mudou de estado
+fn record_portfolio_score(
+    config: &Config,
+    portfolio: &mut PortfolioRisk,
+    asset_id: &[u8; 16],
+    risk_score: u8,
+    current_time: i64,
+) {
+    let before = portfolio.aggregate_score();
+    portfolio.record_score(asset_id, risk_score, current_time);
+    let after = portfolio.aggregate_score();
+    if let Some(threshold) = config.portfolio_breaker {
+        if (before >= threshold) != (after >= threshold) {
+            emit!(PortfolioBreakerChanged {
+                tripped: after >= threshold,
+                aggregate_score: after,
+                threshold,
+                timestamp: current_time,
+            });
+        }
+    }
+}
+
+fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.paused = paused;
+
+    emit!(PauseStatusChanged {
+        paused,
+        authority: ctx.accounts.authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!("CATE {} by {}", if paused { "paused" } else { "unpaused" }, ctx.accounts.authority.key());
+    Ok(())
+}
+
+// ============================================================================
+// Aplicação de Decisões
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchDecision {
+    pub payload: DecisionPayload,
+    pub signatures: Vec<[u8; 64]>,
+    pub signer_pubkeys: Vec<[u8; 32]>,
+    pub secondary: Option<SecondaryAssessment>,
+}
+
+/// Avaliação do engine secundário: assina o mesmo payload trocando apenas
+/// o risk_score pelo seu próprio. A assinatura vai na mesma instrução Ed25519.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SecondaryAssessment {
+    pub risk_score: u8,
+    pub signature: [u8; 64],
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DecisionOutcome {
+    Applied,
+    Parked, // Aguardando revisão do guardian
+}
+
+/// Decisão com o hash já calculado pelo chamador
+pub struct SignedDecision<'a> {
+    pub payload: &'a DecisionPayload,
+    pub decision_hash: [u8; 32],
+    pub signatures: &'a [[u8; 64]],
+    pub signer_pubkeys: &'a [[u8; 32]],
+    pub secondary: Option<&'a SecondaryAssessment>,
+}
+
+/// Valida uma decisão assinada (campos, frescor, hash, quorum, replay) e
+/// grava no AssetRiskStatus. Compartilhado pelo fluxo simples e pelo batch.
+/// Emite RiskStatusUpdated ou DecisionParked; a rejeição fica com o chamador.
+#[allow(clippy::too_many_arguments)]
+fn apply_decision<'info>(
+    program_id: &Pubkey,
+    config: &Config,
+    instructions_sysvar: &AccountInfo,
+    used_decisions: Option<&mut UsedDecisions>,
+    replay_bloom: Option<&mut ReplayBloom>,
+    asset_risk: &mut AssetRiskStatus,
+    policy: Option<&AssetPolicy>,
+    signer_usage: &mut [Account<'info, SignerUsage>],
+    pending: Option<&mut PendingDecision>,
+    session: Option<&SessionKey>,
+    decision: &SignedDecision,
+    current_time: i64,
+) -> Result<DecisionOutcome> {
+    let result = try_apply_decision(
+        program_id,
+        config,
+        instructions_sysvar,
+        used_decisions,
+        replay_bloom,
+        asset_risk,
+        policy,
+        signer_usage,
+        pending,
+        session,
+        decision,
+        current_time,
+    );
+    match &result {
+        Ok(DecisionOutcome::Parked) => emit!(DecisionParked {
+            asset_id: decision.payload.asset_id,
+            decision_hash: decision.decision_hash,
+            risk_score: decision.payload.risk_score,
+            secondary_score: decision.secondary.map(|s| s.risk_score),
+            timestamp: current_time,
+        }),
+        Ok(DecisionOutcome::Applied) => emit!(RiskStatusUpdated {
+            asset_id: asset_risk.asset_id,
+            risk_score: asset_risk.risk_score,
+            risk_tier: asset_risk.risk_tier(),
+            is_blocked: asset_risk.is_blocked != 0,
+            reduce_only: asset_risk.reduce_only != 0,
+            risk_flags: asset_risk.risk_flags,
+            sub_scores: asset_risk.sub_scores,
+            confidence_ratio: asset_risk.confidence_ratio,
+            publisher_count: asset_risk.publisher_count,
+            publisher_ids: asset_risk.publisher_ids,
+            decision_timestamp: asset_risk.timestamp,
+            prev_risk_score: asset_risk.prev_risk_score,
+            score_delta: asset_risk.score_delta,
+            decision_hash: asset_risk.decision_hash,
+            nonce: asset_risk.nonce,
+            sequence: decision.payload.sequence,
+            valid_until: asset_risk.valid_until,
+            signer_count: asset_risk.signer_count,
+            signers: decision
+                .signer_pubkeys
+                .iter()
+                .map(|k| Pubkey::new_from_array(*k))
+                .collect(),
+            timestamp: current_time,
+        }),
+        Err(_) => {}
+    }
+    result
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_apply_decision<'info>(
+    program_id: &Pubkey,
+    config: &Config,
+    instructions_sysvar: &AccountInfo,
+    used_decisions: Option<&mut UsedDecisions>,
+    replay_bloom: Option<&mut ReplayBloom>,
+    asset_risk: &mut AssetRiskStatus,
+    policy: Option<&AssetPolicy>,
+    signer_usage: &mut [Account<'info, SignerUsage>],
+    pending: Option<&mut PendingDecision>,
+    session: Option<&SessionKey>,
+    decision: &SignedDecision,
+    current_time: i64,
+) -> Result<DecisionOutcome> {
+    require!(!config.paused, ErrorCode::ProgramPaused);
+    require!(asset_risk.lifecycle() != AssetLifecycle::Delisted, ErrorCode::AssetDelisted);
+
+    let payload = decision.payload;
+    require!(payload.asset_id[0] != 0, ErrorCode::AssetIdEmpty);
+    require!(payload.risk_score <= 100, ErrorCode::InvalidRiskScore);
+    require!(payload.confidence_ratio <= 10000, ErrorCode::InvalidConfidenceRatio);
+    require!(payload.risk_flags & !RISK_FLAGS_ALL == 0, ErrorCode::InvalidRiskFlags);
+    require!(payload.sub_scores.iter().all(|s| *s <= 100), ErrorCode::InvalidSubScore);
+    require!(payload.publisher_ids_valid(), ErrorCode::InvalidPublisherIds);
+    require!(
+        payload.valid_until == 0 || payload.valid_until > payload.timestamp,
+        ErrorCode::InvalidDecisionExpiry
+    );
+    require!(!payload.is_expired(current_time), ErrorCode::DecisionExpired);
+    // Poucos publishers ou confiança baixa: recusa ou, se configurado, bloqueia
+    let below_floor = config.below_decision_floor(payload);
+    require!(!below_floor || config.block_below_floor, ErrorCode::DecisionBelowFloor);
+
+    // Verifica timestamp (evita assinaturas muito antigas)
+    let replay_policy = config.replay_policy();
+    replay_policy
+        .check_window(payload.timestamp, current_time)
+        .map_err(replay_error)?;
+
+    // O hash assinado deve ser o recomputado a partir do payload completo,
+    // senão os campos poderiam ser substituídos mantendo uma assinatura válida
+    require!(
+        message::decision_hash(program_id, &config.genesis_hash, payload) == decision.decision_hash,
+        ErrorCode::DecisionHashMismatch
+    );
+
+    // Verifica o quorum ponderado dos signers confiáveis, ou a assinatura
+    // única de um signer de emergência (restrito mais abaixo)
+    // As instruções Ed25519/Secp256k1 podem estar em qualquer posição anterior
+    let emergency = config.is_emergency_decision(decision.signer_pubkeys, current_time);
+    let signers = if emergency {
+        require!(decision.signatures.len() == 1, ErrorCode::SignatureCountMismatch);
+        verify_signer(
+            config,
+            instructions_sysvar,
+            &decision.signer_pubkeys[0],
+            &decision.decision_hash,
+            &decision.signatures[0],
+        )?;
+        vec![Pubkey::new_from_array(decision.signer_pubkeys[0])]
+    } else {
+        verify_quorum(
+            config,
+            instructions_sysvar,
+            &decision.decision_hash,
+            decision.signatures,
+            decision.signer_pubkeys,
+            session,
+            &payload.asset_id,
+            current_time,
+        )?
+    };
+    let signer_count = signers.len() as u8;
+    check_signer_expiry(config, &signers, payload.timestamp, current_time)?;
+
+    // Replay protection: hashes usados (lista ou bloom), sequência monotônica
+    // por signer ou última decisão do ativo
+    match config.replay_mode {
+        ReplayMode::HashList => {
+            let used_decisions = used_decisions.ok_or(ErrorCode::ReplayShardMissing)?;
+            // Shard recriado: decisões anteriores ao reset não têm mais registro
+            require!(
+                used_decisions.generation == config.generation
+                    && payload.timestamp > used_decisions.reset_at,
+                ErrorCode::StaleReplayShard
+            );
+            replay_policy
+                .check_and_mark(used_decisions, decision.decision_hash, payload.timestamp, current_time)
+                .map_err(replay_error)?
+        }
+        ReplayMode::Sequence => {
+            asset_risk.advance_sequences(config, &signers, payload.sequence, current_time)?
+        }
+        ReplayMode::Bloom => {
+            let bloom = replay_bloom.ok_or(ErrorCode::ReplayBloomMissing)?;
+            // Um hash é esquecido uma janela depois de marcado: a janela de
+            // frescor pode ter crescido depois da criação do filtro
+            require!(
+                bloom.window_secs >= config.max_past_secs.saturating_add(config.max_future_secs),
+                ErrorCode::ReplayBloomWindowTooShort
+            );
+            bloom
+                .check_and_mark(&decision.decision_hash, current_time)
+                .map_err(replay_error)?
+        }
+        ReplayMode::PerAsset => {
+            require!(
+                decision.decision_hash != asset_risk.decision_hash,
+                ErrorCode::DecisionAlreadyUsed
+            );
+        }
+    }
+
+    // Em qualquer modo de replay (depois dele, para que a repetição seja
+    // reportada como tal), decisão assinada antes da gravada no ativo chegou
+    // atrasada e voltaria o estado de risco no tempo
+    require!(payload.timestamp > asset_risk.timestamp, ErrorCode::DecisionNotNewerThanAsset);
+
+    // Score acima do limiar do ativo bloqueia mesmo que o engine esqueça a
+    // flag; bloqueio do dead-man switch só sai por rearm_deadman
+    let is_blocked = payload.is_blocked
+        || below_floor
+        || asset_risk.is_deadman_blocked()
+        || policy.is_some_and(|p| p.forces_block(payload.risk_score));
+
+    let unblocks = u16::from(asset_risk.is_blocked != 0 && !is_blocked);
+    require!(
+        unblocks == 0 || asset_risk.unblock_allowed(payload.timestamp),
+        ErrorCode::DecisionPredatesEmergencyBlock
+    );
+    let score_decrease = u32::from(asset_risk.risk_score.saturating_sub(payload.risk_score));
+    // Chave de emergência só aperta: bloqueia ou sobe o score, nunca desbloqueia
+    let tightens = payload.is_blocked || payload.risk_score > asset_risk.risk_score;
+    require!(!emergency || (unblocks == 0 && tightens), ErrorCode::EmergencySignerCannotRelax);
+
+    // Modo de dois engines: desbloqueio ou queda grande sem concordância do
+    // secundário fica estacionado para o guardian
+    if let Some(dual) = config.dual_engine.as_ref() {
+        let needs_agreement = unblocks > 0 || score_decrease > u32::from(dual.large_decrease);
+        if needs_agreement
+            && !secondary_agrees(program_id, config, dual, instructions_sysvar, decision)?
+        {
+            let pending = pending.ok_or(ErrorCode::PendingDecisionMissing)?;
+            require!(pending.asset_id == payload.asset_id, ErrorCode::PendingDecisionMissing);
+            pending.payload = Some(payload.clone());
+            pending.decision_hash = decision.decision_hash;
+            pending.signature = decision.signatures[0];
+            pending.signer_pubkey = decision.signer_pubkeys[0];
+            pending.signer_count = signer_count;
+            pending.secondary_score = decision.secondary.map(|s| s.risk_score);
+            pending.parked_at = current_time;
+            return Ok(DecisionOutcome::Parked);
+        }
+    }
+
+    // Decisões que reduzem risco consomem o orçamento diário de cada signer
+    if unblocks > 0 || score_decrease > 0 {
+        enforce_key_usage(
+            config,
+            signer_usage,
+            &signers,
+            current_time / SECONDS_PER_DAY,
+            unblocks,
+            score_decrease,
+        )?;
+    }
+
+    asset_risk.track_cooling(is_blocked, config.unblock_cooldown.as_ref(), current_time);
+    asset_risk.track_ema(payload.risk_score, config.score_ema_alpha_bps);
+    // Guarda a primeira assinatura do quorum para auditoria
+    write_decision(
+        asset_risk,
+        payload,
+        &config.risk_tier_thresholds(),
+        is_blocked,
+        decision.decision_hash,
+        decision.signatures[0],
+        decision.signer_pubkeys[0],
+        signer_count,
+        current_time,
+    );
+    Ok(DecisionOutcome::Applied)
+}
+
+/// Grava uma decisão já validada no AssetRiskStatus
+#[allow(clippy::too_many_arguments)]
+fn write_decision(
+    asset_risk: &mut AssetRiskStatus,
+    payload: &DecisionPayload,
+    risk_tiers: &RiskTierThresholds,
+    is_blocked: bool,
+    decision_hash: [u8; 32],
+    signature: [u8; 64],
+    signer_pubkey: [u8; 32],
+    signer_count: u8,
+    current_time: i64,
+) {
+    // Conta criada pela própria decisão: ativo já em operação
+    if asset_risk.asset_id[0] == 0 {
+        asset_risk.set_lifecycle(AssetLifecycle::Live);
+    }
+    asset_risk.asset_id = payload.asset_id;
+    // Direção da mudança para consumidores que não indexam o histórico
+    asset_risk.prev_risk_score = asset_risk.risk_score;
+    asset_risk.score_delta = payload.risk_score as i8 - asset_risk.risk_score as i8;
+    asset_risk.updates_count = asset_risk.updates_count.saturating_add(1);
+    asset_risk.risk_score = payload.risk_score;
+    asset_risk.set_risk_tier(risk_tiers);
+    // Faixa gravada: conta antiga passa a ser lida pelo campo (também no batch)
+    asset_risk.version = ASSET_RISK_VERSION;
+    asset_risk.is_blocked = u8::from(is_blocked);
+    asset_risk.reduce_only = u8::from(payload.reduce_only);
+    asset_risk.risk_flags = payload.risk_flags;
+    asset_risk.sub_scores = payload.sub_scores;
+    asset_risk.last_updated = current_time;
+    asset_risk.confidence_ratio = payload.confidence_ratio;
+    asset_risk.publisher_count = payload.publisher_count;
+    asset_risk.publisher_ids = payload.publisher_ids;
+    asset_risk.timestamp = payload.timestamp; // Armazena para auditoria
+    asset_risk.set_oracle_confidence(None); // Só a variante switchboard preenche
+    asset_risk.nonce = payload.nonce;
+    asset_risk.valid_until = payload.valid_until;
+
+    asset_risk.decision_hash = decision_hash;
+    asset_risk.signature = signature;
+    asset_risk.signer_pubkey = signer_pubkey;
+    asset_risk.signer_count = signer_count;
+}
+
+/// O secundário assinou o mesmo payload com o próprio score, e os scores
+/// diferem no máximo `tolerance` pontos. Assinatura inválida é erro, não dissenso.
+fn secondary_agrees(
+    program_id: &Pubkey,
+    config: &Config,
+    dual: &DualEngineConfig,
+    instructions_sysvar: &AccountInfo,
+    decision: &SignedDecision,
+) -> Result<bool> {
+    let Some(secondary) = decision.secondary else {
+        return Ok(false);
+    };
+    let mut assessed = decision.payload.clone();
+    assessed.risk_score = secondary.risk_score;
+    let assessed_hash = message::decision_hash(program_id, &config.genesis_hash, &assessed);
+    verify_ed25519_instruction(
+        instructions_sysvar,
+        &dual.secondary_signer.to_bytes(),
+        &assessed_hash,
+        &secondary.signature,
+    )?;
+    Ok(decision.payload.risk_score.abs_diff(secondary.risk_score) <= dual.tolerance)
+}
+
+/// Aplica uma mudança de política vencida sobre o Config
+fn apply_config_change(config: &mut Config, change: &PolicyChange, current_time: i64) -> Result<()> {
+    match change {
+        PolicyChange::MaxStaleness { max_staleness_secs } => {
+            config.max_staleness_secs = *max_staleness_secs;
+            emit!(StalenessWindowUpdated {
+                asset_id: None,
+                max_staleness_secs: Some(*max_staleness_secs),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::KeyUsageLimits { max_unblocks_per_day, max_score_decrease_per_day } => {
+            config.max_unblocks_per_day = *max_unblocks_per_day;
+            config.max_score_decrease_per_day = *max_score_decrease_per_day;
+            emit!(KeyUsageLimitsUpdated {
+                max_unblocks_per_day: *max_unblocks_per_day,
+                max_score_decrease_per_day: *max_score_decrease_per_day,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::CircuitBreaker { circuit_breaker } => {
+            config.circuit_breaker = circuit_breaker.clone();
+            emit!(CircuitBreakerConfigured {
+                circuit_breaker: circuit_breaker.clone(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::PolicyDelay { delay_secs } => {
+            config.policy_delay_secs = *delay_secs;
+        }
+        PolicyChange::SignerRotationDelay { delay_secs } => {
+            config.signer_rotation_delay_secs = *delay_secs;
+        }
+        PolicyChange::TimestampWindow { max_past_secs, max_future_secs } => {
+            config.max_past_secs = *max_past_secs;
+            config.max_future_secs = *max_future_secs;
+            emit!(TimestampWindowUpdated {
+                max_past_secs: *max_past_secs,
+                max_future_secs: *max_future_secs,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::ReplayMode { replay_mode } => {
+            config.replay_mode = *replay_mode;
+            emit!(ReplayModeUpdated { replay_mode: *replay_mode, timestamp: current_time });
+        }
+        PolicyChange::DualEngine { dual_engine } => {
+            // O secundário precisa ser independente do quorum principal
+            if let Some(dual) = dual_engine.as_ref() {
+                require!(
+                    !config.trusted_signers.contains(&dual.secondary_signer),
+                    ErrorCode::InvalidDualEngineConfig
+                );
+            }
+            config.dual_engine = dual_engine.clone();
+            emit!(DualEngineConfigured {
+                dual_engine: dual_engine.clone(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::SignerKeyType { signer, key_type } => {
+            config.signer_key_types.retain(|entry| entry.signer != *signer);
+            if *key_type != KeyType::Ed25519 {
+                require!(
+                    config.signer_key_types.len() < MAX_TRUSTED_SIGNERS,
+                    ErrorCode::TooManySigners
+                );
+                config.signer_key_types.push(SignerKeyType { signer: *signer, key_type: *key_type });
+            }
+            emit!(SignerKeyTypeUpdated {
+                signer: *signer,
+                key_type: *key_type,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::UnblockCooldown { unblock_cooldown } => {
+            config.unblock_cooldown = unblock_cooldown.clone();
+            emit!(UnblockCooldownConfigured {
+                unblock_cooldown: unblock_cooldown.clone(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::ReopenRamp { steps } => {
+            config.reopen_ramp = steps.clone();
+            emit!(ReopenRampUpdated { steps: steps.clone(), timestamp: current_time });
+        }
+        PolicyChange::PortfolioBreaker { threshold } => {
+            config.portfolio_breaker = *threshold;
+            emit!(PortfolioBreakerConfigured { threshold: *threshold, timestamp: current_time });
+        }
+        PolicyChange::HeartbeatSla { sla_secs } => {
+            config.heartbeat_sla_secs = *sla_secs;
+            emit!(HeartbeatSlaConfigured { sla_secs: *sla_secs, timestamp: current_time });
+        }
+        PolicyChange::UpdateFee { update_fee } => {
+            config.update_fee = update_fee.clone();
+            emit!(UpdateFeeConfigured { update_fee: update_fee.clone(), timestamp: current_time });
+        }
+        PolicyChange::KeeperTip { keeper_tip } => {
+            config.keeper_tip = keeper_tip.clone();
+            emit!(KeeperTipConfigured { keeper_tip: keeper_tip.clone(), timestamp: current_time });
+        }
+        PolicyChange::ConsumerAccess { consumer_access } => {
+            config.consumer_access = consumer_access.clone();
+            emit!(ConsumerAccessConfigured {
+                consumer_access: consumer_access.clone(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::ScoreEma { alpha_bps } => {
+            config.score_ema_alpha_bps = *alpha_bps;
+            emit!(ScoreEmaConfigured { alpha_bps: *alpha_bps, timestamp: current_time });
+        }
+        PolicyChange::TradeLimits { min_confidence_ratio, notional_tiers } => {
+            config.min_confidence_ratio = *min_confidence_ratio;
+            config.notional_tiers = notional_tiers.clone();
+            emit!(TradeLimitsUpdated {
+                min_confidence_ratio: *min_confidence_ratio,
+                notional_tiers: notional_tiers.clone(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::DecisionFloor {
+            min_publisher_count,
+            min_confidence_bps,
+            block_below_floor,
+        } => {
+            config.min_publisher_count = *min_publisher_count;
+            config.min_confidence_bps = *min_confidence_bps;
+            config.block_below_floor = *block_below_floor;
+            emit!(DecisionFloorConfigured {
+                min_publisher_count: *min_publisher_count,
+                min_confidence_bps: *min_confidence_bps,
+                block_below_floor: *block_below_floor,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::SignerExpiry { signer, expires_at } => {
+            config.signer_expiries.retain(|entry| entry.signer != *signer);
+            if *expires_at > 0 {
+                require!(
+                    config.signer_expiries.len() < MAX_TRUSTED_SIGNERS,
+                    ErrorCode::TooManySigners
+                );
+                let entry = SignerExpiry { signer: *signer, expires_at: *expires_at };
+                config.signer_expiries.push(entry);
+            }
+            emit!(SignerExpiryUpdated {
+                signer: *signer,
+                expires_at: *expires_at,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::EmergencySigner { signer, enabled } => {
+            config.emergency_signers.retain(|s| s != signer);
+            if *enabled {
+                // Chave separada do quorum principal
+                require!(
+                    !config.trusted_signers.contains(signer),
+                    ErrorCode::InvalidEmergencySigner
+                );
+                require!(
+                    config.emergency_signers.len() < MAX_TRUSTED_SIGNERS,
+                    ErrorCode::TooManySigners
+                );
+                config.emergency_signers.push(*signer);
+            }
+            emit!(EmergencySignerUpdated {
+                signer: *signer,
+                enabled: *enabled,
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::RiskTiers { risk_tiers } => {
+            config.risk_tier_thresholds = *risk_tiers;
+            emit!(RiskTierThresholdsUpdated {
+                risk_tiers: config.risk_tier_thresholds(),
+                timestamp: current_time,
+            });
+        }
+        PolicyChange::AssetPolicy { .. } => return err!(ErrorCode::WrongPolicyChangeKind),
+    }
+    Ok(())
+}
+
+/// Ativo com pyth_check: a confiança declarada pelo engine deve ficar a até
+/// `tolerance_bps` do `conf/price` do feed Pyth, que precisa estar fresco
+fn check_oracle_confidence(
+    policy: Option<&AssetPolicy>,
+    prices: &[pyth::PythPrice],
+    payload: &DecisionPayload,
+    current_time: i64,
+) -> Result<()> {
+    let Some(check) = policy.and_then(|p| p.pyth_check.as_ref()) else {
+        return Ok(());
+    };
+    let price = prices
+        .iter()
+        .find(|price| price.feed_id == check.feed_id)
+        .ok_or(ErrorCode::OracleAccountMissing)?;
+    require!(
+        current_time.saturating_sub(price.publish_time) <= check.max_age_secs,
+        ErrorCode::OraclePriceStale
+    );
+    let oracle_ratio = price.confidence_ratio_bps().ok_or(ErrorCode::InvalidOracleAccount)?;
+    require!(
+        payload.confidence_ratio.abs_diff(oracle_ratio) <= check.tolerance_bps,
+        ErrorCode::ConfidenceMismatch
+    );
+    Ok(())
+}
+
+/// Feed Switchboard do ativo, exigido na variante switchboard: precisa ser o
+/// configurado em AssetPolicy.switchboard_check, fresco e com valor positivo
+fn read_switchboard(
+    policy: Option<&AssetPolicy>,
+    feed: Option<&UncheckedAccount>,
+    required: bool,
+    current_time: i64,
+) -> Result<Option<(Pubkey, switchboard::SwitchboardReading)>> {
+    if !required {
+        return Ok(None);
+    }
+    let check = policy
+        .and_then(|p| p.switchboard_check.as_ref())
+        .ok_or(ErrorCode::SwitchboardNotConfigured)?;
+    let feed = feed.ok_or(ErrorCode::OracleAccountMissing)?;
+    require_keys_eq!(feed.key(), check.feed, ErrorCode::InvalidOracleAccount);
+
+    let reading = switchboard::SwitchboardReading::try_from_account(feed)?;
+    require!(
+        current_time.saturating_sub(reading.last_update_timestamp) <= check.max_age_secs,
+        ErrorCode::OraclePriceStale
+    );
+    require!(reading.confidence_ratio_bps().is_some(), ErrorCode::InvalidOracleAccount);
+    Ok(Some((feed.key(), reading)))
+}
+
+/// Grava a confiança do oráculo ao lado da do engine, depois da decisão aplicada
+fn record_switchboard(
+    asset_risk: &mut AssetRiskStatus,
+    feed: Pubkey,
+    reading: switchboard::SwitchboardReading,
+    current_time: i64,
+) -> Result<()> {
+    let oracle_ratio = reading.confidence_ratio_bps().ok_or(ErrorCode::InvalidOracleAccount)?;
+    asset_risk.set_oracle_confidence(Some(oracle_ratio));
+    asset_risk.oracle_updated_at = reading.last_update_timestamp;
+
+    emit!(OracleConfidenceRecorded {
+        asset_id: asset_risk.asset_id,
+        feed,
+        engine_confidence_ratio: asset_risk.confidence_ratio,
+        oracle_confidence_ratio: oracle_ratio,
+        oracle_updated_at: reading.last_update_timestamp,
+        timestamp: current_time,
+    });
+    Ok(())
+}
+
+/// Arma o breaker se o score subiu mais que o permitido dentro da janela.
+/// O histórico já deve conter a decisão recém-aplicada.
+fn check_circuit_breaker(
+    config: &Config,
+    history: &AssetRiskHistory,
+    asset_risk: &mut AssetRiskStatus,
+    current_time: i64,
+) {
+    let Some(breaker) = config.circuit_breaker.as_ref() else {
+        return;
+    };
+    let rise = history.max_rise_within(breaker.window_secs);
+    if rise <= breaker.max_rise {
+        return;
+    }
+
+    asset_risk.breaker_until = current_time.saturating_add(breaker.cooldown_secs);
+    emit!(CircuitBreakerTripped {
+        asset_id: asset_risk.asset_id,
+        risk_score: asset_risk.risk_score,
+        rise,
+        breaker_until: asset_risk.breaker_until,
+        timestamp: current_time,
+    });
+    msg!("Circuit breaker tripped: +{} points, blocked until {}", rise, asset_risk.breaker_until);
+}
+
+/// AssetPolicy do PDA que toda atualização passa, mesmo sem política: omitir
+/// a conta não pode desligar o block_threshold nem o pyth_check. PDA sem
+/// dados é o ativo sem política. O chamador confere o endereço.
+fn read_asset_policy(program_id: &Pubkey, info: &AccountInfo) -> Result<Option<AssetPolicy>> {
+    if info.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*info.owner, *program_id, ErrorCode::InvalidAssetAccount);
+    Ok(Some(AssetPolicy::try_deserialize(&mut &info.try_borrow_data()?[..])?))
+}
+
+/// Carrega um SignerUsage de remaining_accounts validando o PDA
+fn load_signer_usage<'info>(
+    program_id: &Pubkey,
+    info: &'info AccountInfo<'info>,
+) -> Result<Account<'info, SignerUsage>> {
+    require!(info.is_writable, ErrorCode::InvalidSignerUsageAccount);
+    let usage: Account<'info, SignerUsage> = Account::try_from(info)?;
+    let (expected, _) = Pubkey::find_program_address(
+        &[SEED_NAMESPACE, SIGNER_USAGE_SEED, usage.signer.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(expected, info.key(), ErrorCode::InvalidSignerUsageAccount);
+    Ok(usage)
+}
+
+/// Aplica os tetos diários a todos os signers do quorum (a raiz, no caso de
+/// uma chave de sessão)
+fn enforce_key_usage(
+    config: &Config,
+    signer_usage: &mut [Account<'_, SignerUsage>],
+    signers: &[Pubkey],
+    day: i64,
+    unblocks: u16,
+    score_decrease: u32,
+) -> Result<()> {
+    if config.max_unblocks_per_day.is_none() && config.max_score_decrease_per_day.is_none() {
+        return Ok(());
+    }
+
+    for signer in signers {
+        let usage = signer_usage
+            .iter_mut()
+            .find(|u| u.signer == *signer)
+            .ok_or(ErrorCode::SignerUsageMissing)?;
+        usage.record(config, day, unblocks, score_decrease)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[event]
+pub struct ConfigMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetMigrated {
+    pub asset_id: [u8; 16],
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigReinitialized {
+    pub authority: Pubkey,
+    pub trusted_signer: Pubkey,
+    pub genesis_hash: [u8; 32],
+    pub generation: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReplayShardClosed {
+    pub shard: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigInitialized {
+    pub authority: Pubkey,
+    pub trusted_signer: Pubkey,
+    pub genesis_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TrustedSignerRotated {
+    pub signers: Vec<Pubkey>,
+    pub weights: Vec<u8>, // Paralelo a signers
+    pub quorum: u8, // Peso mínimo somado
+    pub grace_until: i64, // Signers anteriores aceitos até aqui
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerRotationProposed {
+    pub signers: Vec<Pubkey>,
+    pub weights: Vec<u8>, // Paralelo a signers
+    pub quorum: u8, // Peso mínimo somado
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerRotationCancelled {
+    pub signers: Vec<Pubkey>,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct PauseStatusChanged {
+    pub paused: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReplayShardInitialized {
+    pub shard: u8,
+}
+
+#[event]
+pub struct ReplayShardResized {
+    pub shard: u8,
+    pub old_max_size: u16,
+    pub max_size: u16,
+}
+
+#[event]
+pub struct ReplayBloomInitialized {
+    pub false_positive_bps: u16,
+    pub num_hashes: u8,
+    pub capacity: u32,
+    pub window_secs: i64,
+}
+
+#[event]
+pub struct OracleConfidenceRecorded {
+    pub asset_id: [u8; 16],
+    pub feed: Pubkey,
+    pub engine_confidence_ratio: u64,
+    pub oracle_confidence_ratio: u64,
+    pub oracle_updated_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerKeyTypeUpdated {
+    pub signer: Pubkey,
+    pub key_type: KeyType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradeLimitsUpdated {
+    pub min_confidence_ratio: Option<u64>,
+    pub notional_tiers: Vec<NotionalTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyBlocked {
+    pub asset_id: [u8; 16],
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeVaultConfigured {
+    pub prune_bounty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UpdateFeeConfigured {
+    pub update_fee: Option<UpdateFee>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UpdateFeeCollected {
+    pub payer: Pubkey,
+    pub mint: Option<Pubkey>, // None = lamports
+    pub amount: u64,
+    pub updates: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperTipConfigured {
+    pub keeper_tip: Option<KeeperTip>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConsumerAccessConfigured {
+    pub consumer_access: Option<ConsumerAccess>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DecisionFloorConfigured {
+    pub min_publisher_count: u8,
+    pub min_confidence_bps: u64,
+    pub block_below_floor: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerExpiryUpdated {
+    pub signer: Pubkey,
+    pub expires_at: i64, // 0 = sem validade
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerExpiring {
+    pub signer: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionKeyRegistered {
+    pub session_key: Pubkey,
+    pub root_signer: Pubkey,
+    pub expires_at: i64,
+    pub asset_ids: Vec<[u8; 16]>, // Vazio = todos os ativos
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionKeyRevoked {
+    pub session_key: Pubkey,
+    pub root_signer: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencySignerUpdated {
+    pub signer: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskTierThresholdsUpdated {
+    pub risk_tiers: RiskTierThresholds, // Já com o padrão aplicado quando None
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConsumerAllowlistUpdated {
+    pub program_id: Pubkey,
+    pub allowlisted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConsumerSubscribed {
+    pub program_id: Pubkey,
+    pub payer: Pubkey,
+    pub epochs: u64,
+    pub amount: u64, // Lamports pagos ao Treasury
+    pub paid_until_epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConsumerClosed {
+    pub program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingScheduleUpdated {
+    pub asset_id: [u8; 16], // Zeros = global
+    pub open_hours: Vec<WeeklyWindow>,
+    pub closed_hours: Vec<WeeklyWindow>,
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingScheduleClosed {
+    pub asset_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperTipPaid {
+    pub keeper: Pubkey,
+    pub asset_id: [u8; 16],
+    pub age_secs: i64, // Idade do dado substituído
+    pub lamports: u64, // Pode ser menor que o configurado se o Treasury estiver vazio
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Option<Pubkey>, // None = lamports
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFeeShareUpdated {
+    pub fee_share_bps: u16,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceContributed {
+    pub contributor: Pubkey, // Treasury quando from_fees
+    pub amount: u64,
+    pub from_fees: bool,
+    pub balance: u64, // Lamports do fundo, incluindo o rent
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimPaid {
+    pub recipient: Pubkey,
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32], // Decisão contestada
+    pub evidence_hash: [u8; 32], // Dossiê off-chain do sinistro
+    pub amount: u64,
+    pub approved_by: Pubkey,
+    pub payout_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsedDecisionsPruned {
+    pub shard: u8,
+    pub removed: u16,
+    pub remaining: u16,
+    pub bounty: u64, // Pode ser menor que prune_bounty se o cofre estiver vazio
+    pub caller: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskStatusUpdated {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub risk_tier: RiskTier,
+    pub is_blocked: bool,
+    pub reduce_only: bool,
+    pub risk_flags: u8,
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub publisher_ids: [PublisherId; MAX_PUBLISHER_IDS],
+    pub decision_timestamp: i64, // quando foi assinado
+    pub prev_risk_score: u8,
+    pub score_delta: i8,
+    pub decision_hash: [u8; 32],
+    pub nonce: u64, // Correlation id do engine (medição de latência ponta a ponta)
+    pub sequence: u64,
+    pub valid_until: i64, // 0 = sem validade
+    pub signer_count: u8,
+    pub signers: Vec<Pubkey>, // Todos os signers do quorum, para auditoria
+    pub timestamp: i64, // quando foi aplicado
+}
+
+#[event]
+pub struct DecisionVerified {
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub decision_timestamp: i64,
+    pub signer_count: u8,
+}
+
+#[event]
+pub struct DecisionRejected {
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub reason: RejectionClass,
+    pub error_code: u32, // Código exato; `reason` é a categoria
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetPolicyUpdated {
+    pub asset_id: [u8; 16],
+    pub advisory_only: bool,
+    pub block_threshold: Option<u8>,
+    pub max_staleness_secs: Option<i64>,
+    pub pyth_check: Option<PythCheck>,
+    pub switchboard_check: Option<SwitchboardCheck>,
+}
+
+#[event]
+pub struct StalenessWindowUpdated {
+    pub asset_id: Option<[u8; 16]>, // None = janela global do Config
+    pub max_staleness_secs: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetGroupUpdated {
+    pub group_id: [u8; 16],
+    pub member_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GroupBlocked {
+    pub group_id: [u8; 16],
+    pub guardian: Pubkey,
+    pub blocked_assets: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PortfolioWeightSet {
+    pub asset_id: [u8; 16],
+    pub weight: u16,
+    pub aggregate_score: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PortfolioBreakerConfigured {
+    pub threshold: Option<u8>,
+    pub timestamp: i64,
+}
+
+/// Emitido quando o score agregado cruza o limiar do breaker, em qualquer direção
+#[event]
+pub struct PortfolioBreakerChanged {
+    pub tripped: bool,
+    pub aggregate_score: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadmanConfigured {
+    pub sentinel_asset_id: [u8; 16],
+    pub window_seconds: i64,
+    pub block_all: bool,
+    pub asset_count: u8,
+}
+
+#[event]
+pub struct KeyUsageLimitsUpdated {
+    pub max_unblocks_per_day: Option<u16>,
+    pub max_score_decrease_per_day: Option<u32>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SignerUsageReset {
+    pub signer: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerConfigured {
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnblockCooldownConfigured {
+    pub unblock_cooldown: Option<UnblockCooldown>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReopenRampUpdated {
+    pub steps: Vec<RampStep>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EngineHeartbeat {
+    pub signer: Pubkey,
+    pub timestamp: i64, // Timestamp assinado
+}
+
+#[event]
+pub struct HeartbeatSlaConfigured {
+    pub sla_secs: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScoreEmaConfigured {
+    pub alpha_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerTripped {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub rise: u8,
+    pub breaker_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CircuitBreakerReset {
+    pub asset_id: [u8; 16],
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyChangeQueued {
+    pub id: u64,
+    pub change: PolicyChange,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyChangeApplied {
+    pub id: u64,
+    pub change: PolicyChange,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PolicyChangeCancelled {
+    pub id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReplayModeUpdated {
+    pub replay_mode: ReplayMode,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TimestampWindowUpdated {
+    pub max_past_secs: i64,
+    pub max_future_secs: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DualEngineConfigured {
+    pub dual_engine: Option<DualEngineConfig>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DecisionParked {
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub risk_score: u8,
+    pub secondary_score: Option<u8>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PendingDecisionResolved {
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub approved: bool,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskStatusOverridden {
+    pub asset_id: [u8; 16],
+    pub is_blocked: bool,
+    pub reason_code: u16,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReduceOnlySet {
+    pub asset_id: [u8; 16],
+    pub reduce_only: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct V1AssetRetired {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub decision_hash: [u8; 32], // Última decisão v1, para auditoria da migração
+    pub last_updated: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetLifecycleChanged {
+    pub asset_id: [u8; 16],
+    pub from: AssetLifecycle,
+    pub to: AssetLifecycle,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetMintRegistered {
+    pub mint: Pubkey,
+    pub asset_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetRiskClosed {
+    pub asset_id: [u8; 16],
+    pub recipient: Pubkey,
+    pub lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetRegistered {
+    pub asset_id: [u8; 16],
+    pub mint: Pubkey,
+    pub pyth_feed: [u8; 32],
+    pub category: AssetCategory,
+    pub decimals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetUnregistered {
+    pub asset_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetMintUnregistered {
+    pub mint: Pubkey,
+    pub asset_id: [u8; 16],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadmanTriggered {
+    pub last_sentinel_update: i64,
+    pub blocked_assets: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DeadmanRearmed {
+    pub released_assets: u32,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RiskAttestationPublished {
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub emitter: Pubkey,
+    pub sequence: u64, // Sequence da mensagem no core bridge
+    pub timestamp: i64,
+}
+
+/// Eventos emitidos em transações que falham continuam nos logs da transação
+fn emit_rejection(asset_id: &[u8; 16], decision_hash: &[u8; 32], err: &Error, current_time: i64) {
+    let error_code = error_code_of(err);
+    emit!(DecisionRejected {
+        asset_id: *asset_id,
+        decision_hash: *decision_hash,
+        reason: RejectionClass::of(error_code),
+        error_code,
+        timestamp: current_time,
+    });
+}
+
+/// Número do ErrorCode (0 para erros de runtime sem código Anchor)
+fn error_code_of(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(e) => e.error_code_number,
+        Error::ProgramError(_) => 0,
+    }
+}
+
+// ============================================================================
+// Helpers de Asset ID
+// ============================================================================
+
+/// Converte o asset_id em bytes com padding de zeros à direita
+fn asset_id_to_bytes(asset_id: &str) -> Result<[u8; 16]> {
+    require!(asset_id.len() <= 16, ErrorCode::AssetIdTooLong);
+    require!(!asset_id.is_empty(), ErrorCode::AssetIdEmpty);
+    let mut asset_id_bytes = [0u8; 16];
+    asset_id_bytes[..asset_id.len()].copy_from_slice(asset_id.as_bytes());
+    Ok(asset_id_bytes)
+}
+
+/// Remove o padding para reconstruir a seed original do PDA
+fn asset_id_seed(asset_id: &[u8; 16]) -> &[u8] {
+    let len = asset_id.iter().position(|b| *b == 0).unwrap_or(asset_id.len());
+    &asset_id[..len]
+}
+
+/// Regras do gate compartilhadas entre as variantes por asset_id e por mint
+fn trading_gate(
+    config: &Config,
+    asset_risk: &AssetRiskStatus,
+    policy: Option<&AssetPolicy>,
+    portfolio: Option<&PortfolioRisk>,
+    max_staleness: Option<i64>,
+    min_confidence_ratio: Option<u64>,
+    current_time: i64,
+) -> Result<TradingGate> {
+    // Pausado = chave do engine possivelmente comprometida: falha fechado
+    require!(!config.paused, ErrorCode::ProgramPaused);
+    // Engine calado além do SLA: nenhum estado é confiável, falha fechado
+    require!(!config.engine_degraded(current_time), ErrorCode::EngineSilent);
+
+    // Breaker de portfólio: vale para todos os ativos, mesmo em modo advisory
+    if let Some(threshold) = config.portfolio_breaker {
+        let portfolio = portfolio.ok_or(ErrorCode::PortfolioAccountMissing)?;
+        require!(portfolio.aggregate_score() < threshold, ErrorCode::PortfolioBreakerTripped);
+    }
+
+    match asset_risk.lifecycle() {
+        AssetLifecycle::Listed => return err!(ErrorCode::AssetNotLive),
+        AssetLifecycle::Delisted => return err!(ErrorCode::AssetDelisted),
+        AssetLifecycle::Live | AssetLifecycle::Delisting => {}
+    }
+
+    let advisory_only = policy.is_some_and(|policy| policy.advisory_only);
+
+    let mut violation = None;
+    if asset_risk.is_blocked != 0 || asset_risk.is_deadman_blocked() {
+        violation = Some(ErrorCode::AssetBlocked);
+    } else if asset_risk.is_expired(current_time) {
+        violation = Some(ErrorCode::DecisionExpired);
+    } else if asset_risk.breaker_active(current_time) {
+        violation = Some(ErrorCode::CircuitBreakerTripped);
+    } else if asset_risk.is_cooling(current_time) {
+        violation = Some(ErrorCode::AssetCooling);
+    } else if let Some(max_staleness) = max_staleness {
+        if current_time.saturating_sub(asset_risk.timestamp) > max_staleness {
+            violation = Some(ErrorCode::RiskDataStale);
+        }
+    }
+    if violation.is_none() {
+        if let Some(min_confidence_ratio) = min_confidence_ratio {
+            if asset_risk.confidence_ratio < min_confidence_ratio {
+                violation = Some(ErrorCode::ConfidenceBelowFloor);
+            }
+        }
+    }
+
+    if let Some(code) = violation {
+        if !advisory_only {
+            return Err(code.into());
+        }
+        msg!("Advisory mode: trading allowed despite {:?}", code);
+    }
+
+    Ok(TradingGate {
+        allowed: violation.is_none(),
+        advisory_only,
+        warning_code: violation.map_or(0, u32::from),
+        risk_score: asset_risk.risk_score,
+        ema_score: asset_risk.ema_score(),
+        is_blocked: asset_risk.is_blocked != 0,
+        reduce_only: asset_risk.is_reduce_only(),
+        lifecycle: asset_risk.lifecycle(),
+        risk_flags: asset_risk.risk_flags,
+        sub_scores: asset_risk.sub_scores,
+        confidence_ratio: asset_risk.confidence_ratio,
+        timestamp: asset_risk.timestamp,
+        risk_tier: asset_risk.risk_tier(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn pre_trade_gate(
+    config: &Config,
+    asset_risk: &AssetRiskStatus,
+    policy: Option<&AssetPolicy>,
+    portfolio: Option<&PortfolioRisk>,
+    side: TradeSide,
+    notional: u64,
+    score_source: ScoreSource,
+    current_time: i64,
+) -> Result<TradingGate> {
+    let mut gate = trading_gate(
+        config,
+        asset_risk,
+        policy,
+        portfolio,
+        Some(gating::staleness_window(config, policy)),
+        config.min_confidence_ratio,
+        current_time,
+    )?;
+
+    // Rampa de reabertura: fração do teto da faixa logo após um desbloqueio
+    let ramp_bps = config.reopen_factor_bps(current_time.saturating_sub(asset_risk.unblocked_at));
+    let max_notional = config
+        .max_trade_notional(asset_risk.score(score_source))
+        .map(|cap| (u128::from(cap) * u128::from(ramp_bps) / 10_000) as u64);
+
+    let violation = if gate.reduce_only && side == TradeSide::Buy {
+        Some(ErrorCode::ReduceOnlyViolation)
+    } else if max_notional.is_some_and(|cap| notional > cap) {
+        Some(ErrorCode::NotionalLimitExceeded)
+    } else {
+        None
+    };
+    if let Some(code) = violation {
+        if !gate.advisory_only {
+            return Err(code.into());
+        }
+        msg!("Advisory mode: trading allowed despite {:?}", code);
+        if gate.allowed {
+            gate.allowed = false;
+            gate.warning_code = u32::from(code);
+        }
+    }
+    Ok(gate)
+}
+
+/// Contabiliza uma chamada ao gate para o programa da instrução de topo.
+/// Só chamadas bem-sucedidas contam: um gate que falha reverte a transação.
+fn record_integration_usage(
+    stats: Option<&mut IntegrationStats>,
+    instructions_sysvar: Option<&AccountInfo>,
+    gate: &TradingGate,
+    clock: &Clock,
+) -> Result<()> {
+    let Some(stats) = stats else {
+        return Ok(());
+    };
+    let instructions_sysvar =
+        instructions_sysvar.ok_or(ErrorCode::InvalidIntegrationStatsAccount)?;
+    let consumer = top_level_program(instructions_sysvar)?;
+    require!(
+        stats.consumer == consumer && stats.epoch == clock.epoch,
+        ErrorCode::InvalidIntegrationStatsAccount
+    );
+
+    stats.gate_calls = stats.gate_calls.saturating_add(1);
+    if gate.warning_code != 0 {
+        stats.advisory_warnings = stats.advisory_warnings.saturating_add(1);
+    }
+    stats.last_call_at = clock.unix_timestamp;
+    Ok(())
+}
+
+/// Com Config.consumer_access ligado, só consumidores ativos chamam o gate
+/// via CPI; a chamada direta, de topo, continua livre. Um Consumer
+/// informado é contabilizado mesmo com o acesso aberto.
+fn authorize_consumer(
+    config: &Config,
+    consumer: Option<&mut Consumer>,
+    instructions_sysvar: Option<&AccountInfo>,
+    clock: &Clock,
+) -> Result<()> {
+    let via_cpi = get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT;
+    let Some(consumer) = consumer else {
+        require!(
+            config.consumer_access.is_none() || !via_cpi,
+            ErrorCode::ConsumerNotAuthorized
+        );
+        return Ok(());
+    };
+    let instructions_sysvar = instructions_sysvar.ok_or(ErrorCode::InvalidConsumerAccount)?;
+    require_keys_eq!(
+        consumer.program_id,
+        top_level_program(instructions_sysvar)?,
+        ErrorCode::InvalidConsumerAccount
+    );
+    if config.consumer_access.is_some() && via_cpi {
+        require!(consumer.is_active(clock.epoch), ErrorCode::ConsumerNotAuthorized);
+    }
+
+    consumer.gate_calls = consumer.gate_calls.saturating_add(1);
+    consumer.last_call_at = clock.unix_timestamp;
+    Ok(())
+}
+
+/// Programa da instrução de topo em execução: quem originou a CPI no gate
+fn top_level_program(instructions_sysvar: &AccountInfo) -> Result<Pubkey> {
+    let current_index = instructions::load_current_index_checked(instructions_sysvar)? as usize;
+    Ok(load_instruction_at_checked(current_index, instructions_sysvar)?.program_id)
+}
+
+/// Com algum TradingSchedule criado, o gate exige os PDAs global e do ativo;
+/// PDA sem conta = sem restrição. Fora do horário ou em manutenção falha
+/// mesmo em modo advisory, como o ciclo de vida.
+fn check_trading_schedule(
+    config: &Config,
+    global_schedule: Option<&AccountInfo>,
+    asset_schedule: Option<&AccountInfo>,
+    current_time: i64,
+) -> Result<()> {
+    if config.trading_schedules == 0 {
+        return Ok(());
+    }
+    for info in [global_schedule, asset_schedule] {
+        let info = info.ok_or(ErrorCode::ScheduleAccountMissing)?;
+        if info.data_is_empty() {
+            continue;
+        }
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::ScheduleAccountMissing);
+        let schedule = TradingSchedule::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(!schedule.in_maintenance(current_time), ErrorCode::MaintenanceWindowActive);
+        require!(schedule.is_open(current_time), ErrorCode::MarketClosed);
+    }
+    Ok(())
+}
+
+/// asset_id do TradingSchedule: vazio = horário global (zeros)
+fn schedule_scope(asset_id: &str) -> Result<[u8; 16]> {
+    if asset_id.is_empty() {
+        return Ok([0u8; 16]);
+    }
+    asset_id_to_bytes(asset_id)
+}
+
+// ============================================================================
+// Verificação Segura de Ed25519
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Ed25519SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u16,
+    pub public_key_offset: u16,
+    pub public_key_instruction_index: u16,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SIGNATURE_OFFSETS_LEN {
+            return Err(ErrorCode::Ed25519OffsetsTruncated.into());
+        }
+        
+        Ok(Self {
+            signature_offset: u16::from_le_bytes([bytes[0], bytes[1]]),
+            signature_instruction_index: u16::from_le_bytes([bytes[2], bytes[3]]),
+            public_key_offset: u16::from_le_bytes([bytes[4], bytes[5]]),
+            public_key_instruction_index: u16::from_le_bytes([bytes[6], bytes[7]]),
+            message_data_offset: u16::from_le_bytes([bytes[8], bytes[9]]),
+            message_data_size: u16::from_le_bytes([bytes[10], bytes[11]]),
+            message_instruction_index: u16::from_le_bytes([bytes[12], bytes[13]]),
+        })
+    }
+}
+
+fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_pubkey: &[u8; 32],
+    expected_message: &[u8; 32],
+    expected_signature: &[u8; 64],
+) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instructions_sysvar)? as usize;
+    let sysvar_data = instructions_sysvar.try_borrow_data()?;
+
+    // A instrução Ed25519 pode estar em qualquer posição anterior: compute
+    // budget, priority fee ou outras instruções podem ficar no meio
+    let mut found_program = false;
+    for index in (0..current_index).rev() {
+        let (program_id, data) = sysvar_instruction(&sysvar_data, index)?;
+        if program_id != ed25519_program::ID.as_ref() {
+            continue;
+        }
+        found_program = true;
+        if ed25519_entry_matches(
+            &sysvar_data,
+            data,
+            index as u16,
+            expected_pubkey,
+            expected_message,
+            expected_signature,
+        )? {
+            return Ok(());
+        }
+    }
+
+    require!(found_program, ErrorCode::MissingEd25519Instruction);
+    // Se chegou aqui, nenhuma assinatura correspondeu
+    Err(ErrorCode::SignatureVerificationFailed.into())
+}
+
+/// Procura a tupla (pubkey, assinatura, mensagem) nos dados de uma instrução Ed25519
+fn ed25519_entry_matches(
+    sysvar_data: &[u8],
+    data: &[u8],
+    ed25519_ix_index: u16,
+    expected_pubkey: &[u8; 32],
+    expected_message: &[u8; 32],
+    expected_signature: &[u8; 64],
+) -> Result<bool> {
+    require!(data.len() >= ED25519_INSTRUCTION_LEN, ErrorCode::Ed25519HeaderTruncated);
+    
+    let num_signatures = data[0] as usize;
+    let padding = data[1];
+    
+    require!(num_signatures >= 1, ErrorCode::Ed25519NoSignatures);
+    require!(padding == 0, ErrorCode::Ed25519InvalidPadding);
+    
+    // Calcula o tamanho esperado: header + (offsets * num_signatures) + dados
+    let expected_min_len = SIGNATURE_OFFSETS_LEN
+        .checked_mul(num_signatures)
+        .and_then(|len| len.checked_add(ED25519_INSTRUCTION_LEN))
+        .ok_or(ErrorCode::Ed25519OffsetsTruncated)?;
+    require!(data.len() >= expected_min_len, ErrorCode::Ed25519OffsetsTruncated);
+    
+    // A instrução pode conter assinaturas de outros protocolos no mesmo batch:
+    // procura apenas a tupla (pubkey, assinatura, mensagem) esperada e ignora o resto
+    for i in 0..num_signatures.min(MAX_PRECOMPILE_ENTRIES_SCANNED) {
+        let offset_start = ED25519_INSTRUCTION_LEN + (SIGNATURE_OFFSETS_LEN * i);
+        let offset_end = offset_start + SIGNATURE_OFFSETS_LEN;
+        
+        let offsets = Ed25519SignatureOffsets::from_bytes(&data[offset_start..offset_end])?;
+        
+        // Mensagens de outros protocolos podem ter qualquer tamanho
+        if offsets.message_data_size as usize != 32 {
+            continue;
+        }
+        
+        // Cada campo é lido da instrução que o programa Ed25519 de fato verificou
+        // (u16::MAX = esta instrução); índice fora da transação é rejeitado
+        let local = |index: u16| if index == u16::MAX { ed25519_ix_index } else { index };
+        let signature_data = referenced_ix_data(
+            sysvar_data,
+            local(offsets.signature_instruction_index).into(),
+        )?;
+        let pubkey_data = referenced_ix_data(
+            sysvar_data,
+            local(offsets.public_key_instruction_index).into(),
+        )?;
+        let message_data = referenced_ix_data(
+            sysvar_data,
+            local(offsets.message_instruction_index).into(),
+        )?;
+        
+        // Verifica bounds dos offsets; entradas fora dos limites não são nossas
+        let (Some(ix_signature), Some(ix_pubkey), Some(ix_message)) = (
+            slice_at(signature_data, offsets.signature_offset, ED25519_SIG_LEN),
+            slice_at(pubkey_data, offsets.public_key_offset, ED25519_PUBKEY_LEN),
+            slice_at(message_data, offsets.message_data_offset, 32),
+        ) else {
+            continue;
+        };
+        
+        // Comparação constant-time (mitiga timing attacks)
+        if secure_compare(ix_pubkey, expected_pubkey) 
+            && secure_compare(ix_signature, expected_signature)
+            && secure_compare(ix_message, expected_message) {
+            msg!("Ed25519 signature {} verified successfully", i);
+            return Ok(true);
+        }
+    }
+    
+    Ok(false)
+}
+
+pub struct Secp256k1SignatureOffsets {
+    pub signature_offset: u16,
+    pub signature_instruction_index: u8,
+    pub eth_address_offset: u16,
+    pub eth_address_instruction_index: u8,
+    pub message_data_offset: u16,
+    pub message_data_size: u16,
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SECP256K1_OFFSETS_LEN {
+            return Err(ErrorCode::InvalidSecp256k1Data.into());
+        }
+
+        Ok(Self {
+            signature_offset: u16::from_le_bytes([bytes[0], bytes[1]]),
+            signature_instruction_index: bytes[2],
+            eth_address_offset: u16::from_le_bytes([bytes[3], bytes[4]]),
+            eth_address_instruction_index: bytes[5],
+            message_data_offset: u16::from_le_bytes([bytes[6], bytes[7]]),
+            message_data_size: u16::from_le_bytes([bytes[8], bytes[9]]),
+            message_instruction_index: bytes[10],
+        })
+    }
+}
+
+/// Caminho secp256k1 (engines em HSM): como no Ed25519, a instrução do programa
+/// Secp256k1 pode estar em qualquer posição anterior a esta. A mensagem assinada
+/// é o decision_hash (o programa aplica keccak).
+fn verify_secp256k1_instruction(
+    instructions_sysvar: &AccountInfo,
+    expected_address: &[u8],
+    expected_message: &[u8; 32],
+    expected_signature: &[u8; 64],
+) -> Result<()> {
+    let current_index = instructions::load_current_index_checked(instructions_sysvar)? as usize;
+    let sysvar_data = instructions_sysvar.try_borrow_data()?;
+
+    let mut found_program = false;
+    for index in (0..current_index).rev() {
+        let (program_id, data) = sysvar_instruction(&sysvar_data, index)?;
+        if program_id != secp256k1_program::ID.as_ref() {
+            continue;
+        }
+        found_program = true;
+        if secp256k1_entry_matches(
+            &sysvar_data,
+            data,
+            expected_address,
+            expected_message,
+            expected_signature,
+        )? {
+            return Ok(());
+        }
+    }
+
+    require!(found_program, ErrorCode::MissingSecp256k1Instruction);
+    Err(ErrorCode::SignatureVerificationFailed.into())
+}
+
+/// Procura a tupla (endereço, assinatura, mensagem) nos dados de uma instrução Secp256k1
+fn secp256k1_entry_matches(
+    sysvar_data: &[u8],
+    data: &[u8],
+    expected_address: &[u8],
+    expected_message: &[u8; 32],
+    expected_signature: &[u8; 64],
+) -> Result<bool> {
+    require!(data.len() >= SECP256K1_INSTRUCTION_LEN, ErrorCode::InvalidSecp256k1Data);
+    let num_signatures = data[0] as usize;
+    require!(num_signatures >= 1, ErrorCode::InvalidSecp256k1Data);
+
+    let expected_min_len = SECP256K1_OFFSETS_LEN
+        .checked_mul(num_signatures)
+        .and_then(|len| len.checked_add(SECP256K1_INSTRUCTION_LEN))
+        .ok_or(ErrorCode::InvalidSecp256k1Data)?;
+    require!(data.len() >= expected_min_len, ErrorCode::InvalidSecp256k1Data);
+
+    // Mesma regra do caminho Ed25519: mensagem de 32 bytes, campos lidos da
+    // instrução referenciada e a tupla (endereço, assinatura, mensagem) esperada
+    for i in 0..num_signatures.min(MAX_PRECOMPILE_ENTRIES_SCANNED) {
+        let offset_start = SECP256K1_INSTRUCTION_LEN + (SECP256K1_OFFSETS_LEN * i);
+        let offsets = Secp256k1SignatureOffsets::from_bytes(
+            &data[offset_start..offset_start + SECP256K1_OFFSETS_LEN],
+        )?;
+
+        if offsets.message_data_size as usize != 32 {
+            continue;
+        }
+
+        let signature_data =
+            referenced_ix_data(sysvar_data, offsets.signature_instruction_index.into())?;
+        let address_data =
+            referenced_ix_data(sysvar_data, offsets.eth_address_instruction_index.into())?;
+        let message_data =
+            referenced_ix_data(sysvar_data, offsets.message_instruction_index.into())?;
+
+        let (Some(ix_signature), Some(ix_address), Some(ix_message)) = (
+            slice_at(signature_data, offsets.signature_offset, SECP256K1_SIG_LEN),
+            slice_at(address_data, offsets.eth_address_offset, SECP256K1_ADDRESS_LEN),
+            slice_at(message_data, offsets.message_data_offset, 32),
+        ) else {
+            continue;
+        };
+
+        if secure_compare(ix_address, expected_address)
+            && secure_compare(ix_signature, expected_signature)
+            && secure_compare(ix_message, expected_message)
+        {
+            msg!("Secp256k1 signature {} verified successfully", i);
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Shard do replay store responsável por um decision_hash
+pub fn replay_shard(decision_hash: &[u8; 32]) -> u8 {
+    REPLAY_POLICY.shard_for(decision_hash)
+}
+
+fn replay_error(err: ReplayError) -> Error {
+    match err {
+        ReplayError::TimestampOutOfWindow => error!(ErrorCode::InvalidTimestamp),
+        ReplayError::AlreadyUsed => error!(ErrorCode::DecisionAlreadyUsed),
+        ReplayError::StoreFull => error!(ErrorCode::DecisionHistoryFull),
+    }
+}
+
+/// Verifica que signers distintos do conjunto confiável, somando peso de ao
+/// menos `config.quorum`, assinaram o mesmo `decision_hash`. A chave de
+/// `session` assina no lugar do signer que a delegou, se a delegação cobre
+/// `asset_id`. Retorna os signers que contaram para o quorum.
+#[allow(clippy::too_many_arguments)]
+fn verify_quorum(
+    config: &Config,
+    instructions_sysvar: &AccountInfo,
+    decision_hash: &[u8; 32],
+    signatures: &[[u8; 64]],
+    signer_pubkeys: &[[u8; 32]],
+    session: Option<&SessionKey>,
+    asset_id: &[u8; 16],
+    current_time: i64,
+) -> Result<Vec<Pubkey>> {
+    require!(
+        signatures.len() == signer_pubkeys.len(),
+        ErrorCode::SignatureCountMismatch
+    );
+    require!(
+        signer_pubkeys.len() <= MAX_TRUSTED_SIGNERS,
+        ErrorCode::TooManySigners
+    );
+
+    let mut signers = Vec::with_capacity(signer_pubkeys.len());
+    let mut weight: u16 = 0;
+    for (signature, signer_pubkey) in signatures.iter().zip(signer_pubkeys.iter()) {
+        let key = Pubkey::new_from_array(*signer_pubkey);
+        let signer = match session.filter(|s| s.session_key == key) {
+            Some(session) => {
+                require!(session.covers(asset_id, current_time), ErrorCode::SessionKeyNotValid);
+                session.root_signer
+            }
+            None => key,
+        };
+        require!(config.is_trusted_signer(&signer, current_time), ErrorCode::InvalidSigner);
+        // A raiz e a própria sessão contam uma vez só
+        require!(!signers.contains(&signer), ErrorCode::DuplicateSigner);
+
+        verify_signer(config, instructions_sysvar, signer_pubkey, decision_hash, signature)?;
+        weight += config.signer_weight(&signer) as u16;
+        signers.push(signer);
+    }
+
+    require!(weight >= config.quorum as u16, ErrorCode::QuorumNotMet);
+    Ok(signers)
+}
+
+/// Recusa signers cujo `expires_at` já passou no timestamp assinado e emite
+/// SignerExpiring para os que vencem em até SIGNER_EXPIRY_WARNING_SECS
+fn check_signer_expiry(
+    config: &Config,
+    signers: &[Pubkey],
+    signed_at: i64,
+    current_time: i64,
+) -> Result<()> {
+    for &signer in signers {
+        let Some(expires_at) = config.signer_expires_at(&signer) else {
+            continue;
+        };
+        require!(signed_at < expires_at, ErrorCode::SignerExpired);
+        if expires_at.saturating_sub(current_time) <= SIGNER_EXPIRY_WARNING_SECS {
+            emit!(SignerExpiring { signer, expires_at, timestamp: current_time });
+        }
+    }
+    Ok(())
+}
+
+/// Assinatura de um signer sobre `message`, no esquema registrado para a chave
+fn verify_signer(
+    config: &Config,
+    instructions_sysvar: &AccountInfo,
+    signer_pubkey: &[u8; 32],
+    message: &[u8; 32],
+    signature: &[u8; 64],
+) -> Result<()> {
+    match config.key_type(&Pubkey::new_from_array(*signer_pubkey)) {
+        KeyType::Ed25519 => {
+            verify_ed25519_instruction(instructions_sysvar, signer_pubkey, message, signature)
+        }
+        KeyType::Secp256k1 => verify_secp256k1_instruction(
+            instructions_sysvar,
+            &signer_pubkey[..SECP256K1_ADDRESS_LEN],
+            message,
+            signature,
+        ),
+    }
+}
+
+/// Dados da instrução referenciada por um offset de precompile, emprestados
+/// da conta sysvar. Índice inexistente é erro.
+fn referenced_ix_data(sysvar_data: &[u8], index: usize) -> Result<&[u8]> {
+    Ok(sysvar_instruction(sysvar_data, index)?.1)
+}
+
+/// (program_id, dados) da instrução `index` lidos direto dos bytes do sysvar
+/// de instruções. Ao contrário de load_instruction_at_checked, não monta as
+/// contas nem copia os dados. Layout: u16 quantidade e u16 offset por
+/// instrução; em cada uma, u16 contas, (u8 flags + pubkey) por conta,
+/// program_id, u16 tamanho e os dados.
+fn sysvar_instruction(sysvar_data: &[u8], index: usize) -> Result<(&[u8], &[u8])> {
+    let read_u16 = |at: usize| {
+        let bytes = sysvar_data.get(at..at + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let parse = || {
+        if index >= read_u16(0)? {
+            return None;
+        }
+        let start = read_u16(2 + 2 * index)?;
+        let program_at = start + 2 + 33 * read_u16(start)?;
+        let data_at = program_at + 32 + 2;
+        let data_len = read_u16(program_at + 32)?;
+        let program_id = sysvar_data.get(program_at..program_at + 32)?;
+        Some((program_id, sysvar_data.get(data_at..data_at + data_len)?))
+    };
+    parse().ok_or_else(|| error!(ErrorCode::InvalidInstructionIndex))
+}
+
+/// Fatia `len` bytes a partir de `offset`, com checagem explícita de bounds
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Option<&[u8]> {
+    let start = offset as usize;
+    let end = start.checked_add(len)?;
+    data.get(start..end)
+}
+
+/// Comparação constant-time para prevenir timing attacks
+fn secure_compare(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut result = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        result |= x ^ y;
+    }
+    result == 0
+}
+
+// ============================================================================
+// Conta para Replay Protection
+// ============================================================================
+
+#[account]
+pub struct UsedDecisions {
+    pub bump: u8,
+    pub decisions: Vec<DecisionRecord>,
+    pub max_size: u16,
+    pub shard: u8,
+    pub generation: u64, // Config.generation na criação do shard
+    pub reset_at: i64, // Decisões com timestamp até aqui são rejeitadas
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DecisionRecord {
+    pub hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+impl UsedDecisions {
+    pub const LEN: usize = Self::space(MAX_DECISIONS_PER_SHARD);
+
+    // bump + vec len + records (hash + timestamp) + max_size + shard + generation + reset_at
+    pub const fn space(max_size: u16) -> usize {
+        1 + 4 + (40 * max_size as usize) + 2 + 1 + 8 + 8
+    }
+}
+
+impl ReplayStore for UsedDecisions {
+    fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.decisions.iter().any(|d| d.hash == *hash)
+    }
+
+    fn prune(&mut self, cutoff: i64) {
+        // Remove entradas antigas para economizar espaço
+        self.decisions.retain(|d| d.timestamp >= cutoff);
+    }
+
+    fn insert(&mut self, hash: [u8; 32], timestamp: i64) -> std::result::Result<(), ReplayError> {
+        if self.decisions.len() as u16 >= self.max_size {
+            return Err(ReplayError::StoreFull);
+        }
+        self.decisions.push(DecisionRecord { hash, timestamp });
+        Ok(())
+    }
+
+    /// Cheio, o shard funciona como ring buffer: o registro mais antigo é
+    /// sobrescrito no lugar se já saiu da retenção, sem retain() nem deslocar
+    /// o Vec. O crank prune_used_decisions continua removendo em lote.
+    fn insert_evicting(
+        &mut self,
+        hash: [u8; 32],
+        timestamp: i64,
+        cutoff: i64,
+    ) -> std::result::Result<(), ReplayError> {
+        if (self.decisions.len() as u16) < self.max_size {
+            return self.insert(hash, timestamp);
+        }
+        let oldest = self
+            .decisions
+            .iter_mut()
+            .min_by_key(|d| d.timestamp)
+            .filter(|d| d.timestamp < cutoff)
+            .ok_or(ReplayError::StoreFull)?;
+        *oldest = DecisionRecord { hash, timestamp };
+        Ok(())
+    }
+}
+
+/// Replay store do ReplayMode::Bloom: dois filtros de tamanho fixo, um por
+/// janela de `window_secs`. Ao virar a janela, o filtro da retrasada é zerado
+/// e passa a receber as inserções; um hash fica lembrado por pelo menos uma
+/// janela inteira. Consulta e registro custam `num_hashes` bits cada.
+#[account(zero_copy)]
+pub struct ReplayBloom {
+    pub window_secs: i64,
+    pub window: i64, // Índice (timestamp / window_secs) da janela atual
+    pub inserted: [u32; 2], // Inserções em cada filtro
+    pub capacity: u32, // Inserções por filtro dentro da taxa configurada
+    pub bump: u8,
+    pub num_hashes: u8,
+    pub _padding: [u8; 2],
+    pub filters: [[u8; BLOOM_FILTER_BYTES]; 2], // Filtro da janela w em filters[w % 2]
+}
+
+impl ReplayBloom {
+    pub const LEN: usize = std::mem::size_of::<ReplayBloom>();
+    pub const NUM_BITS: u32 = (BLOOM_FILTER_BYTES * 8) as u32;
+
+    /// Avança para a janela de `now`, zerando os filtros das janelas que saem
+    fn rotate(&mut self, now: i64) {
+        let window = now.div_euclid(self.window_secs);
+        if window <= self.window {
+            return;
+        }
+        for expired in (self.window + 1).max(window - 1)..=window {
+            let slot = expired.rem_euclid(2) as usize;
+            self.filters[slot] = [0; BLOOM_FILTER_BYTES];
+            self.inserted[slot] = 0;
+        }
+        self.window = window;
+    }
+
+    pub fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.filters.iter().any(|filter| {
+            (0..self.num_hashes).all(|i| {
+                let bit = bloom_bit(hash, i, Self::NUM_BITS);
+                filter[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+            })
+        })
+    }
+
+    /// Rotaciona, confere os dois filtros e marca o hash no filtro da janela
+    /// atual. Um falso positivo recusa a decisão como já usada.
+    pub fn check_and_mark(
+        &mut self,
+        hash: &[u8; 32],
+        now: i64,
+    ) -> std::result::Result<(), ReplayError> {
+        self.rotate(now);
+        if self.contains(hash) {
+            return Err(ReplayError::AlreadyUsed);
+        }
+        let slot = self.window.rem_euclid(2) as usize;
+        if self.inserted[slot] >= self.capacity {
+            return Err(ReplayError::StoreFull);
+        }
+        for i in 0..self.num_hashes {
+            let bit = bloom_bit(hash, i, Self::NUM_BITS);
+            self.filters[slot][(bit / 8) as usize] |= 1 << (bit % 8);
+        }
+        self.inserted[slot] += 1;
+        Ok(())
+    }
+}
+
+/// Cofre da recompensa do crank `prune_used_decisions`
+#[account]
+pub struct FeeVault {
+    pub bump: u8,
+    pub prune_bounty: u64, // Lamports pagos por chamada que remove registros
+    pub total_paid: u64,
+}
+
+impl FeeVault {
+    pub const LEN: usize = 1 + 8 + 8;
+}
+
+/// Recebe a taxa por atualização paga por quem envia decisões; sacado pela
+/// authority via withdraw_treasury. Tokens SPL ficam em contas cujo owner é o PDA.
+#[account]
+pub struct Treasury {
+    pub bump: u8,
+    pub lamports_collected: u64,
+    pub tokens_collected: u64, // Soma em unidades do mint vigente na cobrança
+    pub tips_paid: u64, // Lamports pagos a keepers via keeper_tip
+}
+
+impl Treasury {
+    pub const LEN: usize = 1 + 8 + 8 + 8;
+}
+
+/// Fundo para indenizar integradores por perdas em classificações erradas
+/// comprovadas do CATE. Recebe depósitos e uma fração das taxas do Treasury;
+/// só a authority paga indenizações
+#[account]
+pub struct InsuranceFund {
+    pub bump: u8,
+    pub fee_share_bps: u16, // Fração das taxas em lamports repassada pelo sweep
+    pub total_contributed: u64, // Depósitos via contribute_insurance
+    pub total_from_fees: u64, // Lamports vindos do Treasury
+    pub fees_swept_watermark: u64, // Treasury.lamports_collected no último sweep
+    pub total_paid_out: u64,
+    pub payout_count: u64,
+    pub last_payout_at: i64,
+}
+
+impl InsuranceFund {
+    pub const LEN: usize = 1 + 2 + 8 + 8 + 8 + 8 + 8 + 8;
+}
+
+/// Chave efêmera que assina decisões em nome de `root_signer` até
+/// `expires_at`, só para `asset_ids` (vazio = todos)
+#[account]
+pub struct SessionKey {
+    pub bump: u8,
+    pub session_key: Pubkey,
+    pub root_signer: Pubkey, // Signer confiável que assinou a delegação
+    pub expires_at: i64,
+    pub asset_ids: Vec<[u8; 16]>,
+    pub revoked: bool,
+    pub payer: Pubkey, // Recebe o aluguel em close_session_key
+    pub registered_at: i64,
+}
+
+impl SessionKey {
+    pub const LEN: usize = 1 + 32 + 32 + 8 + (4 + 16 * MAX_SESSION_ASSETS) + 1 + 32 + 8;
+
+    /// Delegação em vigor e válida para o ativo
+    pub fn covers(&self, asset_id: &[u8; 16], current_time: i64) -> bool {
+        !self.revoked
+            && current_time < self.expires_at
+            && (self.asset_ids.is_empty() || self.asset_ids.contains(asset_id))
+    }
+}
+
+/// Taxa cobrada por atualização aceita: lamports ou `amount` unidades de `mint`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UpdateFee {
+    pub amount: u64,
+    pub mint: Option<Pubkey>, // None = lamports
+}
+
+impl UpdateFee {
+    pub const LEN: usize = 8 + (1 + 32);
+}
+
+/// Gorjeta em lamports, paga pelo Treasury, a quem atualiza um ativo cujo
+/// dado tem pelo menos `min_age_secs`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct KeeperTip {
+    pub min_age_secs: i64,
+    pub lamports: u64,
+}
+
+impl KeeperTip {
+    pub const LEN: usize = 8 + 8;
+}
+
+/// Restrição de quem pode chamar o gate via CPI: programas na allowlist da
+/// authority ou com assinatura paga ao Treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ConsumerAccess {
+    pub fee_per_epoch: u64, // Lamports por epoch de assinatura; 0 = só allowlist
+}
+
+impl ConsumerAccess {
+    pub const LEN: usize = 8;
+}
+
+// ============================================================================
+// Horário de negociação
+// ============================================================================
+
+/// Janela semanal recorrente em UTC: dias marcados em `days` (bit 0 = domingo,
+/// bit 6 = sábado), de `start_minute` até `end_minute` exclusivo, em minutos
+/// desde 00:00. Com start > end a janela atravessa a meia-noite e termina no
+/// dia seguinte.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WeeklyWindow {
+    pub days: u8,
+    pub start_minute: u16,
+    pub end_minute: u16,
+}
+
+impl WeeklyWindow {
+    pub const LEN: usize = 1 + 2 + 2;
+    const MINUTES_PER_DAY: u16 = 1440;
+
+    pub fn is_valid(&self) -> bool {
+        self.days != 0
+            && self.days < 1 << 7
+            && self.start_minute < Self::MINUTES_PER_DAY
+            && self.end_minute < Self::MINUTES_PER_DAY
+            && self.start_minute != self.end_minute
+    }
+
+    pub fn contains(&self, unix_timestamp: i64) -> bool {
+        let days_since_epoch = unix_timestamp.div_euclid(SECONDS_PER_DAY);
+        // 01/01/1970 foi uma quinta-feira
+        let weekday = (days_since_epoch + 4).rem_euclid(7) as u8;
+        let yesterday = (weekday + 6) % 7;
+        let minute = (unix_timestamp.rem_euclid(SECONDS_PER_DAY) / 60) as u16;
+        let on = |day: u8| self.days & (1 << day) != 0;
+        if self.start_minute < self.end_minute {
+            on(weekday) && (self.start_minute..self.end_minute).contains(&minute)
+        } else {
+            (on(weekday) && minute >= self.start_minute)
+                || (on(yesterday) && minute < self.end_minute)
+        }
+    }
+}
+
+/// Manutenção pontual: bloqueia de `start` até `end` exclusivo (unix_timestamp)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl MaintenanceWindow {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn contains(&self, unix_timestamp: i64) -> bool {
+        (self.start..self.end).contains(&unix_timestamp)
+    }
+}
+
+/// Horário de um ativo ou global (asset_id zerado), aplicado pelos gates
+#[account]
+pub struct TradingSchedule {
+    pub bump: u8,
+    pub asset_id: [u8; 16], // Zeros = vale para todos os ativos
+    pub open_hours: Vec<WeeklyWindow>, // Vazio = aberto 24/7
+    pub closed_hours: Vec<WeeklyWindow>, // Manutenção recorrente
+    pub maintenance_windows: Vec<MaintenanceWindow>, // Manutenção pontual
+    pub updated_at: i64,
+}
+
+impl TradingSchedule {
+    pub const LEN: usize = 1 + 16 + (4 + WeeklyWindow::LEN * MAX_SCHEDULE_WINDOWS)
+        + (4 + WeeklyWindow::LEN * MAX_SCHEDULE_WINDOWS)
+        + (4 + MaintenanceWindow::LEN * MAX_SCHEDULE_WINDOWS) + 8;
+
+    /// Dentro do horário de negociação (sem lista = sempre)
+    pub fn is_open(&self, now: i64) -> bool {
+        self.open_hours.is_empty() || self.open_hours.iter().any(|window| window.contains(now))
+    }
+
+    pub fn in_maintenance(&self, now: i64) -> bool {
+        self.closed_hours.iter().any(|window| window.contains(now))
+            || self.maintenance_windows.iter().any(|window| window.contains(now))
+    }
+}
+
+// ============================================================================
+// Papéis
+// ============================================================================
+
+/// Papéis operacionais delegáveis pela authority; ela própria tem todos
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Updater,  // Envia decisões assinadas (update_risk_status e batch)
+    Pauser,   // Aciona o kill switch, sem poder retomar
+    Guardian, // Pausa e resolve decisões estacionadas no modo de dois engines
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RoleGrant {
+    pub key: Pubkey,
+    pub role: Role,
+}
+
+#[account]
+pub struct Roles {
+    pub bump: u8,
+    pub grants: Vec<RoleGrant>,
+}
+
+impl Roles {
+    pub const LEN: usize = 1 + 4 + MAX_ROLE_GRANTS * (32 + 1);
+
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        self.grants.iter().any(|grant| grant.key == *key && grant.role == role)
+    }
+}
+
+/// Authority ou chave com algum dos papéis na conta de papéis (opcional)
+pub fn holds_role(
+    config: &Config,
+    roles: Option<&Roles>,
+    key: &Pubkey,
+    accepted: &[Role],
+) -> bool {
+    config.authority == *key
+        || roles.is_some_and(|roles| accepted.iter().any(|role| roles.has_role(key, *role)))
+}
+
+// ============================================================================
+// Política por Ativo
+// ============================================================================
+
+/// Uso do gate por programa consumidor, agregado por epoch
+#[account]
+pub struct IntegrationStats {
+    pub bump: u8,
+    pub consumer: Pubkey, // Programa da instrução de topo que chamou o gate
+    pub epoch: u64,
+    pub gate_calls: u64,
+    pub advisory_warnings: u64, // Chamadas liberadas apenas pelo modo advisory
+    pub last_call_at: i64,
+}
+
+impl IntegrationStats {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 8 + 8;
+}
+
+/// Programa autorizado a chamar o gate via CPI com Config.consumer_access ligado
+#[account]
+pub struct Consumer {
+    pub bump: u8,
+    pub program_id: Pubkey, // Programa da instrução de topo, como em IntegrationStats
+    pub allowlisted: bool, // Liberado pela authority, sem taxa
+    pub paid_until_epoch: u64, // Assinatura vale até a epoch anterior a esta
+    pub total_paid: u64, // Lamports pagos ao Treasury
+    pub registered_at: i64,
+    pub gate_calls: u64,
+    pub last_call_at: i64,
+}
+
+impl Consumer {
+    pub const LEN: usize = 1 + 32 + 1 + 8 + 8 + 8 + 8 + 8;
+
+    pub fn is_active(&self, epoch: u64) -> bool {
+        self.allowlisted || epoch < self.paid_until_epoch
+    }
+}
+
+/// Motivo da rejeição de uma decisão, derivado do ErrorCode; índice em
+/// `Stats.rejected_updates`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RejectionClass {
+    StaleTimestamp, // Fora da janela de frescor
+    BadSigner, // Signer fora do conjunto, repetido ou quorum não atingido
+    BadSignature, // Assinatura ou decision_hash não conferem
+    BadOffsets, // Instrução Ed25519/secp256k1 ausente ou malformada
+    Replay, // Hash já usado, sequence ou replay store
+    KeyUsage, // Teto diário do signer ou emergency_block posterior
+    Oracle, // Confiança divergente do feed ou feed ausente/velho
+    Other,
+}
+
+pub const REJECTION_CLASS_COUNT: usize = 8;
+
+impl RejectionClass {
+    pub fn of(error_code: u32) -> Self {
+        use ErrorCode::*;
+        let is = |codes: &[ErrorCode]| codes.iter().any(|c| u32::from(*c) == error_code);
+        if is(&[InvalidTimestamp, DecisionExpired]) {
+            RejectionClass::StaleTimestamp
+        } else if is(&[
+            InvalidSigner,
+            TooManySigners,
+            DuplicateSigner,
+            SignatureCountMismatch,
+            QuorumNotMet,
+            SignerExpired,
+            SessionKeyNotValid,
+        ]) {
+            RejectionClass::BadSigner
+        } else if is(&[InvalidSignature, SignatureVerificationFailed, DecisionHashMismatch]) {
+            RejectionClass::BadSignature
+        } else if is(&[
+            MissingEd25519Instruction,
+            InvalidEd25519Program,
+            InvalidEd25519Data,
+            InvalidInstructionIndex,
+            SignatureOffsetOverflow,
+            PubkeyOffsetOverflow,
+            MessageOffsetOverflow,
+            InvalidMessageSize,
+            MissingSecp256k1Instruction,
+            InvalidSecp256k1Data,
+            Ed25519HeaderTruncated,
+            Ed25519NoSignatures,
+            Ed25519InvalidPadding,
+            Ed25519OffsetsTruncated,
+        ]) {
+            RejectionClass::BadOffsets
+        } else if is(&[
+            DecisionAlreadyUsed,
+            DecisionHistoryFull,
+            InvalidReplayShard,
+            SequenceNotIncreasing,
+            SequenceStoreFull,
+            StaleReplayShard,
+            ReplayBloomMissing,
+            ReplayBloomWindowTooShort,
+            ReplayShardMissing,
+            DecisionNotNewerThanAsset,
+        ]) {
+            RejectionClass::Replay
+        } else if is(&[
+            UnblockLimitExceeded,
+            ScoreDecreaseLimitExceeded,
+            DecisionPredatesEmergencyBlock,
+            EmergencySignerCannotRelax,
+        ]) {
+            RejectionClass::KeyUsage
+        } else if is(&[
+            OracleAccountMissing,
+            InvalidOracleAccount,
+            OraclePriceStale,
+            ConfidenceMismatch,
+            DecisionBelowFloor,
+        ]) {
+            RejectionClass::Oracle
+        } else {
+            RejectionClass::Other
+        }
+    }
+}
+
+/// Última rejeição registrada em Stats
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RejectionRecord {
+    pub asset_id: [u8; 16],
+    pub error_code: u32,
+    pub class: RejectionClass,
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl RejectionRecord {
+    pub const LEN: usize = 16 + 4 + 1 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct AssetUpdateCount {
+    pub asset_id: [u8; 16],
+    pub updates: u64,
+}
+
+impl AssetUpdateCount {
+    pub const LEN: usize = 16 + 8;
+}
+
+/// Contadores globais de decisões para observabilidade on-chain
+#[account]
+pub struct Stats {
+    pub bump: u8,
+    pub accepted_updates: u64,
+    pub parked_updates: u64, // Estacionadas para o guardian (dual engine)
+    pub rejected_updates: [u64; REJECTION_CLASS_COUNT], // Por RejectionClass
+    pub verifications: u64, // verify_decision bem-sucedidos
+    pub last_rejection: Option<RejectionRecord>,
+    pub asset_updates: Vec<AssetUpdateCount>, // Primeiros MAX_STATS_ASSETS ativos
+}
+
+impl Stats {
+    pub const LEN: usize = 1 + 8 + 8 + 8 * REJECTION_CLASS_COUNT + 8 + (1 + RejectionRecord::LEN)
+        + (4 + AssetUpdateCount::LEN * MAX_STATS_ASSETS);
+
+    fn record_outcome(&mut self, asset_id: &[u8; 16], outcome: DecisionOutcome) {
+        if outcome == DecisionOutcome::Parked {
+            self.parked_updates += 1;
+            return;
+        }
+        self.accepted_updates += 1;
+        match self.asset_updates.iter_mut().find(|a| a.asset_id == *asset_id) {
+            Some(entry) => entry.updates += 1,
+            None if self.asset_updates.len() < MAX_STATS_ASSETS => {
+                self.asset_updates.push(AssetUpdateCount { asset_id: *asset_id, updates: 1 });
+            }
+            None => {}
+        }
+    }
+
+    fn record_rejection(&mut self, asset_id: &[u8; 16], err: &Error, slot: u64, current_time: i64) {
+        let error_code = error_code_of(err);
+        let class = RejectionClass::of(error_code);
+        self.rejected_updates[class as usize] += 1;
+        self.last_rejection = Some(RejectionRecord {
+            asset_id: *asset_id,
+            error_code,
+            class,
+            slot,
+            timestamp: current_time,
+        });
+    }
+
+    /// Atualizações aceitas do ativo (0 se fora dos contadores por ativo)
+    pub fn asset_update_count(&self, asset_id: &[u8; 16]) -> u64 {
+        self.asset_updates
+            .iter()
+            .find(|a| a.asset_id == *asset_id)
+            .map_or(0, |a| a.updates)
+    }
+}
+
+/// Índice mint SPL -> asset_id; o estado continua no PDA do asset_id
+#[account]
+pub struct AssetMint {
+    pub bump: u8,
+    pub mint: Pubkey,
+    pub asset_id: [u8; 16],
+}
+
+impl AssetMint {
+    pub const LEN: usize = 1 + 32 + 16;
+}
+
+#[account]
+pub struct AssetPolicy {
+    pub bump: u8,
+    pub asset_id: [u8; 16],
+    pub advisory_only: bool, // Burn-in: o gate avisa mas nunca falha
+    pub updated_at: i64,
+    pub block_threshold: Option<u8>, // risk_score >= limiar força is_blocked
+    pub max_staleness_secs: Option<i64>, // Sobrescreve Config.max_staleness_secs
+    pub pyth_check: Option<PythCheck>, // Confere confidence_ratio contra o feed Pyth
+    pub switchboard_check: Option<SwitchboardCheck>, // Feed da variante switchboard
+}
+
+impl AssetPolicy {
+    pub const LEN: usize = 1 + 16 + 1 + 8 + (1 + 1) + (1 + 8) + (1 + PythCheck::LEN)
+        + (1 + SwitchboardCheck::LEN);
+
+    pub fn forces_block(&self, risk_score: u8) -> bool {
+        self.block_threshold.is_some_and(|threshold| risk_score >= threshold)
+    }
+}
+
+/// Resultado do gate retornado via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TradingGate {
+    pub allowed: bool,
+    pub advisory_only: bool,
+    pub warning_code: u32, // 0 = sem aviso; senão o código do ErrorCode violado
+    pub risk_score: u8,
+    pub ema_score: u8, // Média móvel do score (ScoreSource::Ema)
+    pub is_blocked: bool,
+    pub reduce_only: bool, // Só operações que reduzem posição
+    pub lifecycle: AssetLifecycle,
+    pub risk_flags: u8, // Categorias em alerta (RISK_FLAG_*)
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub timestamp: i64,
+    pub risk_tier: RiskTier,
+}
+
+/// Resumo de AssetRiskStatus retornado por view_risk_status (Borsh, 18 bytes)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RiskView {
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub confidence_ratio: u64, // bps
+    pub age_secs: i64, // Agora menos o timestamp da decisão assinada
+}
+
+impl RiskView {
+    pub const LEN: usize = 1 + 1 + 8 + 8;
+
+    pub fn new(status: &AssetRiskStatus, current_time: i64) -> Self {
+        Self {
+            risk_score: status.risk_score,
+            is_blocked: status.is_blocked != 0
+                || status.is_deadman_blocked()
+                || status.is_expired(current_time),
+            confidence_ratio: status.confidence_ratio,
+            age_secs: current_time.saturating_sub(status.timestamp),
+        }
+    }
+}
+
+/// Liveness do engine retornada por get_engine_health
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct EngineHealth {
+    pub last_heartbeat: i64,
+    pub silent_secs: i64, // Agora menos o último heartbeat
+    pub sla_secs: Option<i64>,
+    pub degraded: bool, // Gate bloqueia tudo enquanto verdadeiro
+}
+
+impl EngineHealth {
+    pub fn new(config: &Config, current_time: i64) -> Self {
+        Self {
+            last_heartbeat: config.last_heartbeat,
+            silent_secs: current_time.saturating_sub(config.last_heartbeat),
+            sla_secs: config.heartbeat_sla_secs,
+            degraded: config.engine_degraded(current_time),
+        }
+    }
+}
+
+/// Estado com frescor aplicado, retornado via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EffectiveStatus {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub ema_score: u8, // Média móvel do score (ScoreSource::Ema)
+    pub is_blocked: bool, // Bloqueado pelo engine, por dados velhos ou programa pausado
+    pub reported_blocked: bool, // Flag como gravada pela última decisão
+    pub is_stale: bool,
+    pub expired: bool, // Passou do valid_until da decisão assinada
+    pub breaker_active: bool,
+    pub cooling: bool, // Quarentena pós-desbloqueio
+    pub engine_degraded: bool, // Sem heartbeat dentro do SLA
+    pub reduce_only: bool,
+    pub lifecycle: AssetLifecycle,
+    pub risk_flags: u8,
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT],
+    pub confidence_ratio: u64,
+    pub last_updated: i64,
+    pub max_staleness_secs: i64, // Janela efetiva aplicada
+    pub risk_tier: RiskTier,
+}
+
+// ============================================================================
+// Dead-man Switch
+// ============================================================================
+
+/// Setor de ativos bloqueável de uma vez por block_group
+#[account]
+pub struct AssetGroup {
+    pub bump: u8,
+    pub group_id: [u8; 16],
+    pub members: Vec<[u8; 16]>,
+}
+
+impl AssetGroup {
+    pub const LEN: usize = 1 + 16 + 4 + (16 * MAX_GROUP_MEMBERS);
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortfolioEntry {
+    pub asset_id: [u8; 16],
+    pub weight: u16,
+    pub risk_score: u8, // Último score aplicado do ativo
+}
+
+impl PortfolioEntry {
+    pub const LEN: usize = 16 + 2 + 1;
+}
+
+/// Score médio ponderado dos ativos da carteira, mantido incrementalmente
+/// a cada decisão aplicada
+#[account]
+pub struct PortfolioRisk {
+    pub bump: u8,
+    pub entries: Vec<PortfolioEntry>,
+    pub weighted_sum: u64, // Σ weight * risk_score
+    pub total_weight: u64,
+    pub updated_at: i64,
+}
+
+impl PortfolioRisk {
+    pub const LEN: usize = 1 + (4 + PortfolioEntry::LEN * MAX_PORTFOLIO_ASSETS) + 8 + 8 + 8;
+
+    /// Média ponderada; 0 com a carteira vazia
+    pub fn aggregate_score(&self) -> u8 {
+        self.weighted_sum.checked_div(self.total_weight).unwrap_or(0) as u8
+    }
+
+    /// Define o peso do ativo (0 remove); `risk_score` só vale para entradas novas
+    pub fn set_weight(&mut self, asset_id: [u8; 16], weight: u16, risk_score: u8) -> Result<()> {
+        let risk_score = match self.entries.iter().position(|e| e.asset_id == asset_id) {
+            Some(index) => {
+                let entry = self.entries.swap_remove(index);
+                self.weighted_sum -= entry.weight as u64 * entry.risk_score as u64;
+                self.total_weight -= entry.weight as u64;
+                entry.risk_score
+            }
+            None => risk_score,
+        };
+        if weight == 0 {
+            return Ok(());
+        }
+        require!(self.entries.len() < MAX_PORTFOLIO_ASSETS, ErrorCode::TooManyPortfolioAssets);
+        self.entries.push(PortfolioEntry { asset_id, weight, risk_score });
+        self.weighted_sum += weight as u64 * risk_score as u64;
+        self.total_weight += weight as u64;
+        Ok(())
+    }
+
+    /// Troca o score do ativo na soma; ativos fora da carteira são ignorados
+    pub fn record_score(&mut self, asset_id: &[u8; 16], risk_score: u8, current_time: i64) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.asset_id == *asset_id) {
+            self.weighted_sum -= entry.weight as u64 * entry.risk_score as u64;
+            self.weighted_sum += entry.weight as u64 * risk_score as u64;
+            entry.risk_score = risk_score;
+            self.updated_at = current_time;
+        }
+    }
+}
+
+#[account]
+pub struct DeadmanSwitch {
+    pub bump: u8,
+    pub sentinel_asset_id: [u8; 16],
+    pub window_seconds: i64,
+    pub last_sentinel_update: i64,
+    pub block_all: bool,
+    pub triggered: bool,
+    pub triggered_at: i64,
+    pub assets: Vec<[u8; 16]>,
+}
+
+impl DeadmanSwitch {
+    pub const LEN: usize = 1 + 16 + 8 + 8 + 1 + 1 + 8 + 4 + (16 * MAX_DEADMAN_ASSETS);
+
+    pub fn covers(&self, asset_id: &[u8; 16]) -> bool {
+        self.assets.iter().any(|a| a == asset_id)
+    }
+
+    /// Decisão válida do ativo sentinela reinicia o timer; um acionamento já
+    /// feito só é encerrado por rearm_deadman
+    pub fn rearm_if_sentinel(&mut self, asset_id: &[u8; 16], current_time: i64) {
+        if self.sentinel_asset_id == *asset_id {
+            self.last_sentinel_update = current_time;
+        }
+    }
+}
+
+// ============================================================================
+// Mudanças de Política com Timelock
+// ============================================================================
+
+/// Mudança de parâmetro de política aguardando o atraso obrigatório
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum PolicyChange {
+    /// Janela global de frescor (Config.max_staleness_secs)
+    MaxStaleness { max_staleness_secs: i64 },
+    /// Tetos diários por chave (None = sem teto)
+    KeyUsageLimits {
+        max_unblocks_per_day: Option<u16>,
+        max_score_decrease_per_day: Option<u32>,
+    },
+    /// Breaker por ativo: alta de mais de `max_rise` pontos dentro de
+    /// `window_secs` bloqueia o ativo por `cooldown_secs` (None = desligado)
+    CircuitBreaker { circuit_breaker: Option<CircuitBreakerConfig> },
+    /// O próprio atraso também passa pelo timelock
+    PolicyDelay { delay_secs: i64 },
+    /// Atraso mínimo de propose_signer/propose_signer_set
+    SignerRotationDelay { delay_secs: i64 },
+    /// Janela de frescor do timestamp assinado (Config.max_past_secs/max_future_secs)
+    TimestampWindow { max_past_secs: i64, max_future_secs: i64 },
+    /// Lista de hashes usados ou sequência por signer e ativo
+    ReplayMode { replay_mode: ReplayMode },
+    /// Concordância de um segundo engine para desbloqueios e quedas grandes (None = desligado)
+    DualEngine { dual_engine: Option<DualEngineConfig> },
+    /// Esquema de assinatura de um signer (padrão Ed25519); vale também para
+    /// signers ainda não ativados
+    SignerKeyType { signer: Pubkey, key_type: KeyType },
+    /// Quarentena após desbloqueio (None = desligada)
+    UnblockCooldown { unblock_cooldown: Option<UnblockCooldown> },
+    /// Fração do teto de notional liberada logo após um desbloqueio
+    /// (vazio = teto cheio imediato); no máximo MAX_RAMP_STEPS degraus
+    ReopenRamp { steps: Vec<RampStep> },
+    /// Score agregado do PortfolioRisk a partir do qual todo o trading para
+    /// (None = desligado)
+    PortfolioBreaker { threshold: Option<u8> },
+    /// Peso de cada decisão nova na média móvel exponencial do score
+    ScoreEma { alpha_bps: u16 },
+    /// Silêncio máximo do engine antes de o gate bloquear tudo (None = sem SLA)
+    HeartbeatSla { sla_secs: Option<i64> },
+    /// Taxa por atualização paga ao Treasury (None = sem taxa)
+    UpdateFee { update_fee: Option<UpdateFee> },
+    /// Gorjeta do Treasury para quem renova dado velho (None = desligada)
+    KeeperTip { keeper_tip: Option<KeeperTip> },
+    /// CPI no gate só de consumidores cadastrados (None = aberto)
+    ConsumerAccess { consumer_access: Option<ConsumerAccess> },
+    /// Piso de confiança e teto de notional por ordem de pre_trade_check
+    /// (vazio = sem teto); no máximo MAX_NOTIONAL_TIERS faixas
+    TradeLimits { min_confidence_ratio: Option<u64>, notional_tiers: Vec<NotionalTier> },
+    /// Piso de publisher_count e confidence_ratio exigido de cada decisão
+    /// (0 = sem piso). Abaixo dele a decisão é recusada ou, com
+    /// `block_below_floor`, gravada como bloqueio.
+    DecisionFloor { min_publisher_count: u8, min_confidence_bps: u64, block_below_floor: bool },
+    /// Signer de emergência (chave fora do quorum): sozinho, só tem decisões
+    /// aceitas se bloquearem o ativo ou subirem o score
+    EmergencySigner { signer: Pubkey, enabled: bool },
+    /// Validade de uma chave (0 = sem validade): decisões assinadas a partir
+    /// de `expires_at` são recusadas
+    SignerExpiry { signer: Pubkey, expires_at: i64 },
+    /// Limites de score de cada RiskTier gravado nos ativos (None = padrão);
+    /// vale a partir da próxima decisão de cada ativo
+    RiskTiers { risk_tiers: Option<RiskTierThresholds> },
+    AssetPolicy {
+        asset_id: [u8; 16],
+        advisory_only: bool,
+        block_threshold: Option<u8>,
+        max_staleness_secs: Option<i64>,
+        pyth_check: Option<PythCheck>,
+        switchboard_check: Option<SwitchboardCheck>,
+    },
+}
+
+impl PolicyChange {
+    /// Maior variante (AssetPolicy) + tag; TradeLimits cheio ocupa 1 + 9 + 4 + 9 * 8
+    pub const MAX_LEN: usize = 1 + 16 + 1 + (1 + 1) + (1 + 8) + (1 + PythCheck::LEN)
+        + (1 + SwitchboardCheck::LEN);
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            PolicyChange::MaxStaleness { max_staleness_secs } => {
+                require!(*max_staleness_secs > 0, ErrorCode::InvalidStalenessWindow);
+            }
+            PolicyChange::KeyUsageLimits { .. } => {}
+            PolicyChange::CircuitBreaker { circuit_breaker } => {
+                if let Some(breaker) = circuit_breaker.as_ref() {
+                    require!(
+                        breaker.max_rise > 0
+                            && breaker.max_rise <= 100
+                            && breaker.window_secs > 0
+                            && breaker.cooldown_secs > 0,
+                        ErrorCode::InvalidCircuitBreakerConfig
+                    );
+                }
+            }
+            PolicyChange::PolicyDelay { delay_secs }
+            | PolicyChange::SignerRotationDelay { delay_secs } => {
+                require!(*delay_secs >= 0, ErrorCode::InvalidPolicyDelay);
+            }
+            PolicyChange::ReplayMode { .. } => {}
+            PolicyChange::TimestampWindow { max_past_secs, max_future_secs } => {
+                // Hashes são lembrados por retention_secs: uma janela maior
+                // deixaria decisões esquecidas voltarem a ser aceitas
+                require!(
+                    *max_past_secs > 0
+                        && *max_past_secs <= REPLAY_POLICY.retention_secs
+                        && *max_future_secs >= 0
+                        && *max_future_secs <= REPLAY_POLICY.retention_secs,
+                    ErrorCode::InvalidTimestampWindow
+                );
+            }
+            PolicyChange::DualEngine { dual_engine } => {
+                if let Some(dual) = dual_engine.as_ref() {
+                    require!(
+                        dual.secondary_signer != Pubkey::default()
+                            && dual.guardian != Pubkey::default()
+                            && dual.tolerance <= 100
+                            && dual.large_decrease <= 100,
+                        ErrorCode::InvalidDualEngineConfig
+                    );
+                }
+            }
+            PolicyChange::SignerKeyType { signer, key_type } => {
+                // Signer secp256k1 = endereço de 20 bytes seguido de zeros
+                if *key_type == KeyType::Secp256k1 {
+                    require!(
+                        signer.as_ref()[SECP256K1_ADDRESS_LEN..].iter().all(|b| *b == 0),
+                        ErrorCode::InvalidSignerKeyType
+                    );
+                }
+            }
+            PolicyChange::UnblockCooldown { unblock_cooldown } => {
+                if let Some(cooldown) = unblock_cooldown.as_ref() {
+                    require!(
+                        cooldown.cooldown_secs >= 0
+                            && (cooldown.cooldown_secs > 0 || cooldown.required_decisions > 0),
+                        ErrorCode::InvalidUnblockCooldown
+                    );
+                }
+            }
+            PolicyChange::ReopenRamp { steps } => {
+                require!(
+                    steps.len() <= MAX_RAMP_STEPS
+                        && steps.iter().all(|step| step.until_secs > 0 && step.factor_bps <= 10_000)
+                        && steps.windows(2).all(|pair| pair[0].until_secs < pair[1].until_secs),
+                    ErrorCode::InvalidReopenRamp
+                );
+            }
+            PolicyChange::PortfolioBreaker { threshold } => {
+                if let Some(threshold) = threshold {
+                    require!(
+                        *threshold > 0 && *threshold <= 100,
+                        ErrorCode::InvalidPortfolioBreaker
+                    );
+                }
+            }
+            PolicyChange::HeartbeatSla { sla_secs } => {
+                if let Some(sla) = sla_secs {
+                    require!(*sla > 0, ErrorCode::InvalidHeartbeatSla);
+                }
+            }
+            PolicyChange::UpdateFee { update_fee } => {
+                if let Some(fee) = update_fee {
+                    require!(fee.amount > 0, ErrorCode::InvalidUpdateFee);
+                }
+            }
+            PolicyChange::KeeperTip { keeper_tip } => {
+                if let Some(tip) = keeper_tip {
+                    require!(
+                        tip.min_age_secs > 0 && tip.lamports > 0,
+                        ErrorCode::InvalidKeeperTip
+                    );
+                }
+            }
+            PolicyChange::ScoreEma { alpha_bps } => {
+                require!(*alpha_bps > 0 && *alpha_bps <= 10_000, ErrorCode::InvalidScoreEmaAlpha);
+            }
+            PolicyChange::ConsumerAccess { .. } => {}
+            PolicyChange::DecisionFloor { min_confidence_bps, .. } => {
+                require!(*min_confidence_bps <= 10_000, ErrorCode::InvalidConfidenceRatio);
+            }
+            PolicyChange::EmergencySigner { .. } => {}
+            PolicyChange::RiskTiers { risk_tiers } => {
+                if let Some(tiers) = risk_tiers {
+                    require!(tiers.is_valid(), ErrorCode::InvalidRiskTierThresholds);
+                }
+            }
+            PolicyChange::SignerExpiry { expires_at, .. } => {
+                require!(*expires_at >= 0, ErrorCode::InvalidSignerExpiry);
+            }
+            PolicyChange::TradeLimits { notional_tiers, .. } => {
+                require!(
+                    notional_tiers.len() <= MAX_NOTIONAL_TIERS
+                        && notional_tiers.windows(2).all(|pair| pair[0].max_score < pair[1].max_score),
+                    ErrorCode::InvalidNotionalTiers
+                );
+            }
+            PolicyChange::AssetPolicy {
+                asset_id,
+                block_threshold,
+                max_staleness_secs,
+                pyth_check,
+                switchboard_check,
+                ..
+            } => {
+                require!(asset_id[0] != 0, ErrorCode::AssetIdEmpty);
+                if let Some(threshold) = block_threshold {
+                    require!(*threshold <= 100, ErrorCode::InvalidBlockThreshold);
+                }
+                if let Some(window) = max_staleness_secs {
+                    require!(*window > 0, ErrorCode::InvalidStalenessWindow);
+                }
+                if let Some(check) = pyth_check {
+                    require!(
+                        check.feed_id != [0u8; 32]
+                            && check.tolerance_bps <= 10_000
+                            && check.max_age_secs > 0,
+                        ErrorCode::InvalidPythCheck
+                    );
+                }
+                if let Some(check) = switchboard_check {
+                    require!(
+                        check.feed != Pubkey::default() && check.max_age_secs > 0,
+                        ErrorCode::InvalidSwitchboardCheck
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Decisão estacionada por discordância entre engines, aguardando o guardian
+#[account]
+pub struct PendingDecision {
+    pub bump: u8,
+    pub asset_id: [u8; 16],
+    pub payload: Option<DecisionPayload>, // None = nada pendente
+    pub decision_hash: [u8; 32],
+    pub signature: [u8; 64],
+    pub signer_pubkey: [u8; 32],
+    pub signer_count: u8,
+    pub secondary_score: Option<u8>, // None = secundário não assinou
+    pub parked_at: i64,
+}
+
+impl PendingDecision {
+    // Borsh grava is_blocked e reduce_only em bytes separados: +1 sobre o payload canônico
+    pub const LEN: usize =
+        1 + 16 + (1 + DecisionPayload::LEN + 1) + 32 + 64 + 32 + 1 + (1 + 1) + 8;
+}
+
+#[account]
+pub struct PendingPolicyChange {
+    pub bump: u8,
+    pub id: u64,
+    pub change: PolicyChange,
+    pub queued_at: i64,
+    pub eta: i64, // Aplicável a partir deste unix_timestamp
+}
+
+impl PendingPolicyChange {
+    pub const LEN: usize = 1 + 8 + PolicyChange::MAX_LEN + 8 + 8;
+}
+
+// ============================================================================
+// Histórico de Risco
+// ============================================================================
+
+#[zero_copy]
+pub struct RiskHistoryEntry {
+    pub timestamp: i64, // Timestamp assinado da decisão
+    pub risk_score: u8,
+    pub is_blocked: u8,
+    pub _padding: [u8; 6],
+}
+
+/// Ring buffer dos últimos RISK_HISTORY_LEN scores do ativo, para regras
+/// baseadas em tendência (ex.: velocidade de deterioração)
+#[account(zero_copy)]
+pub struct AssetRiskHistory {
+    pub asset_id: [u8; 16],
+    pub bump: u8,
+    pub head: u8, // Próxima posição a escrever
+    pub len: u8,
+    pub _padding: [u8; 5],
+    pub entries: [RiskHistoryEntry; RISK_HISTORY_LEN],
+}
+
+impl AssetRiskHistory {
+    pub const LEN: usize = std::mem::size_of::<AssetRiskHistory>();
+
+    pub fn push(&mut self, status: &AssetRiskStatus) {
+        self.entries[self.head as usize] = RiskHistoryEntry {
+            timestamp: status.timestamp,
+            risk_score: status.risk_score,
+            is_blocked: status.is_blocked,
+            _padding: [0; 6],
+        };
+        self.head = ((self.head as usize + 1) % RISK_HISTORY_LEN) as u8;
+        self.len = (self.len as usize + 1).min(RISK_HISTORY_LEN) as u8;
+    }
+
+    /// Maior alta do score mais recente em relação às entradas dentro de
+    /// `window_secs` antes dele (0 se o score caiu ou não há histórico)
+    pub fn max_rise_within(&self, window_secs: i64) -> u8 {
+        let mut entries = self.recent();
+        let Some(latest) = entries.next() else {
+            return 0;
+        };
+        let since = latest.timestamp.saturating_sub(window_secs);
+        entries
+            .take_while(|entry| entry.timestamp >= since)
+            .map(|entry| latest.risk_score.saturating_sub(entry.risk_score))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Entradas da mais recente para a mais antiga
+    pub fn recent(&self) -> impl Iterator<Item = &RiskHistoryEntry> {
+        let head = self.head as usize;
+        (1..=self.len as usize)
+            .map(move |i| &self.entries[(head + RISK_HISTORY_LEN - i) % RISK_HISTORY_LEN])
+    }
+}
+
+// ============================================================================
+// Uso de Chaves
+// ============================================================================
+
+/// Contadores diários de decisões que reduzem risco, por signer
+#[account]
+pub struct SignerUsage {
+    pub bump: u8,
+    pub signer: Pubkey,
+    pub day: i64, // unix_timestamp / 86400 dos contadores atuais
+    pub unblock_count: u16,
+    pub score_decrease: u32, // Soma das reduções de risk_score no dia
+}
+
+impl SignerUsage {
+    pub const LEN: usize = 1 + 32 + 8 + 2 + 4;
+
+    /// Vira o dia se necessário e contabiliza a decisão, falhando acima do teto
+    pub fn record(
+        &mut self,
+        config: &Config,
+        day: i64,
+        unblocks: u16,
+        score_decrease: u32,
+    ) -> Result<()> {
+        if self.day != day {
+            self.day = day;
+            self.unblock_count = 0;
+            self.score_decrease = 0;
+        }
+
+        let unblock_count = self.unblock_count.saturating_add(unblocks);
+        if let Some(max) = config.max_unblocks_per_day {
+            require!(unblock_count <= max, ErrorCode::UnblockLimitExceeded);
+        }
+        let total_decrease = self.score_decrease.saturating_add(score_decrease);
+        if let Some(max) = config.max_score_decrease_per_day {
+            require!(total_decrease <= max, ErrorCode::ScoreDecreaseLimitExceeded);
+        }
+
+        self.unblock_count = unblock_count;
+        self.score_decrease = total_decrease;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Accounts
+// ============================================================================
+
+#[account]
+pub struct Config {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub is_initialized: bool,
+    pub trusted_signers: Vec<Pubkey>,
+    pub quorum: u8, // Peso mínimo somado das assinaturas (peso 1 por padrão)
+    pub pending_authority: Option<Pubkey>, // Transferência em duas etapas
+    pub pending_signer_set: Option<PendingSignerSet>, // Rotação com timelock
+    pub signer_rotation_delay_secs: i64,
+    pub old_signers: Vec<Pubkey>, // Conjunto anterior, aceito até rotation_deadline
+    pub rotation_deadline: i64,
+    pub genesis_hash: [u8; 32], // Separação de domínio por cluster
+    pub paused: bool, // Kill switch global
+    pub max_staleness_secs: i64, // Leituras mais antigas que isso contam como bloqueadas
+    pub max_unblocks_per_day: Option<u16>, // Teto por signer
+    pub max_score_decrease_per_day: Option<u32>, // Teto por signer
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub policy_delay_secs: i64, // Timelock das mudanças de política
+    pub dual_engine: Option<DualEngineConfig>, // None = engine único
+    pub max_past_secs: i64, // Idade máxima aceita do timestamp assinado
+    pub max_future_secs: i64, // Tolerância para relógio adiantado do engine
+    pub replay_mode: ReplayMode,
+    pub generation: u64, // Slot da (re)inicialização; shards de outra geração são recusados
+    pub signer_key_types: Vec<SignerKeyType>, // Só signers não-Ed25519
+    pub min_confidence_ratio: Option<u64>, // Piso de pre_trade_check
+    pub notional_tiers: Vec<NotionalTier>, // Teto por ordem em pre_trade_check
+    pub unblock_cooldown: Option<UnblockCooldown>, // None = desbloqueio imediato
+    pub reopen_ramp: Vec<RampStep>, // Teto gradual após desbloqueio
+    pub portfolio_breaker: Option<u8>, // Score agregado que bloqueia todo o trading
+    pub score_ema_alpha_bps: u16, // Peso da decisão nova na média móvel (10000 = sem suavização)
+    pub last_heartbeat: i64, // Timestamp assinado do último heartbeat do engine
+    pub heartbeat_sla_secs: Option<i64>, // Silêncio maior que isso degrada o sistema inteiro
+    pub update_fee: Option<UpdateFee>, // Cobrada de cada atualização aceita
+    pub keeper_tip: Option<KeeperTip>, // Paga a quem renova dado velho
+    pub nonce: u64, // Para tracking de operações (id das mudanças de política)
+    // Campos novos entram a partir daqui, ao final, e sobem CONFIG_VERSION:
+    // contas antigas realocadas os leem zerados
+    pub version: u8,
+    pub consumer_access: Option<ConsumerAccess>, // None = CPI no gate livre para qualquer programa
+    pub trading_schedules: u16, // TradingSchedule existentes; > 0 exige as contas no gate
+    pub min_publisher_count: u8, // Piso de publisher_count das decisões (0 = sem piso)
+    pub min_confidence_bps: u64, // Piso de confidence_ratio das decisões (0 = sem piso)
+    pub block_below_floor: bool, // Abaixo do piso: grava bloqueado em vez de recusar
+    pub signer_weights: Vec<SignerWeight>, // Só pesos != 1, atuais e anteriores na graça
+    pub pending_signer_weights: Vec<SignerWeight>, // Pesos de pending_signer_set
+    pub emergency_signers: Vec<Pubkey>, // Só bloqueiam ou sobem o score
+    pub signer_expiries: Vec<SignerExpiry>, // Só signers com validade
+    pub risk_tier_thresholds: Option<RiskTierThresholds>, // None = RiskTierThresholds::DEFAULT
+}
+
+impl Config {
+    pub const LEN: usize = 1 + 32 + 1 + (4 + 32 * MAX_TRUSTED_SIGNERS) + 1 + (1 + 32)
+        + (1 + PendingSignerSet::LEN) + 8 + (4 + 32 * MAX_TRUSTED_SIGNERS) + 8 + 32 + 1 + 8
+        + (1 + 2) + (1 + 4) + (1 + CircuitBreakerConfig::LEN) + 8
+        + (1 + DualEngineConfig::LEN) + 8 + 8 + 1 + 8
+        + (4 + SignerKeyType::LEN * MAX_TRUSTED_SIGNERS) + (1 + 8)
+        + (4 + NotionalTier::LEN * MAX_NOTIONAL_TIERS) + (1 + UnblockCooldown::LEN)
+        + (4 + RampStep::LEN * MAX_RAMP_STEPS) + (1 + 1) + 2 + 8 + (1 + 8)
+        + (1 + UpdateFee::LEN) + (1 + KeeperTip::LEN) + 8 + 1 // + nonce, version
+        + (1 + ConsumerAccess::LEN) + 2 + 1 + 8 + 1
+        + (4 + SignerWeight::LEN * 2 * MAX_TRUSTED_SIGNERS)
+        + (4 + SignerWeight::LEN * MAX_TRUSTED_SIGNERS) + (4 + 32 * MAX_TRUSTED_SIGNERS)
+        + (4 + SignerExpiry::LEN * MAX_TRUSTED_SIGNERS) + (1 + RiskTierThresholds::LEN);
+
+    /// Valores padrão de um Config novo ou reinicializado (nonce fica com o chamador)
+    fn fresh(
+        bump: u8,
+        authority: Pubkey,
+        trusted_signer: Pubkey,
+        genesis_hash: [u8; 32],
+        generation: u64,
+    ) -> Self {
+        Self {
+            bump,
+            authority,
+            is_initialized: true,
+            // Começa com 1-of-1; use propose_signer_set para quorum M-of-N
+            trusted_signers: vec![trusted_signer],
+            quorum: 1,
+            pending_authority: None,
+            pending_signer_set: None,
+            signer_rotation_delay_secs: DEFAULT_SIGNER_ROTATION_DELAY_SECS,
+            old_signers: Vec::new(),
+            rotation_deadline: 0,
+            // Genesis hash do cluster não é acessível on-chain; fixado na inicialização
+            genesis_hash,
+            paused: false,
+            max_staleness_secs: DEFAULT_MAX_STALENESS_SECS,
+            max_unblocks_per_day: None,
+            max_score_decrease_per_day: None,
+            circuit_breaker: None,
+            policy_delay_secs: DEFAULT_POLICY_DELAY_SECS,
+            dual_engine: None,
+            max_past_secs: REPLAY_POLICY.max_past_secs,
+            max_future_secs: REPLAY_POLICY.max_future_secs,
+            replay_mode: ReplayMode::HashList,
+            generation,
+            signer_key_types: Vec::new(),
+            min_confidence_ratio: None,
+            notional_tiers: Vec::new(),
+            unblock_cooldown: None,
+            reopen_ramp: Vec::new(),
+            portfolio_breaker: None,
+            score_ema_alpha_bps: DEFAULT_SCORE_EMA_ALPHA_BPS,
+            last_heartbeat: 0,
+            heartbeat_sla_secs: None,
+            update_fee: None,
+            keeper_tip: None,
+            nonce: 0,
+            version: CONFIG_VERSION,
+            consumer_access: None,
+            trading_schedules: 0,
+            min_publisher_count: 0,
+            min_confidence_bps: 0,
+            block_below_floor: false,
+            signer_weights: Vec::new(),
+            pending_signer_weights: Vec::new(),
+            emergency_signers: Vec::new(),
+            signer_expiries: Vec::new(),
+            risk_tier_thresholds: None,
+        }
+    }
+
+    /// Limites de RiskTier em vigor (os configurados ou o padrão)
+    pub fn risk_tier_thresholds(&self) -> RiskTierThresholds {
+        self.risk_tier_thresholds.unwrap_or(RiskTierThresholds::DEFAULT)
+    }
+
+    /// Teto de notional por ordem para o score: a primeira faixa com
+    /// `max_score >= risk_score`; acima da última, zero. None sem faixas.
+    pub fn max_trade_notional(&self, risk_score: u8) -> Option<u64> {
+        if self.notional_tiers.is_empty() {
+            return None;
+        }
+        Some(
+            self.notional_tiers
+                .iter()
+                .find(|tier| risk_score <= tier.max_score)
+                .map_or(0, |tier| tier.max_notional),
+        )
+    }
+
+    /// Fração do teto liberada `elapsed` segundos após o desbloqueio: o
+    /// primeiro degrau com `until_secs` ainda não alcançado; depois, 100%
+    pub fn reopen_factor_bps(&self, elapsed: i64) -> u16 {
+        self.reopen_ramp
+            .iter()
+            .find(|step| elapsed < step.until_secs)
+            .map_or(10_000, |step| step.factor_bps)
+    }
+
+    /// Desserializa um Config de versão anterior: campos ausentes no fim da
+    /// conta são lidos como zero (versão 0, None, Vec vazio)
+    pub fn try_deserialize_tolerant(data: &[u8]) -> Result<Self> {
+        Self::try_deserialize(&mut zero_extended(data, 8 + Self::LEN).as_slice())
+    }
+
+    /// Authority gravada em dados de Config de qualquer versão: bump e
+    /// authority são os primeiros campos desde o layout original
+    pub fn stored_authority(data: &[u8]) -> Option<Pubkey> {
+        if !data.starts_with(Config::DISCRIMINATOR) {
+            return None;
+        }
+        let bytes: [u8; 32] = data.get(9..41)?.try_into().ok()?;
+        Some(Pubkey::new_from_array(bytes))
+    }
+
+    /// Signers atuais, mais os anteriores enquanto a janela de graça não acabou
+    pub fn is_trusted_signer(&self, key: &Pubkey, current_time: i64) -> bool {
+        self.trusted_signers.iter().any(|s| s == key)
+            || (current_time < self.rotation_deadline && self.old_signers.iter().any(|s| s == key))
+    }
+
+    /// Assinatura única de um signer de emergência que não está no conjunto
+    /// confiável; vale a regra restrita de try_apply_decision
+    pub fn is_emergency_decision(&self, signer_pubkeys: &[[u8; 32]], current_time: i64) -> bool {
+        match signer_pubkeys {
+            [key] => {
+                let signer = Pubkey::new_from_array(*key);
+                self.emergency_signers.contains(&signer)
+                    && !self.is_trusted_signer(&signer, current_time)
+            }
+            _ => false,
+        }
+    }
+
+    /// Esquema de assinatura do signer; ausente da lista = Ed25519
+    pub fn key_type(&self, signer: &Pubkey) -> KeyType {
+        self.signer_key_types
+            .iter()
+            .find(|entry| entry.signer == *signer)
+            .map_or(KeyType::Ed25519, |entry| entry.key_type)
+    }
+
+    /// Peso do signer no quorum; ausente da tabela = 1
+    pub fn signer_weight(&self, signer: &Pubkey) -> u8 {
+        SignerWeight::lookup(&self.signer_weights, signer)
+    }
+
+    /// Validade da chave; None = sem validade
+    pub fn signer_expires_at(&self, signer: &Pubkey) -> Option<i64> {
+        self.signer_expiries
+            .iter()
+            .find(|entry| entry.signer == *signer)
+            .map(|entry| entry.expires_at)
+    }
+
+    /// Decisão com menos publishers ou confiança menor que o piso do Config
+    pub fn below_decision_floor(&self, payload: &DecisionPayload) -> bool {
+        payload.publisher_count < self.min_publisher_count
+            || payload.confidence_ratio < self.min_confidence_bps
+    }
+
+    /// Com SLA configurado, engine sem heartbeat há mais de `heartbeat_sla_secs`
+    pub fn engine_degraded(&self, current_time: i64) -> bool {
+        self.heartbeat_sla_secs
+            .is_some_and(|sla| current_time.saturating_sub(self.last_heartbeat) > sla)
+    }
+
+    /// REPLAY_POLICY com a janela de frescor configurada
+    pub fn replay_policy(&self) -> ReplayPolicy {
+        ReplayPolicy {
+            max_past_secs: self.max_past_secs,
+            max_future_secs: self.max_future_secs,
+            ..REPLAY_POLICY
+        }
+    }
+}
+
+/// Conjunto de signers proposto, ativável a partir de `effective_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingSignerSet {
+    pub signers: Vec<Pubkey>,
+    pub quorum: u8,
+    pub effective_at: i64,
+}
+
+impl PendingSignerSet {
+    pub const LEN: usize = (4 + 32 * MAX_TRUSTED_SIGNERS) + 1 + 8;
+}
+
+/// Classe do ativo no cadastro
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetCategory {
+    Major,
+    Stablecoin,
+    Lst,
+    Memecoin,
+    Other,
+}
+
+/// Cadastro do ativo: mint SPL, feed Pyth e metadados fixados pela authority
+#[account]
+pub struct AssetMeta {
+    pub bump: u8,
+    pub asset_id: [u8; 16],
+    pub mint: Pubkey,
+    pub pyth_feed: [u8; 32], // feed_id do Pyth pull oracle
+    pub category: AssetCategory,
+    pub decimals: u8,
+    pub registered_at: i64,
+}
+
+impl AssetMeta {
+    pub const LEN: usize = 1 + 16 + 32 + 32 + 1 + 1 + 8;
+}
+
+/// Ciclo de vida administrativo do ativo, independente do risco
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetLifecycle {
+    Listed,    // Cadastrado, ainda sem trading
+    Live,      // Operação normal
+    Delisting, // Reduce-only até a saída
+    Delisted,  // Terminal: sem trading nem novas decisões
+}
+
+impl AssetLifecycle {
+    /// Inverso de `as u8`, para o campo u8 do AssetRiskStatus zero-copy
+    pub fn from_u8(value: u8) -> Option<Self> {
+        use AssetLifecycle::*;
+        [Listed, Live, Delisting, Delisted].get(usize::from(value)).copied()
+    }
+
+    pub fn can_transition_to(self, next: AssetLifecycle) -> bool {
+        use AssetLifecycle::*;
+        matches!(
+            (self, next),
+            (Listed, Live)
+                | (Listed, Delisted)
+                | (Live, Delisting)
+                | (Delisting, Live)
+                | (Delisting, Delisted)
+        )
+    }
+}
+
+/// Faixa de risco derivada do risk_score pelos limites do Config e gravada
+/// em cada decisão: integradores decidem pela faixa em vez de escolher os
+/// próprios cortes de score
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RiskTier {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl RiskTier {
+    /// Inverso de `as u8`, para o campo u8 do AssetRiskStatus zero-copy
+    pub fn from_u8(value: u8) -> Option<Self> {
+        use RiskTier::*;
+        [Low, Medium, High, Critical].get(usize::from(value)).copied()
+    }
+}
+
+/// Menor risk_score de cada faixa acima de Low
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RiskTierThresholds {
+    pub medium: u8,
+    pub high: u8,
+    pub critical: u8,
+}
+
+impl RiskTierThresholds {
+    pub const LEN: usize = 1 + 1 + 1;
+
+    /// Alinhado ao RISK_SCORE_MODEL.md: até 30 ALLOW, a partir de 70 BLOCK
+    pub const DEFAULT: Self = Self { medium: 31, high: 70, critical: 90 };
+
+    /// Limites estritamente crescentes, dentro da escala 1..=100
+    pub fn is_valid(&self) -> bool {
+        0 < self.medium && self.medium < self.high && self.high < self.critical
+            && self.critical <= 100
+    }
+
+    pub fn tier(&self, risk_score: u8) -> RiskTier {
+        if risk_score >= self.critical {
+            RiskTier::Critical
+        } else if risk_score >= self.high {
+            RiskTier::High
+        } else if risk_score >= self.medium {
+            RiskTier::Medium
+        } else {
+            RiskTier::Low
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CircuitBreakerConfig {
+    pub max_rise: u8, // Pontos de risk_score
+    pub window_secs: i64,
+    pub cooldown_secs: i64,
+}
+
+impl CircuitBreakerConfig {
+    pub const LEN: usize = 1 + 8 + 8;
+}
+
+/// Quarentena de um ativo recém-desbloqueado: trading só volta depois de
+/// `cooldown_secs` e de `required_decisions` decisões seguidas sem bloqueio
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UnblockCooldown {
+    pub cooldown_secs: i64,
+    pub required_decisions: u8,
+}
+
+impl UnblockCooldown {
+    pub const LEN: usize = 8 + 1;
+}
+
+/// Feed Pyth do ativo e tolerância para a confiança declarada pelo engine
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PythCheck {
+    pub feed_id: [u8; 32],
+    pub tolerance_bps: u64, // Diferença máxima entre confidence_ratio e conf/price
+    pub max_age_secs: i64, // Idade máxima do PriceUpdateV2 passado na atualização
+}
+
+impl PythCheck {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+/// Feed Switchboard On-Demand do ativo para update_risk_status_switchboard
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwitchboardCheck {
+    pub feed: Pubkey, // Conta PullFeedAccountData
+    pub max_age_secs: i64, // Idade máxima de last_update_timestamp
+}
+
+impl SwitchboardCheck {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Esquema de assinatura de um signer confiável
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    /// Instrução do programa Ed25519; a chave é a própria Pubkey
+    Ed25519,
+    /// Instrução do programa Secp256k1; a Pubkey carrega o endereço de 20 bytes
+    Secp256k1,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SignerKeyType {
+    pub signer: Pubkey,
+    pub key_type: KeyType,
+}
+
+impl SignerKeyType {
+    pub const LEN: usize = 32 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignerExpiry {
+    pub signer: Pubkey,
+    pub expires_at: i64,
+}
+
+impl SignerExpiry {
+    pub const LEN: usize = 32 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignerWeight {
+    pub signer: Pubkey,
+    pub weight: u8,
+}
+
+impl SignerWeight {
+    pub const LEN: usize = 32 + 1;
+
+    /// Peso de `signer` numa tabela de pesos; ausente = 1
+    pub fn lookup(table: &[SignerWeight], signer: &Pubkey) -> u8 {
+        table.iter().find(|entry| entry.signer == *signer).map_or(1, |entry| entry.weight)
+    }
+}
+
+/// Scores até `max_score` (inclusive) aceitam ordens de até `max_notional`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct NotionalTier {
+    pub max_score: u8,
+    pub max_notional: u64,
+}
+
+impl NotionalTier {
+    pub const LEN: usize = 1 + 8;
+}
+
+/// Até `until_secs` após o desbloqueio vale `factor_bps` do teto normal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct RampStep {
+    pub until_secs: i64,
+    pub factor_bps: u16,
+}
+
+impl RampStep {
+    pub const LEN: usize = 8 + 2;
+}
+
+/// Score usado pelo integrador: o da última decisão ou a média móvel, que
+/// filtra o ruído do engine e evita bloqueios espúrios
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScoreSource {
+    #[default]
+    Raw,
+    Ema,
+}
+
+/// Lado da ordem em pre_trade_check; `Sell` é o que reduz exposição comprada
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Identidade de um signer secp256k1 em trusted_signers/signer_pubkeys
+pub fn secp256k1_signer(eth_address: &[u8; 20]) -> Pubkey {
+    let mut bytes = [0u8; 32];
+    bytes[..SECP256K1_ADDRESS_LEN].copy_from_slice(eth_address);
+    Pubkey::new_from_array(bytes)
+}
+
+/// Como update_risk_status rejeita decisões repetidas
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Hashes usados nos shards UsedDecisions (compatível com engines sem sequence)
+    HashList,
+    /// `sequence` maior que a última aceita de cada signer no ativo; O(1), sem shard
+    Sequence,
+    /// Hashes marcados no ReplayBloom; tamanho e custo fixos, mas um falso
+    /// positivo recusa uma decisão nova, que o engine reassina
+    Bloom,
+    /// Comparação com a última decisão gravada no próprio AssetRiskStatus:
+    /// exige hash diferente, além do timestamp estritamente maior que todos os
+    /// modos exigem. Sem conta compartilhada entre ativos.
+    PerAsset,
+}
+
+/// Última sequence aceita de um signer em um ativo
+#[zero_copy]
+pub struct SignerSequence {
+    pub signer: Pubkey,
+    pub last_sequence: u64,
+}
+
+impl SignerSequence {
+    pub const LEN: usize = 32 + 8;
+}
+
+/// Quem forçou o estado do ativo, quando e por quê
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct OverrideRecord {
+    pub authority: Pubkey,
+    pub timestamp: i64,
+    pub reason_code: u16, // Código definido pela operação (runbook)
+    pub is_blocked: bool,
+}
+
+impl OverrideRecord {
+    pub const LEN: usize = 32 + 8 + 2 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DualEngineConfig {
+    pub secondary_signer: Pubkey, // Chave do engine independente
+    pub guardian: Pubkey, // Revisa as decisões estacionadas
+    pub tolerance: u8, // Diferença máxima de risk_score entre os engines
+    pub large_decrease: u8, // Quedas de score acima disso exigem concordância
+}
+
+impl DualEngineConfig {
+    pub const LEN: usize = 32 + 32 + 1 + 1;
+}
+
+/// Estado de risco do ativo em layout zero-copy (`#[repr(C)]`, sem padding
+/// implícito): programas integradores leem os campos direto dos bytes da
+/// conta, sem Borsh (ver `cate_reader::layout`). Campos de 8 bytes primeiro,
+/// depois arrays de bytes, u16 e u8; bools são u8 (0/1) e Options viram
+/// valor + flag. Campos novos saem de `_reserved`, sem mudar o tamanho.
+#[account(zero_copy)]
+pub struct AssetRiskStatus {
+    pub last_updated: i64,
+    pub confidence_ratio: u64,
+    pub timestamp: i64, // Quando a decisão foi assinada
+    pub nonce: u64,
+    pub breaker_until: i64, // Circuit breaker: bloqueado até este unix_timestamp (0 = livre)
+    pub emergency_blocked_at: i64, // Último emergency_block (0 = nunca)
+    pub oracle_confidence_ratio: u64, // Switchboard, em bps (ver has_oracle_confidence)
+    pub oracle_updated_at: i64, // last_update_timestamp do feed lido
+    pub cooling_until: i64, // Quarentena pós-desbloqueio até este unix_timestamp
+    pub unblocked_at: i64, // Último desbloqueio; início da rampa de reabertura
+    pub valid_until: i64, // Validade assinada da última decisão (0 = sem validade)
+    pub updates_count: u64, // Decisões gravadas neste ativo
+    pub override_timestamp: i64, // Última intervenção da authority (ver has_override)
+    pub sequences: [SignerSequence; MAX_TRUSTED_SIGNERS], // Modo Sequence (sequence_count usadas)
+    pub asset_id: [u8; 16],
+    pub decision_hash: [u8; 32],
+    pub signature: [u8; 64],
+    pub signer_pubkey: [u8; 32],
+    pub override_authority: Pubkey,
+    pub publisher_ids: [PublisherId; MAX_PUBLISHER_IDS], // Publishers que embasaram a decisão
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT], // Score por categoria (SUB_SCORE_*)
+    pub score_ema: u16, // Média móvel exponencial do risk_score, em centésimos de ponto
+    pub override_reason_code: u16, // Código definido pela operação (runbook)
+    pub bump: u8,
+    pub risk_score: u8,
+    pub is_blocked: u8,
+    pub publisher_count: u8,
+    pub signer_count: u8, // Quantos signers atingiram o quorum
+    pub lifecycle: u8, // AssetLifecycle (ver lifecycle())
+    pub reduce_only: u8, // Sinal da última decisão do engine
+    pub admin_reduce_only: u8, // Definido pela authority; sobrevive às decisões
+    pub risk_flags: u8, // Categorias em alerta (RISK_FLAG_*)
+    pub cooling_decisions_left: u8, // Decisões sem bloqueio que ainda faltam
+    pub version: u8, // ASSET_RISK_VERSION
+    pub prev_risk_score: u8, // risk_score antes da última decisão (0 na primeira)
+    pub score_delta: i8, // risk_score menos prev_risk_score
+    pub sequence_count: u8,
+    pub has_override: u8,
+    pub override_is_blocked: u8,
+    pub has_oracle_confidence: u8,
+    pub risk_tier: u8, // RiskTier do risk_score atual (ver risk_tier())
+    pub deadman_blocked: u8, // Bloqueado por trigger_deadman até rearm_deadman
+    pub _reserved: [u8; 125],
+}
+
+impl AssetRiskStatus {
+    pub const LEN: usize = std::mem::size_of::<AssetRiskStatus>();
+
+    /// Lê a conta sem Borsh nem exigir alinhamento: discriminator conferido e
+    /// os LEN bytes seguintes copiados
+    pub fn from_account_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 + Self::LEN && data.starts_with(Self::DISCRIMINATOR),
+            ErrorCode::InvalidAssetAccount
+        );
+        Ok(bytemuck::pod_read_unaligned(&data[8..8 + Self::LEN]))
+    }
+
+    /// Como from_account_data, aceitando também as contas Borsh anteriores
+    /// ao zero-copy (convertidas como migrate_asset faria). O layout v1
+    /// difere no meio da struct (ver AssetRiskStatusV1).
+    pub fn try_deserialize_tolerant(data: &[u8]) -> Result<Self> {
+        require!(!AssetRiskStatusV1::is_v1(data), ErrorCode::V1AccountNeedsRetire);
+        if AssetRiskStatusV3::is_legacy(data) {
+            return AssetRiskStatusV3::try_from_data(data).map(Self::from);
+        }
+        Self::from_account_data(data)
+    }
+
+    pub fn lifecycle(&self) -> AssetLifecycle {
+        // Só set_lifecycle escreve o campo; valor desconhecido é tratado como terminal
+        AssetLifecycle::from_u8(self.lifecycle).unwrap_or(AssetLifecycle::Delisted)
+    }
+
+    pub fn set_lifecycle(&mut self, lifecycle: AssetLifecycle) {
+        self.lifecycle = lifecycle as u8;
+    }
+
+    /// Faixa gravada na última decisão. Conta de versão anterior não tem o
+    /// campo (lê zero): a faixa sai do risk_score pelos limites padrão até a
+    /// próxima decisão do ativo
+    pub fn risk_tier(&self) -> RiskTier {
+        if self.version < RISK_TIER_VERSION {
+            return RiskTierThresholds::DEFAULT.tier(self.risk_score);
+        }
+        // Só set_risk_tier escreve o campo; valor desconhecido é tratado como o pior
+        RiskTier::from_u8(self.risk_tier).unwrap_or(RiskTier::Critical)
+    }
+
+    /// Recalcula a faixa do risk_score atual
+    pub fn set_risk_tier(&mut self, thresholds: &RiskTierThresholds) {
+        self.risk_tier = thresholds.tier(self.risk_score) as u8;
+    }
+
+    /// Última intervenção da authority
+    pub fn last_override(&self) -> Option<OverrideRecord> {
+        (self.has_override != 0).then_some(OverrideRecord {
+            authority: self.override_authority,
+            timestamp: self.override_timestamp,
+            reason_code: self.override_reason_code,
+            is_blocked: self.override_is_blocked != 0,
+        })
+    }
+
+    pub fn set_last_override(&mut self, record: &OverrideRecord) {
+        self.override_authority = record.authority;
+        self.override_timestamp = record.timestamp;
+        self.override_reason_code = record.reason_code;
+        self.override_is_blocked = u8::from(record.is_blocked);
+        self.has_override = 1;
+    }
+
+    /// Confiança do Switchboard lida com a última decisão, em bps
+    pub fn oracle_confidence(&self) -> Option<u64> {
+        (self.has_oracle_confidence != 0).then_some(self.oracle_confidence_ratio)
+    }
+
+    pub fn set_oracle_confidence(&mut self, ratio: Option<u64>) {
+        self.oracle_confidence_ratio = ratio.unwrap_or(0);
+        self.has_oracle_confidence = u8::from(ratio.is_some());
+    }
+
+    /// Sequences registradas (modo Sequence)
+    pub fn sequences(&self) -> &[SignerSequence] {
+        &self.sequences[..usize::from(self.sequence_count).min(MAX_TRUSTED_SIGNERS)]
+    }
+
+    /// Categoria sinalizada pelo engine (ex.: `has_flag(RISK_FLAG_ORACLE)`)
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.risk_flags & flag != 0
+    }
+
+    /// O estado atual veio de override_risk_status, não de uma decisão assinada
+    pub fn is_overridden(&self) -> bool {
+        self.has_override != 0 && self.override_timestamp == self.last_updated
+    }
+
+    /// Recém-desbloqueado e ainda em quarentena: o gate trata como bloqueado
+    pub fn is_cooling(&self, current_time: i64) -> bool {
+        self.cooling_decisions_left > 0 || current_time < self.cooling_until
+    }
+
+    /// Chamado antes de gravar uma decisão: a que desbloqueia abre a
+    /// quarentena, as seguintes sem bloqueio a consomem e um bloqueio a zera
+    pub fn track_cooling(
+        &mut self,
+        is_blocked: bool,
+        cooldown: Option<&UnblockCooldown>,
+        current_time: i64,
+    ) {
+        if is_blocked {
+            self.cooling_until = 0;
+            self.cooling_decisions_left = 0;
+        } else if self.is_blocked != 0 {
+            self.unblocked_at = current_time;
+            if let Some(cooldown) = cooldown {
+                self.cooling_until = current_time.saturating_add(cooldown.cooldown_secs);
+                self.cooling_decisions_left = cooldown.required_decisions;
+            }
+        } else {
+            self.cooling_decisions_left = self.cooling_decisions_left.saturating_sub(1);
+        }
+    }
+
+    /// Média móvel do score arredondada para a escala do risk_score
+    pub fn ema_score(&self) -> u8 {
+        ((self.score_ema + 50) / 100) as u8
+    }
+
+    /// Score que o integrador escolheu para gating
+    pub fn score(&self, source: ScoreSource) -> u8 {
+        match source {
+            ScoreSource::Raw => self.risk_score,
+            ScoreSource::Ema => self.ema_score(),
+        }
+    }
+
+    /// Chamado antes de gravar uma decisão; a primeira decisão do ativo
+    /// inicializa a média com o próprio score
+    pub fn track_ema(&mut self, risk_score: u8, alpha_bps: u16) {
+        let sample = i64::from(risk_score) * 100;
+        if self.last_updated == 0 {
+            self.score_ema = sample as u16;
+            return;
+        }
+        let current = i64::from(self.score_ema);
+        let delta = (sample - current) * i64::from(alpha_bps) / 10_000;
+        self.score_ema = (current + delta) as u16;
+    }
+
+    /// Bloqueado pelo dead-man switch: nenhuma decisão desbloqueia até rearm_deadman
+    pub fn is_deadman_blocked(&self) -> bool {
+        self.deadman_blocked != 0
+    }
+
+    /// Após um emergency_block, só decisão assinada depois dele desbloqueia
+    pub fn unblock_allowed(&self, decision_timestamp: i64) -> bool {
+        decision_timestamp > self.emergency_blocked_at
+    }
+
+    /// Publisher listado na última decisão (ver `publisher_id`)
+    pub fn has_publisher(&self, id: &PublisherId) -> bool {
+        *id != [0u8; 8] && self.publisher_ids.contains(id)
+    }
+
+    /// Todos os publishers exigidos estão na última decisão (ex.: Pyth + Switchboard)
+    pub fn has_publishers(&self, required: &[PublisherId]) -> bool {
+        required.iter().all(|id| self.has_publisher(id))
+    }
+
+    /// Payload publicado por publish_risk_attestation
+    pub fn attestation(&self) -> cate_decision::Attestation {
+        let mut flags = 0;
+        if self.is_blocked != 0 {
+            flags |= cate_decision::ATTESTATION_FLAG_BLOCKED;
+        }
+        if self.is_reduce_only() {
+            flags |= cate_decision::ATTESTATION_FLAG_REDUCE_ONLY;
+        }
+        cate_decision::Attestation {
+            asset_id: self.asset_id,
+            risk_score: self.risk_score,
+            flags,
+            risk_flags: self.risk_flags,
+            sub_scores: self.sub_scores,
+            confidence_ratio: self.confidence_ratio,
+            publisher_count: self.publisher_count,
+            signer_count: self.signer_count,
+            timestamp: self.timestamp,
+            last_updated: self.last_updated,
+            breaker_until: self.breaker_until,
+            valid_until: self.valid_until,
+            decision_hash: self.decision_hash,
+        }
+    }
+
+    /// Só reduções de posição: sinal do engine, da authority ou ativo em delisting
+    pub fn is_reduce_only(&self) -> bool {
+        self.reduce_only != 0
+            || self.admin_reduce_only != 0
+            || self.lifecycle() == AssetLifecycle::Delisting
+    }
+
+    /// A decisão gravada passou de `valid_until`: o gate trata como bloqueado
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        self.valid_until != 0 && current_time >= self.valid_until
+    }
+
+    pub fn breaker_active(&self, current_time: i64) -> bool {
+        current_time < self.breaker_until
+    }
+
+    /// Última sequence aceita do signer neste ativo (0 = nenhuma)
+    pub fn last_sequence(&self, signer: &Pubkey) -> u64 {
+        self.sequences()
+            .iter()
+            .find(|s| s.signer == *signer)
+            .map_or(0, |s| s.last_sequence)
+    }
+
+    /// Exige `sequence` maior que a última de cada signer e a registra
+    fn advance_sequences(
+        &mut self,
+        config: &Config,
+        signers: &[Pubkey],
+        sequence: u64,
+        current_time: i64,
+    ) -> Result<()> {
+        for signer in signers {
+            require!(sequence > self.last_sequence(signer), ErrorCode::SequenceNotIncreasing);
+        }
+        for &signer in signers {
+            let count = self.sequences().len();
+            if let Some(entry) = self.sequences[..count].iter_mut().find(|s| s.signer == signer) {
+                entry.last_sequence = sequence;
+                continue;
+            }
+            // Signers fora do conjunto (rotação encerrada) liberam espaço; as
+            // decisões deles não passam mais em verify_quorum
+            if count >= MAX_TRUSTED_SIGNERS {
+                let mut kept = 0;
+                for i in 0..count {
+                    let entry = self.sequences[i];
+                    if config.is_trusted_signer(&entry.signer, current_time)
+                        || config.emergency_signers.contains(&entry.signer)
+                    {
+                        self.sequences[kept] = entry;
+                        kept += 1;
+                    }
+                }
+                self.sequence_count = kept as u8;
+            }
+            let count = self.sequences().len();
+            require!(count < MAX_TRUSTED_SIGNERS, ErrorCode::SequenceStoreFull);
+            self.sequences[count] = SignerSequence { signer, last_sequence: sequence };
+            self.sequence_count = (count + 1) as u8;
+        }
+        Ok(())
+    }
+}
+
+/// AssetRiskStatus em Borsh, das versões 0 a 3: mesmo discriminator e PDA,
+/// tamanho diferente do layout zero-copy. Só lido para conversão por
+/// migrate_asset e pelas ferramentas off-chain (try_deserialize_tolerant).
+#[derive(AnchorDeserialize, Clone)]
+pub struct AssetRiskStatusV3 {
+    pub bump: u8,
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub last_updated: i64,
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub timestamp: i64, // NOVO: quando foi assinado
+    pub nonce: u64,
+    pub decision_hash: [u8; 32],
+    pub signature: [u8; 64],
+    pub signer_pubkey: [u8; 32],
+    pub signer_count: u8, // Quantos signers atingiram o quorum
+    pub breaker_until: i64, // Circuit breaker: bloqueado até este unix_timestamp (0 = livre)
+    pub lifecycle: AssetLifecycle,
+    pub reduce_only: bool, // Sinal da última decisão do engine
+    pub admin_reduce_only: bool, // Definido pela authority; sobrevive às decisões
+    pub risk_flags: u8, // Categorias em alerta (RISK_FLAG_*)
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT], // Score por categoria (SUB_SCORE_*)
+    pub publisher_ids: [PublisherId; MAX_PUBLISHER_IDS], // Publishers que embasaram a decisão
+    pub sequences: Vec<(Pubkey, u64)>, // (signer, last_sequence), no máximo um por signer
+    pub emergency_blocked_at: i64, // Último emergency_block (0 = nunca)
+    pub last_override: Option<OverrideRecord>, // Última intervenção da authority
+    pub oracle_confidence_ratio: Option<u64>, // Switchboard, em bps, da mesma decisão
+    pub oracle_updated_at: i64, // last_update_timestamp do feed lido
+    pub cooling_until: i64, // Quarentena pós-desbloqueio até este unix_timestamp
+    pub cooling_decisions_left: u8, // Decisões sem bloqueio que ainda faltam
+    pub unblocked_at: i64, // Último desbloqueio; início da rampa de reabertura
+    pub score_ema: u16, // Média móvel exponencial do risk_score, em centésimos de ponto
+    pub version: u8, // Campos seguintes só existem a partir das versões 1 a 3
+    pub valid_until: i64, // Validade assinada da última decisão (0 = sem validade)
+    pub prev_risk_score: u8, // risk_score antes da última decisão (0 na primeira)
+    pub score_delta: i8, // risk_score menos prev_risk_score
+    pub updates_count: u64, // Decisões gravadas neste ativo
+}
+
+impl AssetRiskStatusV3 {
+    pub const LEN: usize = 1 + 16 + 1 + 1 + 8 + 8 + 1 + 8 + 8 + 32 + 64 + 32 + 1 + 8 + 1 + 1 + 1 + 1 + RISK_CATEGORY_COUNT
+        + 8 * MAX_PUBLISHER_IDS + (4 + SignerSequence::LEN * MAX_TRUSTED_SIGNERS) + 8
+        + (1 + OverrideRecord::LEN) + (1 + 8) + 8 + 8 + 1 + 8 + 2 + 1 + 8 + 1 + 1 + 8;
+
+    /// Conta Borsh pré-zero-copy (exceto o layout v1, que tem o próprio tamanho)
+    pub fn is_legacy(data: &[u8]) -> bool {
+        data.starts_with(AssetRiskStatus::DISCRIMINATOR)
+            && data.len() != 8 + AssetRiskStatus::LEN
+            && !AssetRiskStatusV1::is_v1(data)
+    }
+
+    /// Versões anteriores a 3 são prefixos desta: completa com zeros, como
+    /// Config::try_deserialize_tolerant
+    pub fn try_from_data(data: &[u8]) -> Result<Self> {
+        require!(
+            data.starts_with(AssetRiskStatus::DISCRIMINATOR),
+            ErrorCode::InvalidAssetAccount
+        );
+        Ok(Self::deserialize(&mut &zero_extended(data, 8 + Self::LEN)[8..])?)
+    }
+}
+
+/// get_risk_status devolve os bytes da própria conta (sem discriminator):
+/// o chamador lê com from_account_data ou pelos offsets de cate_reader::layout
+impl AnchorSerialize for AssetRiskStatus {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(bytemuck::bytes_of(self))
+    }
+}
+
+impl From<AssetRiskStatusV3> for AssetRiskStatus {
+    fn from(legacy: AssetRiskStatusV3) -> Self {
+        let mut status: AssetRiskStatus = bytemuck::Zeroable::zeroed();
+        status.last_updated = legacy.last_updated;
+        status.confidence_ratio = legacy.confidence_ratio;
+        status.timestamp = legacy.timestamp;
+        status.nonce = legacy.nonce;
+        status.breaker_until = legacy.breaker_until;
+        status.emergency_blocked_at = legacy.emergency_blocked_at;
+        status.set_oracle_confidence(legacy.oracle_confidence_ratio);
+        status.oracle_updated_at = legacy.oracle_updated_at;
+        status.cooling_until = legacy.cooling_until;
+        status.unblocked_at = legacy.unblocked_at;
+        status.valid_until = legacy.valid_until;
+        status.updates_count = legacy.updates_count;
+        for (entry, (signer, last_sequence)) in
+            status.sequences.iter_mut().zip(legacy.sequences.iter().copied())
+        {
+            *entry = SignerSequence { signer, last_sequence };
+        }
+        status.sequence_count = legacy.sequences.len().min(MAX_TRUSTED_SIGNERS) as u8;
+        status.asset_id = legacy.asset_id;
+        status.decision_hash = legacy.decision_hash;
+        status.signature = legacy.signature;
+        status.signer_pubkey = legacy.signer_pubkey;
+        if let Some(record) = legacy.last_override {
+            status.set_last_override(&record);
+        }
+        status.publisher_ids = legacy.publisher_ids;
+        status.sub_scores = legacy.sub_scores;
+        status.score_ema = legacy.score_ema;
+        status.bump = legacy.bump;
+        status.risk_score = legacy.risk_score;
+        status.is_blocked = u8::from(legacy.is_blocked);
+        status.publisher_count = legacy.publisher_count;
+        status.signer_count = legacy.signer_count;
+        status.set_lifecycle(legacy.lifecycle);
+        status.reduce_only = u8::from(legacy.reduce_only);
+        status.admin_reduce_only = u8::from(legacy.admin_reduce_only);
+        status.risk_flags = legacy.risk_flags;
+        status.cooling_decisions_left = legacy.cooling_decisions_left;
+        status.version = ASSET_RISK_VERSION;
+        status.prev_risk_score = legacy.prev_risk_score;
+        status.score_delta = legacy.score_delta;
+        status.set_risk_tier(&RiskTierThresholds::DEFAULT);
+        status
+    }
+}
+
+/// Cópia dos dados completada com zeros até `len`, para ler layouts que
+/// cresceram acrescentando campos ao final
+fn zero_extended(data: &[u8], len: usize) -> Vec<u8> {
+    let mut bytes = data.to_vec();
+    if bytes.len() < len {
+        bytes.resize(len, 0);
+    }
+    bytes
+}
+
+/// AssetRiskStatus da implantação v1: mesmo discriminator e PDA, sem
+/// timestamp/nonce. Só lido para migração por `retire_v1_asset`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AssetRiskStatusV1 {
+    pub bump: u8,
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub last_updated: i64,
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub decision_hash: [u8; 32],
+    pub signature: [u8; 64],
+    pub signer_pubkey: [u8; 32],
+}
+
+impl AssetRiskStatusV1 {
+    pub const LEN: usize = 1 + 16 + 1 + 1 + 8 + 8 + 1 + 32 + 64 + 32;
+
+    /// Dados de conta no layout v1 (o tamanho distingue do layout atual)
+    pub fn is_v1(data: &[u8]) -> bool {
+        data.len() == 8 + Self::LEN && data.starts_with(AssetRiskStatus::DISCRIMINATOR)
+    }
+
+    pub fn try_from_account(info: &AccountInfo, program_id: &Pubkey) -> Result<Self> {
+        require_keys_eq!(*info.owner, *program_id, ErrorCode::NotV1Account);
+        let data = info.try_borrow_data()?;
+        require!(Self::is_v1(&data), ErrorCode::NotV1Account);
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+// ============================================================================
+// Contexts
+// ============================================================================
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump,
+        payer = authority,
+        space = 8 + Config::LEN
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[0u8]],
+        bump,
+        space = 8 + UsedDecisions::LEN
+    )]
+    pub used_decisions: Account<'info, UsedDecisions>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReinitializeConfig<'info> {
+    /// CHECK: pode não desserializar; dono e authority conferidos pelas constraints
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump,
+        owner = crate::ID @ ErrorCode::NotInitialized,
+        constraint = Config::stored_authority(&config.try_borrow_data()?)
+            == Some(authority.key()) @ ErrorCode::Unauthorized,
+        realloc = 8 + Config::LEN,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// CHECK: layout antigo; lido por Config::try_deserialize_tolerant
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump,
+        owner = crate::ID @ ErrorCode::NotInitialized,
+        constraint = Config::stored_authority(&config.try_borrow_data()?)
+            == Some(authority.key()) @ ErrorCode::Unauthorized,
+        realloc = 8 + Config::LEN,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct MigrateAsset<'info> {
+    /// Lido como UncheckedAccount: o Config também pode estar num layout antigo.
+    /// As constraints de layout do ativo (v1, já zero-copy) precisam rodar
+    /// antes do realloc dele.
+    /// CHECK: authority conferida nos bytes estáveis
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump,
+        owner = crate::ID @ ErrorCode::NotInitialized,
+        constraint = Config::stored_authority(&config.try_borrow_data()?)
+            == Some(authority.key()) @ ErrorCode::Unauthorized,
+        constraint = !AssetRiskStatusV1::is_v1(&asset_risk_status.try_borrow_data()?)
+            @ ErrorCode::V1AccountNeedsRetire,
+        constraint = AssetRiskStatusV3::is_legacy(&asset_risk_status.try_borrow_data()?)
+            @ ErrorCode::AlreadyMigrated
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: layout Borsh; lido por AssetRiskStatusV3::try_from_data
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump,
+        owner = crate::ID @ ErrorCode::InvalidAssetAccount,
+        realloc = 8 + AssetRiskStatus::LEN,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub asset_risk_status: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTrustedSigner<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, ROLES_SEED],
+        bump,
+        space = 8 + Roles::LEN
+    )]
+    pub roles: Account<'info, Roles>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(
+            &config,
+            roles.as_deref(),
+            &authority.key(),
+            &[Role::Pauser, Role::Guardian]
+        ) @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+
+    /// Opcional: papéis delegados pela authority
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct InitializePendingDecision<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, PENDING_DECISION_SEED, asset_id.as_bytes()],
+        bump,
+        space = 8 + PendingDecision::LEN
+    )]
+    pub pending_decision: Account<'info, PendingDecision>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct ResolvePendingDecision<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config
+            .dual_engine
+            .as_ref()
+            .is_some_and(|dual| dual.guardian == guardian.key())
+            || roles
+                .as_ref()
+                .is_some_and(|roles| roles.has_role(&guardian.key(), Role::Guardian))
+                @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, PENDING_DECISION_SEED, asset_id.as_bytes()],
+        bump = pending_decision.bump
+    )]
+    pub pending_decision: Account<'info, PendingDecision>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()],
+        bump = asset_policy.bump
+    )]
+    pub asset_policy: Option<Account<'info, AssetPolicy>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_HISTORY_SEED, asset_id.as_bytes()],
+        bump
+    )]
+    pub asset_history: Option<AccountLoader<'info, AssetRiskHistory>>,
+
+    pub guardian: Signer<'info>,
+
+    /// Opcional: guardians adicionais com o papel Guardian
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// Exigida quando o Config tem portfolio_breaker
+    #[account(mut, seeds = [SEED_NAMESPACE, PORTFOLIO_SEED], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, PortfolioRisk>>,
+}
+
+#[derive(Accounts)]
+#[instruction(signer: Pubkey)]
+pub struct InitializeSignerUsage<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, SIGNER_USAGE_SEED, signer.as_ref()],
+        bump,
+        space = 8 + SignerUsage::LEN
+    )]
+    pub signer_usage: Account<'info, SignerUsage>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(signer: Pubkey)]
+pub struct ResetSignerUsage<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, SIGNER_USAGE_SEED, signer.as_ref()],
+        bump = signer_usage.bump
+    )]
+    pub signer_usage: Account<'info, SignerUsage>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct SetAssetLifecycle<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + AssetRiskStatus::LEN
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct SetTradingSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, SCHEDULE_SEED, asset_id.as_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + TradingSchedule::LEN
+    )]
+    pub schedule: Account<'info, TradingSchedule>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct CloseTradingSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_NAMESPACE, SCHEDULE_SEED, asset_id.as_bytes()],
+        bump = schedule.bump
+    )]
+    pub schedule: Account<'info, TradingSchedule>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct SetReduceOnly<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct OverrideRiskStatus<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct EmergencyBlock<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(&config, roles.as_deref(), &guardian.key(), &[Role::Guardian])
+            @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    pub guardian: Signer<'info>,
+
+    /// Opcional: guardians com o papel Guardian
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(group_id: String)]
+pub struct SetAssetGroup<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, ASSET_GROUP_SEED, group_id.as_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + AssetGroup::LEN
+    )]
+    pub asset_group: Account<'info, AssetGroup>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct SetPortfolioWeight<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, PORTFOLIO_SEED],
+        bump,
+        payer = payer,
+        space = 8 + PortfolioRisk::LEN
+    )]
+    pub portfolio: Account<'info, PortfolioRisk>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_META_SEED, asset_id.as_bytes()],
+        bump = asset_meta.bump
+    )]
+    pub asset_meta: Account<'info, AssetMeta>,
+
+    /// Opcional: score inicial da entrada
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: Option<AccountLoader<'info, AssetRiskStatus>>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(group_id: String)]
+pub struct BlockGroup<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(&config, roles.as_deref(), &guardian.key(), &[Role::Guardian])
+            @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_GROUP_SEED, group_id.as_bytes()],
+        bump = asset_group.bump
+    )]
+    pub asset_group: Account<'info, AssetGroup>,
+
+    pub guardian: Signer<'info>,
+
+    /// Opcional: guardians com o papel Guardian
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct RetireV1Asset<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: layout v1 validado por AssetRiskStatusV1::try_from_account
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump
+    )]
+    pub v1_asset_risk: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct CloseAssetRisk<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    /// CHECK: apenas recebe os lamports da conta fechada
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct ResetCircuitBreaker<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct QueuePolicyChange<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, PENDING_CHANGE_SEED, &config.nonce.to_le_bytes()],
+        bump,
+        space = 8 + PendingPolicyChange::LEN
+    )]
+    pub pending_change: Account<'info, PendingPolicyChange>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ApplyPolicyChange<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, PENDING_CHANGE_SEED, &id.to_le_bytes()],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Account<'info, PendingPolicyChange>,
+
+    /// CHECK: só recebe o aluguel do PDA fechado
+    #[account(mut, address = config.authority @ ErrorCode::Unauthorized)]
+    pub authority: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64, asset_id: String)]
+pub struct ApplyAssetPolicyChange<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, PENDING_CHANGE_SEED, &id.to_le_bytes()],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Account<'info, PendingPolicyChange>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + AssetPolicy::LEN
+    )]
+    pub asset_policy: Account<'info, AssetPolicy>,
+
+    /// CHECK: só recebe o aluguel do PDA fechado
+    #[account(mut, address = config.authority @ ErrorCode::Unauthorized)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct CancelPolicyChange<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, PENDING_CHANGE_SEED, &id.to_le_bytes()],
+        bump = pending_change.bump,
+        close = authority
+    )]
+    pub pending_change: Account<'info, PendingPolicyChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.pending_authority == Some(new_authority.key()) @ ErrorCode::NotPendingAuthority
+    )]
+    pub config: Account<'info, Config>,
+
+    pub new_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct InitializeReplayShard<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard]],
+        bump,
+        space = 8 + UsedDecisions::LEN
+    )]
+    pub used_decisions: Account<'info, UsedDecisions>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8, max_size: u16)]
+pub struct ResizeReplayShard<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard]],
+        bump = used_decisions.bump,
+        realloc = 8 + UsedDecisions::space(max_size),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub used_decisions: Account<'info, UsedDecisions>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeReplayBloom<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, REPLAY_BLOOM_SEED],
+        bump,
+        space = 8 + ReplayBloom::LEN
+    )]
+    pub replay_bloom: AccountLoader<'info, ReplayBloom>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct CloseUsedDecisions<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: pode estar corrompido; dono e discriminator conferidos na instrução
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard]],
+        bump
+    )]
+    pub used_decisions: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureFeeVault<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, FEE_VAULT_SEED],
+        bump,
+        space = 8 + FeeVault::LEN
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, TREASURY_SEED],
+        bump,
+        space = 8 + Treasury::LEN
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Saque em SPL: conta do Treasury, destino, mint e token program
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub recipient_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(seeds = [SEED_NAMESPACE, TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, INSURANCE_FUND_SEED],
+        bump,
+        space = 8 + InsuranceFund::LEN
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceFeeShare<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [SEED_NAMESPACE, INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ContributeInsurance<'info> {
+    #[account(mut, seeds = [SEED_NAMESPACE, INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepFeesToInsurance<'info> {
+    #[account(mut, seeds = [SEED_NAMESPACE, TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut, seeds = [SEED_NAMESPACE, INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+}
+
+#[derive(Accounts)]
+pub struct PayInsuranceClaim<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [SEED_NAMESPACE, INSURANCE_FUND_SEED], bump = insurance_fund.bump)]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+
+    /// CHECK: integrador indenizado; só recebe lamports
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct RegisterSessionKey<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SessionKey::LEN,
+        seeds = [SEED_NAMESPACE, SESSION_KEY_SEED, session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Instructions sysvar verification
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSessionKey<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, SESSION_KEY_SEED, session.session_key.as_ref()],
+        bump = session.bump
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSessionKey<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, SESSION_KEY_SEED, session.session_key.as_ref()],
+        bump = session.bump,
+        close = payer
+    )]
+    pub session: Account<'info, SessionKey>,
+
+    /// CHECK: quem pagou o registro; só recebe o aluguel
+    #[account(mut, address = session.payer)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(shard: u8)]
+pub struct PruneUsedDecisions<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[shard]],
+        bump = used_decisions.bump
+    )]
+    pub used_decisions: Account<'info, UsedDecisions>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, FEE_VAULT_SEED],
+        bump = fee_vault.bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    asset_id: String,
+    risk_score: u8,
+    is_blocked: bool,
+    reduce_only: bool,
+    risk_flags: u8,
+    sub_scores: [u8; RISK_CATEGORY_COUNT],
+    confidence_ratio: u64,
+    publisher_count: u8,
+    publisher_ids: [PublisherId; MAX_PUBLISHER_IDS],
+    timestamp: i64,
+    nonce: u64,
+    sequence: u64,
+    valid_until: i64,
+    decision_hash: [u8; 32]
+)]
+pub struct UpdateRiskStatus<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(&config, roles.as_deref(), &authority.key(), &[Role::Updater])
+            @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Exigido no ReplayMode::HashList: shard do hash da decisão
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, USED_DECISIONS_SEED, &[replay_shard(&decision_hash)]],
+        bump = used_decisions.bump
+    )]
+    pub used_decisions: Option<Account<'info, UsedDecisions>>,
+    
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump,
+        payer = keeper,
+        space = 8 + AssetRiskStatus::LEN
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+    
+    /// Papel Updater; só autoriza a decisão
+    pub authority: Signer<'info>,
+
+    /// Paga a transação, o rent, a taxa e recebe o keeper_tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar verification
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Opcional: só necessário quando o dead-man switch está configurado
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, DEADMAN_SEED],
+        bump = deadman.bump
+    )]
+    pub deadman: Option<Account<'info, DeadmanSwitch>>,
+
+    /// CHECK: PDA da AssetPolicy, sempre passado; lido por read_asset_policy
+    /// (sem dados = ativo sem política)
+    #[account(seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()], bump)]
+    pub asset_policy: UncheckedAccount<'info>,
+
+    /// Opcional: exigida no modo de dois engines quando a decisão reduz risco
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, PENDING_DECISION_SEED, asset_id.as_bytes()],
+        bump = pending_decision.bump
+    )]
+    pub pending_decision: Option<Account<'info, PendingDecision>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, ASSET_HISTORY_SEED, asset_id.as_bytes()],
+        bump,
+        payer = keeper,
+        space = 8 + AssetRiskHistory::LEN
+    )]
+    pub asset_history: AccountLoader<'info, AssetRiskHistory>,
+    
+    /// CHECK: opcional; PriceUpdateV2 do Pyth, dono e layout conferidos em
+    /// PythPrice::try_from_account. Exigida quando o ativo tem pyth_check
+    pub pyth_price: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: PullFeedAccountData da Switchboard; exigida e conferida só em
+    /// update_risk_status_switchboard
+    pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+    /// Opcional: papéis delegados pela authority
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// Cadastro do ativo: sem ele a decisão é recusada
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_META_SEED, asset_id.as_bytes()],
+        bump = asset_meta.bump
+    )]
+    pub asset_meta: Account<'info, AssetMeta>,
+
+    /// Exigida quando o Config tem portfolio_breaker
+    #[account(mut, seeds = [SEED_NAMESPACE, PORTFOLIO_SEED], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, PortfolioRisk>>,
+
+    /// Opcional: contadores de Stats
+    #[account(mut, seeds = [SEED_NAMESPACE, STATS_SEED], bump = stats.bump)]
+    pub stats: Option<Account<'info, Stats>>,
+
+    /// Exigido no ReplayMode::Bloom
+    #[account(mut, seeds = [SEED_NAMESPACE, REPLAY_BLOOM_SEED], bump = replay_bloom.load()?.bump)]
+    pub replay_bloom: Option<AccountLoader<'info, ReplayBloom>>,
+
+    /// Exigido quando o Config tem update_fee; também paga o keeper_tip
+    #[account(mut, seeds = [SEED_NAMESPACE, TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// Contas da taxa em SPL: origem do pagador, conta do Treasury e mint
+    #[account(mut)]
+    pub payer_fee_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_fee_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Exigida quando a decisão é assinada por uma chave de sessão
+    #[account(
+        seeds = [SEED_NAMESPACE, SESSION_KEY_SEED, session_key.session_key.as_ref()],
+        bump = session_key.bump
+    )]
+    pub session_key: Option<Account<'info, SessionKey>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRiskStatusBatch<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(&config, roles.as_deref(), &authority.key(), &[Role::Updater])
+            @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Papel Updater; só autoriza as decisões
+    pub authority: Signer<'info>,
+
+    /// Paga a transação e a taxa e recebe o keeper_tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: Instructions sysvar verification
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, DEADMAN_SEED],
+        bump = deadman.bump
+    )]
+    pub deadman: Option<Account<'info, DeadmanSwitch>>,
+
+    /// Opcional: papéis delegados pela authority
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+
+    /// Opcional: contadores de Stats
+    #[account(mut, seeds = [SEED_NAMESPACE, STATS_SEED], bump = stats.bump)]
+    pub stats: Option<Account<'info, Stats>>,
+
+    /// Exigido no ReplayMode::Bloom
+    #[account(mut, seeds = [SEED_NAMESPACE, REPLAY_BLOOM_SEED], bump = replay_bloom.load()?.bump)]
+    pub replay_bloom: Option<AccountLoader<'info, ReplayBloom>>,
+
+    /// Exigido quando o Config tem update_fee
+    #[account(mut, seeds = [SEED_NAMESPACE, TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// Contas da taxa em SPL: origem do pagador, conta do Treasury e mint
+    #[account(mut)]
+    pub payer_fee_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_fee_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Exigido com update_fee em lamports
+    pub system_program: Option<Program<'info, System>>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = holds_role(&config, roles.as_deref(), &authority.key(), &[Role::Updater])
+            @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: Instructions sysvar verification
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Opcional: relayers com o papel Updater
+    #[account(seeds = [SEED_NAMESPACE, ROLES_SEED], bump = roles.bump)]
+    pub roles: Option<Account<'info, Roles>>,
+}
+
+#[derive(Accounts)]
+pub struct GetEngineHealth<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyDecision<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Instructions sysvar verification
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    /// Opcional: grava verificações e rejeições
+    #[account(mut, seeds = [SEED_NAMESPACE, STATS_SEED], bump = stats.bump)]
+    pub stats: Option<Account<'info, Stats>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct GetRiskStatus<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    /// CHECK: Instructions sysvar, exigido junto com consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct GetEffectiveStatus<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    /// Opcional: janela de frescor específica do ativo
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()],
+        bump = asset_policy.bump
+    )]
+    pub asset_policy: Option<Account<'info, AssetPolicy>>,
+
+    /// Exigida quando o Config tem portfolio_breaker
+    #[account(seeds = [SEED_NAMESPACE, PORTFOLIO_SEED], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, PortfolioRisk>>,
+
+    /// CHECK: Instructions sysvar, exigido junto com consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+
+    /// CHECK: TradingSchedule global; exigida se Config.trading_schedules > 0,
+    /// sem conta = sem restrição
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED], bump)]
+    pub global_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule do ativo, nas mesmas condições
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED, asset_id.as_bytes()], bump)]
+    pub asset_schedule: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct AssertTradingAllowed<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id.as_bytes()],
+        bump = asset_policy.bump
+    )]
+    pub asset_policy: Option<Account<'info, AssetPolicy>>,
+
+    /// Exigida quando o Config tem portfolio_breaker
+    #[account(seeds = [SEED_NAMESPACE, PORTFOLIO_SEED], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, PortfolioRisk>>,
+
+    /// Opcional: contador de uso do integrador na epoch atual
+    #[account(mut)]
+    pub integration_stats: Option<Account<'info, IntegrationStats>>,
+
+    /// CHECK: Instructions sysvar, exigido junto com integration_stats ou consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+
+    /// CHECK: TradingSchedule global; exigida se Config.trading_schedules > 0,
+    /// sem conta = sem restrição
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED], bump)]
+    pub global_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule do ativo, nas mesmas condições
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED, asset_id.as_bytes()], bump)]
+    pub asset_schedule: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct ViewRiskStatus<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    /// CHECK: Instructions sysvar, exigido junto com consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct SetConsumerAllowlisted<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, program_id.as_ref()],
+        bump,
+        space = 8 + Consumer::LEN
+    )]
+    pub consumer: Account<'info, Consumer>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct SubscribeConsumer<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, program_id.as_ref()],
+        bump,
+        space = 8 + Consumer::LEN
+    )]
+    pub consumer: Account<'info, Consumer>,
+
+    #[account(mut, seeds = [SEED_NAMESPACE, TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct CloseConsumer<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Account<'info, Consumer>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(consumer: Pubkey, epoch: u64)]
+pub struct InitializeIntegrationStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, INTEGRATION_STATS_SEED, consumer.as_ref(), &epoch.to_le_bytes()],
+        bump,
+        space = 8 + IntegrationStats::LEN
+    )]
+    pub integration_stats: Account<'info, IntegrationStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_NAMESPACE, STATS_SEED],
+        bump,
+        space = 8 + Stats::LEN
+    )]
+    pub stats: Account<'info, Stats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String, mint: Pubkey)]
+pub struct RegisterAssetMint<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.as_ref()],
+        bump,
+        payer = payer,
+        space = 8 + AssetMint::LEN
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct UnregisterAssetMint<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.as_ref()],
+        bump = asset_mint.bump
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct RegisterAsset<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        seeds = [SEED_NAMESPACE, ASSET_META_SEED, asset_id.as_bytes()],
+        bump,
+        payer = payer,
+        space = 8 + AssetMeta::LEN
+    )]
+    pub asset_meta: Account<'info, AssetMeta>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct UnregisterAsset<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [SEED_NAMESPACE, ASSET_META_SEED, asset_id.as_bytes()],
+        bump = asset_meta.bump
+    )]
+    pub asset_meta: Account<'info, AssetMeta>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct GetEffectiveStatusByMint<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.as_ref()],
+        bump = asset_mint.bump
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&asset_mint.asset_id)],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id_seed(&asset_mint.asset_id)],
+        bump = asset_policy.bump
+    )]
+    pub asset_policy: Option<Account<'info, AssetPolicy>>,
+
+    /// CHECK: Instructions sysvar, exigido junto com consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+}
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct AssertTradingAllowedByMint<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_MINT_SEED, mint.as_ref()],
+        bump = asset_mint.bump
+    )]
+    pub asset_mint: Account<'info, AssetMint>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id_seed(&asset_mint.asset_id)],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_POLICY_SEED, asset_id_seed(&asset_mint.asset_id)],
+        bump = asset_policy.bump
+    )]
+    pub asset_policy: Option<Account<'info, AssetPolicy>>,
+
+    /// Exigida quando o Config tem portfolio_breaker
+    #[account(seeds = [SEED_NAMESPACE, PORTFOLIO_SEED], bump = portfolio.bump)]
+    pub portfolio: Option<Account<'info, PortfolioRisk>>,
+
+    /// Opcional: contador de uso do integrador na epoch atual
+    #[account(mut)]
+    pub integration_stats: Option<Account<'info, IntegrationStats>>,
+
+    /// CHECK: Instructions sysvar, exigido junto com integration_stats ou consumer
+    #[account(address = instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// Exigida em CPI quando o Config tem consumer_access
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, CONSUMER_SEED, consumer.program_id.as_ref()],
+        bump = consumer.bump
+    )]
+    pub consumer: Option<Account<'info, Consumer>>,
+
+    /// CHECK: TradingSchedule global; exigida se Config.trading_schedules > 0,
+    /// sem conta = sem restrição
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED], bump)]
+    pub global_schedule: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: TradingSchedule do ativo, nas mesmas condições
+    #[account(seeds = [SEED_NAMESPACE, SCHEDULE_SEED, asset_id_seed(&asset_mint.asset_id)], bump)]
+    pub asset_schedule: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureDeadman<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        seeds = [SEED_NAMESPACE, DEADMAN_SEED],
+        bump,
+        payer = payer,
+        space = 8 + DeadmanSwitch::LEN
+    )]
+    pub deadman: Account<'info, DeadmanSwitch>,
+
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerDeadman<'info> {
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, DEADMAN_SEED],
+        bump = deadman.bump
+    )]
+    pub deadman: Account<'info, DeadmanSwitch>,
+}
+
+#[derive(Accounts)]
+pub struct RearmDeadman<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_initialized @ ErrorCode::NotInitialized,
+        constraint = config.authority == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [SEED_NAMESPACE, DEADMAN_SEED],
+        bump = deadman.bump
+    )]
+    pub deadman: Account<'info, DeadmanSwitch>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_id: String)]
+pub struct PublishRiskAttestation<'info> {
+    #[account(
+        seeds = [SEED_NAMESPACE, ASSET_RISK_SEED, asset_id.as_bytes()],
+        bump = asset_risk_status.load()?.bump
+    )]
+    pub asset_risk_status: AccountLoader<'info, AssetRiskStatus>,
+
+    /// CHECK: core bridge do Wormhole (mainnet ou devnet)
+    #[account(
+        constraint = wormhole::is_core_bridge(wormhole_program.key)
+            @ ErrorCode::InvalidWormholeProgram
+    )]
+    pub wormhole_program: UncheckedAccount<'info>,
+
+    /// CHECK: BridgeData do core bridge; a taxa é lida por wormhole::message_fee
+    #[account(
+        mut,
+        seeds = [wormhole::BRIDGE_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    /// CHECK: recebe a taxa da mensagem
+    #[account(
+        mut,
+        seeds = [wormhole::FEE_COLLECTOR_SEED],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    /// CHECK: PDA sem dados; assina a mensagem como emitter do CATE
+    #[account(seeds = [SEED_NAMESPACE, WORMHOLE_EMITTER_SEED], bump)]
+    pub wormhole_emitter: UncheckedAccount<'info>,
+
+    /// CHECK: sequence do emitter; o core bridge cria na primeira mensagem
+    #[account(
+        mut,
+        seeds = [wormhole::SEQUENCE_SEED, wormhole_emitter.key().as_ref()],
+        bump,
+        seeds::program = wormhole_program.key()
+    )]
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    /// CHECK: PDA da próxima sequence, conferido no handler; o core bridge cria a conta
+    #[account(mut)]
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    pub system_program: Program<'info, System>,
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Asset ID exceeds maximum length of 16 characters")]
+    AssetIdTooLong,
+    #[msg("Asset ID cannot be empty")]
+    AssetIdEmpty,
+    #[msg("Risk score must be between 0 and 100")]
+    InvalidRiskScore,
+    #[msg("Confidence ratio must be between 0 and 10000 basis points")]
+    InvalidConfidenceRatio,
+    #[msg("Invalid timestamp")]
+    InvalidTimestamp,
+    #[msg("Program has not been initialized")]
+    NotInitialized,
+    #[msg("Unauthorized: caller is not the authority")]
+    Unauthorized,
+    #[msg("Invalid signer: does not match trusted signer")]
+    InvalidSigner,
+    #[msg("Invalid Ed25519 signature")]
+    InvalidSignature,
+    #[msg("Missing Ed25519 verification instruction")]
+    MissingEd25519Instruction,
+    #[msg("Invalid Ed25519 program")]
+    InvalidEd25519Program,
+    // Não é mais emitido (ver Ed25519HeaderTruncated e seguintes); mantido
+    // para não renumerar os códigos
+    #[msg("Invalid Ed25519 instruction data")]
+    InvalidEd25519Data,
+    #[msg("Invalid instruction index in Ed25519 data")]
+    InvalidInstructionIndex,
+    #[msg("Signature offset overflow")]
+    SignatureOffsetOverflow,
+    #[msg("Public key offset overflow")]
+    PubkeyOffsetOverflow,
+    #[msg("Message offset overflow")]
+    MessageOffsetOverflow,
+    #[msg("Invalid message size")]
+    InvalidMessageSize,
+    #[msg("Signature verification failed")]
+    SignatureVerificationFailed,
+    #[msg("Decision hash already used")]
+    DecisionAlreadyUsed,
+    #[msg("Decision history full")]
+    DecisionHistoryFull,
+    #[msg("Decision expired")]
+    DecisionExpired,
+    #[msg("Dead-man window must be positive")]
+    InvalidDeadmanWindow,
+    #[msg("Too many assets in dead-man switch")]
+    TooManyDeadmanAssets,
+    #[msg("Dead-man switch window has not expired")]
+    DeadmanNotExpired,
+    #[msg("Asset is not covered by the dead-man switch")]
+    DeadmanAssetNotCovered,
+    #[msg("Invalid asset risk account")]
+    InvalidAssetAccount,
+    #[msg("Quorum must be between 1 and the number of signers")]
+    InvalidQuorum,
+    #[msg("Too many signers")]
+    TooManySigners,
+    #[msg("Duplicate signer")]
+    DuplicateSigner,
+    #[msg("Signatures and signer pubkeys length mismatch")]
+    SignatureCountMismatch,
+    #[msg("Not enough valid signatures to meet quorum")]
+    QuorumNotMet,
+    #[msg("Invalid replay store shard index")]
+    InvalidReplayShard,
+    #[msg("Signer is not the pending authority")]
+    NotPendingAuthority,
+    #[msg("Decision hash does not match the signed payload")]
+    DecisionHashMismatch,
+    #[msg("Batch must contain at least one decision")]
+    EmptyBatch,
+    #[msg("Batch exceeds maximum size")]
+    BatchTooLarge,
+    #[msg("Trading blocked for this asset")]
+    AssetBlocked,
+    #[msg("Risk data is stale")]
+    RiskDataStale,
+    #[msg("Confidence ratio below required floor")]
+    ConfidenceBelowFloor,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Block threshold must be 0-100")]
+    InvalidBlockThreshold,
+    #[msg("Staleness window must be positive")]
+    InvalidStalenessWindow,
+    #[msg("Invalid signer usage account")]
+    InvalidSignerUsageAccount,
+    #[msg("Signer usage account required for risk-reducing decision")]
+    SignerUsageMissing,
+    #[msg("Signer exceeded daily unblock limit")]
+    UnblockLimitExceeded,
+    #[msg("Signer exceeded daily risk score decrease limit")]
+    ScoreDecreaseLimitExceeded,
+    #[msg("Invalid circuit breaker configuration")]
+    InvalidCircuitBreakerConfig,
+    #[msg("Circuit breaker tripped for this asset")]
+    CircuitBreakerTripped,
+    #[msg("Policy change delay has not elapsed")]
+    PolicyChangeNotReady,
+    #[msg("Policy change must be applied with a different instruction")]
+    WrongPolicyChangeKind,
+    #[msg("Policy delay cannot be negative")]
+    InvalidPolicyDelay,
+    #[msg("Invalid asset lifecycle transition")]
+    InvalidLifecycleTransition,
+    #[msg("Asset is listed but not live yet")]
+    AssetNotLive,
+    #[msg("Asset is delisted")]
+    AssetDelisted,
+    #[msg("No pending signer rotation")]
+    NoPendingSigner,
+    #[msg("Pending signer set is not effective yet")]
+    SignerNotYetEffective,
+    #[msg("Signer activation time is earlier than the rotation delay allows")]
+    SignerActivationTooEarly,
+    #[msg("Integration stats account does not match the calling program or current epoch")]
+    InvalidIntegrationStatsAccount,
+    #[msg("Risk flags contain unknown categories")]
+    InvalidRiskFlags,
+    #[msg("Sub-score must be between 0 and 100")]
+    InvalidSubScore,
+    #[msg("Invalid dual-engine configuration")]
+    InvalidDualEngineConfig,
+    #[msg("Pending decision account required to park this decision")]
+    PendingDecisionMissing,
+    #[msg("No pending decision for this asset")]
+    NoPendingDecision,
+    #[msg("Pending decision is older than the current asset state")]
+    PendingDecisionSuperseded,
+    #[msg("Publisher ids must be unique, zero-padded and within publisher_count")]
+    InvalidPublisherIds,
+    #[msg("Timestamp window must be positive and within the replay retention")]
+    InvalidTimestampWindow,
+    #[msg("Account is not a v1 asset risk account")]
+    NotV1Account,
+    #[msg("Decision sequence must be greater than the signer's last accepted sequence")]
+    SequenceNotIncreasing,
+    #[msg("No room for another signer sequence on this asset")]
+    SequenceStoreFull,
+    #[msg("No expired decision records to prune")]
+    NothingToPrune,
+    #[msg("Only delisted assets can be closed")]
+    AssetNotDelisted,
+    #[msg("Replay shard is from another config generation or was reset after the decision was signed")]
+    StaleReplayShard,
+    #[msg("Role table is full")]
+    RoleTableFull,
+    #[msg("Key does not hold this role")]
+    RoleNotGranted,
+    #[msg("Unblocking decision was signed before the asset's emergency block")]
+    DecisionPredatesEmergencyBlock,
+    #[msg("Missing Secp256k1 verification instruction")]
+    MissingSecp256k1Instruction,
+    #[msg("Invalid Secp256k1 instruction data")]
+    InvalidSecp256k1Data,
+    #[msg("Secp256k1 signers must be a 20-byte address padded with zeros")]
+    InvalidSignerKeyType,
+    #[msg("Pyth check needs a non-zero feed id, tolerance up to 10000 bps and a positive max age")]
+    InvalidPythCheck,
+    #[msg("Asset requires its Pyth price account")]
+    OracleAccountMissing,
+    #[msg("Not a fully verified Pyth PriceUpdateV2 account with a positive price")]
+    InvalidOracleAccount,
+    #[msg("Pyth price is older than the asset's max age")]
+    OraclePriceStale,
+    #[msg("Decision confidence ratio differs from the Pyth conf/price beyond tolerance")]
+    ConfidenceMismatch,
+    #[msg("Switchboard check needs a feed account and a positive max age")]
+    InvalidSwitchboardCheck,
+    #[msg("Asset has no Switchboard feed configured")]
+    SwitchboardNotConfigured,
+    #[msg("Notional tiers must be ascending by score, at most 8")]
+    InvalidNotionalTiers,
+    #[msg("Asset is reduce-only; only sells are allowed")]
+    ReduceOnlyViolation,
+    #[msg("Order notional exceeds the limit for the asset's risk tier")]
+    NotionalLimitExceeded,
+    #[msg("Unblock cooldown needs a non-negative duration and a duration or decision count")]
+    InvalidUnblockCooldown,
+    #[msg("Asset was recently unblocked and is still cooling down")]
+    AssetCooling,
+    #[msg("Reopen ramp steps must be ascending, positive and at most 10000 bps, at most 4")]
+    InvalidReopenRamp,
+    #[msg("Asset is not registered; call register_asset first")]
+    AssetNotRegistered,
+    #[msg("Too many assets in group")]
+    TooManyGroupMembers,
+    #[msg("Asset is not a member of the group")]
+    AssetNotInGroup,
+    #[msg("Every group member's risk account must be passed to block_group")]
+    GroupMemberMissing,
+    #[msg("Portfolio already holds the maximum number of assets")]
+    TooManyPortfolioAssets,
+    #[msg("Portfolio risk account is required while the portfolio breaker is configured")]
+    PortfolioAccountMissing,
+    #[msg("Aggregate portfolio risk is at or above the breaker threshold")]
+    PortfolioBreakerTripped,
+    #[msg("Portfolio breaker threshold must be between 1 and 100")]
+    InvalidPortfolioBreaker,
+    #[msg("Score EMA alpha must be between 1 and 10000 bps")]
+    InvalidScoreEmaAlpha,
+    #[msg("Heartbeat SLA must be positive")]
+    InvalidHeartbeatSla,
+    #[msg("Heartbeat timestamp is not newer than the last one")]
+    StaleHeartbeat,
+    #[msg("Engine has been silent longer than the heartbeat SLA")]
+    EngineSilent,
+    #[msg("Ed25519 instruction data is shorter than its header")]
+    Ed25519HeaderTruncated,
+    #[msg("Ed25519 instruction carries no signatures")]
+    Ed25519NoSignatures,
+    #[msg("Ed25519 instruction header padding must be zero")]
+    Ed25519InvalidPadding,
+    #[msg("Ed25519 instruction data ends before its signature offsets")]
+    Ed25519OffsetsTruncated,
+    #[msg("Update fee amount must be greater than zero")]
+    InvalidUpdateFee,
+    #[msg("Update fee is configured but the treasury or payment accounts are missing")]
+    FeeAccountsMissing,
+    #[msg("Fee token account does not match the treasury or fee mint")]
+    InvalidFeeAccount,
+    #[msg("Treasury balance is insufficient for this withdrawal")]
+    TreasuryInsufficientFunds,
+    #[msg("Keeper tip needs a positive minimum age and amount")]
+    InvalidKeeperTip,
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    #[msg("Account uses the v1 layout; retire it with retire_v1_asset")]
+    V1AccountNeedsRetire,
+    #[msg("Replay shard size must be between its current record count and the maximum")]
+    InvalidReplayShardSize,
+    #[msg("Bloom false-positive rate must be between 1 and 9999 bps")]
+    InvalidBloomParams,
+    #[msg("Replay bloom window must cover the whole timestamp freshness window")]
+    ReplayBloomWindowTooShort,
+    #[msg("Replay bloom account is required in bloom replay mode")]
+    ReplayBloomMissing,
+    #[msg("Decision timestamp is not newer than the asset's latest decision")]
+    DecisionNotNewerThanAsset,
+    #[msg("Asset has no accepted decision to attest")]
+    NoDecisionToAttest,
+    #[msg("Program is not a known Wormhole core bridge")]
+    InvalidWormholeProgram,
+    #[msg("Wormhole account does not match the expected core bridge account")]
+    InvalidWormholeAccount,
+    #[msg("Calling program is not an allowlisted or subscribed consumer")]
+    ConsumerNotAuthorized,
+    #[msg("Consumer account does not belong to the calling program")]
+    InvalidConsumerAccount,
+    #[msg("Consumer subscriptions are not enabled")]
+    ConsumerSubscriptionsDisabled,
+    #[msg("Subscription must cover at least one epoch")]
+    InvalidSubscriptionLength,
+    #[msg("Trading is outside the configured market hours")]
+    MarketClosed,
+    #[msg("Trading is halted for a maintenance window")]
+    MaintenanceWindowActive,
+    #[msg("Schedule windows need days, minutes below 1440 and distinct start and end")]
+    InvalidScheduleWindow,
+    #[msg("Too many windows in a trading schedule")]
+    TooManyScheduleWindows,
+    #[msg("Trading schedule accounts are required while schedules exist")]
+    ScheduleAccountMissing,
+    #[msg("Insurance fee share must be at most 10000 bps")]
+    InvalidInsuranceFeeShare,
+    #[msg("Insurance amount must be greater than zero")]
+    InvalidInsuranceAmount,
+    #[msg("No treasury fees pending for the insurance fund")]
+    NothingToSweep,
+    #[msg("Insurance fund balance above rent is insufficient")]
+    InsuranceInsufficientFunds,
+    #[msg("valid_until must be zero or later than the decision timestamp")]
+    InvalidDecisionExpiry,
+    #[msg("Decision publisher_count or confidence_ratio is below the configured floor")]
+    DecisionBelowFloor,
+    #[msg("Signer weights must match the signers and be at least 1")]
+    InvalidSignerWeight,
+    #[msg("Emergency signers may only block an asset or raise its risk score")]
+    EmergencySignerCannotRelax,
+    #[msg("Emergency signer must not be a trusted signer")]
+    InvalidEmergencySigner,
+    #[msg("Signer key expired before the decision timestamp")]
+    SignerExpired,
+    #[msg("Signer expiry must be zero or a unix timestamp")]
+    InvalidSignerExpiry,
+    #[msg("Session key must not be a trusted signer")]
+    InvalidSessionKey,
+    #[msg("Session expiry must be in the future and within the maximum session duration")]
+    InvalidSessionExpiry,
+    #[msg("Too many or empty asset ids in the session delegation")]
+    InvalidSessionAssets,
+    #[msg("Session key is revoked, expired or not delegated for this asset")]
+    SessionKeyNotValid,
+    #[msg("Session key has not expired yet")]
+    SessionKeyActive,
+    #[msg("Risk tier thresholds must be increasing and at most 100")]
+    InvalidRiskTierThresholds,
+    #[msg("Dead-man switch is triggered; re-arm it first")]
+    DeadmanStillTriggered,
+    #[msg("Replay shard account is required in hash-list replay mode")]
+    ReplayShardMissing,
+    #[msg("Asset has no decision since the dead-man trigger within the window")]
+    DeadmanNoFreshDecision,
+    #[msg("Batch has more than one decision for the same asset")]
+    DuplicateBatchAsset,
+}
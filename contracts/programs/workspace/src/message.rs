@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+pub use cate_decision::{
+    DOMAIN_TAG, HEARTBEAT_TAG, MAX_PUBLISHER_IDS, MAX_SESSION_ASSETS, RISK_CATEGORY_COUNT,
+    RISK_FLAGS_ALL, RISK_FLAG_DEPEG, RISK_FLAG_LIQUIDITY, RISK_FLAG_ORACLE, RISK_FLAG_VOLATILITY,
+    SESSION_TAG, SUB_SCORE_DEPEG, SUB_SCORE_LIQUIDITY, SUB_SCORE_ORACLE, SUB_SCORE_VOLATILITY,
+};
+
+/// Igual a cate_decision::PublisherId; declarado aqui para o IDL do Anchor
+pub type PublisherId = [u8; 8];
+
+/// Id de 8 bytes de um publisher (ex.: chave do publisher Pyth ou do oráculo Switchboard)
+pub fn publisher_id(publisher: &Pubkey) -> PublisherId {
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&publisher.as_ref()[..8]);
+    id
+}
+
+// ============================================================================
+// Payload canônico da decisão (ver DECISION_HASH_SPEC.txt e cate-decision)
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DecisionPayload {
+    pub asset_id: [u8; 16],
+    pub risk_score: u8,
+    pub is_blocked: bool,
+    pub reduce_only: bool, // Só operações que reduzem posição
+    pub risk_flags: u8, // Categorias em alerta (RISK_FLAG_*)
+    pub sub_scores: [u8; RISK_CATEGORY_COUNT], // 0-100 por categoria (SUB_SCORE_*)
+    pub confidence_ratio: u64,
+    pub publisher_count: u8,
+    pub publisher_ids: [PublisherId; MAX_PUBLISHER_IDS], // Preenchidos primeiro, resto zerado
+    pub timestamp: i64,
+    pub nonce: u64,
+    pub sequence: u64, // Monotônico por signer e ativo (modo ReplayMode::Sequence)
+    pub valid_until: i64, // Depois disto o ativo é tratado como bloqueado (0 = sem validade)
+}
+
+impl DecisionPayload {
+    pub const LEN: usize = cate_decision::PAYLOAD_LEN;
+
+    /// Byte de flags: bit 0 = is_blocked, bit 1 = reduce_only
+    pub fn flags(&self) -> u8 {
+        cate_decision::Decision::from(self).flags()
+    }
+
+    /// Serialização canônica, definida em cate-decision
+    pub fn to_bytes(&self) -> [u8; Self::LEN] {
+        cate_decision::Decision::from(self).to_bytes()
+    }
+
+    /// Passou de `valid_until` (0 = nunca expira)
+    pub fn is_expired(&self, current_time: i64) -> bool {
+        self.valid_until != 0 && current_time >= self.valid_until
+    }
+
+    /// Ids não zerados, na ordem assinada
+    pub fn listed_publishers(&self) -> impl Iterator<Item = &PublisherId> {
+        self.publisher_ids.iter().take_while(|id| **id != [0u8; 8])
+    }
+
+    /// Ids contíguos a partir do início, sem repetição e no máximo publisher_count
+    pub fn publisher_ids_valid(&self) -> bool {
+        let listed = self.listed_publishers().count();
+        let padded = self.publisher_ids[listed..].iter().all(|id| *id == [0u8; 8]);
+        let unique = (1..listed)
+            .all(|i| !self.publisher_ids[..i].contains(&self.publisher_ids[i]));
+        padded && unique && listed <= usize::from(self.publisher_count)
+    }
+}
+
+impl From<&DecisionPayload> for cate_decision::Decision {
+    fn from(payload: &DecisionPayload) -> Self {
+        Self {
+            asset_id: payload.asset_id,
+            risk_score: payload.risk_score,
+            is_blocked: payload.is_blocked,
+            reduce_only: payload.reduce_only,
+            risk_flags: payload.risk_flags,
+            sub_scores: payload.sub_scores,
+            confidence_ratio: payload.confidence_ratio,
+            publisher_count: payload.publisher_count,
+            publisher_ids: payload.publisher_ids,
+            timestamp: payload.timestamp,
+            nonce: payload.nonce,
+            sequence: payload.sequence,
+            valid_until: payload.valid_until,
+        }
+    }
+}
+
+/// Prefixo de domínio: DOMAIN_TAG || program_id || genesis_hash do cluster.
+/// Uma assinatura feita para devnet ou para outro programa nunca valida aqui.
+pub fn domain_prefix(
+    program_id: &Pubkey,
+    genesis_hash: &[u8; 32],
+) -> [u8; cate_decision::DOMAIN_PREFIX_LEN] {
+    cate_decision::domain_prefix(&program_id.to_bytes(), genesis_hash)
+}
+
+/// heartbeat_hash = SHA256(domain_prefix || HEARTBEAT_TAG || timestamp LE)
+pub fn heartbeat_hash(program_id: &Pubkey, genesis_hash: &[u8; 32], timestamp: i64) -> [u8; 32] {
+    hashv(&[&domain_prefix(program_id, genesis_hash), HEARTBEAT_TAG, &timestamp.to_le_bytes()])
+        .to_bytes()
+}
+
+/// session_hash = SHA256(domain_prefix || SESSION_TAG || session_key ||
+/// expires_at LE || u8 count || asset_ids). Igual a cate_decision::session_hash.
+pub fn session_hash(
+    program_id: &Pubkey,
+    genesis_hash: &[u8; 32],
+    session_key: &Pubkey,
+    expires_at: i64,
+    asset_ids: &[[u8; 16]],
+) -> [u8; 32] {
+    let prefix = domain_prefix(program_id, genesis_hash);
+    let expires_at = expires_at.to_le_bytes();
+    let count = [asset_ids.len() as u8];
+    let mut parts: Vec<&[u8]> =
+        vec![&prefix, SESSION_TAG, session_key.as_ref(), &expires_at, &count];
+    parts.extend(asset_ids.iter().map(|id| id.as_slice()));
+    hashv(&parts).to_bytes()
+}
+
+/// decision_hash = SHA256(domain_prefix || payload) — é a mensagem assinada via Ed25519.
+/// Mesmo resultado de cate_decision::decision_hash, calculado pela syscall.
+pub fn decision_hash(
+    program_id: &Pubkey,
+    genesis_hash: &[u8; 32],
+    payload: &DecisionPayload,
+) -> [u8; 32] {
+    hashv(&[&domain_prefix(program_id, genesis_hash), &payload.to_bytes()]).to_bytes()
+}
@@ -0,0 +1,64 @@
+//! Leitura mínima de contas `PriceUpdateV2` do receiver do Pyth pull oracle.
+//!
+//! Só o necessário para conferir a confiança declarada pelo engine contra o
+//! `conf/price` on-chain, sem depender do SDK do Pyth. Layout Borsh:
+//! discriminator (8) | write_authority (32) | verification_level (1 ou 2) |
+//! PriceFeedMessage | posted_slot (8).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+use crate::ErrorCode;
+
+/// Programa receiver do Pyth, dono das contas PriceUpdateV2
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Tag de VerificationLevel::Full (Partial = 0 seguido de num_signatures)
+const VERIFICATION_FULL: u8 = 1;
+
+/// feed_id + price + conf + exponent + publish_time + prev_publish_time + ema_price + ema_conf
+const PRICE_MESSAGE_LEN: usize = 32 + 8 + 8 + 4 + 8 + 8 + 8 + 8;
+
+/// Campos do PriceFeedMessage usados na conferência
+#[derive(Clone, Copy, Debug)]
+pub struct PythPrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub publish_time: i64,
+}
+
+impl PythPrice {
+    /// Aceita só contas do receiver com verificação completa do guardian set
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, PYTH_RECEIVER_ID, ErrorCode::InvalidOracleAccount);
+        let data = info.try_borrow_data()?;
+        Self::from_bytes(&data)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let discriminator = &hash(b"account:PriceUpdateV2").to_bytes()[..8];
+        require!(data.starts_with(discriminator), ErrorCode::InvalidOracleAccount);
+        require!(data.get(40) == Some(&VERIFICATION_FULL), ErrorCode::InvalidOracleAccount);
+
+        let message = data.get(41..41 + PRICE_MESSAGE_LEN).ok_or(ErrorCode::InvalidOracleAccount)?;
+        let le8 = |at: usize| -> [u8; 8] { message[at..at + 8].try_into().unwrap() };
+        Ok(Self {
+            feed_id: message[..32].try_into().unwrap(),
+            price: i64::from_le_bytes(le8(32)),
+            conf: u64::from_le_bytes(le8(40)),
+            publish_time: i64::from_le_bytes(le8(52)),
+        })
+    }
+
+    /// `conf/price` em basis points, a mesma escala de confidence_ratio.
+    /// O expoente é comum a price e conf e se cancela. None se price <= 0.
+    pub fn confidence_ratio_bps(&self) -> Option<u64> {
+        if self.price <= 0 {
+            return None;
+        }
+        let ratio = u128::from(self.conf) * 10_000 / self.price as u128;
+        Some(u64::try_from(ratio).unwrap_or(u64::MAX))
+    }
+}
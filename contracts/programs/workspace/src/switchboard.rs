@@ -0,0 +1,66 @@
+//! Leitura mínima de feeds Switchboard On-Demand (`PullFeedAccountData`).
+//!
+//! Só o resultado agregado e o horário da última atualização, sem depender do
+//! SDK da Switchboard. A conta é zero-copy (`repr(C)`): offsets fixos a partir
+//! do discriminator, valores i128 escalados por 10^18.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::pubkey;
+
+use crate::ErrorCode;
+
+/// Programa Switchboard On-Demand (mainnet), dono das contas de feed
+pub const SWITCHBOARD_ON_DEMAND_ID: Pubkey =
+    pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// discriminator + submissions (32 x 64) + authority + queue + feed_hash +
+/// initialized_at + permissions + max_variance + min_responses + name + 4 x u8
+const LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 2048 + 32 + 32 + 32 + 8 + 8 + 8 + 4 + 32 + 4;
+/// ... + last_update_timestamp + lut_slot + reserved: início do CurrentResult
+const RESULT_OFFSET: usize = LAST_UPDATE_TIMESTAMP_OFFSET + 8 + 8 + 32;
+/// value + std_dev + mean + range + min_value + max_value (i128) + num_samples
+const RESULT_LEN: usize = 16 * 6 + 1;
+
+/// Resultado agregado do feed
+#[derive(Clone, Copy, Debug)]
+pub struct SwitchboardReading {
+    pub value: i128,
+    pub std_dev: i128,
+    pub num_samples: u8,
+    pub last_update_timestamp: i64,
+}
+
+impl SwitchboardReading {
+    pub fn try_from_account(info: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*info.owner, SWITCHBOARD_ON_DEMAND_ID, ErrorCode::InvalidOracleAccount);
+        let data = info.try_borrow_data()?;
+        Self::from_bytes(&data)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let discriminator = &hash(b"account:PullFeedAccountData").to_bytes()[..8];
+        require!(data.starts_with(discriminator), ErrorCode::InvalidOracleAccount);
+        let result = data
+            .get(RESULT_OFFSET..RESULT_OFFSET + RESULT_LEN)
+            .ok_or(ErrorCode::InvalidOracleAccount)?;
+        let timestamp = &data[LAST_UPDATE_TIMESTAMP_OFFSET..LAST_UPDATE_TIMESTAMP_OFFSET + 8];
+
+        Ok(Self {
+            value: i128::from_le_bytes(result[..16].try_into().unwrap()),
+            std_dev: i128::from_le_bytes(result[16..32].try_into().unwrap()),
+            num_samples: result[96],
+            last_update_timestamp: i64::from_le_bytes(timestamp.try_into().unwrap()),
+        })
+    }
+
+    /// `std_dev/value` em basis points, a escala de confidence_ratio.
+    /// None sem amostras ou com valor <= 0.
+    pub fn confidence_ratio_bps(&self) -> Option<u64> {
+        if self.num_samples == 0 || self.value <= 0 || self.std_dev < 0 {
+            return None;
+        }
+        let ratio = self.std_dev.checked_mul(10_000)? / self.value;
+        Some(u64::try_from(ratio).unwrap_or(u64::MAX))
+    }
+}
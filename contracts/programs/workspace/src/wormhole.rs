@@ -0,0 +1,125 @@
+//! CPI mínima para o core bridge do Wormhole (`post_message`).
+//!
+//! Sem depender do SDK do Wormhole: só os PDAs do bridge, a taxa gravada em
+//! `BridgeData` e o encoding Borsh da instrução. Layout de `BridgeData`:
+//! guardian_set_index (4) | last_lamports (8) | guardian_set_expiration_time (4) | fee (8).
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::pubkey;
+
+use crate::ErrorCode;
+
+/// Core bridge em mainnet-beta e em devnet
+pub const WORMHOLE_CORE_MAINNET_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+pub const WORMHOLE_CORE_DEVNET_ID: Pubkey = pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+
+/// Seeds dos PDAs do core bridge (derivados com o id do bridge)
+pub const BRIDGE_SEED: &[u8] = b"Bridge";
+pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
+pub const SEQUENCE_SEED: &[u8] = b"Sequence";
+
+/// Tag de PostMessage no enum de instruções do core bridge
+const POST_MESSAGE_TAG: u8 = 1;
+
+/// ConsistencyLevel::Finalized; os guardians só assinam após finalização
+pub const CONSISTENCY_FINALIZED: u8 = 1;
+
+pub fn is_core_bridge(program_id: &Pubkey) -> bool {
+    *program_id == WORMHOLE_CORE_MAINNET_ID || *program_id == WORMHOLE_CORE_DEVNET_ID
+}
+
+/// Taxa em lamports cobrada por mensagem, lida da conta Bridge
+pub fn message_fee(bridge: &AccountInfo) -> Result<u64> {
+    require!(is_core_bridge(bridge.owner), ErrorCode::InvalidWormholeAccount);
+    let data = bridge.try_borrow_data()?;
+    let fee = data.get(16..24).ok_or(ErrorCode::InvalidWormholeAccount)?;
+    Ok(u64::from_le_bytes(fee.try_into().unwrap()))
+}
+
+/// Sequence que a próxima mensagem do emitter vai receber; a conta só
+/// existe depois da primeira mensagem
+pub fn next_sequence(sequence: &AccountInfo) -> Result<u64> {
+    if sequence.data_is_empty() {
+        return Ok(0);
+    }
+    require!(is_core_bridge(sequence.owner), ErrorCode::InvalidWormholeAccount);
+    let data = sequence.try_borrow_data()?;
+    let value = data.get(..8).ok_or(ErrorCode::InvalidWormholeAccount)?;
+    Ok(u64::from_le_bytes(value.try_into().unwrap()))
+}
+
+/// Contas de post_message, na ordem esperada pelo core bridge
+pub struct PostMessageAccounts<'info> {
+    pub bridge: AccountInfo<'info>,
+    pub message: AccountInfo<'info>,
+    pub emitter: AccountInfo<'info>,
+    pub sequence: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub fee_collector: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> PostMessageAccounts<'info> {
+    fn metas(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new(self.bridge.key(), false),
+            AccountMeta::new(self.message.key(), true),
+            AccountMeta::new_readonly(self.emitter.key(), true),
+            AccountMeta::new(self.sequence.key(), false),
+            AccountMeta::new(self.payer.key(), true),
+            AccountMeta::new(self.fee_collector.key(), false),
+            AccountMeta::new_readonly(self.clock.key(), false),
+            AccountMeta::new_readonly(self.rent.key(), false),
+            AccountMeta::new_readonly(self.system_program.key(), false),
+        ]
+    }
+
+    fn infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![
+            self.bridge.clone(),
+            self.message.clone(),
+            self.emitter.clone(),
+            self.sequence.clone(),
+            self.payer.clone(),
+            self.fee_collector.clone(),
+            self.clock.clone(),
+            self.rent.clone(),
+            self.system_program.clone(),
+        ]
+    }
+}
+
+/// Dados de PostMessage: tag | nonce (u32) | payload (Vec<u8>) | consistency_level
+pub fn post_message_data(nonce: u32, payload: &[u8], consistency_level: u8) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + 4 + 4 + payload.len() + 1);
+    data.push(POST_MESSAGE_TAG);
+    data.extend_from_slice(&nonce.to_le_bytes());
+    data.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    data.extend_from_slice(payload);
+    data.push(consistency_level);
+    data
+}
+
+/// Publica `payload`; emitter e message assinam pelos seeds do programa.
+/// A taxa do bridge já precisa estar no fee_collector.
+pub fn post_message<'info>(
+    core_bridge: &AccountInfo<'info>,
+    accounts: PostMessageAccounts<'info>,
+    payload: &[u8],
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(is_core_bridge(core_bridge.key), ErrorCode::InvalidWormholeProgram);
+    let ix = Instruction {
+        program_id: core_bridge.key(),
+        accounts: accounts.metas(),
+        data: post_message_data(0, payload, CONSISTENCY_FINALIZED),
+    };
+    let mut infos = accounts.infos();
+    infos.push(core_bridge.clone());
+    invoke_signed(&ix, &infos, signer_seeds)?;
+    Ok(())
+}
@@ -1,308 +1,561 @@
-import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
-import { Workspace } from "../target/types/workspace";
-import { PublicKey, Keypair, SystemProgram, Transaction, Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
-import { expect } from "chai";
-import nacl from "tweetnacl";
-
-describe("CATE Workspace", () => {
-  // Configure the client to use the local cluster
-  const provider = anchor.AnchorProvider.env();
-  anchor.setProvider(provider);
-
-  const program = anchor.workspace.Workspace as Program<Workspace>;
-  
-  // Test accounts
-  const authority = Keypair.generate();
-  const trustedSigner = Keypair.generate();
-  const attacker = Keypair.generate();
-  
-  // PDAs
-  let configPda: PublicKey;
-  let usedDecisionsPda: PublicKey;
-  let configBump: number;
-  let usedDecisionsBump: number;
-
-  before(async () => {
-    // Airdrop SOL to authority
-    await provider.connection.requestAirdrop(
-      authority.publicKey,
-      10 * anchor.web3.LAMPORTS_PER_SOL
-    );
-
-    // Find PDAs
-    [configPda, configBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
-      program.programId
-    );
-
-    [usedDecisionsPda, usedDecisionsBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("used_decisions")],
-      program.programId
-    );
-  });
-
-  describe("Initialization", () => {
-    it("Should initialize config with trusted signer", async () => {
-      await program.methods
-        .initializeConfig(trustedSigner.publicKey)
-        .accounts({
-          config: configPda,
-          usedDecisions: usedDecisionsPda,
-          authority: authority.publicKey,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([authority])
-        .rpc();
-
-      const config = await program.account.config.fetch(configPda);
-      expect(config.authority.toString()).to.equal(authority.publicKey.toString());
-      expect(config.trustedSigner.toString()).to.equal(trustedSigner.publicKey.toString());
-      expect(config.isInitialized).to.be.true;
-    });
-
-    it("Should fail to initialize twice", async () => {
-      try {
-        await program.methods
-          .initializeConfig(trustedSigner.publicKey)
-          .accounts({
-            config: configPda,
-            usedDecisions: usedDecisionsPda,
-            authority: authority.publicKey,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([authority])
-          .rpc();
-        expect.fail("Should have thrown error");
-      } catch (error) {
-        expect(error.toString()).to.include("already in use");
-      }
-    });
-  });
-
-  describe("Risk Status Update", () => {
-    const assetId = "SOL/USD";
-    let assetRiskPda: PublicKey;
-    let assetBump: number;
-
-    before(() => {
-      [assetRiskPda, assetBump] = PublicKey.findProgramAddressSync(
-        [Buffer.from("asset_risk"), Buffer.from(assetId)],
-        program.programId
-      );
-    });
-
-    it("Should update risk status with valid signature", async () => {
-      const timestamp = Math.floor(Date.now() / 1000);
-      const decisionHash = Buffer.from(nacl.hash(Buffer.from("test")), 0, 32);
-      
-      // Create Ed25519 signature
-      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
-      
-      // Create Ed25519 instruction
-      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
-        publicKey: trustedSigner.publicKey.toBytes(),
-        message: decisionHash,
-        signature: signature,
-      });
-
-      await program.methods
-        .updateRiskStatus(
-          assetId,
-          25, // risk_score
-          false, // is_blocked
-          9500, // confidence_ratio (95%)
-          5, // publisher_count
-          timestamp,
-          Array.from(decisionHash),
-          Array.from(signature),
-          Array.from(trustedSigner.publicKey.toBytes())
-        )
-        .accounts({
-          config: configPda,
-          usedDecisions: usedDecisionsPda,
-          assetRiskStatus: assetRiskPda,
-          authority: authority.publicKey,
-          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([authority])
-        .preInstructions([ed25519Ix])
-        .rpc();
-
-      const riskStatus = await program.account.assetRiskStatus.fetch(assetRiskPda);
-      expect(riskStatus.assetId.slice(0, 7)).to.deep.equal(Buffer.from("SOL/USD"));
-      expect(riskStatus.riskScore).to.equal(25);
-      expect(riskStatus.isBlocked).to.be.false;
-      expect(riskStatus.confidenceRatio.toNumber()).to.equal(9500);
-    });
-
-    it("Should reject replay attack (same hash)", async () => {
-      const timestamp = Math.floor(Date.now() / 1000);
-      const decisionHash = Buffer.from(nacl.hash(Buffer.from("test")), 0, 32);
-      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
-      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
-        publicKey: trustedSigner.publicKey.toBytes(),
-        message: decisionHash,
-        signature: signature,
-      });
-
-      try {
-        await program.methods
-          .updateRiskStatus(
-            assetId,
-            50,
-            true,
-            5000,
-            3,
-            timestamp,
-            Array.from(decisionHash),
-            Array.from(signature),
-            Array.from(trustedSigner.publicKey.toBytes())
-          )
-          .accounts({
-            config: configPda,
-            usedDecisions: usedDecisionsPda,
-            assetRiskStatus: assetRiskPda,
-            authority: authority.publicKey,
-            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([authority])
-          .preInstructions([ed25519Ix])
-          .rpc();
-        expect.fail("Should have rejected replay");
-      } catch (error) {
-        expect(error.toString()).to.include("DecisionAlreadyUsed");
-      }
-    });
-
-    it("Should reject invalid timestamp (too old)", async () => {
-      const oldTimestamp = Math.floor(Date.now() / 1000) - 1000; // 1000 seconds ago
-      const decisionHash = Buffer.from(nacl.hash(Buffer.from("old")), 0, 32);
-      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
-      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
-        publicKey: trustedSigner.publicKey.toBytes(),
-        message: decisionHash,
-        signature: signature,
-      });
-
-      try {
-        await program.methods
-          .updateRiskStatus(
-            assetId,
-            25,
-            false,
-            9500,
-            5,
-            oldTimestamp,
-            Array.from(decisionHash),
-            Array.from(signature),
-            Array.from(trustedSigner.publicKey.toBytes())
-          )
-          .accounts({
-            config: configPda,
-            usedDecisions: usedDecisionsPda,
-            assetRiskStatus: assetRiskPda,
-            authority: authority.publicKey,
-            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([authority])
-          .preInstructions([ed25519Ix])
-          .rpc();
-        expect.fail("Should have rejected old timestamp");
-      } catch (error) {
-        expect(error.toString()).to.include("InvalidTimestamp");
-      }
-    });
-
-    it("Should reject unauthorized signer", async () => {
-      const timestamp = Math.floor(Date.now() / 1000);
-      const decisionHash = Buffer.from(nacl.hash(Buffer.from("unauthorized")), 0, 32);
-      const signature = nacl.sign.detached(decisionHash, attacker.secretKey);
-      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
-        publicKey: attacker.publicKey.toBytes(),
-        message: decisionHash,
-        signature: signature,
-      });
-
-      try {
-        await program.methods
-          .updateRiskStatus(
-            assetId,
-            25,
-            false,
-            9500,
-            5,
-            timestamp,
-            Array.from(decisionHash),
-            Array.from(signature),
-            Array.from(attacker.publicKey.toBytes())
-          )
-          .accounts({
-            config: configPda,
-            usedDecisions: usedDecisionsPda,
-            assetRiskStatus: assetRiskPda,
-            authority: authority.publicKey,
-            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([authority])
-          .preInstructions([ed25519Ix])
-          .rpc();
-        expect.fail("Should have rejected unauthorized signer");
-      } catch (error) {
-        expect(error.toString()).to.include("InvalidSigner");
-      }
-    });
-  });
-
-  describe("Input Validation", () => {
-    it("Should reject asset_id too long", async () => {
-      const longAssetId = "A".repeat(17);
-      const timestamp = Math.floor(Date.now() / 1000);
-      const decisionHash = Buffer.alloc(32, 1);
-      const signature = Buffer.alloc(64, 1);
-
-      try {
-        await program.methods
-          .updateRiskStatus(
-            longAssetId,
-            25,
-            false,
-            9500,
-            5,
-            timestamp,
-            Array.from(decisionHash),
-            Array.from(signature),
-            Array.from(trustedSigner.publicKey.toBytes())
-          )
-          .accounts({
-            config: configPda,
-            usedDecisions: usedDecisionsPda,
-            assetRiskStatus: Keypair.generate().publicKey,
-            authority: authority.publicKey,
-            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
-            systemProgram: SystemProgram.programId,
-          })
-          .signers([authority])
-          .rpc();
-        expect.fail("Should have rejected long asset_id");
-      } catch (error) {
-        expect(error.toString()).to.include("AssetIdTooLong");
-      }
-    });
-
-    it("Should reject invalid risk score", async () => {
-      const timestamp = Math.floor(Date.now() / 1000);
-      const decisionHash = Buffer.alloc(32, 1);
-      const signature = Buffer.alloc(64, 1);
-
-      try {
-        await program.methods
-          .updateRiskStatus(
-            "BTC/USD",
-            101, // Invalid: > 100
-            false,
-            950
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Workspace } from "../target/types/workspace";
+import { MockMultisig } from "../target/types/mock_multisig";
+import { PublicKey, Keypair, SystemProgram, Transaction, Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+import nacl from "tweetnacl";
+import { createHash } from "crypto";
+
+describe("CATE Workspace", () => {
+  // Configure the client to use the local cluster
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Workspace as Program<Workspace>;
+  
+  // Test accounts
+  const authority = Keypair.generate();
+  const trustedSigner = Keypair.generate();
+  const attacker = Keypair.generate();
+  
+  // PDAs
+  let configPda: PublicKey;
+  let usedDecisionsPda: PublicKey;
+  let configBump: number;
+  let usedDecisionsBump: number;
+  let genesisHash: Buffer;
+
+  before(async () => {
+    // Airdrop SOL to authority
+    await provider.connection.requestAirdrop(
+      authority.publicKey,
+      10 * anchor.web3.LAMPORTS_PER_SOL
+    );
+
+    genesisHash = Buffer.from(
+      anchor.utils.bytes.bs58.decode(await provider.connection.getGenesisHash())
+    );
+
+    // Find PDAs
+    [configPda, configBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    [usedDecisionsPda, usedDecisionsBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("used_decisions"), Buffer.from([0])],
+      program.programId
+    );
+  });
+
+  // decision_hash = SHA256("CATE_RISK_V1" || program_id || genesis_hash || payload), ver DECISION_HASH_SPEC.txt
+  const decisionHashFor = (
+    assetId: string,
+    riskScore: number,
+    isBlocked: boolean,
+    confidenceRatio: number,
+    publisherCount: number,
+    timestamp: number,
+    nonce: number
+  ): Buffer => {
+    const payload = Buffer.alloc(192); // campos 8-12 da spec (risk_flags a valid_until) zerados
+    Buffer.from(assetId).copy(payload, 0);
+    payload.writeUInt8(riskScore, 16);
+    payload.writeUInt8(isBlocked ? 1 : 0, 17); // flags: bit0 blocked, bit1 reduce-only
+    payload.writeBigUInt64LE(BigInt(confidenceRatio), 18);
+    payload.writeUInt8(publisherCount, 26);
+    payload.writeBigInt64LE(BigInt(timestamp), 27);
+    payload.writeBigUInt64LE(BigInt(nonce), 35);
+    return createHash("sha256")
+      .update(Buffer.from("CATE_RISK_V1"))
+      .update(program.programId.toBuffer())
+      .update(genesisHash)
+      .update(payload)
+      .digest();
+  };
+
+  // O replay store é dividido em shards escolhidos pelo primeiro byte do hash
+  const REPLAY_SHARD_COUNT = 8;
+  const usedDecisionsShardPda = (decisionHash: Buffer): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("used_decisions"), Buffer.from([decisionHash[0] % REPLAY_SHARD_COUNT])],
+      program.programId
+    )[0];
+
+  describe("Initialization", () => {
+    it("Should initialize config with trusted signer", async () => {
+      await program.methods
+        .initializeConfig(trustedSigner.publicKey, Array.from(genesisHash))
+        .accounts({
+          config: configPda,
+          usedDecisions: usedDecisionsPda,
+          authority: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      expect(config.authority.toString()).to.equal(authority.publicKey.toString());
+      expect(config.trustedSigners.map((s) => s.toString())).to.deep.equal([trustedSigner.publicKey.toString()]);
+      expect(config.quorum).to.equal(1);
+      expect(config.isInitialized).to.be.true;
+    });
+
+    it("Should initialize the remaining replay store shards", async () => {
+      for (let shard = 1; shard < REPLAY_SHARD_COUNT; shard++) {
+        await program.methods
+          .initializeReplayShard(shard)
+          .accounts({
+            config: configPda,
+            usedDecisions: PublicKey.findProgramAddressSync(
+              [Buffer.from("used_decisions"), Buffer.from([shard])],
+              program.programId
+            )[0],
+            authority: authority.publicKey,
+            payer: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+      }
+    });
+
+    it("Should fail to initialize twice", async () => {
+      try {
+        await program.methods
+          .initializeConfig(trustedSigner.publicKey, Array.from(genesisHash))
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsPda,
+            authority: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have thrown error");
+      } catch (error) {
+        expect(error.toString()).to.include("already in use");
+      }
+    });
+  });
+
+  describe("Risk Status Update", () => {
+    const assetId = "SOL/USD";
+    let assetRiskPda: PublicKey;
+    let assetBump: number;
+    let firstTimestamp: number;
+
+    before(() => {
+      [assetRiskPda, assetBump] = PublicKey.findProgramAddressSync(
+        [Buffer.from("asset_risk"), Buffer.from(assetId)],
+        program.programId
+      );
+    });
+
+    it("Should register the asset before its first decision", async () => {
+      await program.methods
+        .registerAsset(assetId, PublicKey.default, Array(32).fill(0), { major: {} }, 9)
+        .accounts({
+          config: configPda,
+          authority: authority.publicKey,
+          payer: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+    });
+
+    it("Should update risk status with valid signature", async () => {
+      const timestamp = Math.floor(Date.now() / 1000);
+      firstTimestamp = timestamp;
+      const decisionHash = decisionHashFor(assetId, 25, false, 9500, 5, timestamp, 0);
+      
+      // Create Ed25519 signature
+      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
+      
+      // Create Ed25519 instruction
+      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
+        publicKey: trustedSigner.publicKey.toBytes(),
+        message: decisionHash,
+        signature: signature,
+      });
+
+      await program.methods
+        .updateRiskStatus(
+          assetId,
+          25, // risk_score
+          false, // is_blocked
+          false, // reduceOnly
+          0, // riskFlags
+          [0, 0, 0, 0], // subScores
+          9500, // confidence_ratio (95%)
+          5, // publisher_count
+          Array(16).fill(Array(8).fill(0)), // publisherIds
+          timestamp,
+          new anchor.BN(0), // nonce
+          new anchor.BN(0), // sequence
+          new anchor.BN(0), // validUntil
+          Array.from(decisionHash),
+          [Array.from(signature)],
+          [Array.from(trustedSigner.publicKey.toBytes())],
+          null // secondary
+        )
+        .accounts({
+          config: configPda,
+          usedDecisions: usedDecisionsShardPda(decisionHash),
+          assetRiskStatus: assetRiskPda,
+          authority: authority.publicKey,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([authority])
+        .preInstructions([ed25519Ix])
+        .rpc();
+
+      const riskStatus = await program.account.assetRiskStatus.fetch(assetRiskPda);
+      expect(riskStatus.assetId.slice(0, 7)).to.deep.equal(Buffer.from("SOL/USD"));
+      expect(riskStatus.riskScore).to.equal(25);
+      expect(riskStatus.isBlocked).to.equal(0);
+      expect(riskStatus.confidenceRatio.toNumber()).to.equal(9500);
+    });
+
+    it("Should reject replay attack (same hash)", async () => {
+      // Mesmo payload da primeira atualização
+      const timestamp = firstTimestamp;
+      const decisionHash = decisionHashFor(assetId, 25, false, 9500, 5, timestamp, 0);
+      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
+      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
+        publicKey: trustedSigner.publicKey.toBytes(),
+        message: decisionHash,
+        signature: signature,
+      });
+
+      try {
+        await program.methods
+          .updateRiskStatus(
+            assetId,
+            25,
+            false,
+            false, // reduceOnly
+            0, // riskFlags
+            [0, 0, 0, 0], // subScores
+            9500,
+            5,
+            Array(16).fill(Array(8).fill(0)), // publisherIds
+            timestamp,
+            new anchor.BN(0), // nonce
+            new anchor.BN(0), // sequence
+            new anchor.BN(0), // validUntil
+            Array.from(decisionHash),
+            [Array.from(signature)],
+            [Array.from(trustedSigner.publicKey.toBytes())],
+            null // secondary
+          )
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsShardPda(decisionHash),
+            assetRiskStatus: assetRiskPda,
+            authority: authority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .preInstructions([ed25519Ix])
+          .rpc();
+        expect.fail("Should have rejected replay");
+      } catch (error) {
+        expect(error.toString()).to.include("DecisionAlreadyUsed");
+      }
+    });
+
+    it("Should reject invalid timestamp (too old)", async () => {
+      const oldTimestamp = Math.floor(Date.now() / 1000) - 1000; // 1000 seconds ago
+      const decisionHash = decisionHashFor(assetId, 25, false, 9500, 5, oldTimestamp, 0);
+      const signature = nacl.sign.detached(decisionHash, trustedSigner.secretKey);
+      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
+        publicKey: trustedSigner.publicKey.toBytes(),
+        message: decisionHash,
+        signature: signature,
+      });
+
+      try {
+        await program.methods
+          .updateRiskStatus(
+            assetId,
+            25,
+            false,
+            false, // reduceOnly
+            0, // riskFlags
+            [0, 0, 0, 0], // subScores
+            9500,
+            5,
+            Array(16).fill(Array(8).fill(0)), // publisherIds
+            oldTimestamp,
+            new anchor.BN(0), // nonce
+            new anchor.BN(0), // sequence
+            new anchor.BN(0), // validUntil
+            Array.from(decisionHash),
+            [Array.from(signature)],
+            [Array.from(trustedSigner.publicKey.toBytes())],
+            null // secondary
+          )
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsShardPda(decisionHash),
+            assetRiskStatus: assetRiskPda,
+            authority: authority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .preInstructions([ed25519Ix])
+          .rpc();
+        expect.fail("Should have rejected old timestamp");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidTimestamp");
+      }
+    });
+
+    it("Should reject unauthorized signer", async () => {
+      const timestamp = Math.floor(Date.now() / 1000);
+      const decisionHash = decisionHashFor(assetId, 25, false, 9500, 5, timestamp, 0);
+      const signature = nacl.sign.detached(decisionHash, attacker.secretKey);
+      const ed25519Ix = Ed25519Program.createInstructionWithPublicKey({
+        publicKey: attacker.publicKey.toBytes(),
+        message: decisionHash,
+        signature: signature,
+      });
+
+      try {
+        await program.methods
+          .updateRiskStatus(
+            assetId,
+            25,
+            false,
+            false, // reduceOnly
+            0, // riskFlags
+            [0, 0, 0, 0], // subScores
+            9500,
+            5,
+            Array(16).fill(Array(8).fill(0)), // publisherIds
+            timestamp,
+            new anchor.BN(0), // nonce
+            new anchor.BN(0), // sequence
+            new anchor.BN(0), // validUntil
+            Array.from(decisionHash),
+            [Array.from(signature)],
+            [Array.from(attacker.publicKey.toBytes())],
+            null // secondary
+          )
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsShardPda(decisionHash),
+            assetRiskStatus: assetRiskPda,
+            authority: authority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .preInstructions([ed25519Ix])
+          .rpc();
+        expect.fail("Should have rejected unauthorized signer");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidSigner");
+      }
+    });
+  });
+
+  describe("Input Validation", () => {
+    it("Should reject asset_id too long", async () => {
+      const longAssetId = "A".repeat(17);
+      const timestamp = Math.floor(Date.now() / 1000);
+      const decisionHash = Buffer.alloc(32, 1);
+      const signature = Buffer.alloc(64, 1);
+
+      try {
+        await program.methods
+          .updateRiskStatus(
+            longAssetId,
+            25,
+            false,
+            false, // reduceOnly
+            0, // riskFlags
+            [0, 0, 0, 0], // subScores
+            9500,
+            5,
+            Array(16).fill(Array(8).fill(0)), // publisherIds
+            timestamp,
+            new anchor.BN(0), // nonce
+            new anchor.BN(0), // sequence
+            new anchor.BN(0), // validUntil
+            Array.from(decisionHash),
+            [Array.from(signature)],
+            [Array.from(trustedSigner.publicKey.toBytes())],
+            null // secondary
+          )
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsShardPda(decisionHash),
+            assetRiskStatus: Keypair.generate().publicKey,
+            authority: authority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have rejected long asset_id");
+      } catch (error) {
+        expect(error.toString()).to.include("AssetIdTooLong");
+      }
+    });
+
+    it("Should reject invalid risk score", async () => {
+      const timestamp = Math.floor(Date.now() / 1000);
+      const decisionHash = Buffer.alloc(32, 1);
+      const signature = Buffer.alloc(64, 1);
+
+      try {
+        await program.methods
+          .updateRiskStatus(
+            "BTC/USD",
+            101, // Invalid: > 100
+            false,
+            false, // reduceOnly
+            0, // riskFlags
+            [0, 0, 0, 0], // subScores
+            950
+            5,
+            Array(16).fill(Array(8).fill(0)), // publisherIds
+            timestamp,
+            new anchor.BN(0), // nonce
+            new anchor.BN(0), // sequence
+            new anchor.BN(0), // validUntil
+            Array.from(decisionHash),
+            [Array.from(signature)],
+            [Array.from(trustedSigner.publicKey.toBytes())],
+            null // secondary
+          )
+          .accounts({
+            config: configPda,
+            usedDecisions: usedDecisionsShardPda(decisionHash),
+            assetRiskStatus: Keypair.generate().publicKey,
+            authority: authority.publicKey,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have rejected invalid risk score");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidRiskScore");
+      }
+    });
+  });
+
+  describe("Multisig authority", () => {
+    // Vault do mock-multisig: PDA sem chave privada, como o cofre do Squads
+    const multisig = anchor.workspace.MockMultisig as Program<MockMultisig>;
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault")],
+      multisig.programId
+    );
+
+    // Repassa a instrução ao CATE com o vault assinando via CPI
+    const executeAsVault = async (ix: anchor.web3.TransactionInstruction) =>
+      multisig.methods
+        .execute(ix.data)
+        .accounts({ vault: vaultPda, targetProgram: program.programId })
+        .remainingAccounts(
+          ix.keys.map((key) => ({
+            ...key,
+            isSigner: key.isSigner && !key.pubkey.equals(vaultPda),
+          }))
+        )
+        .rpc();
+
+    it("Should hand the authority to the multisig vault", async () => {
+      await program.methods
+        .proposeAuthority(vaultPda)
+        .accounts({ config: configPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+      const accept = await program.methods
+        .acceptAuthority()
+        .accounts({ config: configPda, newAuthority: vaultPda })
+        .instruction();
+      await executeAsVault(accept);
+
+      const config = await program.account.config.fetch(configPda);
+      expect(config.authority.toString()).to.equal(vaultPda.toString());
+    });
+
+    it("Should pause and unpause through the vault", async () => {
+      const pause = await program.methods
+        .pause()
+        .accounts({ config: configPda, authority: vaultPda, roles: null })
+        .instruction();
+      await executeAsVault(pause);
+      expect((await program.account.config.fetch(configPda)).paused).to.be.true;
+
+      const unpause = await program.methods
+        .unpause()
+        .accounts({ config: configPda, authority: vaultPda, roles: null })
+        .instruction();
+      await executeAsVault(unpause);
+      expect((await program.account.config.fetch(configPda)).paused).to.be.false;
+    });
+
+    it("Should queue a policy change with rent paid by another signer", async () => {
+      const { nonce } = await program.account.config.fetch(configPda);
+      const [pendingChangePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_change"), nonce.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const queue = await program.methods
+        .queuePolicyChange({ heartbeatSla: { slaSecs: new anchor.BN(600) } })
+        .accounts({
+          config: configPda,
+          pendingChange: pendingChangePda,
+          authority: vaultPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction();
+      await executeAsVault(queue);
+
+      const pending = await program.account.pendingPolicyChange.fetch(pendingChangePda);
+      expect(pending.id.toString()).to.equal(nonce.toString());
+    });
+
+    it("Should reject the previous authority", async () => {
+      try {
+        await program.methods
+          .pause()
+          .accounts({ config: configPda, authority: authority.publicKey, roles: null })
+          .signers([authority])
+          .rpc();
+        expect.fail("Should have rejected the previous authority");
+      } catch (error) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+    });
+
+    it("Should hand the authority back from the vault", async () => {
+      const propose = await program.methods
+        .proposeAuthority(authority.publicKey)
+        .accounts({ config: configPda, authority: vaultPda })
+        .instruction();
+      await executeAsVault(propose);
+      await program.methods
+        .acceptAuthority()
+        .accounts({ config: configPda, newAuthority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      expect(config.authority.toString()).to.equal(authority.publicKey.toString());
+    });
+  });
+});
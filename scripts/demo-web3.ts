@@ -84,8 +84,15 @@ function getAssetRiskPDA(assetId: string): [PublicKey, number] {
   );
 }
 
-function getUsedDecisionsPDA(): [PublicKey, number] {
-  return PublicKey.findProgramAddressSync([Buffer.from('used_decisions')], PROGRAM_ID);
+// Deve coincidir com REPLAY_SHARD_COUNT do programa
+const REPLAY_SHARD_COUNT = 8;
+
+// Shard do replay store: o primeiro byte do decision_hash escolhe o PDA
+function getUsedDecisionsPDA(shard: number): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from('used_decisions'), Buffer.from([shard])],
+    PROGRAM_ID
+  );
 }
 
 async function main() {
@@ -170,7 +177,9 @@ async function main() {
   // Get PDAs
   const [configPDA] = getConfigPDA();
   const [assetRiskPDA] = getAssetRiskPDA(asset.id);
-  const [usedDecisionsPDA] = getUsedDecisionsPDA();
+  const [usedDecisionsPDA] = getUsedDecisionsPDA(signed.decisionHash[0] % REPLAY_SHARD_COUNT);
+  // initialize_config cria só o shard 0
+  const [initialShardPDA] = getUsedDecisionsPDA(0);
 
   console.log(`\n[PDA] Config: ${configPDA.toBase58()}`);
   console.log(`[PDA] AssetRisk: ${assetRiskPDA.toBase58()}`);
@@ -192,7 +201,7 @@ async function main() {
     const initIx = new TransactionInstruction({
       keys: [
         { pubkey: configPDA, isSigner: false, isWritable: true },
-        { pubkey: usedDecisionsPDA, isSigner: false, isWritable: true },
+        { pubkey: initialShardPDA, isSigner: false, isWritable: true },
         { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
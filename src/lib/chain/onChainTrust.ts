@@ -287,7 +287,7 @@ export class OnChainTrustService {
       return {
         assetId: assetIdDecoded,
         riskScore: status.riskScore,
-        isBlocked: status.isBlocked,
+        isBlocked: status.isBlocked !== 0,
         lastUpdated: status.lastUpdated.toNumber() * 1000,
         confidenceRatio: status.confidenceRatio.toNumber() / 100,
         publisherCount: status.publisherCount,
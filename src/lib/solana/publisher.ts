@@ -6,6 +6,8 @@ import idl from '../../idl/workspaceIDL.json';
 
 const PROGRAM_ID = new PublicKey(import.meta.env.VITE_PROGRAM_ID);
 const RPC = "https://api.devnet.solana.com ";
+// Deve coincidir com REPLAY_SHARD_COUNT do programa
+const REPLAY_SHARD_COUNT = 8;
 
 export class SolanaPublisher {
   private program: anchor.Program;
@@ -44,6 +46,11 @@ export class SolanaPublisher {
       [Buffer.from('asset_risk'), Buffer.from(assetId)], 
       PROGRAM_ID
     );
+    // Shard do replay store escolhido pelo primeiro byte do hash
+    const [usedDecisionsPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from('used_decisions'), Buffer.from([hashBytes[0] % REPLAY_SHARD_COUNT])],
+      PROGRAM_ID
+    );
 
     // Instrução Ed25519 nativa do Solana
     const ed25519Ix = anchor.web3.Ed25519Program.createInstructionWithPublicKey({
@@ -67,7 +74,7 @@ export class SolanaPublisher {
       )
       .accounts({
         config: configPDA,
-        usedDecisions: PublicKey.findProgramAddressSync([Buffer.from('used_decisions')], PROGRAM_ID)[0],
+        usedDecisions: usedDecisionsPDA,
         assetRiskStatus: assetPDA,
         authority: this.program.provider.publicKey,
         instructionsSysvar: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
@@ -197,7 +197,7 @@ export function useCATEProgram() {
       return {
         assetId: assetIdDecoded,
         riskScore: status.riskScore,
-        isBlocked: status.isBlocked,
+        isBlocked: status.isBlocked !== 0,
         lastUpdated: status.lastUpdated.toNumber(),
         confidenceRatio: status.confidenceRatio.toNumber() / 100, // Convert from bps
         publisherCount: status.publisherCount,